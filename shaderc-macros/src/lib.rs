@@ -0,0 +1,415 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compile-time shader compilation macros for Shaderc.
+//!
+//! This crate provides the `include_spirv!` macro, which is re-exported by the
+//! [shaderc](https://docs.rs/shaderc) crate when its `macros` feature is
+//! enabled. Prefer depending on `shaderc` with that feature over using this
+//! crate directly.
+//!
+//! The macro talks to the Shaderc library through
+//! [shaderc-sys](https://docs.rs/shaderc-sys) directly, so the usual native
+//! library discovery rules of shaderc-sys apply at build time.
+
+extern crate libc;
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate shaderc_sys;
+extern crate syn;
+
+use shaderc_sys as scs;
+
+use libc::{c_char, c_int, c_void, size_t};
+use proc_macro2::{Literal, Span};
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+use std::{env, fs, mem, ptr, slice};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+/// Shader stage names accepted by `include_spirv!`, mapped to the
+/// corresponding `shaderc_shader_kind` values.
+///
+/// The short names match the file extensions recognized by glslc and the long
+/// names match its `-fshader-stage` values.
+static STAGES: &[(&str, i32)] = &[
+    ("vert", 0),
+    ("vertex", 0),
+    ("frag", 1),
+    ("fragment", 1),
+    ("comp", 2),
+    ("compute", 2),
+    ("geom", 3),
+    ("geometry", 3),
+    ("tesc", 4),
+    ("tesscontrol", 4),
+    ("tese", 5),
+    ("tesseval", 5),
+    ("rgen", 14),
+    ("rahit", 15),
+    ("rchit", 16),
+    ("rmiss", 17),
+    ("rint", 18),
+    ("rcall", 19),
+    ("task", 26),
+    ("mesh", 27),
+];
+
+/// Target environment names accepted by `include_spirv!`, mapped to the
+/// corresponding `shaderc_target_env` and `shaderc_env_version` values.
+static TARGET_ENVS: &[(&str, i32, u32)] = &[
+    ("vulkan", 0, 1 << 22),
+    ("vulkan1_0", 0, 1 << 22),
+    ("vulkan1_1", 0, (1 << 22) | (1 << 12)),
+    ("vulkan1_2", 0, (1 << 22) | (2 << 12)),
+    ("vulkan1_3", 0, (1 << 22) | (3 << 12)),
+    ("opengl", 1, 450),
+    ("opengl4_5", 1, 450),
+];
+
+/// Compiles a GLSL or HLSL file into SPIR-V at build time and expands to a
+/// `&'static [u32]` holding the module.
+///
+/// ```ignore
+/// let words: &[u32] = include_spirv!("shaders/tri.frag", frag, vulkan1_2);
+/// ```
+///
+/// The first argument is the path of the shader source, relative to the
+/// directory containing the invoking crate's `Cargo.toml`. The optional second
+/// argument is the shader stage (`vert`, `frag`, `comp`, `geom`, `tesc`,
+/// `tese`, `rgen`, `rahit`, `rchit`, `rmiss`, `rint`, `rcall`, `task`,
+/// `mesh`, or the long forms accepted by glslc's `-fshader-stage`). When it is
+/// omitted, the stage is deduced from the file extension, like glslc does. The
+/// optional third argument is the target environment (`vulkan1_0` to
+/// `vulkan1_3`, or `opengl4_5`) and defaults to `vulkan1_0`.
+///
+/// Files with an `.hlsl` extension are compiled as HLSL. `#include "..."`
+/// directives are resolved relative to the including file and
+/// `#include <...>` directives relative to the crate root. The shader and all
+/// of its includes are tracked so that editing any of them rebuilds the
+/// invoking crate.
+///
+/// Compilation errors are reported as compile errors at the macro call site.
+#[proc_macro]
+pub fn include_spirv(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let invocation = syn::parse_macro_input!(input as Invocation);
+    match invocation.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The parsed arguments of an `include_spirv!` invocation.
+struct Invocation {
+    path: LitStr,
+    stage: Option<Ident>,
+    target_env: Option<Ident>,
+}
+
+impl Parse for Invocation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut idents = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            if idents.len() == 2 {
+                return Err(input.error("unexpected argument to include_spirv!"));
+            }
+            idents.push(input.parse::<Ident>()?);
+        }
+        let mut idents = idents.into_iter();
+        Ok(Invocation {
+            path,
+            stage: idents.next(),
+            target_env: idents.next(),
+        })
+    }
+}
+
+impl Invocation {
+    fn expand(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| syn::Error::new(self.path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+        let path = Path::new(&manifest_dir).join(self.path.value());
+
+        let kind = match self.stage {
+            Some(ref stage) => stage_kind(&stage.to_string()).ok_or_else(|| {
+                syn::Error::new(stage.span(), format!("unknown shader stage `{stage}`"))
+            })?,
+            None => stage_from_extension(&path).ok_or_else(|| {
+                syn::Error::new(
+                    self.path.span(),
+                    "cannot deduce the shader stage from the file extension; \
+                     pass it explicitly, e.g. `include_spirv!(\"shader.glsl\", frag)`",
+                )
+            })?,
+        };
+        let (env, env_version) = match self.target_env {
+            Some(ref target_env) => {
+                target_env_values(&target_env.to_string()).ok_or_else(|| {
+                    syn::Error::new(
+                        target_env.span(),
+                        format!("unknown target environment `{target_env}`"),
+                    )
+                })?
+            }
+            None => (0, 1 << 22),
+        };
+
+        let source = fs::read_to_string(&path).map_err(|error| {
+            syn::Error::new(
+                self.path.span(),
+                format!("cannot read {}: {error}", path.display()),
+            )
+        })?;
+        let is_hlsl = path.extension().is_some_and(|ext| ext == "hlsl");
+
+        let mut dependencies = vec![path.clone()];
+        let words = compile(
+            &source,
+            &path,
+            kind,
+            env,
+            env_version,
+            is_hlsl,
+            &mut dependencies,
+        )
+        .map_err(|error| syn::Error::new(self.path.span(), error))?;
+
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| LitStr::new(&dependency.to_string_lossy(), Span::call_site()));
+        let words = words.into_iter().map(Literal::u32_suffixed);
+        Ok(quote! {{
+            #( const _: &[u8] = include_bytes!(#dependencies); )*
+            &[#(#words),*]
+        }})
+    }
+}
+
+fn stage_kind(name: &str) -> Option<i32> {
+    STAGES
+        .iter()
+        .find(|&&(stage, _)| stage == name)
+        .map(|&(_, kind)| kind)
+}
+
+/// Deduces the shader stage from extensions like `tri.frag` or
+/// `tri.frag.hlsl`.
+fn stage_from_extension(path: &Path) -> Option<i32> {
+    let ext = path.extension()?.to_str()?;
+    if ext == "glsl" || ext == "hlsl" {
+        stage_from_extension(Path::new(path.file_stem()?))
+    } else {
+        stage_kind(ext)
+    }
+}
+
+fn target_env_values(name: &str) -> Option<(i32, u32)> {
+    TARGET_ENVS
+        .iter()
+        .find(|&&(env, _, _)| env == name)
+        .map(|&(_, env, version)| (env, version))
+}
+
+/// Compiles `source` into SPIR-V words, recording every resolved include in
+/// `dependencies`.
+fn compile(
+    source: &str,
+    path: &Path,
+    kind: i32,
+    env: i32,
+    env_version: u32,
+    is_hlsl: bool,
+    dependencies: &mut Vec<PathBuf>,
+) -> Result<Vec<u32>, String> {
+    let c_source = CString::new(source).map_err(|_| "source contains a NUL character")?;
+    let c_file = CString::new(path.to_string_lossy().into_owned())
+        .map_err(|_| "path contains a NUL character")?;
+    let c_entry_point = CString::new("main").unwrap();
+
+    unsafe {
+        let compiler = scs::shaderc_compiler_initialize();
+        if compiler.is_null() {
+            return Err("failed to initialize the shaderc compiler".to_string());
+        }
+        let options = scs::shaderc_compile_options_initialize();
+        if options.is_null() {
+            scs::shaderc_compiler_release(compiler);
+            return Err("failed to initialize the shaderc compile options".to_string());
+        }
+        scs::shaderc_compile_options_set_target_env(options, env, env_version);
+        if is_hlsl {
+            scs::shaderc_compile_options_set_source_language(options, 1);
+        }
+        scs::shaderc_compile_options_set_include_callbacks(
+            options,
+            resolve_include,
+            release_include,
+            dependencies as *mut Vec<PathBuf> as *mut c_void,
+        );
+
+        let result = scs::shaderc_compile_into_spv(
+            compiler,
+            c_source.as_ptr(),
+            source.len(),
+            kind,
+            c_file.as_ptr(),
+            c_entry_point.as_ptr(),
+            options,
+        );
+        let outcome = if result.is_null() {
+            Err("shaderc returned a null compilation result".to_string())
+        } else if scs::shaderc_result_get_compilation_status(result) != 0 {
+            let message = CStr::from_ptr(scs::shaderc_result_get_error_message(result));
+            Err(message.to_string_lossy().trim_end().to_string())
+        } else {
+            let len = scs::shaderc_result_get_length(result);
+            let bytes =
+                slice::from_raw_parts(scs::shaderc_result_get_bytes(result) as *const u8, len);
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+                .collect())
+        };
+
+        if !result.is_null() {
+            scs::shaderc_result_release(result);
+        }
+        scs::shaderc_compile_options_release(options);
+        scs::shaderc_compiler_release(compiler);
+        outcome
+    }
+}
+
+/// Owns the strings pointed to by an include result handed to shaderc.
+struct IncludeResult {
+    source_name: CString,
+    content: CString,
+    wrapped: scs::shaderc_include_result,
+}
+
+extern "C" fn resolve_include(
+    user_data: *mut c_void,
+    requested_source: *const c_char,
+    type_: c_int,
+    requesting_source: *const c_char,
+    _include_depth: size_t,
+) -> *mut scs::shaderc_include_result {
+    let dependencies = unsafe { &mut *(user_data as *mut Vec<PathBuf>) };
+    let requested_source = unsafe { CStr::from_ptr(requested_source).to_string_lossy() };
+    let requesting_source = unsafe { CStr::from_ptr(requesting_source).to_string_lossy() };
+
+    // Relative includes are looked up next to the including file, standard
+    // includes relative to the crate root.
+    let base = if type_ == 0 {
+        Path::new(&*requesting_source)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    } else {
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default())
+    };
+    let path = base.join(&*requested_source);
+
+    let resolved = fs::read_to_string(&path)
+        .map_err(|error| format!("cannot read {}: {error}", path.display()))
+        .and_then(|content| {
+            let source_name = CString::new(path.to_string_lossy().into_owned())
+                .map_err(|_| "include path contains a NUL character".to_string())?;
+            let content = CString::new(content)
+                .map_err(|_| format!("{} contains a NUL character", path.display()))?;
+            Ok((source_name, content))
+        });
+    let (source_name, content) = match resolved {
+        Ok(resolved) => {
+            dependencies.push(path);
+            resolved
+        }
+        // An empty source name signals an error, with the message as content.
+        Err(error) => (CString::default(), CString::new(error).unwrap_or_default()),
+    };
+
+    let mut result = Box::new(IncludeResult {
+        source_name,
+        content,
+        wrapped: unsafe { mem::zeroed() },
+    });
+    result.wrapped = scs::shaderc_include_result {
+        source_name: result.source_name.as_ptr(),
+        source_name_length: result.source_name.as_bytes().len(),
+        content: result.content.as_ptr(),
+        content_length: result.content.as_bytes().len(),
+        user_data: ptr::null_mut(),
+    };
+    let result = Box::into_raw(result);
+    unsafe {
+        (*result).wrapped.user_data = result as *mut c_void;
+        &mut (*result).wrapped
+    }
+}
+
+extern "C" fn release_include(_: *mut c_void, include_result: *mut scs::shaderc_include_result) {
+    let user_data = unsafe { &*include_result }.user_data;
+    drop(unsafe { Box::from_raw(user_data as *mut IncludeResult) });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_invocation() {
+        let invocation: Invocation =
+            syn::parse_str(r#""shaders/tri.frag", frag, vulkan1_2"#).unwrap();
+        assert_eq!("shaders/tri.frag", invocation.path.value());
+        assert_eq!("frag", invocation.stage.unwrap().to_string());
+        assert_eq!("vulkan1_2", invocation.target_env.unwrap().to_string());
+
+        let invocation: Invocation = syn::parse_str(r#""shaders/tri.frag","#).unwrap();
+        assert!(invocation.stage.is_none());
+        assert!(invocation.target_env.is_none());
+
+        assert!(syn::parse_str::<Invocation>(r#""a.frag", frag, vulkan, extra"#).is_err());
+        assert!(syn::parse_str::<Invocation>("frag").is_err());
+    }
+
+    #[test]
+    fn test_stage_from_extension() {
+        assert_eq!(Some(1), stage_from_extension(Path::new("shaders/tri.frag")));
+        assert_eq!(Some(0), stage_from_extension(Path::new("tri.vert.hlsl")));
+        assert_eq!(
+            Some(27),
+            stage_from_extension(Path::new("meshlet.mesh.glsl"))
+        );
+        assert_eq!(None, stage_from_extension(Path::new("tri.glsl")));
+        assert_eq!(None, stage_from_extension(Path::new("tri")));
+    }
+
+    #[test]
+    fn test_target_env_values() {
+        assert_eq!(
+            Some((0, (1 << 22) | (2 << 12))),
+            target_env_values("vulkan1_2")
+        );
+        assert_eq!(Some((1, 450)), target_env_values("opengl4_5"));
+        assert_eq!(None, target_env_values("webgpu"));
+    }
+}
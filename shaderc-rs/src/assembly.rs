@@ -0,0 +1,132 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation to a SPIR-V module together with its assembly text.
+
+use crate::{CompilationArtifact, CompileOptions, Compiler, Result, ShaderKind};
+#[cfg(feature = "spirv-tools")]
+use crate::{Error, SpirvModule};
+
+/// A SPIR-V binary module and its assembly text, as returned by
+/// `Compiler::compile_into_spirv_with_assembly`.
+pub struct SpirvWithAssembly {
+    /// The binary module, with the warnings of the compilation.
+    pub binary: CompilationArtifact,
+    /// The assembly text of `binary`.
+    pub assembly: String,
+}
+
+impl Compiler {
+    /// Compiles the source string `source_text` to a SPIR-V binary module
+    /// along with its assembly text, for debug tools showing the assembly of
+    /// the module actually shipped.
+    ///
+    /// Compiling twice, once with `compile_into_spirv` and once with
+    /// `compile_into_spirv_assembly`, may give different modules when the
+    /// options or the library change in between. Here the source is compiled
+    /// once. With the `spirv-tools` feature, it is compiled to a binary which
+    /// SPIRV-Tools disassembles, so the binary is exactly the one
+    /// `compile_into_spirv` gives. Without it, the source is compiled to
+    /// assembly and the binary is assembled from that text with the same
+    /// options, so both describe the same instructions, but the header of
+    /// the binary names the SPIR-V assembler as its generator.
+    pub fn compile_into_spirv_with_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<SpirvWithAssembly> {
+        self.compile_with_assembly(
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+    }
+
+    #[cfg(feature = "spirv-tools")]
+    fn compile_with_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<SpirvWithAssembly> {
+        let binary = self.compile_into_spirv(
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )?;
+        let assembly = SpirvModule::from_artifact(&binary)
+            .map_err(|error| Error::InvalidAssembly(error.to_string()))?
+            .disassemble()?;
+        Ok(SpirvWithAssembly { binary, assembly })
+    }
+
+    #[cfg(not(feature = "spirv-tools"))]
+    fn compile_with_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<SpirvWithAssembly> {
+        let compiled = self.compile_into_spirv_assembly(
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )?;
+        let assembly = compiled.as_text();
+        let mut binary = self
+            .assemble(&assembly, additional_options)?
+            .with_input_file_name(input_file_name);
+        if compiled.get_num_warnings() > 0 {
+            binary =
+                binary.with_warnings(compiled.get_num_warnings(), compiled.get_warning_messages());
+        }
+        Ok(SpirvWithAssembly { binary, assembly })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::SpirvModule;
+
+    #[test]
+    fn test_compile_into_spirv_with_assembly() {
+        let compiler = Compiler::new().unwrap();
+        let compiled = compiler
+            .compile_into_spirv_with_assembly(
+                "#version 450\nvoid main() {}",
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                None,
+            )
+            .unwrap();
+        assert!(compiled.assembly.starts_with("; SPIR-V\n"));
+        assert!(compiled.assembly.contains("OpEntryPoint Vertex"));
+        assert!(SpirvModule::from_artifact(&compiled.binary).is_ok());
+    }
+}
@@ -0,0 +1,579 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation on the blocking thread pool of a tokio runtime.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll};
+
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
+
+use crate::settings::Settings;
+use crate::sharded::BaseOptions;
+use crate::{
+    options_or_default, CompilationArtifact, CompileJob, CompileOptions, Compiler, Error, Result,
+    ShaderCompiler, ShaderKind, ShardedCompiler, VersionedResult,
+};
+
+type Job = Box<dyn FnOnce() -> VersionedResult + Send>;
+
+/// Priority of a compilation submitted to an `AsyncCompiler`.
+///
+/// Queued compilations start in order of priority, then in the order they
+/// were submitted, so that a shader needed for the next frame overtakes the
+/// queued part of a background rebuild. Running compilations are never
+/// interrupted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Work nothing waits for, like prewarming a cache.
+    Low,
+    /// The priority of `compile_into_spirv` and `compile_into_spirv_assembly`.
+    #[default]
+    Normal,
+    /// Work something waits for, like a shader needed for the next frame.
+    High,
+}
+
+/// A compiler usable from async code, with a bound on the number of
+/// compilations in flight.
+///
+/// Each compilation runs on the blocking thread pool of the current tokio
+/// runtime once fewer than `max_in_flight` others are running. Until then it
+/// waits in a queue, ordered by `Priority`, without occupying any thread, so
+/// a burst of requests cannot exhaust the blocking pool. Available with the
+/// `tokio` feature.
+///
+/// This is not a pool of compilers: all compilations share the one wrapped
+/// compiler, which must therefore be a `ShaderCompiler` that is `Send` and
+/// `Sync`. A plain `Compiler` is, but its concurrent compilations then all
+/// use the same native compiler; `pooled` wraps a `ShardedCompiler` instead,
+/// giving each compilation in flight a native compiler of its own. Test
+/// doubles like `MockCompiler` work as well.
+///
+/// ```no_run
+/// # extern crate shaderc;
+/// # extern crate tokio;
+/// use shaderc::{AsyncCompiler, ShaderKind};
+///
+/// let compiler = AsyncCompiler::pooled(4).unwrap();
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let artifact = runtime
+///     .block_on(compiler.compile_into_spirv(
+///         "void main() {}",
+///         ShaderKind::Fragment,
+///         "tri.frag",
+///         "main",
+///         None,
+///     ))
+///     .unwrap();
+/// ```
+pub struct AsyncCompiler<C = Compiler> {
+    compiler: Arc<C>,
+    queue: Arc<Queue>,
+    base: Arc<BaseOptions>,
+}
+
+/// Future returned by the compilation methods of `AsyncCompiler`.
+///
+/// Dropping it before the compilation starts removes the compilation from
+/// the queue.
+pub struct CompileFuture {
+    state: State,
+}
+
+/// Future returned by `AsyncCompiler::submit_versioned`.
+///
+/// Like `CompileFuture`, it removes the compilation from the queue if
+/// dropped before the compilation starts.
+pub struct VersionedCompileFuture(CompileFuture);
+
+enum State {
+    /// The queue, until this future has been polled once, and the receiver
+    /// of the result.
+    Queued(Option<Arc<Queue>>, oneshot::Receiver<VersionedResult>),
+    Failed(Option<Error>),
+}
+
+/// The compilations of an `AsyncCompiler` waiting to start.
+struct Queue {
+    state: Mutex<QueueState>,
+}
+
+struct QueueState {
+    /// How many more compilations may run.
+    available: usize,
+    waiting: BinaryHeap<Queued>,
+    /// How many compilations were ever queued, to order them.
+    submitted: u64,
+}
+
+/// A compilation waiting in a `Queue`.
+struct Queued {
+    priority: Priority,
+    order: u64,
+    job: Job,
+    result: oneshot::Sender<VersionedResult>,
+}
+
+/// A running compilation, letting the next queued one start when dropped.
+struct Slot {
+    queue: Arc<Queue>,
+    runtime: Handle,
+}
+
+impl AsyncCompiler<ShardedCompiler> {
+    /// Creates `max_in_flight` compilers, allowing as many compilations to
+    /// run at the same time, each on a compiler of its own.
+    ///
+    /// Returns `None` if a compiler cannot be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_in_flight` is zero.
+    pub fn pooled(max_in_flight: usize) -> Option<AsyncCompiler<ShardedCompiler>> {
+        assert!(max_in_flight > 0, "max_in_flight must not be zero");
+        ShardedCompiler::new(max_in_flight)
+            .map(|compiler| AsyncCompiler::new(compiler, max_in_flight))
+    }
+}
+
+impl<C: ShaderCompiler + Send + Sync + 'static> AsyncCompiler<C> {
+    /// Wraps `compiler`, allowing at most `max_in_flight` compilations to
+    /// run at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_in_flight` is zero.
+    pub fn new(compiler: C, max_in_flight: usize) -> AsyncCompiler<C> {
+        assert!(max_in_flight > 0, "max_in_flight must not be zero");
+        AsyncCompiler {
+            compiler: Arc::new(compiler),
+            queue: Arc::new(Queue {
+                state: Mutex::new(QueueState {
+                    available: max_in_flight,
+                    waiting: BinaryHeap::new(),
+                    submitted: 0,
+                }),
+            }),
+            base: Arc::default(),
+        }
+    }
+
+    /// Returns the wrapped compiler.
+    pub fn compiler(&self) -> &C {
+        &self.compiler
+    }
+
+    /// Returns the number of compilations which could start right away.
+    pub fn available_permits(&self) -> usize {
+        self.queue.lock().available
+    }
+
+    /// Replaces the base options, used by compilations given no options, by
+    /// a copy of `options`, or removes them if `None`. Returns the new
+    /// version of the base options.
+    ///
+    /// Queued compilations take the base options when they start, so they
+    /// use the new ones; running compilations keep the options they started
+    /// with. Options with an include callback give an `Error::InternalError`.
+    /// The base options of the wrapped compiler, if any, are only used when
+    /// this compiler has none.
+    pub fn set_base_options(&self, options: Option<&CompileOptions>) -> Result<u64> {
+        self.base.set(options)
+    }
+
+    /// Returns the version of the base options, 0 until they are first set.
+    pub fn base_options_version(&self) -> u64 {
+        self.base.version()
+    }
+
+    /// Like `ShaderCompiler::compile_into_spirv`, run on the blocking thread
+    /// pool.
+    ///
+    /// The options are copied, so the returned future does not borrow them.
+    /// Options with an include callback cannot be copied to another thread
+    /// and give an `Error::InternalError`.
+    pub fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> CompileFuture {
+        let job = CompileJob::new(source_text, shader_kind, input_file_name)
+            .entry_point(entry_point_name);
+        self.spawn(
+            additional_options,
+            Priority::Normal,
+            move |compiler, options| job.compile(compiler, options),
+        )
+    }
+
+    /// Like `ShaderCompiler::compile_into_spirv_assembly`, run on the
+    /// blocking thread pool.
+    ///
+    /// Options are handled as by `compile_into_spirv`.
+    pub fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> CompileFuture {
+        let source_text = source_text.to_string();
+        let input_file_name = input_file_name.to_string();
+        let entry_point_name = entry_point_name.to_string();
+        self.spawn(
+            additional_options,
+            Priority::Normal,
+            move |compiler, options| {
+                compiler.compile_into_spirv_assembly(
+                    &source_text,
+                    shader_kind,
+                    &input_file_name,
+                    &entry_point_name,
+                    options,
+                )
+            },
+        )
+    }
+
+    /// Compiles `job` into a SPIR-V binary module on the blocking thread
+    /// pool, once the queued compilations of a higher priority, and those of
+    /// the same priority submitted before, have started.
+    ///
+    /// Options are handled as by `compile_into_spirv`.
+    ///
+    /// ```no_run
+    /// # extern crate shaderc;
+    /// # extern crate tokio;
+    /// use shaderc::{AsyncCompiler, CompileJob, Priority, ShaderKind};
+    ///
+    /// let compiler = AsyncCompiler::pooled(4).unwrap();
+    /// let prewarm: Vec<_> = (0..100)
+    ///     .map(|i| {
+    ///         let job = CompileJob::new("void main() {}", ShaderKind::Fragment, &format!("{i}.frag"));
+    ///         compiler.submit(job, Priority::Low, None)
+    ///     })
+    ///     .collect();
+    /// // Starts before the prewarming compilations which are still queued.
+    /// let needed = compiler.submit(
+    ///     CompileJob::new("void main() {}", ShaderKind::Fragment, "lit.frag"),
+    ///     Priority::High,
+    ///     None,
+    /// );
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let artifact = runtime.block_on(needed).unwrap();
+    /// # drop(prewarm);
+    /// ```
+    pub fn submit(
+        &self,
+        job: CompileJob,
+        priority: Priority,
+        additional_options: Option<&CompileOptions>,
+    ) -> CompileFuture {
+        self.spawn(additional_options, priority, move |compiler, options| {
+            job.compile(compiler, options)
+        })
+    }
+
+    /// Like `submit` with no options, the future also giving the version of
+    /// the base options the compilation used.
+    pub fn submit_versioned(&self, job: CompileJob, priority: Priority) -> VersionedCompileFuture {
+        VersionedCompileFuture(self.submit(job, priority, None))
+    }
+
+    /// Queues `compile`, to be called with the wrapped compiler and a copy of
+    /// `additional_options`, or of the base options if `None`.
+    fn spawn<F>(
+        &self,
+        additional_options: Option<&CompileOptions>,
+        priority: Priority,
+        compile: F,
+    ) -> CompileFuture
+    where
+        F: FnOnce(&C, Option<&CompileOptions>) -> Result<CompilationArtifact> + Send + 'static,
+    {
+        let settings: Option<Settings> = additional_options.map(|o| o.settings.clone());
+        if settings.as_ref().is_some_and(|s| s.include_callback) {
+            return CompileFuture {
+                state: State::Failed(Some(Error::InternalError(
+                    "include callbacks cannot be used with AsyncCompiler".to_string(),
+                ))),
+            };
+        }
+        let compiler = Arc::clone(&self.compiler);
+        let base = Arc::clone(&self.base);
+        let job: Job = Box::new(move || {
+            let (options_version, options) = match settings {
+                Some(settings) => {
+                    let options = options_or_default(None).map(|mut options| {
+                        settings.apply_to(&mut options);
+                        Some(options)
+                    });
+                    (0, options)
+                }
+                None => base.options(),
+            };
+            VersionedResult {
+                options_version,
+                result: options.and_then(|options| compile(&compiler, options.as_ref())),
+            }
+        });
+        let (result, receiver) = oneshot::channel();
+        let mut state = self.queue.lock();
+        let order = state.submitted;
+        state.submitted += 1;
+        state.waiting.push(Queued {
+            priority,
+            order,
+            job,
+            result,
+        });
+        CompileFuture {
+            state: State::Queued(Some(Arc::clone(&self.queue)), receiver),
+        }
+    }
+}
+
+impl Queue {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        // Jobs run outside of the lock, so a poisoned state is consistent.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Starts queued compilations on the blocking thread pool of `runtime`
+    /// while fewer than the maximum are running.
+    fn dispatch(self: &Arc<Queue>, runtime: &Handle) {
+        let mut started = Vec::new();
+        {
+            let mut state = self.lock();
+            while state.available > 0 {
+                let queued = match state.waiting.pop() {
+                    Some(queued) => queued,
+                    None => break,
+                };
+                // Nothing waits for the result of a dropped future.
+                if !queued.result.is_closed() {
+                    state.available -= 1;
+                    started.push(queued);
+                }
+            }
+        }
+        // Spawning outside of the lock, as a job which cannot be spawned
+        // drops its slot right away.
+        for queued in started {
+            let slot = Slot {
+                queue: Arc::clone(self),
+                runtime: runtime.clone(),
+            };
+            runtime.spawn_blocking(move || {
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(queued.job)).unwrap_or_else(|_| {
+                        VersionedResult {
+                            options_version: 0,
+                            result: Err(Error::InternalError("compilation panicked".to_string())),
+                        }
+                    });
+                // The place is freed before the result is seen, so that
+                // `available_permits` counts it.
+                drop(slot);
+                let _ = queued.result.send(result);
+            });
+        }
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.queue.lock().available += 1;
+        self.queue.dispatch(&self.runtime);
+    }
+}
+
+/// Orders the compilations of a `BinaryHeap` by priority, then the earliest
+/// submitted first.
+impl Ord for Queued {
+    fn cmp(&self, other: &Queued) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Queued) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Queued) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl CompileFuture {
+    fn poll_versioned(&mut self, cx: &mut Context) -> Poll<VersionedResult> {
+        match self.state {
+            State::Queued(ref mut queue, ref mut receiver) => {
+                // Compilations start from within a runtime, so not before
+                // the first poll of a future.
+                if let Some(queue) = queue.take() {
+                    queue.dispatch(&Handle::current());
+                }
+                Pin::new(receiver).poll(cx).map(|result| {
+                    result.unwrap_or_else(|_| VersionedResult {
+                        options_version: 0,
+                        result: Err(Error::InternalError(
+                            "compilation dropped by the runtime".to_string(),
+                        )),
+                    })
+                })
+            }
+            State::Failed(ref mut error) => Poll::Ready(VersionedResult {
+                options_version: 0,
+                result: Err(error.take().expect("future polled after completion")),
+            }),
+        }
+    }
+}
+
+impl Future for CompileFuture {
+    type Output = Result<CompilationArtifact>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.poll_versioned(cx).map(|versioned| versioned.result)
+    }
+}
+
+impl Future for VersionedCompileFuture {
+    type Output = VersionedResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.0.poll_versioned(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockCompiler;
+    use tokio::runtime::Builder;
+
+    #[test]
+    fn test_async_compiler() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let compiler = AsyncCompiler::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+            1,
+        );
+        let first = compiler.compile_into_spirv("a", ShaderKind::Vertex, "a.vert", "main", None);
+        let second =
+            compiler.compile_into_spirv_assembly("b", ShaderKind::Vertex, "b.vert", "main", None);
+        assert_eq!(0, compiler.compiler().calls().len());
+        assert_eq!(&[0x0723_0203], runtime.block_on(first).unwrap().as_binary());
+        assert!(runtime.block_on(second).is_ok());
+        assert_eq!(1, compiler.available_permits());
+        let calls = compiler.compiler().calls();
+        assert_eq!(2, calls.len());
+        assert_eq!("compile_into_spirv_assembly", calls[1].method);
+    }
+
+    #[test]
+    fn test_async_compiler_priority() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let compiler = AsyncCompiler::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+            1,
+        );
+        let submit = |name, priority| {
+            compiler.submit(
+                CompileJob::new("void main() {}", ShaderKind::Fragment, name),
+                priority,
+                None,
+            )
+        };
+        let first = submit("first.frag", Priority::Low);
+        let dropped = submit("dropped.frag", Priority::Low);
+        let second = submit("second.frag", Priority::Low);
+        let urgent = submit("urgent.frag", Priority::High);
+        drop(dropped);
+        // The queued low-priority jobs wait for the urgent one.
+        assert!(runtime.block_on(urgent).is_ok());
+        assert!(runtime.block_on(second).is_ok());
+        assert!(runtime.block_on(first).is_ok());
+        let names: Vec<String> = compiler
+            .compiler()
+            .calls()
+            .into_iter()
+            .map(|call| call.input_file_name)
+            .collect();
+        assert_eq!(vec!["urgent.frag", "first.frag", "second.frag"], names);
+        assert_eq!(1, compiler.available_permits());
+    }
+
+    #[test]
+    fn test_async_compiler_pooled() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let compiler = AsyncCompiler::pooled(2).unwrap();
+        assert_eq!(2, compiler.compiler().shards());
+        assert_eq!(2, compiler.available_permits());
+        let artifact = runtime
+            .block_on(compiler.compile_into_spirv(
+                "void main() {}",
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                None,
+            ))
+            .unwrap();
+        assert_eq!(0x0723_0203, artifact.as_binary()[0]);
+    }
+
+    #[test]
+    fn test_async_compiler_base_options() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let compiler = AsyncCompiler::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+            1,
+        );
+        let job = || CompileJob::new("void main() {}", ShaderKind::Vertex, "a.vert");
+        assert_eq!(0, compiler.base_options_version());
+        // The queued compilation takes the options current when it starts.
+        let queued = compiler.submit_versioned(job(), Priority::Normal);
+        assert_eq!(1, compiler.set_base_options(None).unwrap());
+        let versioned = runtime.block_on(queued);
+        assert_eq!(1, versioned.options_version);
+        assert!(versioned.result.is_ok());
+
+        assert_eq!(2, compiler.set_base_options(None).unwrap());
+        let versioned = runtime.block_on(compiler.submit_versioned(job(), Priority::Normal));
+        assert_eq!(2, versioned.options_version);
+        assert_eq!(2, compiler.compiler().calls().len());
+    }
+}
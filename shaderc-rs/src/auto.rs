@@ -0,0 +1,159 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation of inputs whose format is detected from their contents.
+
+use std::str;
+
+use crate::names::stage_from_extension;
+use crate::spirv_module::{SpirvModule, MAGIC};
+use crate::util::words_from_bytes;
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Compiler, Error, Result, ShaderKind,
+    SourceLanguage,
+};
+
+/// Format of an input of `Compiler::compile_auto`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InputFormat {
+    Binary,
+    Assembly,
+    Source,
+}
+
+/// Detects the format of `source`: a SPIR-V binary if it starts with the
+/// magic number in either byte order, SPIR-V assembly if its first line is
+/// the `; SPIR-V` header or its first instruction is `OpCapability`, and
+/// shader source otherwise.
+fn detect(source: &[u8]) -> InputFormat {
+    let magic = source.get(..4);
+    if magic == Some(&MAGIC.to_le_bytes()) || magic == Some(&MAGIC.to_be_bytes()) {
+        return InputFormat::Binary;
+    }
+    let text = String::from_utf8_lossy(source);
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("; SPIR-V") {
+            return InputFormat::Assembly;
+        }
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with("OpCapability") {
+            return InputFormat::Assembly;
+        }
+        break;
+    }
+    InputFormat::Source
+}
+
+impl Compiler {
+    /// Compiles `source` into a SPIR-V binary module, whatever its format.
+    ///
+    /// SPIR-V binaries are checked to be well-formed modules and returned
+    /// as they are, SPIR-V assembly is assembled, and other inputs are
+    /// compiled as GLSL or, for names ending in `.hlsl`, HLSL when
+    /// `additional_options` sets no source language. The stage of shader
+    /// source is inferred from the extension of `input_file_name`, like
+    /// `.frag`, or from a `#pragma shader_stage` in the source.
+    ///
+    /// This suits asset importers handling folders of mixed inputs.
+    pub fn compile_auto(
+        &self,
+        source: &[u8],
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let text = || {
+            str::from_utf8(source).map_err(|e| {
+                Error::InvalidStage(format!("{input_file_name}: input is not UTF-8: {e}"))
+            })
+        };
+        match detect(source) {
+            InputFormat::Binary => {
+                let mut words = words_from_bytes(source)
+                    .map_err(|e| Error::InvalidAssembly(format!("{input_file_name}: {e}")))?
+                    .into_owned();
+                if source[..4] == MAGIC.to_be_bytes() {
+                    for word in &mut words {
+                        *word = word.swap_bytes();
+                    }
+                }
+                let module = SpirvModule::from_words(words)
+                    .map_err(|e| Error::InvalidAssembly(format!("{input_file_name}: {e}")))?;
+                Ok(CompilationArtifact::from_binary(module.into_words()))
+            }
+            InputFormat::Assembly => self.assemble(text()?, additional_options),
+            InputFormat::Source => {
+                let kind =
+                    stage_from_extension(input_file_name).unwrap_or(ShaderKind::InferFromSource);
+                let hlsl = input_file_name.ends_with(".hlsl")
+                    && additional_options.is_none_or(|o| o.settings.source_language.is_none());
+                if !hlsl {
+                    return self.compile_into_spirv(
+                        text()?,
+                        kind,
+                        input_file_name,
+                        entry_point_name,
+                        additional_options,
+                    );
+                }
+                let mut options = options_or_default(additional_options)?;
+                options.set_source_language(SourceLanguage::HLSL);
+                self.compile_into_spirv(
+                    text()?,
+                    kind,
+                    input_file_name,
+                    entry_point_name,
+                    Some(&options),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(InputFormat::Binary, detect(&[0x03, 0x02, 0x23, 0x07, 0, 0]));
+        assert_eq!(
+            InputFormat::Assembly,
+            detect(b"; SPIR-V\n; Version: 1.0\nOpCapability Shader\n")
+        );
+        assert_eq!(
+            InputFormat::Assembly,
+            detect(b"\n; hand written\n  OpCapability Shader\n")
+        );
+        assert_eq!(
+            InputFormat::Source,
+            detect(b"#version 450\nvoid main() {}\n")
+        );
+        assert_eq!(InputFormat::Source, detect(b""));
+    }
+
+    #[test]
+    fn test_compile_auto_big_endian() {
+        let words = [MAGIC, 0x0001_0000, 0, 1, 0, (2 << 16) | 17, 1];
+        let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+        assert_eq!(InputFormat::Binary, detect(&bytes));
+        let compiler = Compiler::new().unwrap();
+        let artifact = compiler
+            .compile_auto(&bytes, "a.spv", "main", None)
+            .unwrap();
+        assert_eq!(&words, artifact.as_binary());
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batches of compilations whose results are returned as they finish.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use std::{fmt, result, thread, vec};
+
+#[cfg(feature = "tokio")]
+use futures_core::Stream;
+#[cfg(feature = "tokio")]
+use tokio::sync::mpsc as async_mpsc;
+
+use crate::settings::Settings;
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Error, Result, ShaderCompiler,
+    ShaderKind,
+};
+
+/// One compilation of a batch.
+#[derive(Clone)]
+pub struct CompileJob {
+    pub source_text: String,
+    pub shader_kind: ShaderKind,
+    pub input_file_name: String,
+    pub entry_point_name: String,
+    /// Change to the batch's options for this job only, see `options`.
+    pub options: Option<JobOptions>,
+}
+
+/// A change to the options of a single job, called on a copy of the batch's
+/// options before the job is compiled.
+pub type JobOptions = Arc<dyn Fn(&mut CompileOptions) + Send + Sync>;
+
+impl CompileJob {
+    /// Creates a job compiling `source_text` with the entry point `main`.
+    pub fn new(source_text: &str, shader_kind: ShaderKind, input_file_name: &str) -> CompileJob {
+        CompileJob {
+            source_text: source_text.to_string(),
+            shader_kind,
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: "main".to_string(),
+            options: None,
+        }
+    }
+
+    /// Returns this job with the entry point `entry_point_name`.
+    pub fn entry_point(mut self, entry_point_name: &str) -> CompileJob {
+        self.entry_point_name = entry_point_name.to_string();
+        self
+    }
+
+    /// Returns this job compiled with the batch's options changed by `f`,
+    /// for instance to define a macro for fragment shaders only.
+    ///
+    /// Changes given in earlier calls are replaced.
+    ///
+    /// ```
+    /// use shaderc::{CompileJob, ShaderKind};
+    ///
+    /// let job = CompileJob::new("void main() {}", ShaderKind::Fragment, "a.frag")
+    ///     .options(|options| options.add_macro_definition("EARLY_Z", None));
+    /// ```
+    pub fn options<F>(mut self, f: F) -> CompileJob
+    where
+        F: Fn(&mut CompileOptions) + Send + Sync + 'static,
+    {
+        self.options = Some(Arc::new(f));
+        self
+    }
+
+    pub(crate) fn compile<C: ShaderCompiler + ?Sized>(
+        &self,
+        compiler: &C,
+        options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let changed;
+        let options = match self.options {
+            Some(ref change) => {
+                let mut copy = options_or_default(options)?;
+                change(&mut copy);
+                changed = copy;
+                Some(&changed)
+            }
+            None => options,
+        };
+        compiler.compile_into_spirv(
+            &self.source_text,
+            self.shader_kind,
+            &self.input_file_name,
+            &self.entry_point_name,
+            options,
+        )
+    }
+}
+
+impl fmt::Debug for CompileJob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CompileJob")
+            .field("source_text", &self.source_text)
+            .field("shader_kind", &self.shader_kind)
+            .field("input_file_name", &self.input_file_name)
+            .field("entry_point_name", &self.entry_point_name)
+            .field("options", &self.options.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Jobs are equal when they compile the same source in the same way; their
+/// option changes are compared by identity.
+impl PartialEq for CompileJob {
+    fn eq(&self, other: &CompileJob) -> bool {
+        self.source_text == other.source_text
+            && self.shader_kind == other.shader_kind
+            && self.input_file_name == other.input_file_name
+            && self.entry_point_name == other.entry_point_name
+            && match (&self.options, &other.options) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl Eq for CompileJob {}
+
+/// Iterator over the results of `compile_batch_streaming`, in the order the
+/// jobs finish.
+///
+/// Dropping the iterator stops the jobs which have not started yet.
+pub struct BatchResults<'a, C: ?Sized> {
+    inner: Inner<'a, C>,
+    input_file_names: Vec<String>,
+    started: Instant,
+    completed: usize,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+/// A callback passed to `BatchResults::on_progress`.
+type ProgressCallback<'a> = Box<dyn FnMut(&BatchProgress) + 'a>;
+
+/// The progress of a batch, reported by `BatchResults::on_progress` each
+/// time a job finishes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// The number of jobs finished, including this one.
+    pub completed: usize,
+    pub total: usize,
+    /// The index of the job just finished.
+    pub index: usize,
+    /// The input file name of the job just finished.
+    pub input_file_name: String,
+    /// The time since the batch started.
+    pub elapsed: Duration,
+}
+
+impl BatchProgress {
+    /// Returns the estimated time until the batch is done, from the average
+    /// time per job so far.
+    pub fn eta(&self) -> Duration {
+        if self.completed == 0 {
+            return Duration::ZERO;
+        }
+        let remaining = (self.total - self.completed) as u32;
+        self.elapsed / self.completed as u32 * remaining
+    }
+}
+
+enum Inner<'a, C: ?Sized> {
+    Threads(
+        mpsc::Receiver<(usize, Result<CompilationArtifact>)>,
+        Arc<AtomicBool>,
+    ),
+    Sequential(
+        Arc<C>,
+        vec::IntoIter<CompileJob>,
+        usize,
+        Option<Box<CompileOptions<'a>>>,
+    ),
+}
+
+/// Compiles `jobs` with `compiler` into SPIR-V binary modules on a thread per
+/// core, returning each result with the index of its job as soon as it is
+/// available.
+///
+/// Progress can be reported while iterating, or with
+/// `BatchResults::on_progress`, and a batch can be aborted at its first error
+/// by dropping the iterator. Jobs can change `base_options` for themselves
+/// with `CompileJob::options`. When `base_options` has an include callback,
+/// which can only be called from the current thread, jobs are instead
+/// compiled one at a time as the iterator advances.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// use shaderc::{compile_batch_streaming, CompileJob, Compiler, ShaderKind};
+///
+/// let jobs = vec![
+///     CompileJob::new("void main() {}", ShaderKind::Vertex, "a.vert"),
+///     CompileJob::new("void main() {}", ShaderKind::Fragment, "a.frag"),
+/// ];
+/// let total = jobs.len();
+/// let compiler = Arc::new(Compiler::new().unwrap());
+/// for (done, (index, result)) in compile_batch_streaming(compiler, jobs, None).enumerate() {
+///     println!("[{}/{}] job {}: {}", done + 1, total, index, result.is_ok());
+/// }
+/// ```
+pub fn compile_batch_streaming<'a, C>(
+    compiler: Arc<C>,
+    jobs: Vec<CompileJob>,
+    base_options: Option<&'a CompileOptions>,
+) -> BatchResults<'a, C>
+where
+    C: ShaderCompiler + Send + Sync + ?Sized + 'static,
+{
+    let input_file_names = jobs.iter().map(|job| job.input_file_name.clone()).collect();
+    let inner = match base_options.filter(|o| o.settings.include_callback) {
+        Some(base_options) => Inner::Sequential(
+            compiler,
+            jobs.into_iter(),
+            0,
+            base_options.clone().map(Box::new),
+        ),
+        None => {
+            let (sender, receiver) = mpsc::channel();
+            let stop = spawn_workers(compiler, jobs, base_options, move |index, result| {
+                sender.send((index, result)).is_ok()
+            });
+            Inner::Threads(receiver, stop)
+        }
+    };
+    BatchResults {
+        inner,
+        input_file_names,
+        started: Instant::now(),
+        completed: 0,
+        on_progress: None,
+    }
+}
+
+impl<'a, C: ?Sized> BatchResults<'a, C> {
+    /// Returns these results with `f` called with the progress of the batch
+    /// each time the iterator yields a result, for rendering progress bars.
+    pub fn on_progress<F>(mut self, f: F) -> BatchResults<'a, C>
+    where
+        F: FnMut(&BatchProgress) + 'a,
+    {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+}
+
+/// Stream of the results of `compile_batch_stream`, in the order the jobs
+/// finish.
+///
+/// Dropping the stream stops the jobs which have not started yet. Available
+/// with the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub struct BatchStream {
+    receiver: async_mpsc::UnboundedReceiver<(usize, Result<CompilationArtifact>)>,
+    stop: Arc<AtomicBool>,
+}
+
+/// Like `compile_batch_streaming`, but returns the results as a
+/// `futures_core::Stream`, for use from async code.
+///
+/// The jobs are compiled on worker threads without blocking the async
+/// runtime. Options with an include callback cannot be used from other
+/// threads, and give an `Error::InternalError` for every job. Available with
+/// the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub fn compile_batch_stream<C>(
+    compiler: Arc<C>,
+    jobs: Vec<CompileJob>,
+    base_options: Option<&CompileOptions>,
+) -> BatchStream
+where
+    C: ShaderCompiler + Send + Sync + ?Sized + 'static,
+{
+    let (sender, receiver) = async_mpsc::unbounded_channel();
+    if base_options.is_some_and(|o| o.settings.include_callback) {
+        for index in 0..jobs.len() {
+            let error = Error::InternalError(
+                "include callbacks cannot be used with compile_batch_stream".to_string(),
+            );
+            let _ = sender.send((index, Err(error)));
+        }
+        return BatchStream {
+            receiver,
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+    }
+    let stop = spawn_workers(compiler, jobs, base_options, move |index, result| {
+        sender.send((index, result)).is_ok()
+    });
+    BatchStream { receiver, stop }
+}
+
+/// Compiles `jobs` on worker threads, passing each result to `send` until it
+/// returns `false`. Returns a flag stopping the workers once set.
+fn spawn_workers<C, F>(
+    compiler: Arc<C>,
+    jobs: Vec<CompileJob>,
+    base_options: Option<&CompileOptions>,
+    send: F,
+) -> Arc<AtomicBool>
+where
+    C: ShaderCompiler + Send + Sync + ?Sized + 'static,
+    F: Fn(usize, Result<CompilationArtifact>) -> bool + Clone + Send + 'static,
+{
+    // Each worker creates its own options from the recorded settings, as
+    // options objects cannot be shared between threads.
+    let settings: Option<Settings> = base_options.map(|o| o.settings.clone());
+    let jobs = Arc::new(jobs);
+    let next = Arc::new(AtomicUsize::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(jobs.len());
+    for _ in 0..threads {
+        let (compiler, jobs, next, stop) = (
+            Arc::clone(&compiler),
+            Arc::clone(&jobs),
+            Arc::clone(&next),
+            Arc::clone(&stop),
+        );
+        let (settings, send) = (settings.clone(), send.clone());
+        thread::spawn(move || {
+            let options = settings.map(|settings| {
+                options_or_default(None).map(|mut options| {
+                    settings.apply_to(&mut options);
+                    options
+                })
+            });
+            while !stop.load(Ordering::Relaxed) {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let job = match jobs.get(index) {
+                    Some(job) => job,
+                    None => break,
+                };
+                let result = match options {
+                    Some(Err(ref error)) => Err(error.clone()),
+                    Some(Ok(ref options)) => job.compile(&*compiler, Some(options)),
+                    None => job.compile(&*compiler, None),
+                };
+                if !send(index, result) {
+                    break;
+                }
+            }
+        });
+    }
+    stop
+}
+
+impl<'a, C: ShaderCompiler + ?Sized> Iterator for BatchResults<'a, C> {
+    type Item = (usize, Result<CompilationArtifact>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next_result()?;
+        self.completed += 1;
+        if let Some(ref mut on_progress) = self.on_progress {
+            on_progress(&BatchProgress {
+                completed: self.completed,
+                total: self.input_file_names.len(),
+                index: next.0,
+                input_file_name: self.input_file_names[next.0].clone(),
+                elapsed: self.started.elapsed(),
+            });
+        }
+        Some(next)
+    }
+}
+
+impl<'a, C: ShaderCompiler + ?Sized> BatchResults<'a, C> {
+    fn next_result(&mut self) -> Option<(usize, Result<CompilationArtifact>)> {
+        match self.inner {
+            Inner::Threads(ref receiver, _) => receiver.recv().ok(),
+            Inner::Sequential(ref compiler, ref mut jobs, ref mut index, ref options) => {
+                let job = jobs.next()?;
+                let result = match *options {
+                    Some(ref options) => job.compile(&**compiler, Some(options)),
+                    None => Err(Error::InternalError(
+                        "cannot clone compile options".to_string(),
+                    )),
+                };
+                *index += 1;
+                Some((*index - 1, result))
+            }
+        }
+    }
+}
+
+impl<'a, C: ?Sized> Drop for BatchResults<'a, C> {
+    fn drop(&mut self) {
+        if let Inner::Threads(_, ref stop) = self.inner {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Stream for BatchStream {
+    type Item = (usize, Result<CompilationArtifact>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for BatchStream {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Results of a batch with identical outputs stored once, as returned by
+/// `dedup_artifacts`.
+///
+/// Many variants of an ubershader compile to the same binary, so writing
+/// only `artifacts` and the index of each key saves storing duplicates.
+/// Keys sharing an output share one artifact, borrowed from here: an
+/// `Arc` would not let it cross threads, as `CompilationArtifact` is not
+/// `Sync`.
+pub struct UniqueArtifacts<K> {
+    artifacts: Vec<CompilationArtifact>,
+    results: BTreeMap<K, Result<usize>>,
+}
+
+impl<K: Ord> UniqueArtifacts<K> {
+    /// Returns the distinct artifacts, in the order of the first key giving
+    /// each.
+    pub fn artifacts(&self) -> &[CompilationArtifact] {
+        &self.artifacts
+    }
+
+    /// Returns the index in `artifacts` of the output of each key, or its
+    /// error.
+    pub fn results(&self) -> &BTreeMap<K, Result<usize>> {
+        &self.results
+    }
+
+    /// Returns the artifact or error of `key`.
+    pub fn get(&self, key: &K) -> Option<result::Result<&CompilationArtifact, &Error>> {
+        self.results
+            .get(key)
+            .map(|result| result.as_ref().map(|&i| &self.artifacts[i]))
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for UniqueArtifacts<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UniqueArtifacts")
+            .field("artifacts", &self.artifacts.len())
+            .field("results", &self.results)
+            .finish()
+    }
+}
+
+/// Stores each distinct output of `results` once, like those of
+/// `Compiler::compile_variants` or `compile_batch_streaming`.
+///
+/// Outputs are compared byte for byte. Artifacts with the same output but
+/// different warnings share the artifact of the first key, in key order.
+///
+/// ```no_run
+/// use shaderc::{dedup_artifacts, Compiler, ShaderKind, VariantSet};
+///
+/// let variants = VariantSet::new().axis("SHADOWS", ["0", "1"]).axis("MSAA", ["1", "4"]);
+/// let compiler = Compiler::new().unwrap();
+/// let source = "#version 450\nvoid main() {}";
+/// let unique = dedup_artifacts(compiler.compile_variants(
+///     source,
+///     ShaderKind::Fragment,
+///     "lit.frag",
+///     "main",
+///     &variants,
+///     None,
+/// ));
+/// println!("{} variants, {} modules", unique.results().len(), unique.artifacts().len());
+/// ```
+pub fn dedup_artifacts<K, I>(results: I) -> UniqueArtifacts<K>
+where
+    K: Ord,
+    I: IntoIterator<Item = (K, Result<CompilationArtifact>)>,
+{
+    let results: BTreeMap<K, Result<CompilationArtifact>> = results.into_iter().collect();
+    let mut artifacts: Vec<CompilationArtifact> = Vec::new();
+    let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+    let results = results
+        .into_iter()
+        .map(|(key, result)| {
+            let result = result.map(|artifact| {
+                let mut hasher = DefaultHasher::new();
+                artifact.as_binary_u8().hash(&mut hasher);
+                let same = by_hash.entry(hasher.finish()).or_default();
+                match same
+                    .iter()
+                    .find(|&&i| artifacts[i].as_binary_u8() == artifact.as_binary_u8())
+                {
+                    Some(&i) => i,
+                    None => {
+                        same.push(artifacts.len());
+                        artifacts.push(artifact);
+                        artifacts.len() - 1
+                    }
+                }
+            });
+            (key, result)
+        })
+        .collect();
+    UniqueArtifacts { artifacts, results }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockCompiler;
+
+    #[test]
+    fn test_compile_batch_streaming() {
+        let compiler = Arc::new(
+            MockCompiler::new()
+                .with_result("b.frag", Err(Error::CompilationError(1, String::new())))
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+        );
+        let jobs = vec![
+            CompileJob::new("a", ShaderKind::Vertex, "a.vert"),
+            CompileJob::new("b", ShaderKind::Fragment, "b.frag").entry_point("ps"),
+            CompileJob::new("c", ShaderKind::Compute, "c.comp"),
+        ];
+        let mut results: Vec<(usize, bool)> =
+            compile_batch_streaming(Arc::clone(&compiler), jobs, None)
+                .map(|(index, result)| (index, result.is_ok()))
+                .collect();
+        results.sort();
+        assert_eq!(vec![(0, true), (1, false), (2, true)], results);
+        let mut calls = compiler.calls();
+        calls.sort_by(|a, b| a.source_text.cmp(&b.source_text));
+        assert_eq!("ps", calls[1].entry_point_name);
+        assert_eq!(Some(ShaderKind::Compute), calls[2].shader_kind);
+
+        assert_eq!(
+            0,
+            compile_batch_streaming(compiler, Vec::new(), None).count()
+        );
+    }
+
+    #[test]
+    fn test_compile_batch_streaming_job_options() {
+        let compiler = Arc::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+        );
+        let changed = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&changed);
+        let jobs = vec![
+            CompileJob::new("a", ShaderKind::Vertex, "a.vert"),
+            CompileJob::new("b", ShaderKind::Fragment, "b.frag").options(move |options| {
+                options.add_macro_definition("EARLY_Z", None);
+                assert_eq!("EARLY_Z", options.macro_definitions()[0].0);
+                counter.fetch_add(1, Ordering::Relaxed);
+            }),
+        ];
+        assert!(compile_batch_streaming(compiler, jobs, None).all(|(_, result)| result.is_ok()));
+        assert_eq!(1, changed.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_compile_batch_streaming_progress() {
+        let compiler = Arc::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+        );
+        let jobs = vec![
+            CompileJob::new("a", ShaderKind::Vertex, "a.vert"),
+            CompileJob::new("b", ShaderKind::Fragment, "b.frag"),
+        ];
+        let mut progress = Vec::new();
+        let results = compile_batch_streaming(compiler, jobs, None)
+            .on_progress(|p| progress.push(p.clone()))
+            .count();
+        assert_eq!(2, results);
+        assert_eq!(
+            vec![1, 2],
+            progress.iter().map(|p| p.completed).collect::<Vec<_>>()
+        );
+        assert!(progress.iter().all(|p| p.total == 2));
+        let mut names: Vec<_> = progress
+            .iter()
+            .map(|p| p.input_file_name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(vec!["a.vert", "b.frag"], names);
+        assert_eq!(Duration::ZERO, progress[1].eta());
+    }
+
+    #[test]
+    fn test_dedup_artifacts() {
+        let binary = |words: Vec<u32>| Ok(CompilationArtifact::from_binary(words));
+        let unique = dedup_artifacts(vec![
+            ("d", binary(vec![0x0723_0203, 2])),
+            ("a", binary(vec![0x0723_0203, 1])),
+            ("c", Err(Error::CompilationError(1, String::new()))),
+            ("b", binary(vec![0x0723_0203, 1])),
+        ]);
+        assert_eq!(2, unique.artifacts().len());
+        let indices: Vec<_> = unique.results().values().map(|r| r.clone().ok()).collect();
+        assert_eq!(vec![Some(0), Some(0), None, Some(1)], indices);
+        assert!(std::ptr::eq(
+            unique.get(&"a").unwrap().unwrap(),
+            unique.get(&"b").unwrap().unwrap()
+        ));
+        assert_eq!(
+            &[0x0723_0203, 2],
+            unique.get(&"d").unwrap().unwrap().as_binary()
+        );
+        assert!(unique.get(&"c").unwrap().is_err());
+        assert!(unique.get(&"e").is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_compile_batch_stream() {
+        use std::future;
+
+        let compiler = Arc::new(
+            MockCompiler::new()
+                .with_default_result(Ok(CompilationArtifact::from_binary(vec![0x0723_0203]))),
+        );
+        let jobs = vec![
+            CompileJob::new("a", ShaderKind::Vertex, "a.vert"),
+            CompileJob::new("b", ShaderKind::Fragment, "b.frag"),
+        ];
+        let mut stream = compile_batch_stream(compiler, jobs, None);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let mut indices = Vec::new();
+        while let Some((index, result)) =
+            runtime.block_on(future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)))
+        {
+            assert!(result.is_ok());
+            indices.push(index);
+        }
+        indices.sort();
+        assert_eq!(vec![0, 1], indices);
+    }
+}
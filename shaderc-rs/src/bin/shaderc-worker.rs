@@ -0,0 +1,29 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The worker process run by `shaderc::isolated::IsolatedCompiler`.
+//!
+//! Install with `cargo install shaderc --features isolated`, next to the
+//! executable using it or on `PATH`.
+
+extern crate shaderc;
+
+use std::process;
+
+fn main() {
+    if let Err(e) = shaderc::isolated::run_worker() {
+        eprintln!("shaderc-worker: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,700 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of glslc-compatible command-line arguments.
+
+use std::path::{Path, PathBuf};
+
+use shaderc::{EnvVersion, OptimizationLevel, ShaderKind, SourceLanguage, SpirvVersion, TargetEnv};
+
+pub const USAGE: &str = "\
+Usage: shaderc [options] file...
+       shaderc build [--watch] [--message-format=<format>] [manifest]
+       shaderc reflect [--json] file.spv
+       shaderc dis file.spv
+       shaderc val [--target-env=<environment>] file.spv
+       shaderc doctor
+
+The build command compiles the shaders listed in a project manifest,
+shaders.toml by default. The reflect command prints the entry points,
+descriptor bindings, and inputs and outputs of a SPIR-V binary module. The
+dis command prints the assembly text of a SPIR-V binary module, and the val
+command validates it for an environment, vulkan1.0 by default. The doctor
+command prints how the native shaderc library is linked, where it is loaded
+from, and the versions it supports.
+
+An input file of - represents standard input, and requires -fshader-stage
+unless preprocessing. An output file of - represents standard output.
+
+Options:
+  -c                Only run preprocess, compile, and assemble steps.
+  -D<macro>[=<value>]
+                    Add an implicit macro definition.
+  -E                Outputs only the results of the preprocessing step.
+  -fentry-point=<name>
+                    Specify the entry point name for HLSL compilation.
+  -fshader-stage=<stage>
+                    Treat subsequent input files as having stage <stage>.
+  -g                Generate source-level debug information.
+  -h, --help        Display available options.
+  -I <value>        Add directory to include search path.
+  -MD               Write a make-style dependency file listing the input and
+                    its includes next to each output, as <output>.d.
+  -MF <file>        Write the dependency file of -MD to <file>.
+  -MT <target>      Name the target of the dependency file <target> instead of
+                    the output file.
+  --message-format=<format>
+                    Print results as human-readable text (human, the
+                    default) or as one JSON object per line (json).
+  -o <file>         Write output to <file>.
+  -O                Optimize the generated SPIR-V code for better performance.
+  -Os               Optimize the generated SPIR-V code for smaller size.
+  -O0               Disable optimization.
+  -S                Only run preprocess and compilation steps.
+  --target-env=<environment>
+                    Set the target client environment: vulkan, vulkan1.0,
+                    vulkan1.1, vulkan1.2, vulkan1.3, opengl or opengl4.5.
+  --target-spv=<spirv-version>
+                    Set the SPIR-V version to be used for the generated SPIR-V
+                    module, e.g. spv1.3.
+  --version         Display compiler version information.
+  --watch           Compile again whenever an input file or one of its
+                    includes changes.
+  -x <language>     Treat subsequent input files as having type <language>:
+                    glsl or hlsl.
+";
+
+/// The last step to run on the inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Compile a single input into `a.spv` by default.
+    Link,
+    /// Compile each input into SPIR-V binary (`-c`).
+    Compile,
+    /// Compile each input into SPIR-V assembly (`-S`).
+    Assembly,
+    /// Preprocess each input (`-E`).
+    Preprocess,
+}
+
+/// Format of the results printed for each input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Diagnostics on stderr, as glslc prints them.
+    Human,
+    /// One JSON object per input on stdout, then a `build-finished` object.
+    Json,
+}
+
+/// An input file, with the stage and language given by the flags preceding it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    pub path: PathBuf,
+    pub stage: Option<ShaderKind>,
+    pub language: Option<SourceLanguage>,
+}
+
+/// Options of a compilation invocation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args {
+    pub mode: Mode,
+    pub inputs: Vec<Input>,
+    pub output: Option<PathBuf>,
+    pub include_dirs: Vec<PathBuf>,
+    pub macros: Vec<(String, Option<String>)>,
+    pub optimization: Option<OptimizationLevel>,
+    pub debug_info: bool,
+    pub target_env: Option<(TargetEnv, EnvVersion)>,
+    pub target_spirv: Option<SpirvVersion>,
+    pub entry_point: String,
+    pub watch: bool,
+    pub message_format: MessageFormat,
+    /// Whether to write a dependency file for each output (`-MD`).
+    pub depfile: bool,
+    pub depfile_path: Option<PathBuf>,
+    pub depfile_target: Option<String>,
+}
+
+/// What the command line asks for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Compile(Args),
+    /// Build the project with the given manifest.
+    Build {
+        manifest: PathBuf,
+        watch: bool,
+        message_format: MessageFormat,
+    },
+    /// Print the reflection of the given SPIR-V binary.
+    Reflect {
+        input: PathBuf,
+        json: bool,
+    },
+    /// Print the assembly text of the given SPIR-V binary.
+    Disassemble {
+        input: PathBuf,
+    },
+    /// Validate the given SPIR-V binary for an environment.
+    Validate {
+        input: PathBuf,
+        target_env: TargetEnv,
+        env_version: EnvVersion,
+    },
+    /// Print a report of the native shaderc library in use.
+    Doctor,
+    Help,
+    Version,
+}
+
+/// Parses the command-line arguments, excluding the program name.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("build") => {
+            args.next();
+            return parse_build(args);
+        }
+        Some("reflect") => {
+            args.next();
+            return parse_reflect(args);
+        }
+        Some("doctor") => {
+            args.next();
+            return parse_doctor(args);
+        }
+        Some("dis") => {
+            args.next();
+            return parse_dis(args);
+        }
+        Some("val") => {
+            args.next();
+            return parse_val(args);
+        }
+        _ => {}
+    }
+    let mut parsed = Args {
+        mode: Mode::Link,
+        inputs: Vec::new(),
+        output: None,
+        include_dirs: Vec::new(),
+        macros: Vec::new(),
+        optimization: None,
+        debug_info: false,
+        target_env: None,
+        target_spirv: None,
+        entry_point: "main".to_string(),
+        watch: false,
+        message_format: MessageFormat::Human,
+        depfile: false,
+        depfile_path: None,
+        depfile_target: None,
+    };
+    let mut stage = None;
+    let mut language = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--version" => return Ok(Command::Version),
+            "-c" => parsed.mode = Mode::Compile,
+            "-S" => parsed.mode = Mode::Assembly,
+            "-E" => parsed.mode = Mode::Preprocess,
+            "-g" => parsed.debug_info = true,
+            "--watch" => parsed.watch = true,
+            "-MD" => parsed.depfile = true,
+            "-O" => parsed.optimization = Some(OptimizationLevel::Performance),
+            "-Os" => parsed.optimization = Some(OptimizationLevel::Size),
+            "-O0" => parsed.optimization = Some(OptimizationLevel::Zero),
+            "-o" | "-I" | "-x" | "-MF" | "-MT" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("missing argument to '{arg}'"))?;
+                match arg.as_str() {
+                    "-o" => parsed.output = Some(value.into()),
+                    "-I" => parsed.include_dirs.push(value.into()),
+                    "-MF" => parsed.depfile_path = Some(value.into()),
+                    "-MT" => parsed.depfile_target = Some(value),
+                    _ => language = Some(parse_language(&value)?),
+                }
+            }
+            "-" => parsed.inputs.push(Input {
+                path: arg.into(),
+                stage,
+                language,
+            }),
+            _ => {
+                if let Some(value) = arg.strip_prefix("--target-env=") {
+                    parsed.target_env = Some(parse_target_env(value)?);
+                } else if let Some(value) = arg.strip_prefix("--target-spv=") {
+                    parsed.target_spirv = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid value '{value}' in '{arg}'"))?,
+                    );
+                } else if let Some(value) = arg.strip_prefix("-fshader-stage=") {
+                    stage = Some(parse_stage(value)?);
+                } else if let Some(value) = arg.strip_prefix("--message-format=") {
+                    parsed.message_format = parse_message_format(value)?;
+                } else if let Some(value) = arg.strip_prefix("-fentry-point=") {
+                    parsed.entry_point = value.to_string();
+                } else if let Some(value) = arg.strip_prefix("-D") {
+                    if value.is_empty() {
+                        return Err("-D requires a macro name".to_string());
+                    }
+                    parsed.macros.push(match value.find('=') {
+                        Some(i) => (value[..i].to_string(), Some(value[i + 1..].to_string())),
+                        None => (value.to_string(), None),
+                    });
+                } else if let Some(value) = arg.strip_prefix("-I") {
+                    parsed.include_dirs.push(value.into());
+                } else if let Some(value) = arg.strip_prefix("-o") {
+                    parsed.output = Some(value.into());
+                } else if let Some(value) = arg.strip_prefix("-x") {
+                    language = Some(parse_language(value)?);
+                } else if arg.starts_with('-') {
+                    return Err(format!("unknown argument: '{arg}'"));
+                } else {
+                    parsed.inputs.push(Input {
+                        path: arg.into(),
+                        stage,
+                        language,
+                    });
+                }
+            }
+        }
+    }
+
+    if parsed.inputs.is_empty() {
+        return Err("no input files".to_string());
+    }
+    if parsed.output.is_some() && parsed.inputs.len() > 1 {
+        return Err("cannot specify -o when generating multiple output files".to_string());
+    }
+    let stdin_inputs: Vec<&Input> = parsed
+        .inputs
+        .iter()
+        .filter(|input| input.path == Path::new("-"))
+        .collect();
+    if stdin_inputs.len() > 1 {
+        return Err("cannot read standard input more than once".to_string());
+    }
+    if parsed.watch && !stdin_inputs.is_empty() {
+        return Err("cannot watch standard input".to_string());
+    }
+    if parsed.mode != Mode::Preprocess && stdin_inputs.iter().any(|input| input.stage.is_none()) {
+        return Err("reading from standard input requires -fshader-stage".to_string());
+    }
+    let writes_stdout = match parsed.output {
+        Some(ref output) => output == Path::new("-"),
+        None => parsed.mode == Mode::Preprocess,
+    };
+    if parsed.message_format == MessageFormat::Json && writes_stdout {
+        return Err(
+            "cannot write output to standard output with --message-format=json".to_string(),
+        );
+    }
+    if !parsed.depfile && (parsed.depfile_path.is_some() || parsed.depfile_target.is_some()) {
+        return Err("-MF and -MT require -MD".to_string());
+    }
+    if parsed.depfile && writes_stdout {
+        return Err("cannot write a dependency file for standard output".to_string());
+    }
+    if parsed.depfile_path.is_some() && parsed.inputs.len() > 1 {
+        return Err("cannot specify -MF when generating multiple output files".to_string());
+    }
+    if parsed.mode == Mode::Link && parsed.inputs.len() > 1 {
+        return Err(
+            "linking multiple files is not supported yet; use -c to compile files \
+                    individually"
+                .to_string(),
+        );
+    }
+    Ok(Command::Compile(parsed))
+}
+
+/// Parses the arguments of the `build` subcommand.
+fn parse_build<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut manifest = None;
+    let mut watch = false;
+    let mut message_format = MessageFormat::Human;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--watch" => watch = true,
+            _ if arg.starts_with("--message-format=") => {
+                message_format = parse_message_format(&arg["--message-format=".len()..])?
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown argument: '{arg}'")),
+            _ if manifest.is_some() => return Err("build takes a single manifest".to_string()),
+            _ => manifest = Some(PathBuf::from(arg)),
+        }
+    }
+    Ok(Command::Build {
+        manifest: manifest.unwrap_or_else(|| PathBuf::from("shaders.toml")),
+        watch,
+        message_format,
+    })
+}
+
+/// Parses the arguments of the `reflect` subcommand.
+fn parse_reflect<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut input = None;
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => json = true,
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown argument: '{arg}'"))
+            }
+            _ if input.is_some() => return Err("reflect takes a single input file".to_string()),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    Ok(Command::Reflect {
+        input: input.ok_or("no input files")?,
+        json,
+    })
+}
+
+/// Parses the arguments of the `dis` subcommand.
+fn parse_dis<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut input = None;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown argument: '{arg}'"))
+            }
+            _ if input.is_some() => return Err("dis takes a single input file".to_string()),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    Ok(Command::Disassemble {
+        input: input.ok_or("no input files")?,
+    })
+}
+
+/// Parses the arguments of the `val` subcommand.
+fn parse_val<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut input = None;
+    let mut target = (TargetEnv::Vulkan, EnvVersion::Vulkan1_0);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--target-env" => {
+                let value = args.next().ok_or("missing value for '--target-env'")?;
+                target = parse_target_env(&value)?;
+            }
+            _ if arg.starts_with("--target-env=") => {
+                target = parse_target_env(&arg["--target-env=".len()..])?;
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown argument: '{arg}'"))
+            }
+            _ if input.is_some() => return Err("val takes a single input file".to_string()),
+            _ => input = Some(PathBuf::from(arg)),
+        }
+    }
+    Ok(Command::Validate {
+        input: input.ok_or("no input files")?,
+        target_env: target.0,
+        env_version: target.1,
+    })
+}
+
+/// Parses the arguments of the `doctor` subcommand.
+fn parse_doctor<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next() {
+        None => Ok(Command::Doctor),
+        Some(ref arg) if arg == "-h" || arg == "--help" => Ok(Command::Help),
+        Some(arg) => Err(format!("unknown argument: '{arg}'")),
+    }
+}
+
+/// Parses a `-fshader-stage` value, which only accepts the stage names of
+/// glslc and not the default or inferred shader kinds.
+fn parse_stage(value: &str) -> Result<ShaderKind, String> {
+    match value.parse() {
+        Ok(kind) if is_stage(kind) => Ok(kind),
+        _ => Err(format!("stage not recognized: '{value}'")),
+    }
+}
+
+/// Returns whether `kind` forces a single shader stage.
+fn is_stage(kind: ShaderKind) -> bool {
+    match kind {
+        ShaderKind::InferFromSource | ShaderKind::SpirvAssembly => false,
+        _ => !kind.name().starts_with("default-"),
+    }
+}
+
+fn parse_message_format(value: &str) -> Result<MessageFormat, String> {
+    match value {
+        "human" => Ok(MessageFormat::Human),
+        "json" => Ok(MessageFormat::Json),
+        _ => Err(format!("message format not recognized: '{value}'")),
+    }
+}
+
+fn parse_language(value: &str) -> Result<SourceLanguage, String> {
+    value
+        .parse()
+        .map_err(|_| format!("language not recognized: '{value}'"))
+}
+
+/// Parses a `--target-env` value into the target environment and version.
+fn parse_target_env(value: &str) -> Result<(TargetEnv, EnvVersion), String> {
+    match value {
+        "vulkan" => Ok((TargetEnv::Vulkan, EnvVersion::Vulkan1_0)),
+        "opengl" => Ok((TargetEnv::OpenGL, EnvVersion::OpenGL4_5)),
+        _ => match value.parse() {
+            Ok(EnvVersion::OpenGL4_5) => Ok((TargetEnv::OpenGL, EnvVersion::OpenGL4_5)),
+            Ok(EnvVersion::WebGPU) | Err(_) => {
+                Err(format!("invalid value '{value}' in '--target-env={value}'"))
+            }
+            Ok(version) => Ok((TargetEnv::Vulkan, version)),
+        },
+    }
+}
+
+/// Infers the shader kind of a file from its extension, the way glslc does:
+/// `.vert`, `.frag`, etc., optionally followed by `.glsl` or `.hlsl`, and
+/// `.spvasm` for SPIR-V assembly.
+pub fn stage_from_extension(name: &str) -> Option<ShaderKind> {
+    let name = name
+        .strip_suffix(".glsl")
+        .or_else(|| name.strip_suffix(".hlsl"))
+        .unwrap_or(name);
+    let extension = name.rsplit('.').next()?;
+    match extension.parse() {
+        Ok(ShaderKind::SpirvAssembly) => Some(ShaderKind::SpirvAssembly),
+        Ok(kind) if is_stage(kind) => Some(kind),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn parse_compile(args: &[&str]) -> Args {
+        match parse_args(args) {
+            Ok(Command::Compile(args)) => args,
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_glslc_flags() {
+        let args = parse_compile(&[
+            "-c",
+            "-o",
+            "out.spv",
+            "-Iinclude",
+            "-I",
+            "common",
+            "-DFOO",
+            "-DBAR=1",
+            "-Os",
+            "-g",
+            "--target-env=vulkan1.2",
+            "--target-spv=spv1.5",
+            "-fshader-stage=frag",
+            "shader.glsl",
+        ]);
+        assert_eq!(Mode::Compile, args.mode);
+        assert_eq!(Some(PathBuf::from("out.spv")), args.output);
+        assert_eq!(
+            vec![PathBuf::from("include"), PathBuf::from("common")],
+            args.include_dirs
+        );
+        assert_eq!(
+            vec![
+                ("FOO".to_string(), None),
+                ("BAR".to_string(), Some("1".to_string()))
+            ],
+            args.macros
+        );
+        assert_eq!(Some(OptimizationLevel::Size), args.optimization);
+        assert!(args.debug_info);
+        assert_eq!(
+            Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_2)),
+            args.target_env
+        );
+        assert_eq!(Some(SpirvVersion::V1_5), args.target_spirv);
+        assert_eq!(
+            vec![Input {
+                path: "shader.glsl".into(),
+                stage: Some(ShaderKind::Fragment),
+                language: None,
+            }],
+            args.inputs
+        );
+    }
+
+    #[test]
+    fn test_parse_positional_stage() {
+        let args = parse_compile(&["-c", "a.glsl", "-fshader-stage=vertex", "b.glsl"]);
+        assert_eq!(None, args.inputs[0].stage);
+        assert_eq!(Some(ShaderKind::Vertex), args.inputs[1].stage);
+        assert!(parse_args(&["-fshader-stage=default-vert", "a.glsl"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&["-o"]).is_err());
+        assert!(parse_args(&["--bogus", "a.vert"]).is_err());
+        assert!(parse_args(&["a.vert", "b.frag"]).is_err());
+        assert!(parse_args(&["-c", "-o", "out.spv", "a.vert", "b.frag"]).is_err());
+        assert!(parse_args(&["--target-env=vulkan9", "a.vert"]).is_err());
+        assert!(parse_args(&["--watch", "-fshader-stage=vert", "-"]).is_err());
+        assert_eq!(Ok(Command::Help), parse_args(&["a.vert", "--help"]));
+    }
+
+    #[test]
+    fn test_parse_stdin() {
+        let args = parse_compile(&["-fshader-stage=comp", "-", "-o", "-"]);
+        assert_eq!(PathBuf::from("-"), args.inputs[0].path);
+        assert_eq!(Some(PathBuf::from("-")), args.output);
+        assert!(parse_args(&["-E", "-"]).is_ok());
+        assert!(parse_args(&["-c", "-"]).is_err());
+        assert!(parse_args(&["-c", "-fshader-stage=vert", "-", "-"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_depfile() {
+        let args = parse_compile(&["-c", "-MD", "-MF", "a.d", "-MT", "out/a.spv", "a.vert"]);
+        assert!(args.depfile);
+        assert_eq!(Some(PathBuf::from("a.d")), args.depfile_path);
+        assert_eq!(Some("out/a.spv".to_string()), args.depfile_target);
+        assert!(parse_args(&["-c", "-MF", "a.d", "a.vert"]).is_err());
+        assert!(parse_args(&["-E", "-MD", "a.vert"]).is_err());
+        assert!(parse_args(&["-c", "-MD", "-MF", "a.d", "a.vert", "b.frag"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_build() {
+        assert_eq!(
+            Ok(Command::Build {
+                manifest: "shaders.toml".into(),
+                watch: false,
+                message_format: MessageFormat::Human,
+            }),
+            parse_args(&["build"])
+        );
+        assert_eq!(
+            Ok(Command::Build {
+                manifest: "gfx/shaders.toml".into(),
+                watch: true,
+                message_format: MessageFormat::Json,
+            }),
+            parse_args(&[
+                "build",
+                "--watch",
+                "--message-format=json",
+                "gfx/shaders.toml"
+            ])
+        );
+        assert!(parse_args(&["build", "a.toml", "b.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_message_format() {
+        let args = parse_compile(&["-c", "--message-format=json", "a.vert"]);
+        assert_eq!(MessageFormat::Json, args.message_format);
+        assert_eq!(
+            MessageFormat::Human,
+            parse_compile(&["a.vert"]).message_format
+        );
+        assert!(parse_args(&["--message-format=xml", "a.vert"]).is_err());
+        assert!(parse_args(&["-E", "--message-format=json", "a.vert"]).is_err());
+        assert!(parse_args(&["--message-format=json", "-o", "-", "a.vert"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_reflect() {
+        assert_eq!(
+            Ok(Command::Reflect {
+                input: "a.spv".into(),
+                json: true
+            }),
+            parse_args(&["reflect", "a.spv", "--json"])
+        );
+        assert!(parse_args(&["reflect"]).is_err());
+        assert!(parse_args(&["reflect", "a.spv", "b.spv"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_dis_val() {
+        assert_eq!(
+            Ok(Command::Disassemble {
+                input: "a.spv".into()
+            }),
+            parse_args(&["dis", "a.spv"])
+        );
+        assert!(parse_args(&["dis"]).is_err());
+        assert!(parse_args(&["dis", "--json", "a.spv"]).is_err());
+        assert_eq!(
+            Ok(Command::Validate {
+                input: "a.spv".into(),
+                target_env: TargetEnv::Vulkan,
+                env_version: EnvVersion::Vulkan1_0,
+            }),
+            parse_args(&["val", "a.spv"])
+        );
+        let vulkan1_2 = Ok(Command::Validate {
+            input: "a.spv".into(),
+            target_env: TargetEnv::Vulkan,
+            env_version: EnvVersion::Vulkan1_2,
+        });
+        assert_eq!(
+            vulkan1_2,
+            parse_args(&["val", "--target-env", "vulkan1.2", "a.spv"])
+        );
+        assert_eq!(
+            vulkan1_2,
+            parse_args(&["val", "a.spv", "--target-env=vulkan1.2"])
+        );
+        assert!(parse_args(&["val", "a.spv", "--target-env"]).is_err());
+        assert!(parse_args(&["val", "--target-env=vulkan9", "a.spv"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_doctor() {
+        assert_eq!(Ok(Command::Doctor), parse_args(&["doctor"]));
+        assert_eq!(Ok(Command::Help), parse_args(&["doctor", "--help"]));
+        assert!(parse_args(&["doctor", "a.vert"]).is_err());
+    }
+
+    #[test]
+    fn test_stage_from_extension() {
+        assert_eq!(Some(ShaderKind::Vertex), stage_from_extension("a.vert"));
+        assert_eq!(
+            Some(ShaderKind::Fragment),
+            stage_from_extension("dir/a.frag.hlsl")
+        );
+        assert_eq!(
+            Some(ShaderKind::SpirvAssembly),
+            stage_from_extension("a.spvasm")
+        );
+        assert_eq!(None, stage_from_extension("a.glsl"));
+        assert_eq!(None, stage_from_extension("a.txt"));
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Make-style dependency files, as written by `-MD`.
+
+use std::path::{Path, PathBuf};
+
+/// Returns the path of the dependency file of `output` when `-MF` is not
+/// given: `output` with `.d` appended.
+pub fn default_path(output: &Path) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(".d");
+    PathBuf::from(path)
+}
+
+/// Returns a dependency file making `target` depend on `dependencies`, in the
+/// format read by make and ninja.
+pub fn contents<'a, I: IntoIterator<Item = &'a Path>>(target: &str, dependencies: I) -> String {
+    let mut contents = escape(target);
+    contents.push(':');
+    for dependency in dependencies {
+        contents.push(' ');
+        contents.push_str(&escape(&dependency.to_string_lossy()));
+    }
+    contents.push('\n');
+    contents
+}
+
+/// Escapes the characters of `path` which are special in make rules.
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '$' => escaped.push_str("$$"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depfile() {
+        assert_eq!(
+            PathBuf::from("a.vert.spv.d"),
+            default_path(Path::new("a.vert.spv"))
+        );
+        assert_eq!(
+            "a.spv: a.vert my\\ dir/common.glsl $$x\\#.glsl\n",
+            contents(
+                "a.spv",
+                vec![
+                    Path::new("a.vert"),
+                    Path::new("my dir/common.glsl"),
+                    Path::new("$x#.glsl")
+                ]
+            )
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal JSON output for machine-readable results.
+
+use std::fmt::Write;
+
+/// Returns `value` as a JSON string literal.
+pub fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Returns `value` as a JSON string literal, or `null`.
+pub fn optional_string(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_string(), string)
+}
+
+/// Returns a JSON object with the given members, whose values are already
+/// encoded.
+pub fn object(members: &[(&str, String)]) -> String {
+    let members: Vec<String> = members
+        .iter()
+        .map(|(name, value)| format!("{}:{}", string(name), value))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// Returns a JSON array of already encoded values.
+pub fn array<I: IntoIterator<Item = String>>(values: I) -> String {
+    format!("[{}]", values.into_iter().collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        assert_eq!(r#""a\"b\\c\n\u0001""#, string("a\"b\\c\n\u{1}"));
+        assert_eq!("null", optional_string(None));
+        assert_eq!(
+            r#"{"name":"x","list":[1,2]}"#,
+            object(&[
+                ("name", string("x")),
+                ("list", array(vec!["1".to_string(), "2".to_string()]))
+            ])
+        );
+    }
+}
@@ -0,0 +1,707 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A glslc-compatible command-line compiler backed by the shaderc crate.
+//!
+//! Install with `cargo install shaderc --features cli`. Run `shaderc --help`
+//! for the supported flags, `shaderc build` to build a project manifest
+//! (see `shaderc::project`), `shaderc reflect`, `shaderc dis` and `shaderc
+//! val` to inspect, disassemble and validate a SPIR-V binary, or `shaderc
+//! doctor` to find out which native shaderc library is in use.
+
+extern crate notify;
+extern crate sha2;
+extern crate shaderc;
+
+mod args;
+mod depfile;
+mod json;
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal, Read, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{env, fs, process};
+
+use args::{Args, Command, Input, MessageFormat, Mode};
+use sha2::{Digest, Sha256};
+use shaderc::{project, watch};
+use shaderc::{
+    CompileOptions, Compiler, Diagnostic, EnvVersion, IncludeType, ModuleReflection,
+    ResolvedInclude, ShaderKind, SourceLanguage, SpirvModule, StageVariable, TargetEnv,
+};
+
+fn main() {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("shaderc: error: {message}");
+            eprintln!("Try 'shaderc --help' for more information.");
+            process::exit(2);
+        }
+    };
+    let success = match command {
+        Command::Help => {
+            print!("{}", args::USAGE);
+            true
+        }
+        Command::Version => {
+            let (major, minor) = shaderc::get_spirv_version();
+            println!("shaderc {}", env!("CARGO_PKG_VERSION"));
+            println!("Target: SPIR-V {major}.{minor}");
+            true
+        }
+        Command::Build {
+            manifest,
+            watch: false,
+            message_format,
+        } => report_build(project::build(&manifest), message_format),
+        Command::Build {
+            manifest,
+            watch: true,
+            message_format,
+        } => exit_on_watch_error(project::watch(&manifest, |result| {
+            report_build(result, message_format);
+            eprintln!("shaderc: watching for changes...");
+            ControlFlow::Continue(())
+        })),
+        Command::Reflect { input, json } => report(reflect(&input, json)),
+        Command::Disassemble { input } => report(disassemble(&input)),
+        Command::Validate {
+            input,
+            target_env,
+            env_version,
+        } => report(validate(&input, target_env, env_version)),
+        Command::Doctor => {
+            let report = shaderc::diagnose_environment();
+            print!("{report}");
+            report.compiler_available
+        }
+        Command::Compile(ref args) if args.watch => exit_on_watch_error(watch::watch(|files| {
+            finish(args.message_format, run(args, files));
+            eprintln!("shaderc: watching for changes...");
+            ControlFlow::Continue(())
+        })),
+        Command::Compile(ref args) => finish(args.message_format, run(args, &mut BTreeSet::new())),
+    };
+    if !success {
+        process::exit(1);
+    }
+}
+
+/// Prints the outcome of a project build, returning whether it succeeded.
+fn report_build(
+    result: Result<project::BuildReport, project::ProjectError>,
+    format: MessageFormat,
+) -> bool {
+    if format == MessageFormat::Json {
+        return report_build_json(result);
+    }
+    match result {
+        Ok(report) => {
+            println!(
+                "Compiled {} shader(s), {} up to date",
+                report.compiled.len(),
+                report.cached.len()
+            );
+            true
+        }
+        Err(e) => report(Err(format!("shaderc: error: {e}"))),
+    }
+}
+
+/// Prints one JSON object per output of a project build, then one per
+/// shader failing to compile, then a `build-finished` object.
+fn report_build_json(result: Result<project::BuildReport, project::ProjectError>) -> bool {
+    let success = match result {
+        Ok(report) => {
+            let outputs = report
+                .compiled
+                .iter()
+                .map(|output| (output, false))
+                .chain(report.cached.iter().map(|output| (output, true)));
+            for (output, fresh) in outputs {
+                let sha256 = fs::read(output).ok().map(|bytes| sha256_hex(&bytes));
+                println!(
+                    "{}",
+                    json::object(&[
+                        ("reason", json::string("shader-built")),
+                        ("output", json::string(&output.to_string_lossy())),
+                        ("fresh", fresh.to_string()),
+                        ("sha256", json::optional_string(sha256.as_deref())),
+                    ])
+                );
+            }
+            true
+        }
+        Err(project::ProjectError::Compile(failures)) => {
+            for (source, error) in failures {
+                println!(
+                    "{}",
+                    json::object(&[
+                        ("reason", json::string("shader-failed")),
+                        ("input", json::string(&source.to_string_lossy())),
+                        ("diagnostics", diagnostics_json(&error.to_string())),
+                    ])
+                );
+            }
+            false
+        }
+        Err(e) => {
+            print_diagnostics(&format!("shaderc: error: {e}"));
+            false
+        }
+    };
+    print_build_finished(success);
+    success
+}
+
+fn print_build_finished(success: bool) {
+    println!(
+        "{}",
+        json::object(&[
+            ("reason", json::string("build-finished")),
+            ("success", success.to_string()),
+        ])
+    );
+}
+
+/// Returns the diagnostics in compiler messages as a JSON array.
+fn diagnostics_json(messages: &str) -> String {
+    json::array(Diagnostic::parse(messages).iter().map(|diagnostic| {
+        json::object(&[
+            (
+                "severity",
+                json::string(diagnostic.severity.to_string().as_str()),
+            ),
+            ("file", json::optional_string(diagnostic.file.as_deref())),
+            (
+                "line",
+                diagnostic
+                    .line
+                    .map_or_else(|| "null".to_string(), |line| line.to_string()),
+            ),
+            ("message", json::string(&diagnostic.message)),
+        ])
+    }))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Prints the outcome of a compilation run, returning whether it succeeded.
+///
+/// In JSON mode, inputs have been reported by `run` as they were compiled,
+/// and only the `build-finished` object and errors outside of any input are
+/// left to print.
+fn finish(format: MessageFormat, result: Result<(), String>) -> bool {
+    let success = report(result);
+    if format == MessageFormat::Json {
+        print_build_finished(success);
+    }
+    success
+}
+
+/// Prints the diagnostics of a failed compilation, returning whether it
+/// succeeded.
+fn report(result: Result<(), String>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(message) => {
+            print_diagnostics(&message);
+            false
+        }
+    }
+}
+
+fn exit_on_watch_error(result: notify::Result<()>) -> bool {
+    if let Err(e) = result {
+        eprintln!("shaderc: error: cannot watch files: {e}");
+        process::exit(1);
+    }
+    true
+}
+
+/// Prints compiler diagnostics to stderr, highlighting errors and warnings
+/// when stderr is a terminal and `NO_COLOR` is not set.
+fn print_diagnostics(text: &str) {
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let mut stderr = io::stderr().lock();
+    for line in text.lines() {
+        let style = if !color {
+            None
+        } else if line.contains("error:") || line.contains("error generated") {
+            Some("1;31")
+        } else if line.contains("warning:") {
+            Some("1;33")
+        } else {
+            None
+        };
+        let _ = match style {
+            Some(style) => writeln!(stderr, "\x1b[{style}m{line}\x1b[0m"),
+            None => writeln!(stderr, "{line}"),
+        };
+    }
+}
+
+/// Reads the SPIR-V binary `input`, or standard input for `-`.
+fn read_module(input: &Path) -> Result<SpirvModule, String> {
+    let name = input.display();
+    let bytes = if input == Path::new("-") {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("shaderc: error: cannot read standard input: {e}"))?;
+        bytes
+    } else {
+        fs::read(input)
+            .map_err(|e| format!("shaderc: error: cannot open input file '{name}': {e}"))?
+    };
+    let words = shaderc::util::words_from_bytes(&bytes)
+        .map_err(|e| format!("shaderc: error: '{name}': {e}"))?
+        .into_owned();
+    SpirvModule::from_words(words).map_err(|e| format!("shaderc: error: '{name}': {e}"))
+}
+
+/// Prints the assembly text of the SPIR-V binary `input` to stdout.
+fn disassemble(input: &Path) -> Result<(), String> {
+    let assembly = read_module(input)?
+        .disassemble()
+        .map_err(|e| format!("shaderc: error: '{}': {e}", input.display()))?;
+    print!("{assembly}");
+    Ok(())
+}
+
+/// Validates the SPIR-V binary `input` for `target_env` at `env_version`.
+fn validate(input: &Path, target_env: TargetEnv, env_version: EnvVersion) -> Result<(), String> {
+    match read_module(input)?.validate(target_env, env_version) {
+        Ok(()) => Ok(()),
+        Err(shaderc::Error::CompilationError(_, message)) => {
+            Err(format!("{}: error: {message}", input.display()))
+        }
+        Err(e) => Err(format!("shaderc: error: '{}': {e}", input.display())),
+    }
+}
+
+/// Prints the reflection of the SPIR-V binary `input` to stdout, as JSON if
+/// `json` is set.
+fn reflect(input: &Path, json: bool) -> Result<(), String> {
+    let reflection = read_module(input)?
+        .reflect()
+        .map_err(|e| format!("shaderc: error: '{}': {e}", input.display()))?;
+    if json {
+        println!("{}", reflection_json(&reflection));
+        return Ok(());
+    }
+    for entry_point in reflection.entry_points() {
+        match entry_point.shader_kind {
+            Some(kind) => println!("entry point {} ({})", entry_point.name, kind),
+            None => println!(
+                "entry point {} (execution model {})",
+                entry_point.name, entry_point.execution_model
+            ),
+        }
+    }
+    for binding in reflection.descriptor_bindings() {
+        let count = match binding.count {
+            1 => String::new(),
+            0 => "[]".to_string(),
+            count => format!("[{count}]"),
+        };
+        println!(
+            "set {} binding {}: {}{} {}",
+            binding.set,
+            binding.binding,
+            binding.descriptor_type,
+            count,
+            binding.name.as_deref().unwrap_or("<unnamed>")
+        );
+    }
+    let variables = [
+        ("input", reflection.inputs()),
+        ("output", reflection.outputs()),
+    ];
+    for (direction, variables) in variables.iter() {
+        for variable in variables.iter() {
+            let location = match variable.location {
+                Some(location) if variable.component > 0 => {
+                    format!("location {location} component {}", variable.component)
+                }
+                Some(location) => format!("location {location}"),
+                None if variable.builtin => "built-in".to_string(),
+                None => "no location".to_string(),
+            };
+            println!(
+                "{} {}: {}{} {}",
+                direction,
+                location,
+                if variable.patch { "patch " } else { "" },
+                variable.type_name,
+                variable.name.as_deref().unwrap_or("<unnamed>")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn reflection_json(reflection: &ModuleReflection) -> String {
+    let entry_points = reflection.entry_points().iter().map(|entry_point| {
+        json::object(&[
+            ("name", json::string(&entry_point.name)),
+            ("execution_model", entry_point.execution_model.to_string()),
+            (
+                "stage",
+                json::optional_string(entry_point.shader_kind.map(ShaderKind::name)),
+            ),
+        ])
+    });
+    let bindings = reflection.descriptor_bindings().iter().map(|binding| {
+        json::object(&[
+            ("name", json::optional_string(binding.name.as_deref())),
+            ("set", binding.set.to_string()),
+            ("binding", binding.binding.to_string()),
+            ("type", json::string(binding.descriptor_type.name())),
+            ("count", binding.count.to_string()),
+        ])
+    });
+    let variables = |variables: &[StageVariable]| {
+        json::array(variables.iter().map(|variable| {
+            json::object(&[
+                ("name", json::optional_string(variable.name.as_deref())),
+                (
+                    "location",
+                    variable
+                        .location
+                        .map_or_else(|| "null".to_string(), |l| l.to_string()),
+                ),
+                ("component", variable.component.to_string()),
+                ("builtin", variable.builtin.to_string()),
+                ("patch", variable.patch.to_string()),
+                ("type", json::string(&variable.type_name)),
+            ])
+        }))
+    };
+    json::object(&[
+        ("entry_points", json::array(entry_points)),
+        ("descriptor_bindings", json::array(bindings)),
+        ("inputs", variables(reflection.inputs())),
+        ("outputs", variables(reflection.outputs())),
+    ])
+}
+
+/// An input compiled by `compile_input`.
+struct Compiled {
+    /// The output file, or `None` for stdout.
+    output: Option<PathBuf>,
+    sha256: String,
+    warnings: String,
+}
+
+/// Compiles every input of `args`, stopping at the first failure.
+///
+/// The paths of the inputs and includes read are added to `files`, and with
+/// `-MD` written to the dependency file of each output. In JSON
+/// mode, the result of each input is printed as it is compiled, and the
+/// error returned for a failed input is empty.
+fn run(args: &Args, files: &mut BTreeSet<PathBuf>) -> Result<(), String> {
+    files.extend(args.inputs.iter().map(|input| input.path.clone()));
+    let compiler = Compiler::new().ok_or("shaderc: error: failed to create compiler")?;
+    let includes = Rc::new(RefCell::new(BTreeSet::new()));
+    let options = compile_options(args, includes.clone())?;
+    let mut result = Ok(());
+    for input in &args.inputs {
+        let compiled = compile_input(&compiler, &options, args, input).and_then(|compiled| {
+            let includes = std::mem::take(&mut *includes.borrow_mut());
+            if args.depfile {
+                write_depfile(args, input, &compiled, &includes)?;
+            }
+            files.extend(includes);
+            Ok(compiled)
+        });
+        match args.message_format {
+            MessageFormat::Json => println!("{}", input_json(input, &compiled)),
+            MessageFormat::Human => {
+                if let Ok(Compiled { ref warnings, .. }) = compiled {
+                    print_diagnostics(warnings);
+                }
+            }
+        }
+        if let Err(message) = compiled {
+            result = match args.message_format {
+                MessageFormat::Json => Err(String::new()),
+                MessageFormat::Human => Err(message),
+            };
+            break;
+        }
+    }
+    files.append(&mut includes.borrow_mut());
+    result
+}
+
+/// Writes the dependency file of the output of `input`, which includes the
+/// files in `includes`.
+fn write_depfile(
+    args: &Args,
+    input: &Input,
+    compiled: &Compiled,
+    includes: &BTreeSet<PathBuf>,
+) -> Result<(), String> {
+    // Parsing rejects -MD when writing to standard output.
+    let output = match compiled.output {
+        Some(ref output) => output,
+        None => return Ok(()),
+    };
+    let path = args
+        .depfile_path
+        .clone()
+        .unwrap_or_else(|| depfile::default_path(output));
+    let target = args
+        .depfile_target
+        .clone()
+        .unwrap_or_else(|| output.to_string_lossy().into_owned());
+    let dependencies = Some(input.path.as_path())
+        .filter(|path| *path != Path::new("-"))
+        .into_iter()
+        .chain(includes.iter().map(PathBuf::as_path));
+    fs::write(&path, depfile::contents(&target, dependencies)).map_err(|e| {
+        format!(
+            "shaderc: error: cannot write dependency file '{}': {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+/// Returns the JSON object describing the compilation of `input`.
+fn input_json(input: &Input, compiled: &Result<Compiled, String>) -> String {
+    let (output, sha256, messages) = match *compiled {
+        Ok(ref compiled) => (
+            compiled
+                .output
+                .as_ref()
+                .map(|output| output.to_string_lossy().into_owned()),
+            Some(compiled.sha256.as_str()),
+            compiled.warnings.as_str(),
+        ),
+        Err(ref message) => (None, None, message.as_str()),
+    };
+    json::object(&[
+        ("reason", json::string("shader-compiled")),
+        ("input", json::string(&input.path.to_string_lossy())),
+        ("success", compiled.is_ok().to_string()),
+        ("output", json::optional_string(output.as_deref())),
+        ("sha256", json::optional_string(sha256)),
+        ("diagnostics", diagnostics_json(messages)),
+    ])
+}
+
+/// Translates the flags shared by all inputs into compile options, adding the
+/// paths of resolved includes to `includes`.
+fn compile_options(
+    args: &Args,
+    includes: Rc<RefCell<BTreeSet<PathBuf>>>,
+) -> Result<CompileOptions<'static>, String> {
+    let mut options =
+        CompileOptions::new().ok_or("shaderc: error: failed to create compile options")?;
+    for (name, value) in &args.macros {
+        options.add_macro_definition(name, value.as_deref());
+    }
+    if let Some(level) = args.optimization {
+        options.set_optimization_level(level);
+    }
+    if args.debug_info {
+        options.set_generate_debug_info();
+    }
+    if let Some((env, version)) = args.target_env {
+        options.set_target_env(env, version as u32);
+    }
+    if let Some(version) = args.target_spirv {
+        options.set_target_spirv(version);
+    }
+    let include_dirs = args.include_dirs.clone();
+    options.set_include_callback(move |requested, include_type, requesting, _| {
+        let resolved = resolve_include(&include_dirs, requested, include_type, requesting);
+        if let Ok(ref include) = resolved {
+            includes
+                .borrow_mut()
+                .insert(PathBuf::from(&include.resolved_name));
+        }
+        resolved
+    });
+    Ok(options)
+}
+
+/// Resolves `#include "..."` against the directory of the including file, then
+/// against the include search path; `#include <...>` only against the latter.
+fn resolve_include(
+    include_dirs: &[PathBuf],
+    requested: &str,
+    include_type: IncludeType,
+    requesting: &str,
+) -> shaderc::IncludeCallbackResult {
+    let relative_dir = match include_type {
+        IncludeType::Relative => Some(
+            Path::new(requesting)
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .to_path_buf(),
+        ),
+        IncludeType::Standard => None,
+    };
+    for dir in relative_dir.iter().chain(include_dirs) {
+        let path = dir.join(requested);
+        if let Ok(content) = fs::read_to_string(&path) {
+            return Ok(ResolvedInclude {
+                resolved_name: path.to_string_lossy().into_owned(),
+                content,
+            });
+        }
+    }
+    Err(format!("cannot find or open include file '{requested}'"))
+}
+
+fn compile_input(
+    compiler: &Compiler,
+    options: &CompileOptions,
+    args: &Args,
+    input: &Input,
+) -> Result<Compiled, String> {
+    // Diagnostics name standard input the way glslc does.
+    let stdin = input.path == Path::new("-");
+    let name = if stdin {
+        "<stdin>".into()
+    } else {
+        input.path.to_string_lossy()
+    };
+    let source = if stdin {
+        let mut source = String::new();
+        io::stdin()
+            .read_to_string(&mut source)
+            .map_err(|e| format!("shaderc: error: cannot read standard input: {e}"))?;
+        source
+    } else {
+        fs::read_to_string(&input.path)
+            .map_err(|e| format!("shaderc: error: cannot open input file '{name}': {e}"))?
+    };
+
+    let stage = input
+        .stage
+        .or_else(|| args::stage_from_extension(&name))
+        .unwrap_or(ShaderKind::InferFromSource);
+    let language = input.language.unwrap_or(if name.ends_with(".hlsl") {
+        SourceLanguage::HLSL
+    } else {
+        SourceLanguage::GLSL
+    });
+    let mut options = options
+        .clone()
+        .ok_or("shaderc: error: failed to create compile options")?;
+    options.set_source_language(language);
+
+    let result = match (args.mode, stage) {
+        (Mode::Preprocess, _) => {
+            compiler.preprocess(&source, &name, &args.entry_point, Some(&options))
+        }
+        (Mode::Assembly, ShaderKind::SpirvAssembly) => {
+            return Err(format!(
+                "shaderc: error: '{name}': cannot compile SPIR-V assembly to assembly"
+            ))
+        }
+        (_, ShaderKind::SpirvAssembly) => compiler.assemble(&source, Some(&options)),
+        (Mode::Assembly, _) => compiler.compile_into_spirv_assembly(
+            &source,
+            stage,
+            &name,
+            &args.entry_point,
+            Some(&options),
+        ),
+        (Mode::Link, _) | (Mode::Compile, _) => {
+            compiler.compile_into_spirv(&source, stage, &name, &args.entry_point, Some(&options))
+        }
+    };
+    let artifact = match result {
+        Ok(artifact) => artifact,
+        Err(shaderc::Error::CompilationError(count, messages)) => {
+            return Err(format!(
+                "{}\n{} error{} generated.",
+                messages.trim_end(),
+                count,
+                if count == 1 { "" } else { "s" }
+            ))
+        }
+        Err(error) => return Err(format!("{name}: error: {error}")),
+    };
+    let warnings = if artifact.get_num_warnings() > 0 {
+        artifact.get_warning_messages()
+    } else {
+        String::new()
+    };
+
+    let output = match (&args.output, args.mode) {
+        (Some(output), _) => Some(output.clone()),
+        (None, Mode::Preprocess) => None,
+        (None, Mode::Link) => Some(PathBuf::from("a.spv")),
+        (None, mode) => {
+            let file_name = input
+                .path
+                .file_name()
+                .filter(|_| !stdin)
+                .map_or_else(|| "a".into(), |name| name.to_string_lossy().into_owned());
+            let extension = if mode == Mode::Assembly {
+                "spvasm"
+            } else {
+                "spv"
+            };
+            Some(PathBuf::from(format!("{file_name}.{extension}")))
+        }
+    };
+    let bytes = if args.mode == Mode::Preprocess || args.mode == Mode::Assembly {
+        artifact.as_text().into_bytes()
+    } else {
+        artifact.as_binary_u8().to_vec()
+    };
+    let sha256 = sha256_hex(&bytes);
+    let output = output.filter(|path| path != Path::new("-"));
+    let binary = args.mode == Mode::Link || args.mode == Mode::Compile;
+    if output.is_none() && binary && io::stdout().is_terminal() {
+        return Err(
+            "shaderc: error: refusing to write SPIR-V binary to a terminal; redirect \
+             standard output or use -S"
+                .to_string(),
+        );
+    }
+    match output {
+        Some(ref path) => fs::write(path, bytes).map_err(|e| {
+            format!(
+                "shaderc: error: cannot write output file '{}': {}",
+                path.display(),
+                e
+            )
+        })?,
+        None => io::stdout()
+            .write_all(&bytes)
+            .map_err(|e| format!("shaderc: error: cannot write to standard output: {e}"))?,
+    }
+    Ok(Compiled {
+        output,
+        sha256,
+        warnings,
+    })
+}
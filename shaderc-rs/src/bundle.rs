@@ -0,0 +1,642 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Named SPIR-V modules shipped together as a single blob.
+//!
+//! A `ShaderBundle` maps names to compiled modules, each recording the
+//! toolchain that produced it. `ShaderBundle::to_bytes` writes it in a
+//! compact binary format meant to be shipped with an application, for example
+//! through `include_bytes!`, and `ShaderBundle::from_bytes` reads it back.
+//!
+//! With the `signing` feature, `ShaderBundle::sign` signs each entry with an
+//! ed25519 key, and `ShaderBundle::from_bytes_verified` rejects a bundle
+//! whose entries are not all signed by the expected key, so that shipped
+//! shaders cannot be tampered with and mod platforms can attest which
+//! toolchain produced a module:
+//!
+//! ```no_run
+//! # #[cfg(feature = "signing")]
+//! # {
+//! use shaderc::bundle::{ShaderBundle, SigningKey};
+//!
+//! let key = SigningKey::from_bytes(&[7; 32]);
+//! let mut bundle = ShaderBundle::new();
+//! bundle.insert("tri.frag", &[0x0723_0203, 0x0001_0000, 0, 1, 0]);
+//! bundle.sign(&key);
+//! let bytes = bundle.to_bytes();
+//!
+//! let loaded = ShaderBundle::from_bytes_verified(&bytes, &key.verifying_key()).unwrap();
+//! assert!(loaded.get("tri.frag").unwrap().is_signed());
+//! # }
+//! ```
+//!
+//! A library crate can ship its shaders as a `ShaderPackage`, a bundle under
+//! a namespace, written by its build script and embedded with
+//! `include_bytes!`. A binary merges the packages of its dependencies into
+//! its own bundle with `ShaderBundle::merge`, which puts each entry under the
+//! namespace of its package, as `namespace/name`, and fails if that name is
+//! already taken:
+//!
+//! ```no_run
+//! use shaderc::bundle::{ShaderBundle, ShaderPackage};
+//!
+//! // In the build script of the library crate `pbr`:
+//! let mut shaders = ShaderBundle::new();
+//! shaders.insert("lit.frag", &[0x0723_0203, 0x0001_0000, 0, 1, 0]);
+//! let package = ShaderPackage::new("pbr", shaders).unwrap();
+//! std::fs::write("target/pbr.shaderpkg", package.to_bytes()).unwrap();
+//!
+//! // In the binary, with the bytes embedded by `pbr`:
+//! let bytes = std::fs::read("target/pbr.shaderpkg").unwrap();
+//! let mut bundle = ShaderBundle::new();
+//! bundle.merge(&ShaderPackage::from_bytes(&bytes).unwrap()).unwrap();
+//! assert!(bundle.get("pbr/lit.frag").is_some());
+//! ```
+
+#[cfg(feature = "signing")]
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+#[cfg(feature = "signing")]
+use ed25519_dalek::{Signature, Signer};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::{error, fmt};
+
+use crate::{ModuleError, ModuleReflection, SpirvModule};
+
+/// Magic bytes starting a bundle, ending with the format version.
+const BUNDLE_MAGIC: &[u8; 8] = b"SHBUNDL1";
+
+/// Magic bytes starting a package, ending with the format version.
+const PACKAGE_MAGIC: &[u8; 8] = b"SHPACKG1";
+
+/// Separator between the namespace of a package and the names of its
+/// entries.
+const NAMESPACE_SEPARATOR: char = '/';
+
+/// Domain separating entry signatures from other uses of the same key.
+#[cfg(feature = "signing")]
+const SIGNATURE_CONTEXT: &[u8] = b"shaderc bundle entry\0";
+
+/// Length of an ed25519 signature.
+const SIGNATURE_LENGTH: usize = 64;
+
+/// Error returned when loading or verifying a bundle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleError {
+    /// The bytes are not a bundle, or are truncated.
+    Malformed(String),
+    /// The named entry has no signature.
+    Unsigned(String),
+    /// The signature of the named entry does not match its contents and the
+    /// verifying key.
+    BadSignature(String),
+    /// The namespace of a package is empty or contains a `/`.
+    InvalidNamespace(String),
+    /// The named entry of a package is not a well-formed SPIR-V module.
+    InvalidModule(String, ModuleError),
+    /// Merging a package would replace the named entry.
+    Collision(String),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BundleError::Malformed(ref r) => write!(f, "malformed shader bundle: {r}"),
+            BundleError::Unsigned(ref name) => write!(f, "bundle entry '{name}' is not signed"),
+            BundleError::BadSignature(ref name) => {
+                write!(f, "bundle entry '{name}' has an invalid signature")
+            }
+            BundleError::InvalidNamespace(ref namespace) => {
+                write!(f, "invalid package namespace '{namespace}'")
+            }
+            BundleError::InvalidModule(ref name, ref error) => {
+                write!(f, "bundle entry '{name}': {error}")
+            }
+            BundleError::Collision(ref name) => {
+                write!(f, "bundle entry '{name}' already exists")
+            }
+        }
+    }
+}
+
+impl error::Error for BundleError {}
+
+/// A module of a `ShaderBundle`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleEntry {
+    spirv: Vec<u32>,
+    toolchain: String,
+    signature: Option<[u8; SIGNATURE_LENGTH]>,
+}
+
+impl BundleEntry {
+    /// Returns the SPIR-V words of the module.
+    pub fn spirv(&self) -> &[u32] {
+        &self.spirv
+    }
+
+    /// Returns the toolchain that compiled the module, as recorded when it
+    /// was inserted: the versions of this crate and of the SPIR-V generated
+    /// by the native library.
+    pub fn toolchain(&self) -> &str {
+        &self.toolchain
+    }
+
+    /// Returns whether the entry carries a signature.
+    ///
+    /// Only `ShaderBundle::verify` tells whether the signature is valid.
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+}
+
+/// A set of named SPIR-V modules.
+///
+/// Entries are kept sorted by name, so that equal bundles are written to the
+/// same bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShaderBundle {
+    entries: BTreeMap<String, BundleEntry>,
+}
+
+impl ShaderBundle {
+    /// Returns an empty bundle.
+    pub fn new() -> ShaderBundle {
+        ShaderBundle::default()
+    }
+
+    /// Adds the module `spirv` under `name`, recording the toolchain of this
+    /// process as the one that produced it, and returns the entry it
+    /// replaces, if any.
+    ///
+    /// The new entry is unsigned.
+    pub fn insert<N: Into<String>>(&mut self, name: N, spirv: &[u32]) -> Option<BundleEntry> {
+        let entry = BundleEntry {
+            spirv: spirv.to_vec(),
+            toolchain: toolchain(),
+            signature: None,
+        };
+        self.entries.insert(name.into(), entry)
+    }
+
+    /// Returns the entry named `name`.
+    pub fn get(&self, name: &str) -> Option<&BundleEntry> {
+        self.entries.get(name)
+    }
+
+    /// Removes the entry named `name` and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<BundleEntry> {
+        self.entries.remove(name)
+    }
+
+    /// Returns the entries with their names, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BundleEntry)> {
+        self.entries
+            .iter()
+            .map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the bundle has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the bundle in its binary format.
+    ///
+    /// The format is a header identifying it, followed by each entry: the
+    /// length and bytes of its name, of its toolchain and of its SPIR-V in
+    /// little-endian order, then the length and bytes of its signature, if
+    /// any. Lengths are 32-bit little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BUNDLE_MAGIC.to_vec();
+        for (name, entry) in &self.entries {
+            encode_entry(&mut bytes, name, entry);
+            match entry.signature {
+                Some(ref signature) => {
+                    bytes.extend_from_slice(&(SIGNATURE_LENGTH as u32).to_le_bytes());
+                    bytes.extend_from_slice(signature);
+                }
+                None => bytes.extend_from_slice(&0u32.to_le_bytes()),
+            }
+        }
+        bytes
+    }
+
+    /// Reads a bundle written by `to_bytes`.
+    ///
+    /// Signatures are kept but not checked; see `from_bytes_verified`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ShaderBundle, BundleError> {
+        let mut reader = Reader(bytes);
+        if reader.take(BUNDLE_MAGIC.len())? != BUNDLE_MAGIC {
+            return Err(BundleError::Malformed("not a shader bundle".to_string()));
+        }
+        reader.entries()
+    }
+
+    /// Adds the entries of `package` under its namespace, returning the
+    /// number of entries added.
+    ///
+    /// Each entry is named `namespace/name`. If one of these names is
+    /// already taken, returns `BundleError::Collision` for the first of them
+    /// and leaves the bundle unchanged.
+    ///
+    /// The signatures of the entries, which cover their names in the
+    /// package, do not carry over: verify the package before merging it,
+    /// and sign the merged bundle.
+    pub fn merge(&mut self, package: &ShaderPackage) -> Result<usize, BundleError> {
+        let qualified = |name: &str| format!("{}{NAMESPACE_SEPARATOR}{name}", package.namespace);
+        if let Some(name) = package
+            .bundle
+            .entries
+            .keys()
+            .map(|name| qualified(name))
+            .find(|name| self.entries.contains_key(name))
+        {
+            return Err(BundleError::Collision(name));
+        }
+        for (name, entry) in &package.bundle.entries {
+            let entry = BundleEntry {
+                signature: None,
+                ..entry.clone()
+            };
+            self.entries.insert(qualified(name), entry);
+        }
+        Ok(package.bundle.len())
+    }
+
+    /// Signs every entry with `key`, replacing previous signatures.
+    ///
+    /// The signature covers the name, the toolchain and the SPIR-V of the
+    /// entry, so none of them can be changed without invalidating it.
+    ///
+    /// Available with the `signing` feature.
+    #[cfg(feature = "signing")]
+    pub fn sign(&mut self, key: &SigningKey) {
+        for (name, entry) in self.entries.iter_mut() {
+            let signature = key.sign(&signed_message(name, entry));
+            entry.signature = Some(signature.to_bytes());
+        }
+    }
+
+    /// Checks that every entry is signed by the key `key` and unchanged
+    /// since, returning the error for the first entry that is not.
+    ///
+    /// Available with the `signing` feature.
+    #[cfg(feature = "signing")]
+    pub fn verify(&self, key: &VerifyingKey) -> Result<(), BundleError> {
+        for (name, entry) in &self.entries {
+            let signature = entry
+                .signature
+                .ok_or_else(|| BundleError::Unsigned(name.clone()))?;
+            key.verify_strict(
+                &signed_message(name, entry),
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| BundleError::BadSignature(name.clone()))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a bundle written by `to_bytes` and verifies it with `key`.
+    ///
+    /// This is the way to load a shipped bundle: an entry that is unsigned
+    /// or was modified fails loading.
+    ///
+    /// Available with the `signing` feature.
+    #[cfg(feature = "signing")]
+    pub fn from_bytes_verified(
+        bytes: &[u8],
+        key: &VerifyingKey,
+    ) -> Result<ShaderBundle, BundleError> {
+        let bundle = ShaderBundle::from_bytes(bytes)?;
+        bundle.verify(key)?;
+        Ok(bundle)
+    }
+}
+
+/// A bundle published under a namespace, with the reflection of its modules.
+///
+/// Packages let library crates ship shaders for binaries to merge into their
+/// own bundle with `ShaderBundle::merge`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderPackage {
+    namespace: String,
+    bundle: ShaderBundle,
+    reflections: BTreeMap<String, ModuleReflection>,
+}
+
+impl ShaderPackage {
+    /// Returns a package of `bundle` under `namespace`.
+    ///
+    /// Returns an error if the namespace is empty or contains a `/`, or if
+    /// an entry cannot be reflected.
+    pub fn new<N: Into<String>>(
+        namespace: N,
+        bundle: ShaderBundle,
+    ) -> Result<ShaderPackage, BundleError> {
+        let namespace = namespace.into();
+        if namespace.is_empty() || namespace.contains(NAMESPACE_SEPARATOR) {
+            return Err(BundleError::InvalidNamespace(namespace));
+        }
+        let mut reflections = BTreeMap::new();
+        for (name, entry) in &bundle.entries {
+            let reflection = SpirvModule::from_words(entry.spirv.clone())
+                .and_then(|module| module.reflect())
+                .map_err(|error| BundleError::InvalidModule(name.clone(), error))?;
+            reflections.insert(name.clone(), reflection);
+        }
+        Ok(ShaderPackage {
+            namespace,
+            bundle,
+            reflections,
+        })
+    }
+
+    /// Returns the namespace of the package.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns the bundle of the package.
+    pub fn bundle(&self) -> &ShaderBundle {
+        &self.bundle
+    }
+
+    /// Returns the reflection of the entry named `name`, giving its entry
+    /// points, descriptor bindings and stage variables.
+    pub fn reflection(&self, name: &str) -> Option<&ModuleReflection> {
+        self.reflections.get(name)
+    }
+
+    /// Writes the package: a header identifying the format, the length and
+    /// bytes of the namespace, then the entries in bundle format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = PACKAGE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(self.namespace.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(self.namespace.as_bytes());
+        bytes.extend_from_slice(&self.bundle.to_bytes()[BUNDLE_MAGIC.len()..]);
+        bytes
+    }
+
+    /// Reads a package written by `to_bytes`.
+    ///
+    /// The reflection of its modules is computed again rather than stored,
+    /// which also checks them.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ShaderPackage, BundleError> {
+        let mut reader = Reader(bytes);
+        if reader.take(PACKAGE_MAGIC.len())? != PACKAGE_MAGIC {
+            return Err(BundleError::Malformed("not a shader package".to_string()));
+        }
+        let namespace = reader.string()?;
+        let bundle = reader.entries()?;
+        ShaderPackage::new(namespace, bundle)
+    }
+}
+
+/// Appends the name, toolchain and SPIR-V of an entry, in bundle format.
+fn encode_entry(bytes: &mut Vec<u8>, name: &str, entry: &BundleEntry) {
+    bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(&(entry.toolchain.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(entry.toolchain.as_bytes());
+    bytes.extend_from_slice(&(entry.spirv.len() as u32 * 4).to_le_bytes());
+    for word in &entry.spirv {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// Returns the message signed for an entry.
+#[cfg(feature = "signing")]
+fn signed_message(name: &str, entry: &BundleEntry) -> Vec<u8> {
+    let mut message = SIGNATURE_CONTEXT.to_vec();
+    encode_entry(&mut message, name, entry);
+    message
+}
+
+/// Returns the identity of the toolchain recorded in new entries.
+fn toolchain() -> String {
+    let (version, revision) = crate::get_spirv_version();
+    format!(
+        "shaderc {} spirv {}.{} revision {}",
+        env!("CARGO_PKG_VERSION"),
+        (version >> 16) & 0xff,
+        (version >> 8) & 0xff,
+        revision,
+    )
+}
+
+/// Reads the fields of a bundle.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BundleError> {
+        if self.0.len() < len {
+            return Err(BundleError::Malformed(
+                "truncated shader bundle".to_string(),
+            ));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    /// Reads a length followed by that many bytes.
+    fn chunk(&mut self) -> Result<&'a [u8], BundleError> {
+        let len = u32::from_le_bytes(self.take(4)?.try_into().unwrap());
+        self.take(len as usize)
+    }
+
+    /// Reads entries up to the end.
+    fn entries(&mut self) -> Result<ShaderBundle, BundleError> {
+        let mut bundle = ShaderBundle::new();
+        while !self.0.is_empty() {
+            let name = self.string()?;
+            let toolchain = self.string()?;
+            let spirv = self.chunk()?;
+            if spirv.len() % 4 != 0 {
+                return Err(BundleError::Malformed(format!(
+                    "SPIR-V of '{name}' is not a whole number of words"
+                )));
+            }
+            let spirv = spirv
+                .chunks_exact(4)
+                .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+                .collect();
+            let signature = match self.chunk()? {
+                [] => None,
+                signature => Some(signature.try_into().map_err(|_| {
+                    BundleError::Malformed(format!("signature of '{name}' has a bad length"))
+                })?),
+            };
+            let entry = BundleEntry {
+                spirv,
+                toolchain,
+                signature,
+            };
+            if bundle.entries.insert(name.clone(), entry).is_some() {
+                return Err(BundleError::Malformed(format!(
+                    "entry '{name}' appears twice"
+                )));
+            }
+        }
+        Ok(bundle)
+    }
+
+    fn string(&mut self) -> Result<String, BundleError> {
+        String::from_utf8(self.chunk()?.to_vec())
+            .map_err(|_| BundleError::Malformed("name is not UTF-8".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::tests::{inst, module};
+    use crate::spirv_module::{encode_string, op};
+
+    const MODULE: [u32; 5] = [0x0723_0203, 0x0001_0000, 0, 1, 0];
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let mut bundle = ShaderBundle::new();
+        assert!(bundle.insert("b.frag", &MODULE).is_none());
+        bundle.insert("a.vert", &MODULE[..4]);
+        assert!(bundle.insert("b.frag", &MODULE).is_some());
+        assert_eq!(2, bundle.len());
+        let names: Vec<&str> = bundle.iter().map(|(name, _)| name).collect();
+        assert_eq!(vec!["a.vert", "b.frag"], names);
+
+        let entry = bundle.get("b.frag").unwrap();
+        assert_eq!(&MODULE[..], entry.spirv());
+        assert!(entry.toolchain().starts_with("shaderc "));
+        assert!(!entry.is_signed());
+
+        let bytes = bundle.to_bytes();
+        assert_eq!(bundle, ShaderBundle::from_bytes(&bytes).unwrap());
+        assert_matches!(
+            ShaderBundle::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(BundleError::Malformed(_))
+        );
+        assert_matches!(
+            ShaderBundle::from_bytes(b"not a bundle"),
+            Err(BundleError::Malformed(_))
+        );
+        assert_eq!(
+            ShaderBundle::new(),
+            ShaderBundle::from_bytes(BUNDLE_MAGIC).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_package_merge() {
+        let lit = module(&[inst(
+            op::ENTRY_POINT,
+            &[[4, 1].as_ref(), &encode_string("main")].concat(),
+        )]);
+        let mut shaders = ShaderBundle::new();
+        shaders.insert("lit.frag", lit.words());
+        let package = ShaderPackage::new("pbr", shaders.clone()).unwrap();
+        assert_eq!("pbr", package.namespace());
+        assert_eq!(
+            "main",
+            package.reflection("lit.frag").unwrap().entry_points()[0].name
+        );
+
+        let bytes = package.to_bytes();
+        assert_eq!(package, ShaderPackage::from_bytes(&bytes).unwrap());
+        assert_matches!(
+            ShaderPackage::from_bytes(&shaders.to_bytes()),
+            Err(BundleError::Malformed(_))
+        );
+
+        let mut bundle = ShaderBundle::new();
+        bundle.insert("lit.frag", &MODULE);
+        assert_eq!(Ok(1), bundle.merge(&package));
+        assert_eq!(lit.words(), bundle.get("pbr/lit.frag").unwrap().spirv());
+        assert_eq!(&MODULE[..], bundle.get("lit.frag").unwrap().spirv());
+
+        let before = bundle.clone();
+        assert_eq!(
+            Err(BundleError::Collision("pbr/lit.frag".to_string())),
+            bundle.merge(&package)
+        );
+        assert_eq!(before, bundle);
+    }
+
+    #[test]
+    fn test_package_invalid() {
+        let mut shaders = ShaderBundle::new();
+        shaders.insert("lit.frag", &MODULE);
+        for namespace in ["", "pbr/v2"] {
+            assert_eq!(
+                Err(BundleError::InvalidNamespace(namespace.to_string())),
+                ShaderPackage::new(namespace, shaders.clone())
+            );
+        }
+        shaders.insert("bad.frag", &[1, 2, 3]);
+        assert_eq!(
+            Err(BundleError::InvalidModule(
+                "bad.frag".to_string(),
+                ModuleError::BadMagic
+            )),
+            ShaderPackage::new("pbr", shaders)
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_bundle_signing() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let mut bundle = ShaderBundle::new();
+        bundle.insert("a.vert", &MODULE);
+        bundle.insert("b.frag", &MODULE);
+        assert_eq!(
+            Err(BundleError::Unsigned("a.vert".to_string())),
+            bundle.verify(&key.verifying_key())
+        );
+        bundle.sign(&key);
+        let bytes = bundle.to_bytes();
+        let loaded = ShaderBundle::from_bytes_verified(&bytes, &key.verifying_key()).unwrap();
+        assert!(loaded.get("b.frag").unwrap().is_signed());
+
+        let other = SigningKey::from_bytes(&[8; 32]);
+        assert_eq!(
+            Err(BundleError::BadSignature("a.vert".to_string())),
+            ShaderBundle::from_bytes_verified(&bytes, &other.verifying_key())
+        );
+
+        // Flip a bit of the last SPIR-V word of b.frag, just before its
+        // signature.
+        let mut tampered = bytes.clone();
+        let last_word = tampered.len() - SIGNATURE_LENGTH - 4 - 1;
+        tampered[last_word] ^= 1;
+        assert_eq!(
+            Err(BundleError::BadSignature("b.frag".to_string())),
+            ShaderBundle::from_bytes_verified(&tampered, &key.verifying_key())
+        );
+
+        let mut replaced = loaded.clone();
+        replaced.insert("b.frag", &MODULE);
+        assert_eq!(
+            Err(BundleError::Unsigned("b.frag".to_string())),
+            replaced.verify(&key.verifying_key())
+        );
+    }
+}
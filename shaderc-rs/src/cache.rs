@@ -0,0 +1,626 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-disk cache of compilation outputs.
+//!
+//! Entries are keyed by a `CacheKey`, the SHA-256 digest of everything that
+//! affects the output, and stored as one file per entry in the cache
+//! directory. Available with the `cache` feature.
+//!
+//! A cache can be given a maximum size, in which case the least recently
+//! used entries are evicted when inserting makes it grow past that size.
+//!
+//! A whole cache can be exported to a single archive file with
+//! `ShaderCache::export_archive`, for example to keep it as a CI artifact,
+//! and imported into another cache with `ShaderCache::import_archive`.
+//!
+//! ```no_run
+//! use shaderc::cache::{CacheKey, ShaderCache};
+//!
+//! let cache = ShaderCache::open("target/shader-cache").unwrap();
+//! let key = CacheKey::builder().add("tri.frag").add("void main() {}").finish();
+//! if cache.get(&key).is_none() {
+//!     cache.insert(&key, &[0x03, 0x02, 0x23, 0x07]).unwrap();
+//! }
+//! ```
+//!
+//! Preprocessing, which reads every include, can be cached apart from the
+//! compiled modules with `ShaderCache::preprocess`. Its entries are keyed by
+//! the inputs of the preprocessor only, so compiling the same source with
+//! another optimization level or SPIR-V version reuses them.
+//!
+//! ```no_run
+//! use shaderc::cache::{CacheKey, ShaderCache};
+//! use shaderc::{Compiler, ShaderKind};
+//!
+//! let compiler = Compiler::new().unwrap();
+//! let cache = ShaderCache::open("target/shader-cache").unwrap();
+//! let source = "#version 450\n#include \"common.glsl\"\nvoid main() {}";
+//! // Identifies the includes, e.g. by their paths and modification times.
+//! let include_set = ["common.glsl 1700000000"];
+//! let preprocessed = cache
+//!     .preprocess(&compiler, source, "tri.frag", "main", &include_set, None)
+//!     .unwrap();
+//! let key = CacheKey::builder().add("fragment").add(&preprocessed).finish();
+//! if cache.get(&key).is_none() {
+//!     let artifact = compiler
+//!         .compile_into_spirv(&preprocessed, ShaderKind::Fragment, "tri.frag", "main", None)
+//!         .unwrap();
+//!     cache.insert(&key, artifact.as_binary_u8()).unwrap();
+//! }
+//! ```
+
+use sha2::{Digest, Sha256};
+use shaderc_sys as scs;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Instant, SystemTime};
+
+use crate::events::{self, Event};
+use crate::{CompileOptions, Error, ShaderCompiler};
+
+/// Magic bytes starting a cache archive, ending with the format version.
+const ARCHIVE_MAGIC: &[u8; 8] = b"SHCACHE1";
+
+/// Key of a cache entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey([u8; 32]);
+
+impl CacheKey {
+    /// Returns a builder hashing the inputs of a cache entry.
+    ///
+    /// The builder starts with the identity of the compiler: the versions of
+    /// this crate and of the SPIR-V generated by the native library, and where
+    /// the build script linked that library from, down to the size and
+    /// modification time of its files. Upgrading or relinking either,
+    /// which can change the generated code, thus invalidates all entries.
+    pub fn builder() -> CacheKeyBuilder {
+        CacheKeyBuilder::with_identity(&library_identity())
+    }
+
+    /// Returns the key of the preprocessed form of `source_text`, as cached
+    /// by `ShaderCache::preprocess`.
+    ///
+    /// The key covers the source, `input_file_name`, which `__FILE__`,
+    /// `#line` directives and relative includes see, `include_set`, which
+    /// must identify the contents of the files the source includes, and the
+    /// settings of `options` affecting the preprocessor: macros, source
+    /// language, forced version and target environment, which predefines
+    /// `VULKAN` or `GL_SPIRV`. It never equals a key of `builder`.
+    pub fn preprocessed(
+        source_text: &str,
+        input_file_name: &str,
+        include_set: &[&str],
+        options: Option<&CompileOptions>,
+    ) -> CacheKey {
+        let mut builder = CacheKey::builder();
+        builder
+            .add("preprocessed")
+            .add(source_text)
+            .add(input_file_name);
+        builder.add((include_set.len() as u64).to_le_bytes());
+        for include in include_set {
+            builder.add(include);
+        }
+        if let Some(settings) = options.map(|options| &options.settings) {
+            for (name, value) in &settings.macros {
+                builder.add(name).add(value.as_deref().unwrap_or(""));
+            }
+            builder.add(format!(
+                "{:?} {:?} {:?}",
+                settings.source_language, settings.forced_version_profile, settings.target_env
+            ));
+        }
+        builder.finish()
+    }
+
+    /// Returns the raw digest of this key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Formats the key as lowercase hexadecimal.
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Incrementally hashes the inputs of a cache entry into a `CacheKey`.
+#[derive(Clone)]
+pub struct CacheKeyBuilder(Sha256);
+
+impl CacheKeyBuilder {
+    /// Returns a builder starting with the compiler identity `identity`.
+    fn with_identity(identity: &str) -> CacheKeyBuilder {
+        let mut builder = CacheKeyBuilder(Sha256::new());
+        builder.add(identity);
+        builder
+    }
+
+    /// Adds an input to the key.
+    ///
+    /// Each input is length-prefixed, so adding `"ab"` then `"c"` gives a
+    /// different key than adding `"a"` then `"bc"`.
+    pub fn add<T: AsRef<[u8]>>(&mut self, input: T) -> &mut CacheKeyBuilder {
+        let input = input.as_ref();
+        self.0.update((input.len() as u64).to_le_bytes());
+        self.0.update(input);
+        self
+    }
+
+    /// Returns the key of the inputs added so far.
+    pub fn finish(&self) -> CacheKey {
+        CacheKey(self.0.clone().finalize().into())
+    }
+}
+
+/// A directory of cached compilation outputs.
+///
+/// Several processes can share a cache directory: entries are written to a
+/// temporary file first and then renamed into place.
+#[derive(Clone, Debug)]
+pub struct ShaderCache {
+    dir: PathBuf,
+    max_size: Option<u64>,
+}
+
+impl ShaderCache {
+    /// Opens the cache stored in `dir`, creating the directory if needed.
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<ShaderCache> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(ShaderCache {
+            dir,
+            max_size: None,
+        })
+    }
+
+    /// Returns this cache limited to `max_size` bytes of entries.
+    ///
+    /// The limit is enforced by `insert` and `gc`, which evict the least
+    /// recently used entries first.
+    pub fn with_max_size(mut self, max_size: u64) -> ShaderCache {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Returns the maximum size of the entries of the cache, if limited.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Returns the directory the cache is stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the data cached under `key`, or `None` if there is none.
+    ///
+    /// A hit marks the entry as the most recently used one.
+    pub fn get(&self, key: &CacheKey) -> Option<Vec<u8>> {
+        let start = Instant::now();
+        let path = self.entry_path(key);
+        let Ok(data) = fs::read(&path) else {
+            events::emit(Event::CacheMiss {
+                key: &key.0,
+                duration: start.elapsed(),
+            });
+            return None;
+        };
+        events::emit(Event::CacheHit {
+            key: &key.0,
+            duration: start.elapsed(),
+        });
+        if self.max_size.is_some() {
+            let _ = fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(SystemTime::now()));
+        }
+        Some(data)
+    }
+
+    /// Caches `data` under `key`, replacing any previous entry.
+    ///
+    /// If the cache has a maximum size, entries are then evicted as by `gc`.
+    pub fn insert(&self, key: &CacheKey, data: &[u8]) -> io::Result<()> {
+        let path = self.entry_path(key);
+        // Unique per call, so that threads inserting the same key do not
+        // write or rename each other's file.
+        static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let tmp = self.dir.join(format!(
+            "{}.{}.{}.tmp",
+            key,
+            process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })?;
+        if self.max_size.is_some() {
+            self.gc()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the preprocessed form of `source_text`, as by
+    /// `ShaderCompiler::preprocess`, from the cache or else by preprocessing
+    /// it with `compiler` and caching the result.
+    ///
+    /// The entry is keyed by `CacheKey::preprocessed`, so `include_set` must
+    /// change whenever an included file does. Preprocessing errors are not
+    /// cached, and failing to write the entry gives `Error::InternalError`.
+    pub fn preprocess<C: ShaderCompiler + ?Sized>(
+        &self,
+        compiler: &C,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        include_set: &[&str],
+        options: Option<&CompileOptions>,
+    ) -> crate::Result<String> {
+        let key = CacheKey::preprocessed(source_text, input_file_name, include_set, options);
+        if let Some(text) = self.get(&key).and_then(|data| String::from_utf8(data).ok()) {
+            return Ok(text);
+        }
+        let text = compiler
+            .preprocess(source_text, input_file_name, entry_point_name, options)?
+            .as_text();
+        self.insert(&key, text.as_bytes())
+            .map_err(|e| Error::InternalError(format!("cannot write shader cache: {e}")))?;
+        Ok(text)
+    }
+
+    /// Returns the total size in bytes of the entries of the cache.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(self.entries()?.iter().map(|e| e.1).sum())
+    }
+
+    /// Evicts the least recently used entries until the cache fits in its
+    /// maximum size, returning the number of bytes freed.
+    ///
+    /// Does nothing for caches without a maximum size.
+    pub fn gc(&self) -> io::Result<u64> {
+        let max_size = match self.max_size {
+            Some(max_size) => max_size,
+            None => return Ok(0),
+        };
+        let mut entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|e| e.1).sum();
+        entries.sort_by_key(|e| e.2);
+        let mut freed = 0;
+        for (path, len, _) in entries {
+            if size <= max_size {
+                break;
+            }
+            match fs::remove_file(&path) {
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+                result => result?,
+            }
+            size -= len;
+            freed += len;
+        }
+        Ok(freed)
+    }
+
+    /// Writes all entries of the cache to the archive file `path`, returning
+    /// the number of entries written.
+    ///
+    /// The archive holds each key followed by the length and the data of its
+    /// entry, after a header identifying the format.
+    pub fn export_archive<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        let mut entries = self.entries()?;
+        entries.sort();
+        let mut archive = BufWriter::new(fs::File::create(path)?);
+        archive.write_all(ARCHIVE_MAGIC)?;
+        let mut count = 0;
+        for (path, _, _) in entries {
+            let data = match fs::read(&path) {
+                Ok(data) => data,
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let key = path.file_name().and_then(|name| name.to_str()).unwrap();
+            archive.write_all(&parse_key(key).0)?;
+            archive.write_all(&(data.len() as u64).to_le_bytes())?;
+            archive.write_all(&data)?;
+            count += 1;
+        }
+        archive.flush()?;
+        Ok(count)
+    }
+
+    /// Inserts all entries of the archive file `path`, written by
+    /// `export_archive`, into the cache, returning the number of entries
+    /// imported.
+    ///
+    /// Entries already in the cache are replaced. Returns an error of kind
+    /// `InvalidData` if the file is not a cache archive.
+    pub fn import_archive<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        let mut archive = BufReader::new(fs::File::open(path)?);
+        let mut magic = [0; 8];
+        archive.read_exact(&mut magic)?;
+        if &magic != ARCHIVE_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a shader cache archive",
+            ));
+        }
+        let mut count = 0;
+        loop {
+            let mut key = [0; 32];
+            match archive.read_exact(&mut key) {
+                Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                result => result?,
+            }
+            let mut len = [0; 8];
+            archive.read_exact(&mut len)?;
+            let mut data = Vec::new();
+            let len = u64::from_le_bytes(len);
+            if archive.by_ref().take(len).read_to_end(&mut data)? as u64 != len {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "truncated shader cache archive",
+                ));
+            }
+            self.insert(&CacheKey(key), &data)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns the path, size and last use time of each entry.
+    fn entries(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let is_key = name.len() == 64
+                && name
+                    .to_str()
+                    .is_some_and(|n| n.bytes().all(|b| b.is_ascii_hexdigit()));
+            if !is_key {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let used = metadata.modified()?;
+            entries.push((entry.path(), metadata.len(), used));
+        }
+        Ok(entries)
+    }
+
+    /// Removes the entry cached under `key`, if any.
+    pub fn remove(&self, key: &CacheKey) -> io::Result<()> {
+        match fs::remove_file(self.entry_path(key)) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(key.to_string())
+    }
+}
+
+/// Returns the identity of the compiler the outputs depend on: the versions
+/// and the linked native library.
+fn library_identity() -> String {
+    let (spirv_version, revision) = crate::get_spirv_version();
+    format!(
+        "shaderc {} spirv {:#x} revision {} linked {:?}",
+        env!("CARGO_PKG_VERSION"),
+        spirv_version,
+        revision,
+        scs::LINK_FINGERPRINT,
+    )
+}
+
+/// Parses the file name of an entry, 64 hexadecimal digits, back into a key.
+fn parse_key(name: &str) -> CacheKey {
+    let mut key = [0; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&name[2 * i..2 * i + 2], 16).unwrap_or(0);
+    }
+    CacheKey(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CompilationArtifact, EnvVersion, MockCompiler, TargetEnv};
+    use std::env;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("shaderc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let key = CacheKey::builder().add("ab").add("c").finish();
+        assert_eq!(key, CacheKey::builder().add("ab").add("c").finish());
+        assert_ne!(key, CacheKey::builder().add("a").add("bc").finish());
+        assert_eq!(64, key.to_string().len());
+        let mut unversioned = CacheKeyBuilder(Sha256::new());
+        assert_ne!(key, unversioned.add("ab").add("c").finish());
+        assert!(library_identity().starts_with("shaderc "));
+    }
+
+    #[test]
+    fn test_cache_key_library_identity() {
+        let identity = library_identity();
+        assert!(identity.contains(&format!("{:?}", scs::LINK_FINGERPRINT)));
+        let key = CacheKeyBuilder::with_identity(&identity).add("a").finish();
+        assert_eq!(key, CacheKey::builder().add("a").finish());
+        let relinked = identity.replace(" linked ", " linked Some(\"other\") ");
+        let other = CacheKeyBuilder::with_identity(&relinked).add("a").finish();
+        assert_ne!(key, other);
+    }
+
+    #[test]
+    fn test_cache_insert_get_remove() {
+        let dir = test_dir("cache-insert");
+        let cache = ShaderCache::open(&dir).unwrap();
+        let key = CacheKey::builder().add("void main() {}").finish();
+        assert_eq!(None, cache.get(&key));
+        cache.insert(&key, b"spirv").unwrap();
+        assert_eq!(Some(b"spirv".to_vec()), cache.get(&key));
+        cache.remove(&key).unwrap();
+        assert_eq!(None, cache.get(&key));
+        cache.remove(&key).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_insert_concurrent() {
+        let dir = test_dir("cache-insert-concurrent");
+        let cache = ShaderCache::open(&dir).unwrap();
+        let key = CacheKey::builder().add("void main() {}").finish();
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..20 {
+                        cache.insert(&key, b"spirv").unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(Some(b"spirv".to_vec()), cache.get(&key));
+    }
+
+    #[test]
+    fn test_cache_gc() {
+        let dir = test_dir("cache-gc");
+        let cache = ShaderCache::open(&dir).unwrap();
+        let keys: Vec<CacheKey> = (0..3)
+            .map(|i: u8| CacheKey::builder().add([i]).finish())
+            .collect();
+        for key in &keys {
+            cache.insert(key, &[0; 10]).unwrap();
+        }
+        assert_eq!(30, cache.size().unwrap());
+        assert_eq!(0, cache.gc().unwrap());
+
+        let cache = cache.with_max_size(25);
+        let past = SystemTime::now() - std::time::Duration::from_secs(60);
+        for (i, key) in keys.iter().enumerate() {
+            let file = fs::File::options()
+                .write(true)
+                .open(cache.entry_path(key))
+                .unwrap();
+            file.set_modified(past + std::time::Duration::from_secs(i as u64))
+                .unwrap();
+        }
+        // Using the oldest entry makes the second one the least recently used.
+        assert!(cache.get(&keys[0]).is_some());
+        assert_eq!(10, cache.gc().unwrap());
+        assert!(cache.get(&keys[0]).is_some());
+        assert_eq!(None, cache.get(&keys[1]));
+
+        let key = CacheKey::builder().add("new").finish();
+        cache.insert(&key, &[0; 10]).unwrap();
+        assert_eq!(20, cache.size().unwrap());
+        assert!(cache.get(&key).is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_archive() {
+        let dir = test_dir("cache-archive");
+        let cache = ShaderCache::open(dir.join("a")).unwrap();
+        let a = CacheKey::builder().add("a").finish();
+        let b = CacheKey::builder().add("b").finish();
+        cache.insert(&a, b"spirv a").unwrap();
+        cache.insert(&b, b"").unwrap();
+        fs::write(dir.join("a").join("not-an-entry"), b"x").unwrap();
+        assert_eq!(a, parse_key(&a.to_string()));
+
+        let archive = dir.join("cache.bin");
+        assert_eq!(2, cache.export_archive(&archive).unwrap());
+        let imported = ShaderCache::open(dir.join("b")).unwrap();
+        assert_eq!(2, imported.import_archive(&archive).unwrap());
+        assert_eq!(Some(b"spirv a".to_vec()), imported.get(&a));
+        assert_eq!(Some(Vec::new()), imported.get(&b));
+
+        fs::write(&archive, b"not an archive").unwrap();
+        let error = imported.import_archive(&archive).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_preprocess() {
+        let dir = test_dir("cache-preprocess");
+        let cache = ShaderCache::open(&dir).unwrap();
+        let source = "#include \"a.glsl\"\nvoid main() {}";
+        let key = CacheKey::preprocessed(source, "a.frag", &["a.glsl 1"], None);
+        assert_ne!(
+            key,
+            CacheKey::preprocessed(source, "a.frag", &["a.glsl 2"], None)
+        );
+        assert_ne!(
+            key,
+            CacheKey::builder().add(source).add("a.glsl 1").finish()
+        );
+
+        let compiler = MockCompiler::new()
+            .with_default_result(Ok(CompilationArtifact::from_text("expanded".to_string())));
+        for _ in 0..2 {
+            let text = cache
+                .preprocess(&compiler, source, "a.frag", "main", &["a.glsl 1"], None)
+                .unwrap();
+            assert_eq!("expanded", text);
+        }
+        assert_eq!(1, compiler.calls().len());
+        assert_eq!(Some(b"expanded".to_vec()), cache.get(&key));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_key_preprocessed_file_name() {
+        let source = "#line 1\nint line = __LINE__;\n#include \"common.glsl\"";
+        assert_ne!(
+            CacheKey::preprocessed(source, "shaders/a.frag", &[], None),
+            CacheKey::preprocessed(source, "other/a.frag", &[], None)
+        );
+    }
+
+    #[test]
+    fn test_cache_key_preprocessed_target_env() {
+        let source = "#ifdef VULKAN\nvoid main() {}\n#endif";
+        let mut vulkan = CompileOptions::new().unwrap();
+        vulkan.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32);
+        let mut opengl = CompileOptions::new().unwrap();
+        opengl.set_target_env(TargetEnv::OpenGL, EnvVersion::OpenGL4_5 as u32);
+        assert_ne!(
+            CacheKey::preprocessed(source, "a.frag", &[], Some(&vulkan)),
+            CacheKey::preprocessed(source, "a.frag", &[], Some(&opengl))
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The capabilities and extensions declared by SPIR-V modules.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::spirv_module::{literal_string, op, SpirvModule};
+
+/// A SPIR-V capability, as declared by `OpCapability`.
+///
+/// Constants are provided for the capabilities of the core specification
+/// and of the Khronos, AMD and NVIDIA extensions usable from GLSL and HLSL.
+/// Other capabilities can still be represented by their number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Capability(pub u32);
+
+macro_rules! capabilities {
+    ($($constant:ident = $value:expr, $name:expr;)*) => {
+        impl Capability {
+            $(pub const $constant: Capability = Capability($value);)*
+
+            /// Returns the name of this capability in the SPIR-V
+            /// specification, like `Int64`, if it has a constant.
+            pub fn name(self) -> Option<&'static str> {
+                match self.0 {
+                    $($value => Some($name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+capabilities! {
+    MATRIX = 0, "Matrix";
+    SHADER = 1, "Shader";
+    GEOMETRY = 2, "Geometry";
+    TESSELLATION = 3, "Tessellation";
+    ADDRESSES = 4, "Addresses";
+    LINKAGE = 5, "Linkage";
+    KERNEL = 6, "Kernel";
+    VECTOR16 = 7, "Vector16";
+    FLOAT16_BUFFER = 8, "Float16Buffer";
+    FLOAT16 = 9, "Float16";
+    FLOAT64 = 10, "Float64";
+    INT64 = 11, "Int64";
+    INT64_ATOMICS = 12, "Int64Atomics";
+    IMAGE_BASIC = 13, "ImageBasic";
+    IMAGE_READ_WRITE = 14, "ImageReadWrite";
+    IMAGE_MIPMAP = 15, "ImageMipmap";
+    PIPES = 17, "Pipes";
+    GROUPS = 18, "Groups";
+    DEVICE_ENQUEUE = 19, "DeviceEnqueue";
+    LITERAL_SAMPLER = 20, "LiteralSampler";
+    ATOMIC_STORAGE = 21, "AtomicStorage";
+    INT16 = 22, "Int16";
+    TESSELLATION_POINT_SIZE = 23, "TessellationPointSize";
+    GEOMETRY_POINT_SIZE = 24, "GeometryPointSize";
+    IMAGE_GATHER_EXTENDED = 25, "ImageGatherExtended";
+    STORAGE_IMAGE_MULTISAMPLE = 27, "StorageImageMultisample";
+    UNIFORM_BUFFER_ARRAY_DYNAMIC_INDEXING = 28, "UniformBufferArrayDynamicIndexing";
+    SAMPLED_IMAGE_ARRAY_DYNAMIC_INDEXING = 29, "SampledImageArrayDynamicIndexing";
+    STORAGE_BUFFER_ARRAY_DYNAMIC_INDEXING = 30, "StorageBufferArrayDynamicIndexing";
+    STORAGE_IMAGE_ARRAY_DYNAMIC_INDEXING = 31, "StorageImageArrayDynamicIndexing";
+    CLIP_DISTANCE = 32, "ClipDistance";
+    CULL_DISTANCE = 33, "CullDistance";
+    IMAGE_CUBE_ARRAY = 34, "ImageCubeArray";
+    SAMPLE_RATE_SHADING = 35, "SampleRateShading";
+    IMAGE_RECT = 36, "ImageRect";
+    SAMPLED_RECT = 37, "SampledRect";
+    GENERIC_POINTER = 38, "GenericPointer";
+    INT8 = 39, "Int8";
+    INPUT_ATTACHMENT = 40, "InputAttachment";
+    SPARSE_RESIDENCY = 41, "SparseResidency";
+    MIN_LOD = 42, "MinLod";
+    SAMPLED_1D = 43, "Sampled1D";
+    IMAGE_1D = 44, "Image1D";
+    SAMPLED_CUBE_ARRAY = 45, "SampledCubeArray";
+    SAMPLED_BUFFER = 46, "SampledBuffer";
+    IMAGE_BUFFER = 47, "ImageBuffer";
+    IMAGE_MS_ARRAY = 48, "ImageMSArray";
+    STORAGE_IMAGE_EXTENDED_FORMATS = 49, "StorageImageExtendedFormats";
+    IMAGE_QUERY = 50, "ImageQuery";
+    DERIVATIVE_CONTROL = 51, "DerivativeControl";
+    INTERPOLATION_FUNCTION = 52, "InterpolationFunction";
+    TRANSFORM_FEEDBACK = 53, "TransformFeedback";
+    GEOMETRY_STREAMS = 54, "GeometryStreams";
+    STORAGE_IMAGE_READ_WITHOUT_FORMAT = 55, "StorageImageReadWithoutFormat";
+    STORAGE_IMAGE_WRITE_WITHOUT_FORMAT = 56, "StorageImageWriteWithoutFormat";
+    MULTI_VIEWPORT = 57, "MultiViewport";
+    SUBGROUP_DISPATCH = 58, "SubgroupDispatch";
+    NAMED_BARRIER = 59, "NamedBarrier";
+    PIPE_STORAGE = 60, "PipeStorage";
+    GROUP_NON_UNIFORM = 61, "GroupNonUniform";
+    GROUP_NON_UNIFORM_VOTE = 62, "GroupNonUniformVote";
+    GROUP_NON_UNIFORM_ARITHMETIC = 63, "GroupNonUniformArithmetic";
+    GROUP_NON_UNIFORM_BALLOT = 64, "GroupNonUniformBallot";
+    GROUP_NON_UNIFORM_SHUFFLE = 65, "GroupNonUniformShuffle";
+    GROUP_NON_UNIFORM_SHUFFLE_RELATIVE = 66, "GroupNonUniformShuffleRelative";
+    GROUP_NON_UNIFORM_CLUSTERED = 67, "GroupNonUniformClustered";
+    GROUP_NON_UNIFORM_QUAD = 68, "GroupNonUniformQuad";
+    SHADER_LAYER = 69, "ShaderLayer";
+    SHADER_VIEWPORT_INDEX = 70, "ShaderViewportIndex";
+    UNIFORM_DECORATION = 71, "UniformDecoration";
+    FRAGMENT_SHADING_RATE_KHR = 4422, "FragmentShadingRateKHR";
+    SUBGROUP_BALLOT_KHR = 4423, "SubgroupBallotKHR";
+    DRAW_PARAMETERS = 4427, "DrawParameters";
+    SUBGROUP_VOTE_KHR = 4431, "SubgroupVoteKHR";
+    STORAGE_BUFFER_16BIT_ACCESS = 4433, "StorageBuffer16BitAccess";
+    UNIFORM_AND_STORAGE_BUFFER_16BIT_ACCESS = 4434, "UniformAndStorageBuffer16BitAccess";
+    STORAGE_PUSH_CONSTANT_16 = 4435, "StoragePushConstant16";
+    STORAGE_INPUT_OUTPUT_16 = 4436, "StorageInputOutput16";
+    DEVICE_GROUP = 4437, "DeviceGroup";
+    MULTI_VIEW = 4439, "MultiView";
+    VARIABLE_POINTERS_STORAGE_BUFFER = 4441, "VariablePointersStorageBuffer";
+    VARIABLE_POINTERS = 4442, "VariablePointers";
+    ATOMIC_STORAGE_OPS = 4445, "AtomicStorageOps";
+    SAMPLE_MASK_POST_DEPTH_COVERAGE = 4447, "SampleMaskPostDepthCoverage";
+    STORAGE_BUFFER_8BIT_ACCESS = 4448, "StorageBuffer8BitAccess";
+    UNIFORM_AND_STORAGE_BUFFER_8BIT_ACCESS = 4449, "UniformAndStorageBuffer8BitAccess";
+    STORAGE_PUSH_CONSTANT_8 = 4450, "StoragePushConstant8";
+    DENORM_PRESERVE = 4464, "DenormPreserve";
+    DENORM_FLUSH_TO_ZERO = 4465, "DenormFlushToZero";
+    SIGNED_ZERO_INF_NAN_PRESERVE = 4466, "SignedZeroInfNanPreserve";
+    ROUNDING_MODE_RTE = 4467, "RoundingModeRTE";
+    ROUNDING_MODE_RTZ = 4468, "RoundingModeRTZ";
+    RAY_QUERY_PROVISIONAL_KHR = 4471, "RayQueryProvisionalKHR";
+    RAY_QUERY_KHR = 4472, "RayQueryKHR";
+    RAY_TRAVERSAL_PRIMITIVE_CULLING_KHR = 4478, "RayTraversalPrimitiveCullingKHR";
+    RAY_TRACING_KHR = 4479, "RayTracingKHR";
+    FLOAT16_IMAGE_AMD = 5008, "Float16ImageAMD";
+    IMAGE_GATHER_BIAS_LOD_AMD = 5009, "ImageGatherBiasLodAMD";
+    FRAGMENT_MASK_AMD = 5010, "FragmentMaskAMD";
+    STENCIL_EXPORT_EXT = 5013, "StencilExportEXT";
+    IMAGE_READ_WRITE_LOD_AMD = 5015, "ImageReadWriteLodAMD";
+    INT64_IMAGE_EXT = 5016, "Int64ImageEXT";
+    SHADER_CLOCK_KHR = 5055, "ShaderClockKHR";
+    SAMPLE_MASK_OVERRIDE_COVERAGE_NV = 5249, "SampleMaskOverrideCoverageNV";
+    GEOMETRY_SHADER_PASSTHROUGH_NV = 5251, "GeometryShaderPassthroughNV";
+    SHADER_VIEWPORT_INDEX_LAYER_EXT = 5254, "ShaderViewportIndexLayerEXT";
+    SHADER_VIEWPORT_MASK_NV = 5255, "ShaderViewportMaskNV";
+    SHADER_STEREO_VIEW_NV = 5259, "ShaderStereoViewNV";
+    PER_VIEW_ATTRIBUTES_NV = 5260, "PerViewAttributesNV";
+    FRAGMENT_FULLY_COVERED_EXT = 5265, "FragmentFullyCoveredEXT";
+    MESH_SHADING_NV = 5266, "MeshShadingNV";
+    IMAGE_FOOTPRINT_NV = 5282, "ImageFootprintNV";
+    MESH_SHADING_EXT = 5283, "MeshShadingEXT";
+    FRAGMENT_BARYCENTRIC_KHR = 5284, "FragmentBarycentricKHR";
+    COMPUTE_DERIVATIVE_GROUP_QUADS_NV = 5288, "ComputeDerivativeGroupQuadsNV";
+    FRAGMENT_DENSITY_EXT = 5291, "FragmentDensityEXT";
+    GROUP_NON_UNIFORM_PARTITIONED_NV = 5297, "GroupNonUniformPartitionedNV";
+    SHADER_NON_UNIFORM = 5301, "ShaderNonUniform";
+    RUNTIME_DESCRIPTOR_ARRAY = 5302, "RuntimeDescriptorArray";
+    INPUT_ATTACHMENT_ARRAY_DYNAMIC_INDEXING = 5303, "InputAttachmentArrayDynamicIndexing";
+    UNIFORM_TEXEL_BUFFER_ARRAY_DYNAMIC_INDEXING = 5304, "UniformTexelBufferArrayDynamicIndexing";
+    STORAGE_TEXEL_BUFFER_ARRAY_DYNAMIC_INDEXING = 5305, "StorageTexelBufferArrayDynamicIndexing";
+    UNIFORM_BUFFER_ARRAY_NON_UNIFORM_INDEXING = 5306, "UniformBufferArrayNonUniformIndexing";
+    SAMPLED_IMAGE_ARRAY_NON_UNIFORM_INDEXING = 5307, "SampledImageArrayNonUniformIndexing";
+    STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING = 5308, "StorageBufferArrayNonUniformIndexing";
+    STORAGE_IMAGE_ARRAY_NON_UNIFORM_INDEXING = 5309, "StorageImageArrayNonUniformIndexing";
+    INPUT_ATTACHMENT_ARRAY_NON_UNIFORM_INDEXING = 5310, "InputAttachmentArrayNonUniformIndexing";
+    UNIFORM_TEXEL_BUFFER_ARRAY_NON_UNIFORM_INDEXING = 5311, "UniformTexelBufferArrayNonUniformIndexing";
+    STORAGE_TEXEL_BUFFER_ARRAY_NON_UNIFORM_INDEXING = 5312, "StorageTexelBufferArrayNonUniformIndexing";
+    RAY_TRACING_NV = 5340, "RayTracingNV";
+    VULKAN_MEMORY_MODEL = 5345, "VulkanMemoryModel";
+    VULKAN_MEMORY_MODEL_DEVICE_SCOPE = 5346, "VulkanMemoryModelDeviceScope";
+    PHYSICAL_STORAGE_BUFFER_ADDRESSES = 5347, "PhysicalStorageBufferAddresses";
+    COMPUTE_DERIVATIVE_GROUP_LINEAR_NV = 5350, "ComputeDerivativeGroupLinearNV";
+    RAY_TRACING_PROVISIONAL_KHR = 5353, "RayTracingProvisionalKHR";
+    COOPERATIVE_MATRIX_NV = 5357, "CooperativeMatrixNV";
+    FRAGMENT_SHADER_SAMPLE_INTERLOCK_EXT = 5363, "FragmentShaderSampleInterlockEXT";
+    FRAGMENT_SHADER_SHADING_RATE_INTERLOCK_EXT = 5372, "FragmentShaderShadingRateInterlockEXT";
+    SHADER_SM_BUILTINS_NV = 5373, "ShaderSMBuiltinsNV";
+    FRAGMENT_SHADER_PIXEL_INTERLOCK_EXT = 5378, "FragmentShaderPixelInterlockEXT";
+    DEMOTE_TO_HELPER_INVOCATION = 5379, "DemoteToHelperInvocation";
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "Capability({})", self.0),
+        }
+    }
+}
+
+impl SpirvModule {
+    /// Returns the capabilities the module declares with `OpCapability`.
+    ///
+    /// Loaders can check them against the features of a device before
+    /// creating pipelines, to report which feature is missing instead of a
+    /// failure to create the pipeline.
+    ///
+    /// ```
+    /// # use shaderc::{Capability, SpirvModule};
+    /// # fn check(module: &SpirvModule) -> Result<(), String> {
+    /// if module.capabilities().contains(&Capability::INT64) {
+    ///     return Err("shader requires the shaderInt64 device feature".to_string());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> BTreeSet<Capability> {
+        self.instructions()
+            .filter(|inst| inst.opcode() == op::CAPABILITY)
+            .filter_map(|inst| inst.operands().first().map(|&c| Capability(c)))
+            .collect()
+    }
+
+    /// Returns the extensions the module declares with `OpExtension`, like
+    /// `SPV_KHR_ray_tracing`.
+    pub fn extensions(&self) -> BTreeSet<String> {
+        self.instructions()
+            .filter(|inst| inst.opcode() == op::EXTENSION)
+            .map(|inst| literal_string(inst.operands()).0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::encode_string;
+    use crate::spirv_module::tests::{inst, module};
+
+    #[test]
+    fn test_capabilities_and_extensions() {
+        let module = module(&[
+            inst(op::CAPABILITY, &[Capability::SHADER.0]),
+            inst(op::CAPABILITY, &[Capability::RAY_TRACING_KHR.0]),
+            inst(op::CAPABILITY, &[9999]),
+            inst(op::EXTENSION, &encode_string("SPV_KHR_ray_tracing")),
+        ]);
+        let capabilities = module.capabilities();
+        assert_eq!(
+            vec!["Shader", "RayTracingKHR", "Capability(9999)"],
+            capabilities
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert!(!capabilities.contains(&Capability::INT64));
+        assert_eq!(
+            vec!["SPV_KHR_ray_tracing".to_string()],
+            module.extensions().into_iter().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks for test suites, catching resources the bindings failed to free.
+//!
+//! The include callback of `CompileOptions::set_include_callback` hands
+//! libshaderc results it frees once libshaderc releases them. These results
+//! are accounted for per thread, as libshaderc resolves and releases the
+//! includes of a compilation on the thread compiling it.
+//!
+//! ```no_run
+//! use shaderc::{CompileOptions, Compiler, ResolvedInclude, ShaderKind};
+//!
+//! let compiler = Compiler::new().unwrap();
+//! let mut options = CompileOptions::new().unwrap();
+//! options.set_include_callback(|name, _, _, _| {
+//!     Ok(ResolvedInclude {
+//!         resolved_name: name.to_string(),
+//!         content: String::new(),
+//!     })
+//! });
+//! let source = "#version 450\n#include \"a.glsl\"\nvoid main() {}";
+//! compiler
+//!     .compile_into_spirv(source, ShaderKind::Vertex, "a.vert", "main", Some(&options))
+//!     .unwrap();
+//! shaderc::debug::assert_no_leaked_include_results();
+//! ```
+
+use std::cell::Cell;
+
+thread_local! {
+    // Include results handed to libshaderc on this thread and not released.
+    static LIVE_INCLUDE_RESULTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts an include result handed to libshaderc as live until dropped.
+pub(crate) struct IncludeResultGuard(());
+
+impl IncludeResultGuard {
+    pub(crate) fn new() -> IncludeResultGuard {
+        LIVE_INCLUDE_RESULTS.with(|live| live.set(live.get() + 1));
+        IncludeResultGuard(())
+    }
+}
+
+impl Drop for IncludeResultGuard {
+    fn drop(&mut self) {
+        LIVE_INCLUDE_RESULTS.with(|live| live.set(live.get() - 1));
+    }
+}
+
+/// Returns the number of include results the include callback handed to
+/// libshaderc on this thread which libshaderc has not released yet.
+pub fn live_include_results() -> usize {
+    LIVE_INCLUDE_RESULTS.with(Cell::get)
+}
+
+/// Panics if include results handed to libshaderc on this thread have not
+/// been released, as happens when a compilation leaks them.
+///
+/// Call it between compilations: results are only released once the
+/// compilation using them finishes.
+pub fn assert_no_leaked_include_results() {
+    let live = live_include_results();
+    if live != 0 {
+        panic!("{} include results were not released by libshaderc", live);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn test_include_result_guard() {
+        assert_no_leaked_include_results();
+        let guard = IncludeResultGuard::new();
+        assert_eq!(1, live_include_results());
+        assert!(panic::catch_unwind(assert_no_leaked_include_results).is_err());
+        drop(guard);
+        assert_no_leaked_include_results();
+    }
+}
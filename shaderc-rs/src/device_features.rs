@@ -0,0 +1,211 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Vulkan device features and extensions required by SPIR-V modules.
+
+use std::fmt;
+
+use crate::spirv_module::SpirvModule;
+use crate::Capability;
+
+/// A member of a Vulkan feature structure, like `shaderInt64` of
+/// `VkPhysicalDeviceFeatures`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeviceFeature {
+    /// The structure declaring the feature, to chain into
+    /// `VkPhysicalDeviceFeatures2`.
+    pub structure: &'static str,
+    pub name: &'static str,
+}
+
+impl fmt::Display for DeviceFeature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}::{}", self.structure, self.name)
+    }
+}
+
+/// The device features and extensions to enable for a module, as returned by
+/// `SpirvModule::required_device_features`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeviceRequirements {
+    /// The features, sorted by structure and name.
+    pub features: Vec<DeviceFeature>,
+    /// The device extensions providing the capabilities, sorted. Those which
+    /// were promoted to core Vulkan are only needed on older versions.
+    pub extensions: Vec<&'static str>,
+}
+
+const CORE: &str = "VkPhysicalDeviceFeatures";
+const VULKAN11: &str = "VkPhysicalDeviceVulkan11Features";
+const VULKAN12: &str = "VkPhysicalDeviceVulkan12Features";
+const VULKAN13: &str = "VkPhysicalDeviceVulkan13Features";
+
+/// The structure and name of a feature.
+type Feature = (&'static str, &'static str);
+
+/// Capabilities with the feature and extension enabling them, following the
+/// SPIR-V environment appendix of the Vulkan specification. Capabilities
+/// every implementation supports, and those depending on device properties
+/// rather than features, are omitted.
+#[rustfmt::skip]
+static TABLE: &[(Capability, Option<Feature>, Option<&str>)] = &[
+    (Capability::GEOMETRY, Some((CORE, "geometryShader")), None),
+    (Capability::TESSELLATION, Some((CORE, "tessellationShader")), None),
+    (Capability::FLOAT64, Some((CORE, "shaderFloat64")), None),
+    (Capability::INT64, Some((CORE, "shaderInt64")), None),
+    (Capability::INT64_ATOMICS, Some((VULKAN12, "shaderBufferInt64Atomics")), Some("VK_KHR_shader_atomic_int64")),
+    (Capability::INT16, Some((CORE, "shaderInt16")), None),
+    (Capability::TESSELLATION_POINT_SIZE, Some((CORE, "shaderTessellationAndGeometryPointSize")), None),
+    (Capability::GEOMETRY_POINT_SIZE, Some((CORE, "shaderTessellationAndGeometryPointSize")), None),
+    (Capability::IMAGE_GATHER_EXTENDED, Some((CORE, "shaderImageGatherExtended")), None),
+    (Capability::STORAGE_IMAGE_MULTISAMPLE, Some((CORE, "shaderStorageImageMultisample")), None),
+    (Capability::UNIFORM_BUFFER_ARRAY_DYNAMIC_INDEXING, Some((CORE, "shaderUniformBufferArrayDynamicIndexing")), None),
+    (Capability::SAMPLED_IMAGE_ARRAY_DYNAMIC_INDEXING, Some((CORE, "shaderSampledImageArrayDynamicIndexing")), None),
+    (Capability::STORAGE_BUFFER_ARRAY_DYNAMIC_INDEXING, Some((CORE, "shaderStorageBufferArrayDynamicIndexing")), None),
+    (Capability::STORAGE_IMAGE_ARRAY_DYNAMIC_INDEXING, Some((CORE, "shaderStorageImageArrayDynamicIndexing")), None),
+    (Capability::CLIP_DISTANCE, Some((CORE, "shaderClipDistance")), None),
+    (Capability::CULL_DISTANCE, Some((CORE, "shaderCullDistance")), None),
+    (Capability::IMAGE_CUBE_ARRAY, Some((CORE, "imageCubeArray")), None),
+    (Capability::SAMPLE_RATE_SHADING, Some((CORE, "sampleRateShading")), None),
+    (Capability::SPARSE_RESIDENCY, Some((CORE, "shaderResourceResidency")), None),
+    (Capability::MIN_LOD, Some((CORE, "shaderResourceMinLod")), None),
+    (Capability::SAMPLED_CUBE_ARRAY, Some((CORE, "imageCubeArray")), None),
+    (Capability::IMAGE_MS_ARRAY, Some((CORE, "shaderStorageImageMultisample")), None),
+    (Capability::STORAGE_IMAGE_EXTENDED_FORMATS, Some((CORE, "shaderStorageImageExtendedFormats")), None),
+    (Capability::INTERPOLATION_FUNCTION, Some((CORE, "sampleRateShading")), None),
+    (Capability::TRANSFORM_FEEDBACK, Some(("VkPhysicalDeviceTransformFeedbackFeaturesEXT", "transformFeedback")), Some("VK_EXT_transform_feedback")),
+    (Capability::GEOMETRY_STREAMS, Some(("VkPhysicalDeviceTransformFeedbackFeaturesEXT", "geometryStreams")), Some("VK_EXT_transform_feedback")),
+    (Capability::STORAGE_IMAGE_READ_WITHOUT_FORMAT, Some((CORE, "shaderStorageImageReadWithoutFormat")), None),
+    (Capability::STORAGE_IMAGE_WRITE_WITHOUT_FORMAT, Some((CORE, "shaderStorageImageWriteWithoutFormat")), None),
+    (Capability::MULTI_VIEWPORT, Some((CORE, "multiViewport")), None),
+    (Capability::SHADER_LAYER, Some((VULKAN12, "shaderOutputLayer")), None),
+    (Capability::SHADER_VIEWPORT_INDEX, Some((VULKAN12, "shaderOutputViewportIndex")), None),
+    (Capability::FLOAT16, Some((VULKAN12, "shaderFloat16")), Some("VK_KHR_shader_float16_int8")),
+    (Capability::INT8, Some((VULKAN12, "shaderInt8")), Some("VK_KHR_shader_float16_int8")),
+    (Capability::FRAGMENT_SHADING_RATE_KHR, None, Some("VK_KHR_fragment_shading_rate")),
+    (Capability::SUBGROUP_BALLOT_KHR, None, Some("VK_EXT_shader_subgroup_ballot")),
+    (Capability::DRAW_PARAMETERS, Some((VULKAN11, "shaderDrawParameters")), Some("VK_KHR_shader_draw_parameters")),
+    (Capability::SUBGROUP_VOTE_KHR, None, Some("VK_EXT_shader_subgroup_vote")),
+    (Capability::STORAGE_BUFFER_16BIT_ACCESS, Some((VULKAN11, "storageBuffer16BitAccess")), Some("VK_KHR_16bit_storage")),
+    (Capability::UNIFORM_AND_STORAGE_BUFFER_16BIT_ACCESS, Some((VULKAN11, "uniformAndStorageBuffer16BitAccess")), Some("VK_KHR_16bit_storage")),
+    (Capability::STORAGE_PUSH_CONSTANT_16, Some((VULKAN11, "storagePushConstant16")), Some("VK_KHR_16bit_storage")),
+    (Capability::STORAGE_INPUT_OUTPUT_16, Some((VULKAN11, "storageInputOutput16")), Some("VK_KHR_16bit_storage")),
+    (Capability::MULTI_VIEW, Some((VULKAN11, "multiview")), Some("VK_KHR_multiview")),
+    (Capability::VARIABLE_POINTERS_STORAGE_BUFFER, Some((VULKAN11, "variablePointersStorageBuffer")), Some("VK_KHR_variable_pointers")),
+    (Capability::VARIABLE_POINTERS, Some((VULKAN11, "variablePointers")), Some("VK_KHR_variable_pointers")),
+    (Capability::SAMPLE_MASK_POST_DEPTH_COVERAGE, None, Some("VK_EXT_post_depth_coverage")),
+    (Capability::STORAGE_BUFFER_8BIT_ACCESS, Some((VULKAN12, "storageBuffer8BitAccess")), Some("VK_KHR_8bit_storage")),
+    (Capability::UNIFORM_AND_STORAGE_BUFFER_8BIT_ACCESS, Some((VULKAN12, "uniformAndStorageBuffer8BitAccess")), Some("VK_KHR_8bit_storage")),
+    (Capability::STORAGE_PUSH_CONSTANT_8, Some((VULKAN12, "storagePushConstant8")), Some("VK_KHR_8bit_storage")),
+    (Capability::RAY_QUERY_KHR, Some(("VkPhysicalDeviceRayQueryFeaturesKHR", "rayQuery")), Some("VK_KHR_ray_query")),
+    (Capability::RAY_TRAVERSAL_PRIMITIVE_CULLING_KHR, Some(("VkPhysicalDeviceRayTracingPipelineFeaturesKHR", "rayTraversalPrimitiveCulling")), Some("VK_KHR_ray_tracing_pipeline")),
+    (Capability::RAY_TRACING_KHR, Some(("VkPhysicalDeviceRayTracingPipelineFeaturesKHR", "rayTracingPipeline")), Some("VK_KHR_ray_tracing_pipeline")),
+    (Capability::STENCIL_EXPORT_EXT, None, Some("VK_EXT_shader_stencil_export")),
+    (Capability::INT64_IMAGE_EXT, Some(("VkPhysicalDeviceShaderImageAtomicInt64FeaturesEXT", "shaderImageInt64Atomics")), Some("VK_EXT_shader_image_atomic_int64")),
+    (Capability::SHADER_CLOCK_KHR, Some(("VkPhysicalDeviceShaderClockFeaturesKHR", "shaderSubgroupClock")), Some("VK_KHR_shader_clock")),
+    (Capability::SHADER_VIEWPORT_INDEX_LAYER_EXT, None, Some("VK_EXT_shader_viewport_index_layer")),
+    (Capability::FRAGMENT_FULLY_COVERED_EXT, None, Some("VK_EXT_conservative_rasterization")),
+    (Capability::MESH_SHADING_NV, Some(("VkPhysicalDeviceMeshShaderFeaturesNV", "meshShader")), Some("VK_NV_mesh_shader")),
+    (Capability::IMAGE_FOOTPRINT_NV, Some(("VkPhysicalDeviceShaderImageFootprintFeaturesNV", "imageFootprint")), Some("VK_NV_shader_image_footprint")),
+    (Capability::MESH_SHADING_EXT, Some(("VkPhysicalDeviceMeshShaderFeaturesEXT", "meshShader")), Some("VK_EXT_mesh_shader")),
+    (Capability::FRAGMENT_BARYCENTRIC_KHR, Some(("VkPhysicalDeviceFragmentShaderBarycentricFeaturesKHR", "fragmentShaderBarycentric")), Some("VK_KHR_fragment_shader_barycentric")),
+    (Capability::COMPUTE_DERIVATIVE_GROUP_QUADS_NV, Some(("VkPhysicalDeviceComputeShaderDerivativesFeaturesNV", "computeDerivativeGroupQuads")), Some("VK_NV_compute_shader_derivatives")),
+    (Capability::FRAGMENT_DENSITY_EXT, Some(("VkPhysicalDeviceFragmentDensityMapFeaturesEXT", "fragmentDensityMap")), Some("VK_EXT_fragment_density_map")),
+    (Capability::SHADER_NON_UNIFORM, None, Some("VK_EXT_descriptor_indexing")),
+    (Capability::RUNTIME_DESCRIPTOR_ARRAY, Some((VULKAN12, "runtimeDescriptorArray")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::INPUT_ATTACHMENT_ARRAY_DYNAMIC_INDEXING, Some((VULKAN12, "shaderInputAttachmentArrayDynamicIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::UNIFORM_TEXEL_BUFFER_ARRAY_DYNAMIC_INDEXING, Some((VULKAN12, "shaderUniformTexelBufferArrayDynamicIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::STORAGE_TEXEL_BUFFER_ARRAY_DYNAMIC_INDEXING, Some((VULKAN12, "shaderStorageTexelBufferArrayDynamicIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::UNIFORM_BUFFER_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderUniformBufferArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::SAMPLED_IMAGE_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderSampledImageArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderStorageBufferArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::STORAGE_IMAGE_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderStorageImageArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::INPUT_ATTACHMENT_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderInputAttachmentArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::UNIFORM_TEXEL_BUFFER_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderUniformTexelBufferArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::STORAGE_TEXEL_BUFFER_ARRAY_NON_UNIFORM_INDEXING, Some((VULKAN12, "shaderStorageTexelBufferArrayNonUniformIndexing")), Some("VK_EXT_descriptor_indexing")),
+    (Capability::RAY_TRACING_NV, None, Some("VK_NV_ray_tracing")),
+    (Capability::VULKAN_MEMORY_MODEL, Some((VULKAN12, "vulkanMemoryModel")), Some("VK_KHR_vulkan_memory_model")),
+    (Capability::VULKAN_MEMORY_MODEL_DEVICE_SCOPE, Some((VULKAN12, "vulkanMemoryModelDeviceScope")), Some("VK_KHR_vulkan_memory_model")),
+    (Capability::PHYSICAL_STORAGE_BUFFER_ADDRESSES, Some((VULKAN12, "bufferDeviceAddress")), Some("VK_KHR_buffer_device_address")),
+    (Capability::COMPUTE_DERIVATIVE_GROUP_LINEAR_NV, Some(("VkPhysicalDeviceComputeShaderDerivativesFeaturesNV", "computeDerivativeGroupLinear")), Some("VK_NV_compute_shader_derivatives")),
+    (Capability::COOPERATIVE_MATRIX_NV, Some(("VkPhysicalDeviceCooperativeMatrixFeaturesNV", "cooperativeMatrix")), Some("VK_NV_cooperative_matrix")),
+    (Capability::FRAGMENT_SHADER_SAMPLE_INTERLOCK_EXT, Some(("VkPhysicalDeviceFragmentShaderInterlockFeaturesEXT", "fragmentShaderSampleInterlock")), Some("VK_EXT_fragment_shader_interlock")),
+    (Capability::FRAGMENT_SHADER_SHADING_RATE_INTERLOCK_EXT, Some(("VkPhysicalDeviceFragmentShaderInterlockFeaturesEXT", "fragmentShaderShadingRateInterlock")), Some("VK_EXT_fragment_shader_interlock")),
+    (Capability::SHADER_SM_BUILTINS_NV, Some(("VkPhysicalDeviceShaderSMBuiltinsFeaturesNV", "shaderSMBuiltins")), Some("VK_NV_shader_sm_builtins")),
+    (Capability::FRAGMENT_SHADER_PIXEL_INTERLOCK_EXT, Some(("VkPhysicalDeviceFragmentShaderInterlockFeaturesEXT", "fragmentShaderPixelInterlock")), Some("VK_EXT_fragment_shader_interlock")),
+    (Capability::DEMOTE_TO_HELPER_INVOCATION, Some((VULKAN13, "shaderDemoteToHelperInvocation")), Some("VK_EXT_shader_demote_to_helper_invocation")),
+];
+
+impl SpirvModule {
+    /// Returns the Vulkan device features and extensions to enable for the
+    /// capabilities of the module.
+    ///
+    /// The features are named after the members of the feature structures,
+    /// so engines can fill in a `VkPhysicalDeviceFeatures2` chain from them.
+    /// Requirements on device properties, like the supported subgroup
+    /// operations or float controls, are not included.
+    pub fn required_device_features(&self) -> DeviceRequirements {
+        let mut requirements = DeviceRequirements::default();
+        for capability in self.capabilities() {
+            for &(_, feature, extension) in TABLE.iter().filter(|entry| entry.0 == capability) {
+                if let Some((structure, name)) = feature {
+                    requirements
+                        .features
+                        .push(DeviceFeature { structure, name });
+                }
+                requirements.extensions.extend(extension);
+            }
+        }
+        requirements.features.sort();
+        requirements.features.dedup();
+        requirements.extensions.sort();
+        requirements.extensions.dedup();
+        requirements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::op;
+    use crate::spirv_module::tests::{inst, module};
+
+    #[test]
+    fn test_required_device_features() {
+        let module = module(&[
+            inst(op::CAPABILITY, &[Capability::SHADER.0]),
+            inst(op::CAPABILITY, &[Capability::INT64.0]),
+            inst(op::CAPABILITY, &[Capability::STORAGE_BUFFER_16BIT_ACCESS.0]),
+            inst(op::CAPABILITY, &[Capability::STORAGE_PUSH_CONSTANT_16.0]),
+            inst(op::CAPABILITY, &[Capability::STENCIL_EXPORT_EXT.0]),
+        ]);
+        let requirements = module.required_device_features();
+        assert_eq!(
+            vec![
+                "VkPhysicalDeviceFeatures::shaderInt64",
+                "VkPhysicalDeviceVulkan11Features::storageBuffer16BitAccess",
+                "VkPhysicalDeviceVulkan11Features::storagePushConstant16",
+            ],
+            requirements
+                .features
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["VK_EXT_shader_stencil_export", "VK_KHR_16bit_storage"],
+            requirements.extensions
+        );
+    }
+}
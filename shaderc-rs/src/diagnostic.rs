@@ -0,0 +1,658 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured diagnostics parsed from the messages of the compiler, the
+//! policies promoting or silencing them by category, the include stacks of
+//! diagnostics in included files, and the rewriting of their locations through
+//! `#line` directives.
+
+use std::fmt;
+
+use crate::{CompilationArtifact, CompileOptions, Error, Result};
+
+/// Severity of a `Diagnostic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A single error or warning reported by the compiler.
+///
+/// Diagnostics are parsed from the messages of glslang, which have the form
+/// `file:line: severity: message`, where the file and line can be missing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The name of the file the diagnostic is about, if known.
+    pub file: Option<String>,
+    /// The 1-based line the diagnostic is about, if known.
+    pub line: Option<u32>,
+    /// The message, spanning several lines if the compiler printed notes
+    /// after it.
+    pub message: String,
+    /// The files through which the compiled source included `file`, from
+    /// the one including it directly to the compiled source. Empty unless
+    /// `file` was reached through the include callback of a `Compiler`.
+    pub include_stack: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Parses the diagnostics in the compiler messages `messages`.
+    ///
+    /// Lines which do not start a diagnostic are appended to the message of
+    /// the previous one. Summary lines like `1 error generated.` are skipped.
+    pub fn parse(messages: &str) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for line in messages.lines() {
+            if line.trim().is_empty() || summary_count(line).is_some() {
+                continue;
+            }
+            if let (Some(includer), Some(last)) = (
+                line.trim_start().strip_prefix(INCLUDED_FROM),
+                diagnostics.last_mut(),
+            ) {
+                last.include_stack.push(includer.to_string());
+                continue;
+            }
+            match Diagnostic::parse_line(line) {
+                Some(diagnostic) => diagnostics.push(diagnostic),
+                None => match diagnostics.last_mut() {
+                    Some(last) => {
+                        last.message.push('\n');
+                        last.message.push_str(line);
+                    }
+                    None => diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        file: None,
+                        line: None,
+                        message: line.to_string(),
+                        include_stack: Vec::new(),
+                    }),
+                },
+            }
+        }
+        diagnostics
+    }
+
+    fn parse_line(line: &str) -> Option<Diagnostic> {
+        for &severity in &[Severity::Error, Severity::Warning] {
+            let marker = format!("{severity}: ");
+            let (location, message) = if let Some(message) = line.strip_prefix(&marker) {
+                ("", message)
+            } else if let Some(start) = line.find(&format!(": {marker}")) {
+                (&line[..start], &line[start + marker.len() + 2..])
+            } else {
+                continue;
+            };
+            let (file, line) = match location.rsplit_once(':') {
+                Some((file, number)) if number.parse::<u32>().is_ok() => {
+                    (file, number.parse().ok())
+                }
+                _ => (location, None),
+            };
+            return Some(Diagnostic {
+                severity,
+                file: if file.is_empty() {
+                    None
+                } else {
+                    Some(file.to_string())
+                },
+                line,
+                message: message.to_string(),
+                include_stack: Vec::new(),
+            });
+        }
+        None
+    }
+}
+
+/// Formats the diagnostic the way glslang does.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{file}:")?;
+            if let Some(line) = self.line {
+                write!(f, "{line}:")?;
+            }
+            f.write_str(" ")?;
+        }
+        write!(f, "{}: {}", self.severity, self.message)?;
+        for includer in &self.include_stack {
+            write!(f, "\n  {INCLUDED_FROM}{includer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The start of the lines following a diagnostic in an included file, naming
+/// each file in its include stack.
+const INCLUDED_FROM: &str = "included from ";
+
+/// Returns `messages` with the include stack of each diagnostic in an
+/// included file appended to it, from the `(included, includer)` pairs of
+/// resolved names in `includes`.
+pub(crate) fn add_include_stacks(messages: &str, includes: &[(String, String)]) -> String {
+    let mut annotated = String::new();
+    for line in messages.lines() {
+        annotated.push_str(line);
+        annotated.push('\n');
+        let Some(mut file) = Diagnostic::parse_line(line).and_then(|d| d.file) else {
+            continue;
+        };
+        let mut seen = vec![file.clone()];
+        while let Some((_, includer)) = includes.iter().find(|(included, _)| *included == file) {
+            if seen.contains(includer) {
+                break;
+            }
+            annotated.push_str(&format!("  {INCLUDED_FROM}{includer}\n"));
+            seen.push(includer.clone());
+            file = includer.clone();
+        }
+    }
+    annotated
+}
+
+/// Returns the count of a summary line like `2 warnings generated.`.
+pub(crate) fn summary_count(line: &str) -> Option<(u32, Severity)> {
+    let mut words = line.trim().split(' ');
+    let count = words.next()?.parse().ok()?;
+    let severity = match words.next()? {
+        "error" | "errors" => Severity::Error,
+        "warning" | "warnings" => Severity::Warning,
+        _ => return None,
+    };
+    match (words.next(), words.next()) {
+        (Some("generated."), None) => Some((count, severity)),
+        _ => None,
+    }
+}
+
+impl Error {
+    /// Returns the diagnostics in the messages of a compilation error.
+    ///
+    /// Other errors have no diagnostics.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match *self {
+            Error::CompilationError(_, ref messages) => Diagnostic::parse(messages),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl CompilationArtifact {
+    /// Returns the diagnostics in the warning messages.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        Diagnostic::parse(&self.get_warning_messages())
+    }
+}
+
+/// A kind of warning, recognized from its message, which a
+/// `DiagnosticPolicy` can act on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticCategory {
+    /// Use of a deprecated construct, like `attribute` or `varying`.
+    Deprecation,
+    /// A precision qualifier ignored or defaulted, mostly in GLSL ES.
+    Precision,
+    /// A variable or parameter which is never used.
+    UnusedVariable,
+    /// Any other warning.
+    Other,
+}
+
+impl Diagnostic {
+    /// Returns the category of the diagnostic, recognized from its message.
+    pub fn category(&self) -> DiagnosticCategory {
+        let message = self.message.to_ascii_lowercase();
+        if message.contains("deprecated") {
+            DiagnosticCategory::Deprecation
+        } else if message.contains("precision") {
+            DiagnosticCategory::Precision
+        } else if message.contains("unused") {
+            DiagnosticCategory::UnusedVariable
+        } else {
+            DiagnosticCategory::Other
+        }
+    }
+}
+
+/// What a `DiagnosticPolicy` does with the warnings of a category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticAction {
+    /// Fails the compilation, reporting the warning as an error.
+    Error,
+    /// Keeps the warning, the default.
+    Warning,
+    /// Drops the warning.
+    Ignore,
+}
+
+/// Per-category handling of the warnings of a compilation, set with
+/// `CompileOptions::set_diagnostic_policy`.
+///
+/// Unlike `set_warnings_as_errors` and `set_suppress_warnings`, which apply
+/// to every warning, a policy escalates or drops only the warnings of the
+/// given categories. It is applied to the parsed warnings once compiling
+/// succeeded, so errors cannot be downgraded.
+///
+/// ```
+/// use shaderc::{DiagnosticAction, DiagnosticCategory, DiagnosticPolicy};
+///
+/// let policy = DiagnosticPolicy::new()
+///     .set(DiagnosticCategory::Deprecation, DiagnosticAction::Error)
+///     .set(DiagnosticCategory::Precision, DiagnosticAction::Ignore);
+/// assert_eq!(DiagnosticAction::Warning, policy.action(DiagnosticCategory::Other));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticPolicy {
+    actions: Vec<(DiagnosticCategory, DiagnosticAction)>,
+}
+
+impl DiagnosticPolicy {
+    /// Creates a policy keeping every warning.
+    pub fn new() -> DiagnosticPolicy {
+        DiagnosticPolicy::default()
+    }
+
+    /// Returns this policy with `action` taken on the warnings of
+    /// `category`, replacing any previous action.
+    pub fn set(mut self, category: DiagnosticCategory, action: DiagnosticAction) -> Self {
+        self.actions.retain(|&(c, _)| c != category);
+        self.actions.push((category, action));
+        self
+    }
+
+    /// Returns the action taken on the warnings of `category`.
+    pub fn action(&self, category: DiagnosticCategory) -> DiagnosticAction {
+        self.actions
+            .iter()
+            .find(|&&(c, _)| c == category)
+            .map_or(DiagnosticAction::Warning, |&(_, action)| action)
+    }
+
+    /// Applies this policy to the warnings of `artifact`.
+    ///
+    /// Returns `Error::CompilationError` counting the escalated warnings if
+    /// any, with the remaining warnings in its messages, or else the artifact
+    /// without the dropped warnings.
+    pub fn apply(&self, artifact: CompilationArtifact) -> Result<CompilationArtifact> {
+        if self.actions.is_empty() || artifact.get_num_warnings() == 0 {
+            return Ok(artifact);
+        }
+        let mut escalated = 0;
+        let mut kept = Vec::new();
+        for mut diagnostic in artifact.diagnostics() {
+            match self.action(diagnostic.category()) {
+                DiagnosticAction::Error => {
+                    diagnostic.severity = Severity::Error;
+                    escalated += 1;
+                }
+                DiagnosticAction::Warning => {}
+                DiagnosticAction::Ignore => continue,
+            }
+            kept.push(diagnostic);
+        }
+        let messages: String = kept.iter().map(|d| format!("{d}\n")).collect();
+        if escalated > 0 {
+            return Err(Error::CompilationError(escalated, messages));
+        }
+        let num_warnings = kept.len() as u32;
+        if num_warnings == artifact.get_num_warnings() {
+            return Ok(artifact);
+        }
+        Ok(artifact.with_warnings(num_warnings, messages))
+    }
+}
+
+/// Applies the diagnostic policy of `options`, if any, to `artifact`.
+pub(crate) fn apply_policy(
+    artifact: CompilationArtifact,
+    options: Option<&CompileOptions>,
+) -> Result<CompilationArtifact> {
+    match options.and_then(|options| options.settings.diagnostic_policy.as_ref()) {
+        Some(policy) => policy.apply(artifact),
+        None => Ok(artifact),
+    }
+}
+
+/// A `#line` directive of a source.
+#[derive(Debug)]
+struct LineDirective {
+    /// The line of the directive in the source, from 1.
+    source_line: u32,
+    /// The line number the directive gives the line after it.
+    line: u32,
+    /// The file name the directive sets, or the one of an earlier directive.
+    file: Option<String>,
+}
+
+/// The `#line` directives of a source, mapping the lines of the source to
+/// the generator inputs they came from, for
+/// `CompileOptions::set_rewrite_line_directives`.
+#[derive(Debug)]
+pub(crate) struct LineMap {
+    /// The source with its `#line` directives replaced by empty lines.
+    source: String,
+    directives: Vec<LineDirective>,
+}
+
+/// Returns the line number and the quoted file name, if any, of the `#line`
+/// directive `line`.
+fn parse_line_directive(line: &str) -> Option<(u32, Option<&str>)> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    let rest = rest.strip_prefix("line")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim();
+    let (number, file) = match rest.split_once(char::is_whitespace) {
+        Some((number, file)) => (number, file.trim()),
+        None => (rest, ""),
+    };
+    let file = file
+        .strip_prefix('"')
+        .and_then(|file| file.strip_suffix('"'));
+    Some((number.parse().ok()?, file))
+}
+
+impl LineMap {
+    /// Returns the map of the `#line` directives of `source_text` if
+    /// `options` rewrite them and there are any.
+    pub(crate) fn for_options(
+        source_text: &str,
+        options: Option<&CompileOptions>,
+    ) -> Option<LineMap> {
+        if options.is_some_and(|options| options.settings.rewrite_line_directives) {
+            LineMap::new(source_text)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the map of the `#line` directives of `source_text`, or `None`
+    /// if there are none.
+    fn new(source_text: &str) -> Option<LineMap> {
+        let mut source = String::with_capacity(source_text.len());
+        let mut directives: Vec<LineDirective> = Vec::new();
+        for (index, line) in source_text.split_inclusive('\n').enumerate() {
+            match parse_line_directive(line) {
+                Some((number, file)) => {
+                    let file = file
+                        .map(str::to_string)
+                        .or_else(|| directives.last().and_then(|d| d.file.clone()));
+                    directives.push(LineDirective {
+                        source_line: index as u32 + 1,
+                        line: number,
+                        file,
+                    });
+                    if line.ends_with('\n') {
+                        source.push('\n');
+                    }
+                }
+                None => source.push_str(line),
+            }
+        }
+        if directives.is_empty() {
+            return None;
+        }
+        Some(LineMap { source, directives })
+    }
+
+    /// Returns the source to compile, without its `#line` directives.
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the file and line which line `line` of the source comes from,
+    /// or `None` if it comes before every directive.
+    fn locate(&self, line: u32) -> Option<(Option<&str>, u32)> {
+        let directive = self
+            .directives
+            .iter()
+            .rev()
+            .find(|directive| directive.source_line < line)?;
+        Some((
+            directive.file.as_deref(),
+            directive.line + (line - directive.source_line - 1),
+        ))
+    }
+
+    /// Returns `messages` with the diagnostics in `input_file_name` moved to
+    /// the files and lines their `#line` directives give.
+    fn rewrite_messages(&self, messages: &str, input_file_name: &str) -> String {
+        let mut rewritten = String::with_capacity(messages.len());
+        for line in messages.lines() {
+            let diagnostic = Diagnostic::parse_line(line).filter(|diagnostic| {
+                diagnostic.file.as_deref() == Some(input_file_name) && diagnostic.line.is_some()
+            });
+            let located = diagnostic.and_then(|mut diagnostic| {
+                let (file, line) = self.locate(diagnostic.line?)?;
+                if let Some(file) = file {
+                    diagnostic.file = Some(file.to_string());
+                }
+                diagnostic.line = Some(line);
+                Some(diagnostic)
+            });
+            match located {
+                Some(diagnostic) => rewritten.push_str(&diagnostic.to_string()),
+                None => rewritten.push_str(line),
+            }
+            rewritten.push('\n');
+        }
+        rewritten
+    }
+}
+
+/// Rewrites the diagnostics of `result`, the compilation of
+/// `input_file_name`, through `line_map`, if any.
+pub(crate) fn rewrite_lines(
+    result: Result<CompilationArtifact>,
+    line_map: Option<&LineMap>,
+    input_file_name: &str,
+) -> Result<CompilationArtifact> {
+    let Some(line_map) = line_map else {
+        return result;
+    };
+    match result {
+        Ok(artifact) if artifact.get_num_warnings() > 0 => {
+            let messages =
+                line_map.rewrite_messages(&artifact.get_warning_messages(), input_file_name);
+            let num_warnings = artifact.get_num_warnings();
+            Ok(artifact.with_warnings(num_warnings, messages))
+        }
+        Err(Error::CompilationError(num_errors, messages)) => Err(Error::CompilationError(
+            num_errors,
+            line_map.rewrite_messages(&messages, input_file_name),
+        )),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_parse() {
+        let messages = "shader.glsl:3: error: 'x' : undeclared identifier\n\
+                        shader.glsl: warning: version 450 is unknown\n\
+                        \x20 note: while parsing\n\
+                        C:\\shaders\\a.frag:12: error: 'main' : missing\n\
+                        error: no input\n\
+                        3 errors generated.\n";
+        let diagnostics = Diagnostic::parse(messages);
+        assert_eq!(4, diagnostics.len());
+        assert_eq!(
+            Diagnostic {
+                severity: Severity::Error,
+                file: Some("shader.glsl".to_string()),
+                line: Some(3),
+                message: "'x' : undeclared identifier".to_string(),
+                include_stack: Vec::new(),
+            },
+            diagnostics[0]
+        );
+        assert_eq!(Severity::Warning, diagnostics[1].severity);
+        assert_eq!(None, diagnostics[1].line);
+        assert_eq!(
+            "version 450 is unknown\n  note: while parsing",
+            diagnostics[1].message
+        );
+        assert_eq!(Some("C:\\shaders\\a.frag"), diagnostics[2].file.as_deref());
+        assert_eq!(Some(12), diagnostics[2].line);
+        assert_eq!(None, diagnostics[3].file);
+        assert_eq!("error: no input", diagnostics[3].to_string());
+    }
+
+    #[test]
+    fn test_diagnostic_display_round_trip() {
+        let line = "a.vert:7: warning: unused variable";
+        assert_eq!(line, Diagnostic::parse(line)[0].to_string());
+        let error = Error::CompilationError(1, line.replace("warning", "error"));
+        assert_eq!(Severity::Error, error.diagnostics()[0].severity);
+        assert!(Error::InternalError(line.to_string())
+            .diagnostics()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_summary_count() {
+        assert_eq!(
+            Some((2, Severity::Error)),
+            summary_count("2 errors generated.")
+        );
+        assert_eq!(
+            Some((1, Severity::Warning)),
+            summary_count("1 warning generated.")
+        );
+        assert_eq!(None, summary_count("a.vert:1: error: 2 errors generated."));
+    }
+
+    #[test]
+    fn test_diagnostic_policy() {
+        let warnings = "a.vert:1: warning: attribute deprecated in version 130\n\
+                        a.vert:2: warning: 'lowp' : precision qualifier ignored\n\
+                        a.vert:3: warning: extension not supported\n";
+        let artifact =
+            || CompilationArtifact::from_binary(vec![]).with_warnings(3, warnings.into());
+        let categories: Vec<DiagnosticCategory> = artifact()
+            .diagnostics()
+            .iter()
+            .map(Diagnostic::category)
+            .collect();
+        assert_eq!(
+            vec![
+                DiagnosticCategory::Deprecation,
+                DiagnosticCategory::Precision,
+                DiagnosticCategory::Other
+            ],
+            categories
+        );
+
+        let policy =
+            DiagnosticPolicy::new().set(DiagnosticCategory::Precision, DiagnosticAction::Ignore);
+        let filtered = policy.apply(artifact()).ok().unwrap();
+        assert_eq!(2, filtered.get_num_warnings());
+        assert_eq!(
+            "a.vert:1: warning: attribute deprecated in version 130\n\
+             a.vert:3: warning: extension not supported\n",
+            filtered.get_warning_messages()
+        );
+
+        let policy = policy.set(DiagnosticCategory::Deprecation, DiagnosticAction::Error);
+        assert_matches!(
+            policy.apply(artifact()).err(),
+            Some(Error::CompilationError(1, ref messages))
+                if messages.starts_with("a.vert:1: error: attribute deprecated")
+        );
+    }
+
+    #[test]
+    fn test_include_stack() {
+        let includes = [
+            ("common.glsl".to_string(), "lighting.glsl".to_string()),
+            ("lighting.glsl".to_string(), "main.frag".to_string()),
+        ];
+        let messages = add_include_stacks(
+            "common.glsl:4: error: 'x' : undeclared identifier\n\
+             main.frag:2: warning: unused\n",
+            &includes,
+        );
+        assert_eq!(
+            "common.glsl:4: error: 'x' : undeclared identifier\n\
+             \x20 included from lighting.glsl\n\
+             \x20 included from main.frag\n\
+             main.frag:2: warning: unused\n",
+            messages
+        );
+        let diagnostics = Diagnostic::parse(&messages);
+        assert_eq!(2, diagnostics.len());
+        assert_eq!(
+            vec!["lighting.glsl", "main.frag"],
+            diagnostics[0].include_stack
+        );
+        assert_eq!("'x' : undeclared identifier", diagnostics[0].message);
+        assert!(diagnostics[1].include_stack.is_empty());
+        assert_eq!(
+            messages.lines().take(3).collect::<Vec<_>>().join("\n"),
+            diagnostics[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_rewrite_line_directives() {
+        let source = "#version 450\n#line 10 \"gen.in\"\nfloat a;\nfloat b;\n# line 40\nfloat c;\n";
+        assert!(LineMap::new("#version 450\n#lines\n").is_none());
+        let line_map = LineMap::new(source).unwrap();
+        assert_eq!(
+            "#version 450\n\nfloat a;\nfloat b;\n\nfloat c;\n",
+            line_map.source()
+        );
+        let error = Error::CompilationError(
+            3,
+            "shader.glsl:1: error: 'a' : bad\n\
+             shader.glsl:4: error: 'b' : bad\n\
+             shader.glsl:6: error: 'c' : bad\n\
+             other.glsl:6: error: 'd' : bad\n"
+                .to_string(),
+        );
+        let error = rewrite_lines(Err(error), Some(&line_map), "shader.glsl")
+            .err()
+            .unwrap();
+        let located: Vec<_> = error
+            .diagnostics()
+            .iter()
+            .map(|d| (d.file.clone().unwrap(), d.line.unwrap()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("shader.glsl".to_string(), 1),
+                ("gen.in".to_string(), 11),
+                ("gen.in".to_string(), 40),
+                ("other.glsl".to_string(), 6),
+            ],
+            located
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A report of the native shaderc library in use, for triaging linking and
+//! version issues.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use shaderc_sys as scs;
+
+use crate::{get_spirv_version, library_info, Compiler, EnvVersion, SpirvVersion};
+
+/// The native shaderc library in use and what it supports, as returned by
+/// `diagnose_environment`.
+///
+/// The `Display` implementation prints the report as `shaderc doctor` does.
+/// libshaderc does not report the version of glslang it is built with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvironmentReport {
+    /// The version of this crate.
+    pub crate_version: &'static str,
+    /// Where the linked library comes from: `system`, `precompiled` or
+    /// `source`.
+    pub link_origin: Option<&'static str>,
+    /// The directory the build script linked the library from.
+    pub link_dir: Option<&'static str>,
+    pub link_name: Option<&'static str>,
+    /// `static` or `dylib`.
+    pub link_kind: Option<&'static str>,
+    /// The file the library was loaded from at run time: the shared library,
+    /// or the executable when linked statically. `None` where the platform
+    /// cannot tell.
+    pub loaded_from: Option<PathBuf>,
+    /// The SPIR-V version and revision, as returned by `get_spirv_version`.
+    pub spirv_version: (u32, u32),
+    /// Whether a compiler can be created.
+    pub compiler_available: bool,
+    /// The oldest shaderc release with the capabilities found, as in
+    /// `LibraryInfo::min_release`.
+    pub min_release: Option<&'static str>,
+    /// The target environments a trivial shader compiles for.
+    pub target_envs: Vec<EnvVersion>,
+    /// The SPIR-V versions a trivial shader compiles to.
+    pub spirv_versions: Vec<SpirvVersion>,
+}
+
+/// Returns a report of the native shaderc library in use: how it was linked,
+/// where it was loaded from, and the versions it supports.
+///
+/// Supported target environments and SPIR-V versions are those of
+/// `library_info`.
+pub fn diagnose_environment() -> EnvironmentReport {
+    let info = library_info();
+    EnvironmentReport {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        link_origin: scs::LINK_ORIGIN,
+        link_dir: scs::LINK_DIR,
+        link_name: scs::LINK_NAME,
+        link_kind: scs::LINK_KIND,
+        loaded_from: loaded_from(),
+        spirv_version: get_spirv_version(),
+        compiler_available: Compiler::new().is_some(),
+        min_release: info.min_release,
+        target_envs: info.target_envs,
+        spirv_versions: info.spirv_versions,
+    }
+}
+
+/// Returns the file containing libshaderc's code, as reported by `dladdr`.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+))]
+fn loaded_from() -> Option<PathBuf> {
+    use std::ffi::{CStr, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut info: libc::Dl_info = unsafe { std::mem::zeroed() };
+    let function = scs::shaderc_compiler_initialize as *const libc::c_void;
+    if unsafe { libc::dladdr(function, &mut info) } == 0 || info.dli_fname.is_null() {
+        return None;
+    }
+    let name = unsafe { CStr::from_ptr(info.dli_fname) };
+    Some(PathBuf::from(OsStr::from_bytes(name.to_bytes())))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+fn loaded_from() -> Option<PathBuf> {
+    None
+}
+
+impl fmt::Display for EnvironmentReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unknown = |value: Option<&str>| value.unwrap_or("unknown").to_owned();
+        writeln!(f, "shaderc crate: {}", self.crate_version)?;
+        writeln!(
+            f,
+            "native library: {} ({}, {})",
+            unknown(self.link_name),
+            unknown(self.link_kind),
+            unknown(self.link_origin)
+        )?;
+        writeln!(f, "linked from: {}", unknown(self.link_dir))?;
+        match self.loaded_from {
+            Some(ref path) => writeln!(f, "loaded from: {}", path.display())?,
+            None => writeln!(f, "loaded from: unknown")?,
+        }
+        let (version, revision) = self.spirv_version;
+        writeln!(
+            f,
+            "SPIR-V: {}.{}, revision {}",
+            (version >> 16) & 0xff,
+            (version >> 8) & 0xff,
+            revision
+        )?;
+        if !self.compiler_available {
+            return writeln!(f, "compiler: cannot be created");
+        }
+        match self.min_release {
+            Some(release) => writeln!(f, "shaderc release: {release} or later")?,
+            None => writeln!(f, "shaderc release: older than v2020.0")?,
+        }
+        let list = |names: Vec<String>| {
+            if names.is_empty() {
+                "none".to_owned()
+            } else {
+                names.join(", ")
+            }
+        };
+        writeln!(
+            f,
+            "target environments: {}",
+            list(self.target_envs.iter().map(|env| env.to_string()).collect())
+        )?;
+        writeln!(
+            f,
+            "SPIR-V versions: {}",
+            list(self.spirv_versions.iter().map(|v| v.to_string()).collect())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_environment() {
+        let report = diagnose_environment();
+        assert!(report.compiler_available);
+        assert!(report.target_envs.contains(&EnvVersion::Vulkan1_0));
+        assert!(report.spirv_versions.contains(&SpirvVersion::V1_0));
+        assert!(report.link_origin.is_some());
+        let text = report.to_string();
+        assert!(text.contains("target environments: vulkan1.0"));
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A process-wide hook observing compilations, include resolutions and
+//! cache lookups, for telemetry without the `tracing` feature.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::{CompilationArtifact, Error, IncludeCallbackResult, Result};
+
+type Hook = Arc<dyn Fn(&Event) + Send + Sync>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+// Whether a hook is set, checked before building events.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// An event passed to the hook set with `set_event_hook`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event<'a> {
+    /// A compilation entry point of `Compiler`, like `compile_into_spirv`,
+    /// was called.
+    CompileStarted {
+        method: &'static str,
+        input_file_name: &'a str,
+    },
+    /// A compilation finished, with the size of its output in bytes or its
+    /// error.
+    CompileFinished {
+        method: &'static str,
+        input_file_name: &'a str,
+        duration: Duration,
+        result: std::result::Result<usize, &'a Error>,
+    },
+    /// An include callback returned, with the resolved name or its error.
+    IncludeResolved {
+        requested_source: &'a str,
+        requesting_source: &'a str,
+        result: std::result::Result<&'a str, &'a str>,
+        duration: Duration,
+    },
+    /// A `ShaderCache` lookup found the entry with this key.
+    CacheHit {
+        key: &'a [u8; 32],
+        duration: Duration,
+    },
+    /// A `ShaderCache` lookup found no entry with this key.
+    CacheMiss {
+        key: &'a [u8; 32],
+        duration: Duration,
+    },
+}
+
+/// Sets the hook called with every `Event` of the process, replacing any
+/// previous hook.
+///
+/// The hook is called from the thread the event happens on, so it should
+/// return quickly. Events are only built while a hook is set.
+///
+/// ```
+/// use shaderc::{set_event_hook, Event};
+///
+/// set_event_hook(|event| {
+///     if let Event::CompileFinished { input_file_name, duration, .. } = *event {
+///         println!("compiled {} in {:?}", input_file_name, duration);
+///     }
+/// });
+/// ```
+pub fn set_event_hook<F>(hook: F)
+where
+    F: Fn(&Event) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(hook));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Removes the hook set with `set_event_hook`.
+pub fn clear_event_hook() {
+    ENABLED.store(false, Ordering::Release);
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Passes `event` to the hook, if one is set.
+pub(crate) fn emit(event: Event) {
+    if !ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+    // The hook is called without holding the lock, so that it may replace
+    // itself.
+    let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner).clone();
+    if let Some(hook) = hook {
+        hook(&event);
+    }
+}
+
+/// Returns whether a hook is set.
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Runs the compilation `f`, emitting its start and finish events.
+pub(crate) fn observe_compile<F>(
+    method: &'static str,
+    input_file_name: &str,
+    f: F,
+) -> Result<CompilationArtifact>
+where
+    F: FnOnce() -> Result<CompilationArtifact>,
+{
+    if !enabled() {
+        return f();
+    }
+    emit(Event::CompileStarted {
+        method,
+        input_file_name,
+    });
+    let start = Instant::now();
+    let result = f();
+    emit(Event::CompileFinished {
+        method,
+        input_file_name,
+        duration: start.elapsed(),
+        result: result.as_ref().map(|artifact| artifact.len()),
+    });
+    result
+}
+
+/// Runs the include resolution `f`, emitting its event.
+pub(crate) fn observe_include<F>(
+    requested_source: &str,
+    requesting_source: &str,
+    f: F,
+) -> IncludeCallbackResult
+where
+    F: FnOnce() -> IncludeCallbackResult,
+{
+    if !enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    emit(Event::IncludeResolved {
+        requested_source,
+        requesting_source,
+        result: match result {
+            Ok(ref include) => Ok(&include.resolved_name),
+            Err(ref error) => Err(error),
+        },
+        duration: start.elapsed(),
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CompileOptions, Compiler, ResolvedInclude, ShaderKind};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_event_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        set_event_hook(move |event| {
+            // Other tests may compile concurrently, so only the events of
+            // this compilation are recorded.
+            let name = match *event {
+                Event::CompileStarted {
+                    input_file_name: "events.vert",
+                    ..
+                } => "started",
+                Event::CompileFinished {
+                    input_file_name: "events.vert",
+                    result: Ok(_),
+                    ..
+                } => "finished",
+                Event::IncludeResolved {
+                    requested_source: "events_a.h",
+                    result: Ok("events_a.h"),
+                    ..
+                } => "include",
+                _ => return,
+            };
+            recorded.lock().unwrap().push(name);
+        });
+        let compiler = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_include_callback(|name, _, _, _| {
+            Ok(ResolvedInclude {
+                resolved_name: name.to_string(),
+                content: String::new(),
+            })
+        });
+        let source = "#version 450\n#include \"events_a.h\"\nvoid main() {}";
+        compiler
+            .compile_into_spirv(
+                source,
+                ShaderKind::Vertex,
+                "events.vert",
+                "main",
+                Some(&options),
+            )
+            .unwrap();
+        clear_event_hook();
+        assert_eq!(
+            vec!["started", "include", "finished"],
+            *events.lock().unwrap()
+        );
+    }
+}
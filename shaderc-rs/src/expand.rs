@@ -0,0 +1,96 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macro expansion of source snippets, for tools showing what macro-heavy
+//! code expands to.
+
+use crate::{CompileOptions, Compiler, Result};
+
+/// The input file name of the snippets passed to the preprocessor.
+const SNIPPET_NAME: &str = "snippet";
+
+/// Returns `source_text` with its `#include` directives replaced by empty
+/// lines, so that the lines after them keep their numbers.
+fn without_includes(source_text: &str) -> String {
+    source_text
+        .split_inclusive('\n')
+        .map(|line| {
+            let directive = line.trim_start().strip_prefix('#').map(str::trim_start);
+            match directive {
+                Some(directive) if directive.starts_with("include") => {
+                    if line.ends_with('\n') {
+                        "\n"
+                    } else {
+                        ""
+                    }
+                }
+                _ => line,
+            }
+        })
+        .collect()
+}
+
+impl Compiler {
+    /// Returns `source_text` with its macros expanded and its conditional
+    /// blocks resolved, as the preprocessor does, but with its `#include`
+    /// directives dropped instead of resolved.
+    ///
+    /// Unlike `preprocess`, this needs no include callback and does not
+    /// splice headers into the result, so the text stays the size of the
+    /// snippet. Macros defined in included headers are not known, though:
+    /// pass the ones the snippet depends on with
+    /// `CompileOptions::add_macro_definition`. The snippet does not need a
+    /// `#version` directive or a `main` function.
+    pub fn expand_macros(
+        &self,
+        source_text: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<String> {
+        let source = without_includes(source_text);
+        self.preprocess(&source, SNIPPET_NAME, "main", additional_options)
+            .map(|artifact| artifact.as_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_includes() {
+        assert_eq!(
+            "#version 450\n\n\n#define N 4\nfloat a[N];",
+            without_includes(
+                "#version 450\n#include \"a.glsl\"\n  #  include <b.glsl>\n#define N 4\nfloat a[N];"
+            )
+        );
+        assert_eq!("", without_includes("#include \"a.glsl\""));
+    }
+
+    #[test]
+    fn test_expand_macros() {
+        let compiler = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.add_macro_definition("SCALE", Some("2.0"));
+        let expanded = compiler
+            .expand_macros(
+                "#include \"common.glsl\"\n#define TWICE(x) ((x) * SCALE)\nfloat f = TWICE(3.0);\n",
+                Some(&options),
+            )
+            .unwrap();
+        let tokens: String = expanded.split_whitespace().collect();
+        assert!(tokens.contains("floatf=((3.0)*2.0);"));
+        assert!(!tokens.contains("TWICE"));
+    }
+}
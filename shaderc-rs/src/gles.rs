@@ -0,0 +1,235 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compiling for OpenGL ES devices through SPIR-V cross-compilation, as done
+//! for ANGLE and WebGL.
+
+use std::fmt;
+
+use crate::reflection::shader_kind;
+use crate::spirv_module::{decoration, literal_string, op, storage_class, SpirvModule};
+use crate::{Capability, CompileOptions, GlslProfile, Limit, ShaderKind};
+
+/// Resource limits of OpenGL ES 3.0, the minimums of the specification,
+/// which common mobile GPUs do not exceed by much.
+const GLES3_LIMITS: [(Limit, i32); 12] = [
+    (Limit::MaxVertexAttribs, 16),
+    (Limit::MaxVertexUniformVectors, 256),
+    (Limit::MaxVertexOutputVectors, 16),
+    (Limit::MaxVaryingVectors, 15),
+    (Limit::MaxFragmentInputVectors, 15),
+    (Limit::MaxFragmentUniformVectors, 224),
+    (Limit::MaxVertexTextureImageUnits, 16),
+    (Limit::MaxTextureImageUnits, 16),
+    (Limit::MaxCombinedTextureImageUnits, 32),
+    (Limit::MaxDrawBuffers, 4),
+    (Limit::MinProgramTexelOffset, -8),
+    (Limit::MaxProgramTexelOffset, 7),
+];
+
+/// Capabilities OpenGL ES 3.0 does not support, with the version or
+/// extension providing them, if any.
+#[rustfmt::skip]
+static CAPABILITIES: &[(Capability, Option<&str>)] = &[
+    (Capability::ATOMIC_STORAGE, Some("OpenGL ES 3.1")),
+    (Capability::IMAGE_GATHER_EXTENDED, Some("OpenGL ES 3.1")),
+    (Capability::GEOMETRY, Some("OpenGL ES 3.2")),
+    (Capability::TESSELLATION, Some("OpenGL ES 3.2")),
+    (Capability::SAMPLED_BUFFER, Some("OpenGL ES 3.2")),
+    (Capability::IMAGE_BUFFER, Some("OpenGL ES 3.2")),
+    (Capability::SAMPLED_CUBE_ARRAY, Some("OpenGL ES 3.2")),
+    (Capability::IMAGE_CUBE_ARRAY, Some("OpenGL ES 3.2")),
+    (Capability::SAMPLE_RATE_SHADING, Some("OpenGL ES 3.2")),
+    (Capability::CLIP_DISTANCE, Some("GL_EXT_clip_cull_distance")),
+    (Capability::CULL_DISTANCE, Some("GL_EXT_clip_cull_distance")),
+    (Capability::MULTI_VIEW, Some("GL_OVR_multiview2")),
+    (Capability::GROUP_NON_UNIFORM, Some("GL_KHR_shader_subgroup")),
+    (Capability::FLOAT64, None),
+    (Capability::INT64, None),
+    (Capability::INT16, None),
+    (Capability::INT8, None),
+    (Capability::FLOAT16, None),
+    (Capability::SAMPLED_1D, None),
+    (Capability::IMAGE_1D, None),
+    (Capability::SAMPLED_RECT, None),
+    (Capability::IMAGE_RECT, None),
+    (Capability::IMAGE_MS_ARRAY, None),
+    (Capability::STORAGE_IMAGE_MULTISAMPLE, None),
+    (Capability::RAY_QUERY_KHR, None),
+    (Capability::RAY_TRACING_KHR, None),
+    (Capability::MESH_SHADING_EXT, None),
+];
+
+impl<'a> CompileOptions<'a> {
+    /// Returns options for shaders targeting OpenGL ES 3.0 class devices
+    /// through SPIR-V cross-compilation, as for ANGLE or WebGL 2.
+    ///
+    /// Sources are compiled as GLSL ES 3.10, the oldest version glslang
+    /// compiles to SPIR-V, and the resource limits are those of OpenGL ES
+    /// 3.0. Check the compiled modules with `SpirvModule::gles3_issues` for
+    /// the OpenGL ES 3.1 constructs this lets through.
+    ///
+    /// Returns `None` if the options cannot be created.
+    pub fn opengl_es() -> Option<CompileOptions<'a>> {
+        let mut options = CompileOptions::new()?;
+        options.set_forced_version_profile(310, GlslProfile::Es);
+        for &(limit, value) in &GLES3_LIMITS {
+            options.set_limit(limit, value);
+        }
+        Some(options)
+    }
+}
+
+/// A construct of a module which OpenGL ES 3.0 devices do not support, as
+/// returned by `SpirvModule::gles3_issues`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlesIssue {
+    /// The construct, like `capability Geometry` or `compute entry point main`.
+    pub construct: String,
+    /// The OpenGL ES version or extension supporting the construct, if any.
+    pub available_in: Option<&'static str>,
+}
+
+impl fmt::Display for GlesIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.available_in {
+            Some(available_in) => write!(f, "{} requires {}", self.construct, available_in),
+            None => write!(f, "{} is not supported on OpenGL ES", self.construct),
+        }
+    }
+}
+
+impl SpirvModule {
+    /// Returns the constructs of the module which OpenGL ES 3.0 devices do
+    /// not support: stages other than vertex and fragment, storage buffers,
+    /// storage images and the capabilities of newer versions.
+    ///
+    /// An empty result means the module can be cross-compiled to GLSL ES
+    /// 3.00, as WebGL 2 requires.
+    pub fn gles3_issues(&self) -> Vec<GlesIssue> {
+        let mut issues = Vec::new();
+        let mut storage_buffers = false;
+        let mut storage_images = false;
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            match inst.opcode() {
+                op::ENTRY_POINT if operands.len() >= 2 => {
+                    let kind = shader_kind(operands[0]);
+                    let available_in = match kind {
+                        Some(ShaderKind::Vertex) | Some(ShaderKind::Fragment) => continue,
+                        Some(ShaderKind::Compute) => Some("OpenGL ES 3.1"),
+                        Some(ShaderKind::Geometry)
+                        | Some(ShaderKind::TessControl)
+                        | Some(ShaderKind::TessEvaluation) => Some("OpenGL ES 3.2"),
+                        _ => None,
+                    };
+                    let stage = kind.map_or_else(
+                        || format!("execution model {}", operands[0]),
+                        |kind| kind.to_string(),
+                    );
+                    let (name, _) = literal_string(&operands[2..]);
+                    issues.push(GlesIssue {
+                        construct: format!("{stage} entry point {name}"),
+                        available_in,
+                    });
+                }
+                op::TYPE_POINTER if operands.get(1) == Some(&storage_class::STORAGE_BUFFER) => {
+                    storage_buffers = true;
+                }
+                op::DECORATE if operands.get(1) == Some(&decoration::BUFFER_BLOCK) => {
+                    storage_buffers = true;
+                }
+                // Sampled 2 marks images used without a sampler, which are
+                // storage images unless their dimension is SubpassData.
+                op::TYPE_IMAGE if operands.get(6) == Some(&2) && operands[2] != 6 => {
+                    storage_images = true;
+                }
+                _ => {}
+            }
+        }
+        if storage_buffers {
+            issues.push(GlesIssue {
+                construct: "storage buffer".to_string(),
+                available_in: Some("OpenGL ES 3.1"),
+            });
+        }
+        if storage_images {
+            issues.push(GlesIssue {
+                construct: "storage image".to_string(),
+                available_in: Some("OpenGL ES 3.1"),
+            });
+        }
+        let capabilities = self.capabilities();
+        for &(capability, available_in) in CAPABILITIES {
+            if capabilities.contains(&capability) {
+                issues.push(GlesIssue {
+                    construct: format!("capability {capability}"),
+                    available_in,
+                });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::encode_string;
+    use crate::spirv_module::tests::{inst, module};
+
+    #[test]
+    fn test_gles3_issues() {
+        let mut entry_point = vec![4, 1];
+        entry_point.extend(encode_string("main"));
+        let fragment = module(&[
+            inst(op::CAPABILITY, &[Capability::SHADER.0]),
+            inst(op::ENTRY_POINT, &entry_point),
+            inst(op::TYPE_POINTER, &[2, storage_class::UNIFORM, 3]),
+        ]);
+        assert_eq!(Vec::<GlesIssue>::new(), fragment.gles3_issues());
+
+        let mut entry_point = vec![5, 1];
+        entry_point.extend(encode_string("main"));
+        let compute = module(&[
+            inst(op::CAPABILITY, &[Capability::SHADER.0]),
+            inst(op::CAPABILITY, &[Capability::FLOAT64.0]),
+            inst(op::ENTRY_POINT, &entry_point),
+            inst(op::TYPE_POINTER, &[2, storage_class::STORAGE_BUFFER, 3]),
+            inst(op::TYPE_IMAGE, &[4, 5, 1, 0, 0, 0, 2, 1]),
+        ]);
+        assert_eq!(
+            vec![
+                "compute entry point main requires OpenGL ES 3.1",
+                "storage buffer requires OpenGL ES 3.1",
+                "storage image requires OpenGL ES 3.1",
+                "capability Float64 is not supported on OpenGL ES",
+            ],
+            compute
+                .gles3_issues()
+                .iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_opengl_es_options() {
+        let options = CompileOptions::opengl_es().unwrap();
+        assert_eq!(
+            Some((310, GlslProfile::Es)),
+            options.settings.forced_version_profile
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for HLSL sources.
+//!
+//! An HLSL file usually holds several entry points, which glslang compiles
+//! one at a time. `discover_entry_points` lists them, so batch compilers can
+//! compile every entry point of a file without a manifest naming them.
+//!
+//! ```
+//! use shaderc::hlsl::discover_entry_points;
+//! use shaderc::ShaderKind;
+//!
+//! let source = r#"
+//!     [shader("compute")]
+//!     [numthreads(8, 8, 1)]
+//!     void Blur(uint3 id : SV_DispatchThreadID) {}
+//!
+//!     float4 PSMain(float4 position : SV_Position) : SV_Target { return position; }
+//! "#;
+//! assert_eq!(
+//!     vec![
+//!         ("Blur".to_string(), ShaderKind::Compute),
+//!         ("PSMain".to_string(), ShaderKind::Fragment),
+//!     ],
+//!     discover_entry_points(source)
+//! );
+//! ```
+
+use crate::ShaderKind;
+
+/// The stages named by the `[shader("...")]` attribute.
+const SHADER_ATTRIBUTE_STAGES: [(&str, ShaderKind); 14] = [
+    ("vertex", ShaderKind::Vertex),
+    ("pixel", ShaderKind::Fragment),
+    ("geometry", ShaderKind::Geometry),
+    ("hull", ShaderKind::TessControl),
+    ("domain", ShaderKind::TessEvaluation),
+    ("compute", ShaderKind::Compute),
+    ("amplification", ShaderKind::Task),
+    ("mesh", ShaderKind::Mesh),
+    ("raygeneration", ShaderKind::RayGeneration),
+    ("anyhit", ShaderKind::AnyHit),
+    ("closesthit", ShaderKind::ClosestHit),
+    ("miss", ShaderKind::Miss),
+    ("intersection", ShaderKind::Intersection),
+    ("callable", ShaderKind::Callable),
+];
+
+/// The stage prefixes of the conventional entry point names, like `VSMain`,
+/// `ps_main` or `CS`.
+const NAME_PREFIXES: [(&str, ShaderKind); 8] = [
+    ("vs", ShaderKind::Vertex),
+    ("ps", ShaderKind::Fragment),
+    ("gs", ShaderKind::Geometry),
+    ("hs", ShaderKind::TessControl),
+    ("ds", ShaderKind::TessEvaluation),
+    ("cs", ShaderKind::Compute),
+    ("as", ShaderKind::Task),
+    ("ms", ShaderKind::Mesh),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Ident(&'a str),
+    Str(&'a str),
+    Punct(char),
+}
+
+/// Splits `source` into identifiers, string literals and punctuation,
+/// skipping comments, whitespace and numbers.
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.find("*/").map_or("", |end| &comment[end + 2..]);
+        } else if c == '"' {
+            let end = rest[1..].find('"').map_or(rest.len(), |end| end + 1);
+            tokens.push(Token::Str(&rest[1..end]));
+            rest = rest.get(end + 1..).unwrap_or("");
+        } else if c.is_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            if !c.is_ascii_digit() {
+                tokens.push(Token::Ident(&rest[..end]));
+            }
+            rest = &rest[end..];
+        } else {
+            if !c.is_whitespace() {
+                tokens.push(Token::Punct(c));
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+/// Returns the stage of an entry point named `name` by convention, if any.
+fn stage_from_name(name: &str) -> Option<ShaderKind> {
+    let name = name.to_ascii_lowercase();
+    NAME_PREFIXES.iter().find_map(|&(prefix, kind)| {
+        let conventional = name == prefix
+            || name
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest == "main" || rest == "_main")
+            || name
+                .strip_prefix("main")
+                .is_some_and(|rest| rest.trim_start_matches('_') == prefix);
+        conventional.then_some(kind)
+    })
+}
+
+/// Returns the entry points of the HLSL source `source_text` with their
+/// stages, in the order they are defined.
+///
+/// The stage of a function comes from its `[shader("...")]` attribute, as
+/// in shader libraries, or else from its name following the `VSMain`,
+/// `vs_main` or `VS` conventions, or else from a `[numthreads]` attribute,
+/// which marks compute shaders. Functions with none of them are skipped.
+///
+/// This is a lightweight scan of the top-level declarations, not a parse:
+/// functions declared through macros or in inactive preprocessor blocks
+/// are not told apart.
+pub fn discover_entry_points(source_text: &str) -> Vec<(String, ShaderKind)> {
+    let tokens = tokenize(source_text);
+    let mut entry_points: Vec<(String, ShaderKind)> = Vec::new();
+    let mut depth = 0usize;
+    let mut attribute_stage = None;
+    let mut numthreads = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => depth = depth.saturating_sub(1),
+            Token::Punct('[') if depth == 0 => {
+                match tokens[i + 1..] {
+                    [Token::Ident("shader"), Token::Punct('('), Token::Str(stage), ..] => {
+                        attribute_stage = SHADER_ATTRIBUTE_STAGES
+                            .iter()
+                            .find(|entry| entry.0.eq_ignore_ascii_case(stage))
+                            .map(|entry| entry.1);
+                    }
+                    [Token::Ident("numthreads"), ..] => numthreads = true,
+                    _ => {}
+                }
+                while i < tokens.len() && tokens[i] != Token::Punct(']') {
+                    i += 1;
+                }
+            }
+            Token::Punct(';') if depth == 0 => {
+                attribute_stage = None;
+                numthreads = false;
+            }
+            Token::Ident(name) if depth == 0 => {
+                let declared = tokens.get(i + 1) == Some(&Token::Punct('('))
+                    && matches!(
+                        tokens[..i].last(),
+                        Some(Token::Ident(_)) | Some(Token::Punct('>'))
+                    );
+                if declared {
+                    let stage = attribute_stage
+                        .or_else(|| stage_from_name(name))
+                        .or_else(|| numthreads.then_some(ShaderKind::Compute));
+                    if let Some(stage) = stage {
+                        if !entry_points.iter().any(|entry| entry.0 == name) {
+                            entry_points.push((name.to_string(), stage));
+                        }
+                    }
+                    attribute_stage = None;
+                    numthreads = false;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    entry_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_entry_points() {
+        let source = r#"
+            // float4 PSOld(float4 p : SV_Position) : SV_Target { return p; }
+            cbuffer Constants : register(b0) { float4x4 transform; };
+            float4 Helper(float4 p) { return mul(transform, p); }
+
+            struct VSOutput { float4 position : SV_Position; };
+            VSOutput vs_main(float4 p : POSITION) {
+                VSOutput output;
+                output.position = Helper(p);
+                return output;
+            }
+
+            /* [shader("pixel")] */
+            [shader("pixel")]
+            float4 Shade(VSOutput input) : SV_Target { return input.position; }
+
+            [numthreads(64, 1, 1)]
+            void Reduce(uint3 id : SV_DispatchThreadID) {}
+
+            [shader("raygeneration")]
+            void RayGen() {}
+
+            float4 PS(VSOutput input) : SV_Target;
+            float4 PS(VSOutput input) : SV_Target { return 0; }
+        "#;
+        assert_eq!(
+            vec![
+                ("vs_main".to_string(), ShaderKind::Vertex),
+                ("Shade".to_string(), ShaderKind::Fragment),
+                ("Reduce".to_string(), ShaderKind::Compute),
+                ("RayGen".to_string(), ShaderKind::RayGeneration),
+                ("PS".to_string(), ShaderKind::Fragment),
+            ],
+            discover_entry_points(source)
+        );
+        assert_eq!(Some(ShaderKind::Mesh), stage_from_name("MSMain"));
+        assert_eq!(Some(ShaderKind::Vertex), stage_from_name("main_vs"));
+        assert_eq!(None, stage_from_name("main"));
+        assert_eq!(None, stage_from_name("Cast"));
+    }
+}
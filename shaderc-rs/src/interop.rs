@@ -0,0 +1,506 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions to and from types of other crates in the graphics ecosystem.
+//!
+//! Each integration is gated behind a feature named after the crate it
+//! interoperates with.
+
+#[cfg(feature = "ash")]
+use ash::vk;
+#[cfg(feature = "spirv_cross")]
+use spirv_cross::spirv as cross;
+#[cfg(feature = "spirv_cross")]
+use spirv_cross::{msl, ErrorCode};
+#[cfg(feature = "spirv_cross")]
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+#[cfg(feature = "spirv_cross")]
+use crate::reflection::{DescriptorBinding, DescriptorType};
+#[cfg(feature = "ash")]
+use crate::CompilationArtifact;
+#[cfg(feature = "spirv_cross")]
+use crate::SpirvModule;
+use crate::{Error, ShaderKind};
+
+#[cfg(feature = "spirv")]
+impl ShaderKind {
+    /// Returns the SPIR-V execution model of the stage this shader kind
+    /// compiles for.
+    ///
+    /// Default shader kinds map to the execution model of the stage they fall
+    /// back to. Returns `None` for `InferFromSource` and `SpirvAssembly`, which
+    /// do not name a stage.
+    pub fn execution_model(self) -> Option<spirv::ExecutionModel> {
+        use spirv::ExecutionModel as Model;
+        match self {
+            ShaderKind::Vertex | ShaderKind::DefaultVertex => Some(Model::Vertex),
+            ShaderKind::Fragment | ShaderKind::DefaultFragment => Some(Model::Fragment),
+            ShaderKind::Compute | ShaderKind::DefaultCompute => Some(Model::GLCompute),
+            ShaderKind::Geometry | ShaderKind::DefaultGeometry => Some(Model::Geometry),
+            ShaderKind::TessControl | ShaderKind::DefaultTessControl => {
+                Some(Model::TessellationControl)
+            }
+            ShaderKind::TessEvaluation | ShaderKind::DefaultTessEvaluation => {
+                Some(Model::TessellationEvaluation)
+            }
+            ShaderKind::RayGeneration | ShaderKind::DefaultRayGeneration => {
+                Some(Model::RayGenerationKHR)
+            }
+            ShaderKind::AnyHit | ShaderKind::DefaultAnyHit => Some(Model::AnyHitKHR),
+            ShaderKind::ClosestHit | ShaderKind::DefaultClosestHit => Some(Model::ClosestHitKHR),
+            ShaderKind::Miss | ShaderKind::DefaultMiss => Some(Model::MissKHR),
+            ShaderKind::Intersection | ShaderKind::DefaultIntersection => {
+                Some(Model::IntersectionKHR)
+            }
+            ShaderKind::Callable | ShaderKind::DefaultCallable => Some(Model::CallableKHR),
+            ShaderKind::Task | ShaderKind::DefaultTask => Some(Model::TaskEXT),
+            ShaderKind::Mesh | ShaderKind::DefaultMesh => Some(Model::MeshEXT),
+            ShaderKind::InferFromSource | ShaderKind::SpirvAssembly => None,
+        }
+    }
+}
+
+/// Converts a shader kind into the SPIR-V execution model of its stage.
+///
+/// See `ShaderKind::execution_model`. Returns `Error::InvalidStage` for
+/// `InferFromSource` and `SpirvAssembly`.
+#[cfg(feature = "spirv")]
+impl TryFrom<ShaderKind> for spirv::ExecutionModel {
+    type Error = Error;
+
+    fn try_from(kind: ShaderKind) -> Result<Self, Error> {
+        kind.execution_model().ok_or_else(|| {
+            Error::InvalidStage(format!(
+                "{kind:?} does not correspond to a SPIR-V execution model"
+            ))
+        })
+    }
+}
+
+/// Converts a SPIR-V execution model into the forced shader kind compiling for
+/// it.
+///
+/// The NV mesh shading execution models map to the `Task` and `Mesh` kinds.
+/// Returns `Error::InvalidStage` for `Kernel`, which shaderc cannot compile.
+#[cfg(feature = "spirv")]
+impl TryFrom<spirv::ExecutionModel> for ShaderKind {
+    type Error = Error;
+
+    fn try_from(model: spirv::ExecutionModel) -> Result<Self, Error> {
+        use spirv::ExecutionModel as Model;
+        match model {
+            Model::Vertex => Ok(ShaderKind::Vertex),
+            Model::TessellationControl => Ok(ShaderKind::TessControl),
+            Model::TessellationEvaluation => Ok(ShaderKind::TessEvaluation),
+            Model::Geometry => Ok(ShaderKind::Geometry),
+            Model::Fragment => Ok(ShaderKind::Fragment),
+            Model::GLCompute => Ok(ShaderKind::Compute),
+            Model::TaskNV | Model::TaskEXT => Ok(ShaderKind::Task),
+            Model::MeshNV | Model::MeshEXT => Ok(ShaderKind::Mesh),
+            Model::RayGenerationNV => Ok(ShaderKind::RayGeneration),
+            Model::IntersectionNV => Ok(ShaderKind::Intersection),
+            Model::AnyHitNV => Ok(ShaderKind::AnyHit),
+            Model::ClosestHitNV => Ok(ShaderKind::ClosestHit),
+            Model::MissNV => Ok(ShaderKind::Miss),
+            Model::CallableNV => Ok(ShaderKind::Callable),
+            Model::Kernel => Err(Error::InvalidStage(
+                "the Kernel execution model is not supported by shaderc".to_string(),
+            )),
+        }
+    }
+}
+
+/// Converts a naga shader stage into the forced shader kind compiling for it.
+#[cfg(feature = "naga")]
+impl From<naga::ShaderStage> for ShaderKind {
+    fn from(stage: naga::ShaderStage) -> Self {
+        match stage {
+            naga::ShaderStage::Vertex => ShaderKind::Vertex,
+            naga::ShaderStage::Task => ShaderKind::Task,
+            naga::ShaderStage::Mesh => ShaderKind::Mesh,
+            naga::ShaderStage::Fragment => ShaderKind::Fragment,
+            naga::ShaderStage::Compute => ShaderKind::Compute,
+            naga::ShaderStage::RayGeneration => ShaderKind::RayGeneration,
+            naga::ShaderStage::Miss => ShaderKind::Miss,
+            naga::ShaderStage::AnyHit => ShaderKind::AnyHit,
+            naga::ShaderStage::ClosestHit => ShaderKind::ClosestHit,
+        }
+    }
+}
+
+/// Converts a shader kind into the naga shader stage it compiles for.
+///
+/// Default shader kinds map to the stage they fall back to. Returns
+/// `Error::InvalidStage` for kinds without a naga equivalent, like
+/// geometry, tessellation, intersection and callable shaders, and for
+/// `InferFromSource` and `SpirvAssembly`.
+#[cfg(feature = "naga")]
+impl TryFrom<ShaderKind> for naga::ShaderStage {
+    type Error = Error;
+
+    fn try_from(kind: ShaderKind) -> Result<Self, Error> {
+        match kind {
+            ShaderKind::Vertex | ShaderKind::DefaultVertex => Ok(naga::ShaderStage::Vertex),
+            ShaderKind::Fragment | ShaderKind::DefaultFragment => Ok(naga::ShaderStage::Fragment),
+            ShaderKind::Compute | ShaderKind::DefaultCompute => Ok(naga::ShaderStage::Compute),
+            ShaderKind::Task | ShaderKind::DefaultTask => Ok(naga::ShaderStage::Task),
+            ShaderKind::Mesh | ShaderKind::DefaultMesh => Ok(naga::ShaderStage::Mesh),
+            ShaderKind::RayGeneration | ShaderKind::DefaultRayGeneration => {
+                Ok(naga::ShaderStage::RayGeneration)
+            }
+            ShaderKind::AnyHit | ShaderKind::DefaultAnyHit => Ok(naga::ShaderStage::AnyHit),
+            ShaderKind::ClosestHit | ShaderKind::DefaultClosestHit => {
+                Ok(naga::ShaderStage::ClosestHit)
+            }
+            ShaderKind::Miss | ShaderKind::DefaultMiss => Ok(naga::ShaderStage::Miss),
+            _ => Err(Error::InvalidStage(format!(
+                "{kind:?} does not correspond to a naga shader stage"
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "ash")]
+impl CompilationArtifact {
+    /// Returns a `VkShaderModuleCreateInfo` referencing the SPIR-V binary
+    /// module held by this artifact.
+    ///
+    /// The code size is given in bytes and the code pointer is the properly
+    /// aligned word slice returned by `as_binary`, so the result can be passed
+    /// to `vkCreateShaderModule` directly.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the compilation does not generate a
+    /// binary output.
+    pub fn shader_module_create_info(&self) -> vk::ShaderModuleCreateInfo<'_> {
+        vk::ShaderModuleCreateInfo::default().code(self.as_binary())
+    }
+}
+
+/// Converts a shader kind into the Vulkan stage it compiles for.
+///
+/// Default shader kinds map to the stage they fall back to. Returns
+/// `Error::InvalidStage` for `InferFromSource` and `SpirvAssembly`, which do
+/// not name a stage.
+#[cfg(feature = "ash")]
+impl TryFrom<ShaderKind> for vk::ShaderStageFlags {
+    type Error = Error;
+
+    fn try_from(kind: ShaderKind) -> Result<Self, Error> {
+        match kind {
+            ShaderKind::Vertex | ShaderKind::DefaultVertex => Ok(vk::ShaderStageFlags::VERTEX),
+            ShaderKind::Fragment | ShaderKind::DefaultFragment => {
+                Ok(vk::ShaderStageFlags::FRAGMENT)
+            }
+            ShaderKind::Compute | ShaderKind::DefaultCompute => Ok(vk::ShaderStageFlags::COMPUTE),
+            ShaderKind::Geometry | ShaderKind::DefaultGeometry => {
+                Ok(vk::ShaderStageFlags::GEOMETRY)
+            }
+            ShaderKind::TessControl | ShaderKind::DefaultTessControl => {
+                Ok(vk::ShaderStageFlags::TESSELLATION_CONTROL)
+            }
+            ShaderKind::TessEvaluation | ShaderKind::DefaultTessEvaluation => {
+                Ok(vk::ShaderStageFlags::TESSELLATION_EVALUATION)
+            }
+            ShaderKind::RayGeneration | ShaderKind::DefaultRayGeneration => {
+                Ok(vk::ShaderStageFlags::RAYGEN_KHR)
+            }
+            ShaderKind::AnyHit | ShaderKind::DefaultAnyHit => Ok(vk::ShaderStageFlags::ANY_HIT_KHR),
+            ShaderKind::ClosestHit | ShaderKind::DefaultClosestHit => {
+                Ok(vk::ShaderStageFlags::CLOSEST_HIT_KHR)
+            }
+            ShaderKind::Miss | ShaderKind::DefaultMiss => Ok(vk::ShaderStageFlags::MISS_KHR),
+            ShaderKind::Intersection | ShaderKind::DefaultIntersection => {
+                Ok(vk::ShaderStageFlags::INTERSECTION_KHR)
+            }
+            ShaderKind::Callable | ShaderKind::DefaultCallable => {
+                Ok(vk::ShaderStageFlags::CALLABLE_KHR)
+            }
+            ShaderKind::Task | ShaderKind::DefaultTask => Ok(vk::ShaderStageFlags::TASK_EXT),
+            ShaderKind::Mesh | ShaderKind::DefaultMesh => Ok(vk::ShaderStageFlags::MESH_EXT),
+            ShaderKind::InferFromSource | ShaderKind::SpirvAssembly => Err(Error::InvalidStage(
+                format!("{kind:?} does not correspond to a Vulkan shader stage"),
+            )),
+        }
+    }
+}
+
+/// A module translated to the Metal Shading Language by
+/// `SpirvModule::to_msl_with_bindings`.
+#[cfg(feature = "spirv_cross")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MslShader {
+    pub source: String,
+    /// Where each descriptor of the module lives in the Metal argument buffer
+    /// of its set, sorted by set and binding.
+    pub bindings: Vec<MslBinding>,
+}
+
+/// The `[[id(n)]]` indices of a descriptor in the argument buffer of its set.
+///
+/// Combined image samplers have both a texture and a sampler index; other
+/// descriptors have one of the three. Arrays of `count` descriptors use the
+/// `count` indices starting at the given one. Runtime-sized arrays have a
+/// `count` of zero, as their size is only known when the argument buffer is
+/// encoded; they can only grow past their first index when they are the
+/// last descriptor of their set.
+#[cfg(feature = "spirv_cross")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MslBinding {
+    pub name: Option<String>,
+    pub set: u32,
+    pub binding: u32,
+    pub buffer: Option<u32>,
+    pub texture: Option<u32>,
+    pub sampler: Option<u32>,
+    pub count: u32,
+}
+
+#[cfg(feature = "spirv_cross")]
+impl SpirvModule {
+    /// Translates the first entry point of this module to the Metal Shading
+    /// Language with SPIRV-Cross, placing each descriptor set in a Metal
+    /// argument buffer.
+    ///
+    /// The returned bindings give the index of every descriptor in its
+    /// argument buffer, so the buffers can be encoded from the same layouts
+    /// as the Vulkan descriptor sets. Indices are assigned in order of
+    /// binding within each set. Requires MSL 2.0, and returns
+    /// `Error::InvalidStage` for modules that cannot be reflected and for
+    /// stages Metal does not have, like ray tracing and mesh stages.
+    ///
+    /// ```no_run
+    /// # use shaderc::{Compiler, ShaderKind, SpirvModule};
+    /// # let compiler = Compiler::new().unwrap();
+    /// # let source = "";
+    /// let artifact = compiler
+    ///     .compile_into_spirv(source, ShaderKind::Fragment, "a.frag", "main", None)
+    ///     .unwrap();
+    /// let module = SpirvModule::from_words(artifact.as_binary().to_vec()).unwrap();
+    /// let msl = module.to_msl_with_bindings(spirv_cross::msl::Platform::iOS).unwrap();
+    /// for binding in &msl.bindings {
+    ///     println!("set {} binding {}: {:?}", binding.set, binding.binding, binding.texture);
+    /// }
+    /// ```
+    pub fn to_msl_with_bindings(&self, platform: msl::Platform) -> Result<MslShader, Error> {
+        let reflection = self
+            .reflect()
+            .map_err(|error| Error::InvalidStage(error.to_string()))?;
+        let entry_point = reflection
+            .entry_points()
+            .first()
+            .ok_or_else(|| Error::InvalidStage("module has no entry point".to_string()))?;
+        let model = match entry_point.execution_model {
+            0 => cross::ExecutionModel::Vertex,
+            1 => cross::ExecutionModel::TessellationControl,
+            2 => cross::ExecutionModel::TessellationEvaluation,
+            4 => cross::ExecutionModel::Fragment,
+            5 => cross::ExecutionModel::GlCompute,
+            model => {
+                return Err(Error::InvalidStage(format!(
+                    "execution model {model} has no Metal equivalent"
+                )))
+            }
+        };
+        let bindings = msl_bindings(reflection.descriptor_bindings());
+
+        let mut overrides = BTreeMap::new();
+        for b in &bindings {
+            let location = msl::ResourceBindingLocation {
+                stage: model,
+                desc_set: b.set,
+                binding: b.binding,
+            };
+            let binding = msl::ResourceBinding {
+                buffer_id: b.buffer.unwrap_or(0),
+                texture_id: b.texture.unwrap_or(0),
+                sampler_id: b.sampler.unwrap_or(0),
+                count: b.count,
+            };
+            overrides.insert(location, binding);
+        }
+        let mut options = msl::CompilerOptions::default();
+        options.platform = platform;
+        options.version = msl::Version::V2_0;
+        options.enable_argument_buffers = true;
+        options.resource_binding_overrides = overrides;
+        options.entry_point = Some((entry_point.name.clone(), model));
+
+        let module = cross::Module::from_words(self.words());
+        let mut ast = cross::Ast::<msl::Target>::parse(&module).map_err(cross_error)?;
+        ast.set_compiler_options(&options).map_err(cross_error)?;
+        let source = ast.compile().map_err(cross_error)?;
+        Ok(MslShader { source, bindings })
+    }
+}
+
+/// Assigns consecutive argument buffer indices to the descriptors of each
+/// set.
+#[cfg(feature = "spirv_cross")]
+fn msl_bindings(descriptors: &[DescriptorBinding]) -> Vec<MslBinding> {
+    let mut next: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut bindings = Vec::new();
+    for descriptor in descriptors {
+        // Runtime-sized arrays have no fixed count: the indices after their
+        // first are left to the next descriptor, if any.
+        let count = descriptor.count;
+        let reserved = count.max(1);
+        let index = next.entry(descriptor.set).or_insert(0);
+        let first = *index;
+        *index += reserved;
+        let (mut buffer, mut texture, mut sampler) = (None, None, None);
+        match descriptor.descriptor_type {
+            DescriptorType::UniformBuffer
+            | DescriptorType::StorageBuffer
+            | DescriptorType::AccelerationStructure => buffer = Some(first),
+            DescriptorType::Sampler => sampler = Some(first),
+            DescriptorType::CombinedImageSampler => {
+                texture = Some(first);
+                sampler = Some(first + reserved);
+                *index += reserved;
+            }
+            DescriptorType::SampledImage
+            | DescriptorType::StorageImage
+            | DescriptorType::UniformTexelBuffer
+            | DescriptorType::StorageTexelBuffer
+            | DescriptorType::InputAttachment => texture = Some(first),
+        }
+        bindings.push(MslBinding {
+            name: descriptor.name.clone(),
+            set: descriptor.set,
+            binding: descriptor.binding,
+            buffer,
+            texture,
+            sampler,
+            count,
+        });
+    }
+    bindings
+}
+
+#[cfg(feature = "spirv_cross")]
+fn cross_error(error: ErrorCode) -> Error {
+    match error {
+        ErrorCode::CompilationError(message) => Error::CompilationError(1, message),
+        ErrorCode::Unhandled => Error::InternalError("SPIRV-Cross failed".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ash")]
+    use crate::Compiler;
+
+    #[cfg(feature = "ash")]
+    #[test]
+    fn test_ash_shader_module_create_info() {
+        let c = Compiler::new().unwrap();
+        let result = c
+            .compile_into_spirv(
+                "#version 310 es\n void main() {}",
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                None,
+            )
+            .unwrap();
+        let info = result.shader_module_create_info();
+        assert_eq!(result.len(), info.code_size);
+        assert_eq!(result.as_binary().as_ptr(), info.p_code);
+    }
+
+    #[cfg(feature = "spirv")]
+    #[test]
+    fn test_spirv_execution_model() {
+        use spirv::ExecutionModel;
+
+        assert_eq!(
+            Some(ExecutionModel::GLCompute),
+            ShaderKind::DefaultCompute.execution_model()
+        );
+        assert_eq!(None, ShaderKind::SpirvAssembly.execution_model());
+        assert_eq!(
+            Ok(ExecutionModel::MeshEXT),
+            ExecutionModel::try_from(ShaderKind::Mesh)
+        );
+        assert_eq!(
+            Ok(ShaderKind::Task),
+            ShaderKind::try_from(ExecutionModel::TaskNV)
+        );
+        assert!(ShaderKind::try_from(ExecutionModel::Kernel).is_err());
+    }
+
+    #[cfg(feature = "naga")]
+    #[test]
+    fn test_naga_shader_stage() {
+        assert_eq!(ShaderKind::Mesh, ShaderKind::from(naga::ShaderStage::Mesh));
+        assert_eq!(
+            Ok(naga::ShaderStage::Fragment),
+            naga::ShaderStage::try_from(ShaderKind::DefaultFragment)
+        );
+        assert!(naga::ShaderStage::try_from(ShaderKind::Geometry).is_err());
+    }
+
+    #[cfg(feature = "ash")]
+    #[test]
+    fn test_ash_shader_stage_flags() {
+        assert_eq!(
+            Some(vk::ShaderStageFlags::FRAGMENT),
+            vk::ShaderStageFlags::try_from(ShaderKind::Fragment).ok()
+        );
+        assert_eq!(
+            Some(vk::ShaderStageFlags::MESH_EXT),
+            vk::ShaderStageFlags::try_from(ShaderKind::DefaultMesh).ok()
+        );
+        assert!(vk::ShaderStageFlags::try_from(ShaderKind::InferFromSource).is_err());
+    }
+
+    #[cfg(feature = "spirv_cross")]
+    #[test]
+    fn test_msl_bindings() {
+        let descriptor = |set, binding, descriptor_type, count| DescriptorBinding {
+            name: None,
+            set,
+            binding,
+            descriptor_type,
+            count,
+        };
+        let bindings = msl_bindings(&[
+            descriptor(0, 0, DescriptorType::UniformBuffer, 1),
+            descriptor(0, 1, DescriptorType::CombinedImageSampler, 2),
+            descriptor(0, 2, DescriptorType::StorageBuffer, 0),
+            descriptor(1, 0, DescriptorType::SampledImage, 1),
+            descriptor(1, 1, DescriptorType::Sampler, 1),
+        ]);
+        let indices: Vec<_> = bindings
+            .iter()
+            .map(|b| (b.set, b.buffer, b.texture, b.sampler))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, Some(0), None, None),
+                (0, None, Some(1), Some(3)),
+                (0, Some(5), None, None),
+                (1, None, Some(0), None),
+                (1, None, None, Some(1)),
+            ],
+            indices
+        );
+        assert_eq!(0, bindings[2].count);
+    }
+}
@@ -0,0 +1,716 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation in a separate worker process, for untrusted shaders.
+//!
+//! `IsolatedCompiler` runs each compilation in a new `shaderc-worker`
+//! process, the binary built from this crate with the `isolated` feature, so
+//! that a crash or a hang in glslang only takes down the worker. The request
+//! and its result are exchanged over the worker's stdin and stdout.
+//!
+//! Available with the `isolated` feature.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use shaderc::isolated::IsolatedCompiler;
+//! use shaderc::{ShaderCompiler, ShaderKind};
+//!
+//! let compiler = IsolatedCompiler::new().timeout(Duration::from_secs(10));
+//! let result =
+//!     compiler.compile_into_spirv("void main() {}", ShaderKind::Vertex, "a.vert", "main", None);
+//! ```
+
+use std::convert::TryFrom;
+use std::env;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::diagnostic::{self, LineMap};
+use crate::settings::Settings;
+use crate::subprocess::wait_output;
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Compiler, Error, ResourceKind, Result,
+    ShaderCompiler, ShaderKind,
+};
+
+/// File name of the worker binary, without the executable suffix.
+pub const WORKER_NAME: &str = "shaderc-worker";
+
+const RESOURCE_KINDS: [ResourceKind; 6] = [
+    ResourceKind::Image,
+    ResourceKind::Sampler,
+    ResourceKind::Texture,
+    ResourceKind::Buffer,
+    ResourceKind::StorageBuffer,
+    ResourceKind::UnorderedAccessView,
+];
+
+/// A `ShaderCompiler` running each compilation in a new worker process.
+///
+/// A worker crashing is reported as an `InternalError`, and one running past
+/// the timeout is killed and reported as `Error::TimedOut`. Include callbacks
+/// cannot be passed to the worker, so compiling with them fails with an
+/// `InternalError`.
+#[derive(Clone, Debug)]
+pub struct IsolatedCompiler {
+    worker: PathBuf,
+    timeout: Option<Duration>,
+}
+
+impl Default for IsolatedCompiler {
+    fn default() -> IsolatedCompiler {
+        IsolatedCompiler::new()
+    }
+}
+
+impl IsolatedCompiler {
+    /// Returns a compiler running the `shaderc-worker` next to the current
+    /// executable, or else the one found on `PATH`.
+    pub fn new() -> IsolatedCompiler {
+        let file_name = format!("{WORKER_NAME}{}", env::consts::EXE_SUFFIX);
+        let worker = env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.parent()?.join(&file_name)))
+            .filter(|worker| worker.is_file())
+            .unwrap_or_else(|| PathBuf::from(file_name));
+        IsolatedCompiler::with_worker(worker)
+    }
+
+    /// Returns a compiler running the worker binary at `worker`.
+    pub fn with_worker<P: Into<PathBuf>>(worker: P) -> IsolatedCompiler {
+        IsolatedCompiler {
+            worker: worker.into(),
+            timeout: None,
+        }
+    }
+
+    /// Returns this compiler with workers killed after running for
+    /// `timeout`, failing the compilation with `Error::TimedOut`. There is no
+    /// timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> IsolatedCompiler {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn run(
+        &self,
+        mut request: Request,
+        options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let line_map = match request.method {
+            Method::Spirv | Method::Assembly => LineMap::for_options(&request.source, options),
+            _ => None,
+        };
+        if let Some(ref line_map) = line_map {
+            request.source = line_map.source().to_string();
+        }
+        let settings = options.map(|options| &options.settings);
+        if settings.is_some_and(|settings| settings.include_callback) {
+            return Err(Error::InternalError(
+                "include callbacks cannot be passed to an isolated worker".to_string(),
+            ));
+        }
+        #[cfg(feature = "spirv-tools")]
+        if crate::spirv_tools::has_preset(options) {
+            return Err(Error::InternalError(
+                "optimization presets cannot be passed to an isolated worker".to_string(),
+            ));
+        }
+        let mut encoder = Encoder(Vec::new());
+        encoder.request(&request, settings);
+
+        let spawn_error = |e: io::Error| {
+            Error::InternalError(format!("cannot run {}: {e}", self.worker.display()))
+        };
+        let mut child = Command::new(&self.worker)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(spawn_error)?;
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = thread::spawn(move || stdin.write_all(&encoder.0));
+        let output = wait_output(child, self.timeout).map_err(spawn_error)?;
+        // The worker exiting early closes the pipe, which is reported below.
+        let _ = writer.join();
+        let output = output.ok_or_else(|| {
+            Error::TimedOut(format!(
+                "{WORKER_NAME} was killed after running for {:?}",
+                self.timeout.unwrap_or_default()
+            ))
+        })?;
+        if !output.status.success() {
+            return Err(Error::InternalError(format!(
+                "{WORKER_NAME} failed: {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            )));
+        }
+        let result = Decoder(&output.stdout)
+            .response()
+            .unwrap_or_else(|e| {
+                Err(Error::InternalError(format!(
+                    "{WORKER_NAME} sent an invalid response: {e}"
+                )))
+            })
+            .map(|artifact| artifact.with_input_file_name(&request.input_file_name))
+            .and_then(|artifact| diagnostic::apply_policy(artifact, options));
+        diagnostic::rewrite_lines(result, line_map.as_ref(), &request.input_file_name)
+    }
+}
+
+impl ShaderCompiler for IsolatedCompiler {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let request = Request {
+            method: Method::Spirv,
+            source: source_text.to_string(),
+            shader_kind,
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        };
+        self.run(request, additional_options)
+    }
+
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let request = Request {
+            method: Method::Assembly,
+            source: source_text.to_string(),
+            shader_kind,
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        };
+        self.run(request, additional_options)
+    }
+
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let request = Request {
+            method: Method::Preprocess,
+            source: source_text.to_string(),
+            shader_kind: ShaderKind::Vertex,
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        };
+        self.run(request, additional_options)
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let request = Request {
+            method: Method::Assemble,
+            source: source_assembly.to_string(),
+            shader_kind: ShaderKind::Vertex,
+            input_file_name: String::new(),
+            entry_point_name: String::new(),
+        };
+        self.run(request, additional_options)
+    }
+}
+
+/// Serves one request read from stdin, writing the result to stdout.
+///
+/// This is the body of `shaderc-worker`.
+pub fn run_worker() -> io::Result<()> {
+    let mut input = Vec::new();
+    io::stdin().read_to_end(&mut input)?;
+    let (request, settings) = Decoder(&input).request()?;
+    let result = compile(&request, settings.as_ref());
+    let mut encoder = Encoder(Vec::new());
+    encoder.response(request.method, &result);
+    io::stdout().write_all(&encoder.0)
+}
+
+fn compile(request: &Request, settings: Option<&Settings>) -> Result<CompilationArtifact> {
+    let compiler = Compiler::new()
+        .ok_or_else(|| Error::InternalError("cannot create a compiler".to_string()))?;
+    let options = match settings {
+        Some(settings) => {
+            let mut options = options_or_default(None)?;
+            settings.apply_to(&mut options);
+            Some(options)
+        }
+        None => None,
+    };
+    let options = options.as_ref();
+    let r = request;
+    match r.method {
+        Method::Spirv => compiler.compile_into_spirv(
+            &r.source,
+            r.shader_kind,
+            &r.input_file_name,
+            &r.entry_point_name,
+            options,
+        ),
+        Method::Assembly => compiler.compile_into_spirv_assembly(
+            &r.source,
+            r.shader_kind,
+            &r.input_file_name,
+            &r.entry_point_name,
+            options,
+        ),
+        Method::Preprocess => {
+            compiler.preprocess(&r.source, &r.input_file_name, &r.entry_point_name, options)
+        }
+        Method::Assemble => compiler.assemble(&r.source, options),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Method {
+    Spirv,
+    Assembly,
+    Preprocess,
+    Assemble,
+}
+
+impl Method {
+    const ALL: [Method; 4] = [
+        Method::Spirv,
+        Method::Assembly,
+        Method::Preprocess,
+        Method::Assemble,
+    ];
+
+    fn is_binary(self) -> bool {
+        self == Method::Spirv || self == Method::Assemble
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Request {
+    method: Method,
+    source: String,
+    shader_kind: ShaderKind,
+    input_file_name: String,
+    entry_point_name: String,
+}
+
+/// Writes messages as a sequence of little-endian integers and
+/// length-prefixed strings.
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.0.push(value as u8);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.u64(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.bytes(value.as_bytes());
+    }
+
+    fn option<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Encoder, T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            write(self, value);
+        }
+    }
+
+    fn list<T>(&mut self, values: &[T], mut write: impl FnMut(&mut Encoder, &T)) {
+        self.u64(values.len() as u64);
+        for value in values {
+            write(self, value);
+        }
+    }
+
+    fn request(&mut self, request: &Request, settings: Option<&Settings>) {
+        self.u32(request.method as u32);
+        self.str(&request.source);
+        self.str(request.shader_kind.name());
+        self.str(&request.input_file_name);
+        self.str(&request.entry_point_name);
+        self.option(settings, Encoder::settings);
+    }
+
+    fn settings(&mut self, s: &Settings) {
+        self.option(s.target_env, |e, (env, version)| {
+            e.str(env.name());
+            e.u32(version);
+        });
+        self.option(s.target_spirv, |e, version| e.str(version.name()));
+        self.option(s.source_language, |e, language| e.str(language.name()));
+        self.option(s.forced_version_profile, |e, (version, profile)| {
+            e.u32(version);
+            e.str(profile.name());
+        });
+        self.list(&s.limits, |e, &(limit, value)| {
+            e.str(limit.name());
+            e.u32(value as u32);
+        });
+        for flag in [
+            s.auto_bind_uniforms,
+            s.auto_combined_image_sampler,
+            s.hlsl_io_mapping,
+            s.hlsl_offsets,
+            s.auto_map_locations,
+            s.hlsl_functionality1,
+            s.invert_y,
+            s.nan_clamp,
+            s.generate_debug_info,
+            s.suppress_warnings,
+            s.warnings_as_errors,
+        ] {
+            self.bool(flag);
+        }
+        self.list(&s.binding_bases, |e, &(kind, resource, base)| {
+            e.option(kind, |e, kind| e.str(kind.name()));
+            e.u32(resource as u32);
+            e.u32(base);
+        });
+        self.list(&s.hlsl_registers, |e, (kind, register, set, binding)| {
+            e.option(*kind, |e, kind| e.str(kind.name()));
+            e.str(register);
+            e.str(set);
+            e.str(binding);
+        });
+        self.list(&s.macros, |e, (name, value)| {
+            e.str(name);
+            e.option(value.as_deref(), Encoder::str);
+        });
+        self.option(s.optimization_level, |e, level| e.str(level.name()));
+        self.option(s.max_source_size, |e, bytes| e.u64(bytes as u64));
+        self.option(s.max_include_bytes, |e, bytes| e.u64(bytes as u64));
+    }
+
+    fn response(&mut self, method: Method, result: &Result<CompilationArtifact>) {
+        match *result {
+            Ok(ref artifact) => {
+                self.u32(0);
+                if method.is_binary() {
+                    self.bytes(artifact.as_binary_u8());
+                } else {
+                    self.str(&artifact.as_text());
+                }
+                self.u32(artifact.get_num_warnings());
+                self.str(&artifact.get_warning_messages());
+            }
+            Err(ref error) => {
+                let (code, count, message) = match *error {
+                    Error::CompilationError(count, ref message) => (1, count, message),
+                    Error::InternalError(ref message) => (2, 0, message),
+                    Error::InvalidStage(ref message) => (3, 0, message),
+                    Error::InvalidAssembly(ref message) => (4, 0, message),
+                    Error::NullResultObject(ref message) => (5, 0, message),
+                    Error::UnsupportedByLibrary(ref message) => (6, 0, message),
+                    Error::TimedOut(ref message) => (7, 0, message),
+                };
+                self.u32(code);
+                self.u32(count);
+                self.str(message);
+            }
+        }
+        self.u32(method as u32);
+    }
+}
+
+/// Reads the messages written by `Encoder`.
+struct Decoder<'a>(&'a [u8]);
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> io::Result<&[u8]> {
+        if self.0.len() < len {
+            return Err(invalid("truncated message".to_string()));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let (low, high) = (self.u32()?, self.u32()?);
+        Ok(u64::from(low) | u64::from(high) << 32)
+    }
+
+    fn usize(&mut self) -> io::Result<usize> {
+        let value = self.u64()?;
+        usize::try_from(value).map_err(|_| invalid(format!("{value} does not fit in usize")))
+    }
+
+    fn bool(&mut self) -> io::Result<bool> {
+        Ok(self.take(1)?[0] != 0)
+    }
+
+    fn bytes(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.usize()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        String::from_utf8(self.bytes()?).map_err(|e| invalid(e.to_string()))
+    }
+
+    fn name<T: std::str::FromStr>(&mut self) -> io::Result<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        self.string()?
+            .parse()
+            .map_err(|e: T::Err| invalid(e.to_string()))
+    }
+
+    fn option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> io::Result<T>,
+    ) -> io::Result<Option<T>> {
+        match self.bool()? {
+            true => read(self).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    fn list<T>(&mut self, mut read: impl FnMut(&mut Self) -> io::Result<T>) -> io::Result<Vec<T>> {
+        let len = self.usize()?;
+        (0..len).map(|_| read(self)).collect()
+    }
+
+    fn method(&mut self) -> io::Result<Method> {
+        let code = self.u32()?;
+        Method::ALL
+            .get(code as usize)
+            .copied()
+            .ok_or_else(|| invalid(format!("unknown method {code}")))
+    }
+
+    fn request(&mut self) -> io::Result<(Request, Option<Settings>)> {
+        let request = Request {
+            method: self.method()?,
+            source: self.string()?,
+            shader_kind: self.name()?,
+            input_file_name: self.string()?,
+            entry_point_name: self.string()?,
+        };
+        let settings = self.option(Decoder::settings)?;
+        Ok((request, settings))
+    }
+
+    fn settings(&mut self) -> io::Result<Settings> {
+        let mut s = Settings {
+            target_env: self.option(|d| Ok((d.name()?, d.u32()?)))?,
+            target_spirv: self.option(Decoder::name)?,
+            source_language: self.option(Decoder::name)?,
+            forced_version_profile: self.option(|d| Ok((d.u32()?, d.name()?)))?,
+            limits: self.list(|d| Ok((d.name()?, d.u32()? as i32)))?,
+            ..Settings::default()
+        };
+        for flag in [
+            &mut s.auto_bind_uniforms,
+            &mut s.auto_combined_image_sampler,
+            &mut s.hlsl_io_mapping,
+            &mut s.hlsl_offsets,
+            &mut s.auto_map_locations,
+            &mut s.hlsl_functionality1,
+            &mut s.invert_y,
+            &mut s.nan_clamp,
+            &mut s.generate_debug_info,
+            &mut s.suppress_warnings,
+            &mut s.warnings_as_errors,
+        ] {
+            *flag = self.bool()?;
+        }
+        s.binding_bases = self.list(|d| {
+            let kind = d.option(Decoder::name)?;
+            let code = d.u32()?;
+            let resource = RESOURCE_KINDS
+                .get(code as usize)
+                .copied()
+                .ok_or_else(|| invalid(format!("unknown resource kind {code}")))?;
+            Ok((kind, resource, d.u32()?))
+        })?;
+        s.hlsl_registers = self.list(|d| {
+            Ok((
+                d.option(Decoder::name)?,
+                d.string()?,
+                d.string()?,
+                d.string()?,
+            ))
+        })?;
+        s.macros = self.list(|d| Ok((d.string()?, d.option(Decoder::string)?)))?;
+        s.optimization_level = self.option(Decoder::name)?;
+        s.max_source_size = self.option(Decoder::usize)?;
+        s.max_include_bytes = self.option(Decoder::usize)?;
+        Ok(s)
+    }
+
+    fn response(&mut self) -> io::Result<Result<CompilationArtifact>> {
+        let code = self.u32()?;
+        if code != 0 {
+            let count = self.u32()?;
+            let message = self.string()?;
+            self.method()?;
+            return Ok(Err(match code {
+                1 => Error::CompilationError(count, message),
+                2 => Error::InternalError(message),
+                3 => Error::InvalidStage(message),
+                4 => Error::InvalidAssembly(message),
+                5 => Error::NullResultObject(message),
+                6 => Error::UnsupportedByLibrary(message),
+                7 => Error::TimedOut(message),
+                _ => return Err(invalid(format!("unknown error {code}"))),
+            }));
+        }
+        let output = self.bytes()?;
+        let num_warnings = self.u32()?;
+        let warnings = self.string()?;
+        let artifact = if self.method()?.is_binary() {
+            if output.len() % 4 != 0 {
+                return Err(invalid("binary is not a whole number of words".to_string()));
+            }
+            let words = output
+                .chunks_exact(4)
+                .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+                .collect();
+            CompilationArtifact::from_binary(words)
+        } else {
+            let text = String::from_utf8(output).map_err(|e| invalid(e.to_string()))?;
+            CompilationArtifact::from_text(text)
+        };
+        Ok(Ok(if num_warnings > 0 || !warnings.is_empty() {
+            artifact.with_warnings(num_warnings, warnings)
+        } else {
+            artifact
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EnvVersion, Limit, OptimizationLevel, TargetEnv};
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = Settings {
+            target_env: Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as u32)),
+            limits: vec![(Limit::MaxLights, -1)],
+            nan_clamp: true,
+            binding_bases: vec![(Some(ShaderKind::Fragment), ResourceKind::Texture, 4)],
+            hlsl_registers: vec![(None, "t1".into(), "0".into(), "2".into())],
+            macros: vec![("A".into(), None), ("B".into(), Some("1".into()))],
+            optimization_level: Some(OptimizationLevel::Size),
+            max_include_bytes: Some(1 << 20),
+            ..Settings::default()
+        };
+        let request = Request {
+            method: Method::Assembly,
+            source: "void main() {}".into(),
+            shader_kind: ShaderKind::DefaultFragment,
+            input_file_name: "a.frag".into(),
+            entry_point_name: "main".into(),
+        };
+        let mut encoder = Encoder(Vec::new());
+        encoder.request(&request, Some(&settings));
+        let decoded = Decoder(&encoder.0).request().unwrap();
+        assert_eq!((request, Some(settings)), decoded);
+        assert!(Decoder(&encoder.0[..encoder.0.len() - 1])
+            .request()
+            .is_err());
+    }
+
+    #[test]
+    fn test_response_round_trip() {
+        let mut encoder = Encoder(Vec::new());
+        let artifact = CompilationArtifact::from_binary(vec![0x07230203, 1])
+            .with_warnings(1, "a.vert:1: warning: w\n".into());
+        encoder.response(Method::Spirv, &Ok(artifact));
+        let artifact = Decoder(&encoder.0).response().unwrap().unwrap();
+        assert_eq!(&[0x07230203, 1], artifact.as_binary());
+        assert_eq!(1, artifact.get_num_warnings());
+
+        let mut encoder = Encoder(Vec::new());
+        let error = Error::CompilationError(2, "a.vert:1: error: e\n".into());
+        encoder.response(Method::Assembly, &Err(error.clone()));
+        assert_eq!(
+            Err(error),
+            Decoder(&encoder.0).response().unwrap().map(|_| ())
+        );
+    }
+
+    #[cfg(unix)]
+    fn fake_worker(name: &str, script: &str) -> PathBuf {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join(format!("shaderc-{}-{}", name, std::process::id()));
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_isolated_worker_crash_and_hang() {
+        let crash = fake_worker("worker-crash", "echo glslang assert >&2; kill -SEGV $$");
+        let compiler = IsolatedCompiler::with_worker(&crash);
+        let error = compiler
+            .preprocess("", "a.vert", "main", None)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("glslang assert"), "{}", error);
+        std::fs::remove_file(crash).unwrap();
+
+        let hang = fake_worker("worker-hang", "exec sleep 10");
+        let compiler = IsolatedCompiler::with_worker(&hang).timeout(Duration::from_millis(50));
+        let started = std::time::Instant::now();
+        assert_matches!(compiler.assemble("", None).err(), Some(Error::TimedOut(_)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        std::fs::remove_file(hang).unwrap();
+    }
+}
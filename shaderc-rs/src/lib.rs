@@ -66,23 +66,169 @@
 #[cfg(test)]
 #[macro_use]
 extern crate assert_matches;
+#[cfg(feature = "ash")]
+extern crate ash;
+#[cfg(feature = "clap")]
+extern crate clap;
+#[cfg(feature = "signing")]
+extern crate ed25519_dalek;
+#[cfg(feature = "tokio")]
+extern crate futures_core;
 extern crate libc;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "naga")]
+extern crate naga;
+#[cfg(feature = "watch")]
+extern crate notify;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "cache")]
+extern crate sha2;
+#[cfg(feature = "macros")]
+extern crate shaderc_macros;
 extern crate shaderc_sys;
+#[cfg(feature = "spirv")]
+extern crate spirv;
+#[cfg(feature = "spirv_cross")]
+extern crate spirv_cross;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "project")]
+extern crate toml;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
 use shaderc_sys as scs;
 
 use libc::{c_char, c_int, c_void, size_t};
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::panic;
+use std::rc::Rc;
+use std::sync::Arc;
 use std::{error, fmt, ptr, result, slice, str};
 
+/// Compiles a shader file into SPIR-V at build time.
+///
+/// Available with the `macros` feature. See the
+/// [shaderc-macros](https://docs.rs/shaderc-macros) crate for details.
+///
+/// ```ignore
+/// let words: &[u32] = shaderc::include_spirv!("shaders/tri.frag", frag, vulkan1_2);
+/// ```
+#[cfg(feature = "macros")]
+pub use shaderc_macros::include_spirv;
+
+mod assembly;
+#[cfg(feature = "tokio")]
+mod async_compiler;
+mod auto;
+mod batch;
+pub mod bundle;
+#[cfg(feature = "cache")]
+pub mod cache;
+mod capability;
+pub mod debug;
+mod device_features;
+mod diagnostic;
+mod doctor;
+mod events;
+mod expand;
+mod gles;
+pub mod hlsl;
+#[cfg(any(
+    feature = "ash",
+    feature = "naga",
+    feature = "spirv",
+    feature = "spirv_cross"
+))]
+mod interop;
+#[cfg(feature = "isolated")]
+pub mod isolated;
+mod library;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod mock;
+mod multi_stage;
+mod names;
+mod pipeline;
+mod pragma;
+#[cfg(feature = "project")]
+pub mod project;
+mod reflection;
+mod register_map;
+mod report;
+mod repro;
+mod settings;
+mod sharded;
+mod spirv_module;
+#[cfg(feature = "spirv-tools")]
+mod spirv_tools;
+mod subprocess;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod variants;
+mod version;
+#[cfg(feature = "tracing")]
+#[macro_use]
+mod trace;
+pub mod util;
+#[cfg(feature = "watch")]
+pub mod watch;
+#[cfg(feature = "naga")]
+mod wgsl;
+
+pub use assembly::SpirvWithAssembly;
+#[cfg(feature = "tokio")]
+pub use async_compiler::{AsyncCompiler, CompileFuture, Priority, VersionedCompileFuture};
+#[cfg(feature = "tokio")]
+pub use batch::{compile_batch_stream, BatchStream};
+pub use batch::{compile_batch_streaming, BatchProgress, BatchResults, CompileJob, JobOptions};
+pub use batch::{dedup_artifacts, UniqueArtifacts};
+pub use capability::Capability;
+pub use device_features::{DeviceFeature, DeviceRequirements};
+pub use diagnostic::{
+    Diagnostic, DiagnosticAction, DiagnosticCategory, DiagnosticPolicy, Severity,
+};
+pub use doctor::{diagnose_environment, EnvironmentReport};
+pub use events::{clear_event_hook, set_event_hook, Event};
+pub use gles::GlesIssue;
+#[cfg(feature = "spirv_cross")]
+pub use interop::{MslBinding, MslShader};
+pub use library::{library_info, LibraryInfo};
+pub use mock::{MockCall, MockCompiler};
+pub use names::ParseEnumError;
+pub use pipeline::{
+    CompiledPipeline, LimitViolation, LinkError, MeshPipeline, Pipeline, PipelineError,
+};
+pub use pragma::SourcePragma;
+pub use reflection::{
+    CombinedImageSampler, DescriptorBinding, DescriptorType, EntryPoint, ModuleReflection,
+    StageVariable,
+};
+pub use register_map::{HlslRegisterMap, RegisterClass, RegisterMapError, RegisterMapping};
+pub use report::{OpcodeClass, ShaderReport};
+pub use settings::SettingDiff;
+pub use sharded::{ShardedCompiler, VersionedResult};
+pub use spirv_module::{Instruction, Instructions, ModuleError, SpirvModule};
+#[cfg(feature = "spirv-tools")]
+pub use spirv_tools::OptPreset;
+pub use subprocess::SubprocessCompiler;
+pub use variants::{VariantKey, VariantSet};
+pub use version::{try_parse_version_profile, UnknownVersionError, VersionParseError};
+#[cfg(feature = "naga")]
+pub use wgsl::WgslCompiler;
+
+use settings::Settings;
+
 /// Error.
 ///
 /// Each enumerants has an affixed string describing detailed reasons for
 /// the error. The string can be empty in cases.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Error {
     /// Compilation error.
     ///
@@ -92,6 +238,12 @@ pub enum Error {
     InvalidStage(String),
     InvalidAssembly(String),
     NullResultObject(String),
+    /// The target environment or SPIR-V version requested is newer than the
+    /// native library supports, as found by `library_info`.
+    UnsupportedByLibrary(String),
+    /// A compilation run in another process did not finish within its
+    /// timeout, and the process was killed.
+    TimedOut(String),
 }
 
 impl fmt::Display for Error {
@@ -138,6 +290,20 @@ impl fmt::Display for Error {
                     write!(f, "null result object: {r}")
                 }
             }
+            Error::UnsupportedByLibrary(ref r) => {
+                if r.is_empty() {
+                    write!(f, "unsupported by library")
+                } else {
+                    write!(f, "unsupported by library: {r}")
+                }
+            }
+            Error::TimedOut(ref r) => {
+                if r.is_empty() {
+                    write!(f, "timed out")
+                } else {
+                    write!(f, "timed out: {r}")
+                }
+            }
         }
     }
 }
@@ -150,6 +316,8 @@ impl error::Error for Error {
             Error::InvalidStage(_) => "invalid stage",
             Error::InvalidAssembly(_) => "invalid assembly",
             Error::NullResultObject(_) => "null result object",
+            Error::UnsupportedByLibrary(_) => "unsupported by library",
+            Error::TimedOut(_) => "timed out",
         }
     }
 }
@@ -184,13 +352,15 @@ pub enum EnvVersion {
     // See glslang/Standalone/Standalone.cpp
     // Glslang doesn't accept a OpenGL client version of 460.
     OpenGL4_5 = 450,
-    // Deprecated, WebGPU env never defined versions
+    /// Deprecated: the WebGPU environment never defined versions, and newer
+    /// libraries no longer support it. Compiling with it fails with
+    /// `Error::UnsupportedByLibrary`; see `CompileOptions::set_target_webgpu`.
     WebGPU,
 }
 
 /// The known versions of SPIR-V.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SpirvVersion {
     // Use the values used for word 1 of a SPIR-V binary:
     // - bits 24 to 31: zero
@@ -245,7 +415,7 @@ pub enum ResourceKind {
 ///   specified kind of shader when `#pragma` is not found in the source
 ///   code.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ShaderKind {
     Vertex,
     Fragment,
@@ -289,6 +459,104 @@ pub enum ShaderKind {
     DefaultMesh,
 }
 
+impl ShaderKind {
+    /// Returns the forced shader kind of the same stage, e.g. `Vertex` for
+    /// `DefaultVertex`.
+    ///
+    /// Forced shader kinds, `InferFromSource` and `SpirvAssembly` are
+    /// returned unchanged.
+    pub fn forced_variant(self) -> ShaderKind {
+        match self {
+            ShaderKind::DefaultVertex => ShaderKind::Vertex,
+            ShaderKind::DefaultFragment => ShaderKind::Fragment,
+            ShaderKind::DefaultCompute => ShaderKind::Compute,
+            ShaderKind::DefaultGeometry => ShaderKind::Geometry,
+            ShaderKind::DefaultTessControl => ShaderKind::TessControl,
+            ShaderKind::DefaultTessEvaluation => ShaderKind::TessEvaluation,
+            ShaderKind::DefaultRayGeneration => ShaderKind::RayGeneration,
+            ShaderKind::DefaultAnyHit => ShaderKind::AnyHit,
+            ShaderKind::DefaultClosestHit => ShaderKind::ClosestHit,
+            ShaderKind::DefaultMiss => ShaderKind::Miss,
+            ShaderKind::DefaultIntersection => ShaderKind::Intersection,
+            ShaderKind::DefaultCallable => ShaderKind::Callable,
+            ShaderKind::DefaultTask => ShaderKind::Task,
+            ShaderKind::DefaultMesh => ShaderKind::Mesh,
+            kind => kind,
+        }
+    }
+
+    /// Returns the default shader kind of the same stage, e.g.
+    /// `DefaultVertex` for `Vertex`.
+    ///
+    /// Default shader kinds, `InferFromSource` and `SpirvAssembly` are
+    /// returned unchanged.
+    pub fn default_variant(self) -> ShaderKind {
+        match self {
+            ShaderKind::Vertex => ShaderKind::DefaultVertex,
+            ShaderKind::Fragment => ShaderKind::DefaultFragment,
+            ShaderKind::Compute => ShaderKind::DefaultCompute,
+            ShaderKind::Geometry => ShaderKind::DefaultGeometry,
+            ShaderKind::TessControl => ShaderKind::DefaultTessControl,
+            ShaderKind::TessEvaluation => ShaderKind::DefaultTessEvaluation,
+            ShaderKind::RayGeneration => ShaderKind::DefaultRayGeneration,
+            ShaderKind::AnyHit => ShaderKind::DefaultAnyHit,
+            ShaderKind::ClosestHit => ShaderKind::DefaultClosestHit,
+            ShaderKind::Miss => ShaderKind::DefaultMiss,
+            ShaderKind::Intersection => ShaderKind::DefaultIntersection,
+            ShaderKind::Callable => ShaderKind::DefaultCallable,
+            ShaderKind::Task => ShaderKind::DefaultTask,
+            ShaderKind::Mesh => ShaderKind::DefaultMesh,
+            kind => kind,
+        }
+    }
+
+    /// Returns whether this is a default shader kind, which `#pragma`
+    /// directives in the source can override.
+    pub fn is_default(self) -> bool {
+        self.forced_variant() != self
+    }
+
+    /// Returns whether this is a ray tracing stage: ray generation, any hit,
+    /// closest hit, miss, intersection or callable.
+    pub fn is_raytracing(self) -> bool {
+        matches!(
+            self.forced_variant(),
+            ShaderKind::RayGeneration
+                | ShaderKind::AnyHit
+                | ShaderKind::ClosestHit
+                | ShaderKind::Miss
+                | ShaderKind::Intersection
+                | ShaderKind::Callable
+        )
+    }
+
+    /// Returns whether this is a stage of the mesh shading pipeline: task or
+    /// mesh.
+    pub fn is_mesh_pipeline(self) -> bool {
+        matches!(self.forced_variant(), ShaderKind::Task | ShaderKind::Mesh)
+    }
+
+    /// Returns whether this is a stage of a graphics pipeline: the vertex,
+    /// tessellation, geometry and fragment stages, and the mesh shading
+    /// stages.
+    pub fn is_graphics(self) -> bool {
+        self.is_mesh_pipeline()
+            || matches!(
+                self.forced_variant(),
+                ShaderKind::Vertex
+                    | ShaderKind::TessControl
+                    | ShaderKind::TessEvaluation
+                    | ShaderKind::Geometry
+                    | ShaderKind::Fragment
+            )
+    }
+
+    /// Returns whether this is the compute stage.
+    pub fn is_compute(self) -> bool {
+        self.forced_variant() == ShaderKind::Compute
+    }
+}
+
 /// GLSL profile.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -419,10 +687,229 @@ pub enum Limit {
     MaxDualSourceDrawBuffersExt,
 }
 
+impl Limit {
+    /// Every resource limit, in declaration order.
+    pub const ALL: &'static [Limit] = &[
+        Limit::MaxLights,
+        Limit::MaxClipPlanes,
+        Limit::MaxTextureUnits,
+        Limit::MaxTextureCoords,
+        Limit::MaxVertexAttribs,
+        Limit::MaxVertexUniformComponents,
+        Limit::MaxVaryingFloats,
+        Limit::MaxVertexTextureImageUnits,
+        Limit::MaxCombinedTextureImageUnits,
+        Limit::MaxTextureImageUnits,
+        Limit::MaxFragmentUniformComponents,
+        Limit::MaxDrawBuffers,
+        Limit::MaxVertexUniformVectors,
+        Limit::MaxVaryingVectors,
+        Limit::MaxFragmentUniformVectors,
+        Limit::MaxVertexOutputVectors,
+        Limit::MaxFragmentInputVectors,
+        Limit::MinProgramTexelOffset,
+        Limit::MaxProgramTexelOffset,
+        Limit::MaxClipDistances,
+        Limit::MaxComputeWorkGroupCountX,
+        Limit::MaxComputeWorkGroupCountY,
+        Limit::MaxComputeWorkGroupCountZ,
+        Limit::MaxComputeWorkGroupSizeX,
+        Limit::MaxComputeWorkGroupSizeY,
+        Limit::MaxComputeWorkGroupSizeZ,
+        Limit::MaxComputeUniformComponents,
+        Limit::MaxComputeTextureImageUnits,
+        Limit::MaxComputeImageUniforms,
+        Limit::MaxComputeAtomicCounters,
+        Limit::MaxComputeAtomicCounterBuffers,
+        Limit::MaxVaryingComponents,
+        Limit::MaxVertexOutputComponents,
+        Limit::MaxGeometryInputComponents,
+        Limit::MaxGeometryOutputComponents,
+        Limit::MaxFragmentInputComponents,
+        Limit::MaxImageUnits,
+        Limit::MaxCombinedImageUnitsAndFragmentOutputs,
+        Limit::MaxCombinedShaderOutputResources,
+        Limit::MaxImageSamples,
+        Limit::MaxVertexImageUniforms,
+        Limit::MaxTessControlImageUniforms,
+        Limit::MaxTessEvaluationImageUniforms,
+        Limit::MaxGeometryImageUniforms,
+        Limit::MaxFragmentImageUniforms,
+        Limit::MaxCombinedImageUniforms,
+        Limit::MaxGeometryTextureImageUnits,
+        Limit::MaxGeometryOutputVertices,
+        Limit::MaxGeometryTotalOutputComponents,
+        Limit::MaxGeometryUniformComponents,
+        Limit::MaxGeometryVaryingComponents,
+        Limit::MaxTessControlInputComponents,
+        Limit::MaxTessControlOutputComponents,
+        Limit::MaxTessControlTextureImageUnits,
+        Limit::MaxTessControlUniformComponents,
+        Limit::MaxTessControlTotalOutputComponents,
+        Limit::MaxTessEvaluationInputComponents,
+        Limit::MaxTessEvaluationOutputComponents,
+        Limit::MaxTessEvaluationTextureImageUnits,
+        Limit::MaxTessEvaluationUniformComponents,
+        Limit::MaxTessPatchComponents,
+        Limit::MaxPatchVertices,
+        Limit::MaxTessGenLevel,
+        Limit::MaxViewports,
+        Limit::MaxVertexAtomicCounters,
+        Limit::MaxTessControlAtomicCounters,
+        Limit::MaxTessEvaluationAtomicCounters,
+        Limit::MaxGeometryAtomicCounters,
+        Limit::MaxFragmentAtomicCounters,
+        Limit::MaxCombinedAtomicCounters,
+        Limit::MaxAtomicCounterBindings,
+        Limit::MaxVertexAtomicCounterBuffers,
+        Limit::MaxTessControlAtomicCounterBuffers,
+        Limit::MaxTessEvaluationAtomicCounterBuffers,
+        Limit::MaxGeometryAtomicCounterBuffers,
+        Limit::MaxFragmentAtomicCounterBuffers,
+        Limit::MaxCombinedAtomicCounterBuffers,
+        Limit::MaxAtomicCounterBufferSize,
+        Limit::MaxTransformFeedbackBuffers,
+        Limit::MaxTransformFeedbackInterleavedComponents,
+        Limit::MaxCullDistances,
+        Limit::MaxCombinedClipAndCullDistances,
+        Limit::MaxSamples,
+        Limit::MaxMeshOutputVerticesNv,
+        Limit::MaxMeshOutputPrimitivesNv,
+        Limit::MaxMeshWorkGroupSizeXNv,
+        Limit::MaxMeshWorkGroupSizeYNv,
+        Limit::MaxMeshWorkGroupSizeZNv,
+        Limit::MaxTaskWorkGroupSizeXNv,
+        Limit::MaxTaskWorkGroupSizeYNv,
+        Limit::MaxTaskWorkGroupSizeZNv,
+        Limit::MaxMeshViewCountNv,
+        Limit::MaxMeshOutputVerticesExt,
+        Limit::MaxMeshOutputPrimitivesExt,
+        Limit::MaxMeshWorkGroupSizeXExt,
+        Limit::MaxMeshWorkGroupSizeYExt,
+        Limit::MaxMeshWorkGroupSizeZExt,
+        Limit::MaxTaskWorkGroupSizeXExt,
+        Limit::MaxTaskWorkGroupSizeYExt,
+        Limit::MaxTaskWorkGroupSizeZExt,
+        Limit::MaxMeshViewCountExt,
+        Limit::MaxDualSourceDrawBuffersExt,
+    ];
+
+    /// Returns the value libshaderc uses for this limit unless it is changed
+    /// with `CompileOptions::set_limit`.
+    pub fn default_value(self) -> i32 {
+        match self {
+            Limit::MaxLights => 8,
+            Limit::MaxClipPlanes => 6,
+            Limit::MaxTextureUnits => 2,
+            Limit::MaxTextureCoords => 8,
+            Limit::MaxVertexAttribs => 16,
+            Limit::MaxVertexUniformComponents => 4096,
+            Limit::MaxVaryingFloats => 60,
+            Limit::MaxVertexTextureImageUnits => 16,
+            Limit::MaxCombinedTextureImageUnits => 80,
+            Limit::MaxTextureImageUnits => 16,
+            Limit::MaxFragmentUniformComponents => 1024,
+            Limit::MaxDrawBuffers => 8,
+            Limit::MaxVertexUniformVectors => 256,
+            Limit::MaxVaryingVectors => 15,
+            Limit::MaxFragmentUniformVectors => 256,
+            Limit::MaxVertexOutputVectors => 16,
+            Limit::MaxFragmentInputVectors => 15,
+            Limit::MinProgramTexelOffset => -8,
+            Limit::MaxProgramTexelOffset => 7,
+            Limit::MaxClipDistances => 8,
+            Limit::MaxComputeWorkGroupCountX => 65535,
+            Limit::MaxComputeWorkGroupCountY => 65535,
+            Limit::MaxComputeWorkGroupCountZ => 65535,
+            Limit::MaxComputeWorkGroupSizeX => 1024,
+            Limit::MaxComputeWorkGroupSizeY => 1024,
+            Limit::MaxComputeWorkGroupSizeZ => 64,
+            Limit::MaxComputeUniformComponents => 512,
+            Limit::MaxComputeTextureImageUnits => 16,
+            Limit::MaxComputeImageUniforms => 8,
+            Limit::MaxComputeAtomicCounters => 8,
+            Limit::MaxComputeAtomicCounterBuffers => 1,
+            Limit::MaxVaryingComponents => 60,
+            Limit::MaxVertexOutputComponents => 64,
+            Limit::MaxGeometryInputComponents => 64,
+            Limit::MaxGeometryOutputComponents => 128,
+            Limit::MaxFragmentInputComponents => 128,
+            Limit::MaxImageUnits => 8,
+            Limit::MaxCombinedImageUnitsAndFragmentOutputs => 8,
+            Limit::MaxCombinedShaderOutputResources => 8,
+            Limit::MaxImageSamples => 0,
+            Limit::MaxVertexImageUniforms => 0,
+            Limit::MaxTessControlImageUniforms => 0,
+            Limit::MaxTessEvaluationImageUniforms => 0,
+            Limit::MaxGeometryImageUniforms => 0,
+            Limit::MaxFragmentImageUniforms => 8,
+            Limit::MaxCombinedImageUniforms => 8,
+            Limit::MaxGeometryTextureImageUnits => 16,
+            Limit::MaxGeometryOutputVertices => 256,
+            Limit::MaxGeometryTotalOutputComponents => 1024,
+            Limit::MaxGeometryUniformComponents => 512,
+            Limit::MaxGeometryVaryingComponents => 60,
+            Limit::MaxTessControlInputComponents => 128,
+            Limit::MaxTessControlOutputComponents => 128,
+            Limit::MaxTessControlTextureImageUnits => 16,
+            Limit::MaxTessControlUniformComponents => 1024,
+            Limit::MaxTessControlTotalOutputComponents => 4096,
+            Limit::MaxTessEvaluationInputComponents => 128,
+            Limit::MaxTessEvaluationOutputComponents => 128,
+            Limit::MaxTessEvaluationTextureImageUnits => 16,
+            Limit::MaxTessEvaluationUniformComponents => 1024,
+            Limit::MaxTessPatchComponents => 120,
+            Limit::MaxPatchVertices => 32,
+            Limit::MaxTessGenLevel => 64,
+            Limit::MaxViewports => 16,
+            Limit::MaxVertexAtomicCounters => 0,
+            Limit::MaxTessControlAtomicCounters => 0,
+            Limit::MaxTessEvaluationAtomicCounters => 0,
+            Limit::MaxGeometryAtomicCounters => 0,
+            Limit::MaxFragmentAtomicCounters => 8,
+            Limit::MaxCombinedAtomicCounters => 8,
+            Limit::MaxAtomicCounterBindings => 1,
+            Limit::MaxVertexAtomicCounterBuffers => 0,
+            Limit::MaxTessControlAtomicCounterBuffers => 0,
+            Limit::MaxTessEvaluationAtomicCounterBuffers => 0,
+            Limit::MaxGeometryAtomicCounterBuffers => 0,
+            Limit::MaxFragmentAtomicCounterBuffers => 0,
+            Limit::MaxCombinedAtomicCounterBuffers => 1,
+            Limit::MaxAtomicCounterBufferSize => 32,
+            Limit::MaxTransformFeedbackBuffers => 4,
+            Limit::MaxTransformFeedbackInterleavedComponents => 64,
+            Limit::MaxCullDistances => 8,
+            Limit::MaxCombinedClipAndCullDistances => 8,
+            Limit::MaxSamples => 4,
+            Limit::MaxMeshOutputVerticesNv => 256,
+            Limit::MaxMeshOutputPrimitivesNv => 512,
+            Limit::MaxMeshWorkGroupSizeXNv => 32,
+            Limit::MaxMeshWorkGroupSizeYNv => 1,
+            Limit::MaxMeshWorkGroupSizeZNv => 1,
+            Limit::MaxTaskWorkGroupSizeXNv => 32,
+            Limit::MaxTaskWorkGroupSizeYNv => 1,
+            Limit::MaxTaskWorkGroupSizeZNv => 1,
+            Limit::MaxMeshViewCountNv => 4,
+            Limit::MaxMeshOutputVerticesExt => 256,
+            Limit::MaxMeshOutputPrimitivesExt => 256,
+            Limit::MaxMeshWorkGroupSizeXExt => 128,
+            Limit::MaxMeshWorkGroupSizeYExt => 128,
+            Limit::MaxMeshWorkGroupSizeZExt => 128,
+            Limit::MaxTaskWorkGroupSizeXExt => 128,
+            Limit::MaxTaskWorkGroupSizeYExt => 128,
+            Limit::MaxTaskWorkGroupSizeZExt => 128,
+            Limit::MaxMeshViewCountExt => 4,
+            Limit::MaxDualSourceDrawBuffersExt => 1,
+        }
+    }
+}
+
 /// An opaque object managing all compiler states.
 ///
 /// Creating an `Compiler` object has substantial resource costs; so it is
-/// recommended to keep one object around for all tasks.
+/// recommended to keep one object around for all tasks. It can be shared
+/// between threads; `ShardedCompiler` gives concurrent compilations a
+/// compiler each.
 #[derive(Debug)]
 pub struct Compiler {
     raw: *mut scs::ShadercCompiler,
@@ -447,6 +934,20 @@ where
     }
 }
 
+/// Evaluates the compilation `$body` of the method `$name`, emitting its
+/// events, inside a span with the given name and fields when the `tracing`
+/// feature is enabled.
+macro_rules! traced_compile {
+    ($name:literal, $file:expr, ($($span:tt)*), $body:expr) => {{
+        let body = || events::observe_compile($name, $file, || $body);
+        #[cfg(feature = "tracing")]
+        let result = trace::in_compile_span(compile_span!($name, $($span)*), body);
+        #[cfg(not(feature = "tracing"))]
+        let result = body();
+        result
+    }};
+}
+
 /// Returns a valid UTF-8 string from a slice of bytes.
 ///
 /// A few shaderc functions have been observed to return invalid UTF-8 strings as
@@ -469,6 +970,18 @@ fn safe_str_from_utf8(bytes: &[u8]) -> String {
     }
 }
 
+/// Returns a copy of `base`, or default options if there is none, for
+/// changing the options of a single compilation.
+pub(crate) fn options_or_default<'a>(
+    base: Option<&'a CompileOptions>,
+) -> Result<CompileOptions<'a>> {
+    match base {
+        Some(base) => base.clone(),
+        None => CompileOptions::new(),
+    }
+    .ok_or_else(|| Error::InternalError("cannot create compile options".to_string()))
+}
+
 impl Compiler {
     /// Returns an compiler object that can be used to compile SPIR-V modules.
     ///
@@ -486,10 +999,11 @@ impl Compiler {
     fn handle_compilation_result(
         result: *mut scs::ShadercCompilationResult,
         is_binary: bool,
+        input_file_name: &str,
     ) -> Result<CompilationArtifact> {
         let status = unsafe { scs::shaderc_result_get_compilation_status(result) };
         if status == 0 {
-            Ok(CompilationArtifact::new(result, is_binary))
+            Ok(CompilationArtifact::new(result, is_binary, input_file_name))
         } else {
             let num_errors = unsafe { scs::shaderc_result_get_num_errors(result) } as u32;
             let reason = unsafe {
@@ -528,6 +1042,10 @@ impl Compiler {
     ///
     /// `entry_point_name` is a string defines the name of the entry point
     /// to associate with the source string.
+    ///
+    /// A target environment or SPIR-V version newer than the native library
+    /// supports gives `Error::UnsupportedByLibrary`, instead of a module for
+    /// an older version.
     pub fn compile_into_spirv(
         &self,
         source_text: &str,
@@ -536,26 +1054,66 @@ impl Compiler {
         entry_point_name: &str,
         additional_options: Option<&CompileOptions>,
     ) -> Result<CompilationArtifact> {
-        let source_size = source_text.len();
+        let line_map = diagnostic::LineMap::for_options(source_text, additional_options);
+        let source_text = line_map.as_ref().map_or(source_text, |map| map.source());
         let c_source = CString::new(source_text).expect("cannot convert source_text to c string");
+        let result = self.compile_bytes_into_spirv(
+            c_source.as_bytes(),
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        );
+        diagnostic::rewrite_lines(result, line_map.as_ref(), input_file_name)
+    }
+
+    /// Compiles `source`, which holds no NUL byte, to a SPIR-V binary module.
+    ///
+    /// This is `compile_into_spirv` for sources which are not copied into a
+    /// `String`, without the rewriting of `#line` directives.
+    fn compile_bytes_into_spirv(
+        &self,
+        source: &[u8],
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        library::check_versions(additional_options)?;
+        let source_size = source.len();
+        check_source_size(source_size, input_file_name, additional_options)?;
         let c_file =
             CString::new(input_file_name).expect("cannot convert input_file_name to c string");
         let c_entry_point =
             CString::new(entry_point_name).expect("cannot convert entry_point_name to c string");
-        propagate_panic(|| {
-            let result = unsafe {
-                scs::shaderc_compile_into_spv(
-                    self.raw,
-                    c_source.as_ptr(),
-                    source_size,
-                    shader_kind as i32,
-                    c_file.as_ptr(),
-                    c_entry_point.as_ptr(),
-                    additional_options.map_or(ptr::null(), |o| o.raw),
-                )
-            };
-            Compiler::handle_compilation_result(result, true)
-        })
+        traced_compile!(
+            "compile_into_spirv",
+            input_file_name,
+            (
+                input_file_name,
+                entry_point_name,
+                shader_kind = ?shader_kind,
+                source_size
+            ),
+            with_include_trace(|| with_include_budget(additional_options, || propagate_panic(|| {
+                let result = unsafe {
+                    scs::shaderc_compile_into_spv(
+                        self.raw,
+                        source.as_ptr() as *const c_char,
+                        source_size,
+                        shader_kind as i32,
+                        c_file.as_ptr(),
+                        c_entry_point.as_ptr(),
+                        additional_options.map_or(ptr::null(), |o| o.raw),
+                    )
+                };
+                let result = Compiler::handle_compilation_result(result, true, input_file_name);
+                #[cfg(feature = "spirv-tools")]
+                let result =
+                    result.and_then(|artifact| spirv_tools::apply_preset(artifact, additional_options));
+                result.and_then(|artifact| diagnostic::apply_policy(artifact, additional_options))
+            })))
+        )
     }
 
     /// Like `compile_into_spirv` but the result contains SPIR-V assembly text
@@ -572,30 +1130,65 @@ impl Compiler {
         entry_point_name: &str,
         additional_options: Option<&CompileOptions>,
     ) -> Result<CompilationArtifact> {
+        #[cfg(feature = "spirv-tools")]
+        if spirv_tools::has_preset(additional_options) {
+            // Presets optimize binary modules, disassembled afterwards.
+            return self
+                .compile_into_spirv(
+                    source_text,
+                    shader_kind,
+                    input_file_name,
+                    entry_point_name,
+                    additional_options,
+                )
+                .and_then(spirv_tools::to_assembly);
+        }
+        library::check_versions(additional_options)?;
+        let line_map = diagnostic::LineMap::for_options(source_text, additional_options);
+        let source_text = line_map.as_ref().map_or(source_text, |map| map.source());
         let source_size = source_text.len();
+        check_source_size(source_size, input_file_name, additional_options)?;
         let c_source = CString::new(source_text).expect("cannot convert source_text to c string");
         let c_file =
             CString::new(input_file_name).expect("cannot convert input_file_name to c string");
         let c_entry_point =
             CString::new(entry_point_name).expect("cannot convert entry_point_name to c string");
-        propagate_panic(|| {
-            let result = unsafe {
-                scs::shaderc_compile_into_spv_assembly(
-                    self.raw,
-                    c_source.as_ptr(),
-                    source_size,
-                    shader_kind as i32,
-                    c_file.as_ptr(),
-                    c_entry_point.as_ptr(),
-                    additional_options.map_or(ptr::null(), |o| o.raw),
-                )
-            };
-            Compiler::handle_compilation_result(result, false)
-        })
+        traced_compile!(
+            "compile_into_spirv_assembly",
+            input_file_name,
+            (
+                input_file_name,
+                entry_point_name,
+                shader_kind = ?shader_kind,
+                source_size
+            ),
+            with_include_trace(|| with_include_budget(additional_options, || propagate_panic(|| {
+                let result = unsafe {
+                    scs::shaderc_compile_into_spv_assembly(
+                        self.raw,
+                        c_source.as_ptr(),
+                        source_size,
+                        shader_kind as i32,
+                        c_file.as_ptr(),
+                        c_entry_point.as_ptr(),
+                        additional_options.map_or(ptr::null(), |o| o.raw),
+                    )
+                };
+                let result = Compiler::handle_compilation_result(result, false, input_file_name)
+                    .and_then(|artifact| diagnostic::apply_policy(artifact, additional_options));
+                diagnostic::rewrite_lines(result, line_map.as_ref(), input_file_name)
+            })))
+        )
     }
 
     /// Like `compile_into_spirv` but the result contains preprocessed source
     /// code instead of a SPIR-V binary module.
+    ///
+    /// The output is what glslang's preprocessor produces, and libshaderc
+    /// offers no options to change it: comments are always removed, `#line`
+    /// directives are only emitted where `#include` and `#line` directives
+    /// change the current file or line, and the expanded macros are not
+    /// reported.
     pub fn preprocess(
         &self,
         source_text: &str,
@@ -604,26 +1197,35 @@ impl Compiler {
         additional_options: Option<&CompileOptions>,
     ) -> Result<CompilationArtifact> {
         let source_size = source_text.len();
+        check_source_size(source_size, input_file_name, additional_options)?;
         let c_source = CString::new(source_text).expect("cannot convert source to c string");
         let c_file =
             CString::new(input_file_name).expect("cannot convert input_file_name to c string");
         let c_entry_point =
             CString::new(entry_point_name).expect("cannot convert entry_point_name to c string");
-        propagate_panic(|| {
-            let result = unsafe {
-                scs::shaderc_compile_into_preprocessed_text(
-                    self.raw,
-                    c_source.as_ptr(),
-                    source_size,
-                    // Stage doesn't matter for preprocess
-                    ShaderKind::Vertex as i32,
-                    c_file.as_ptr(),
-                    c_entry_point.as_ptr(),
-                    additional_options.map_or(ptr::null(), |o| o.raw),
-                )
-            };
-            Compiler::handle_compilation_result(result, false)
-        })
+        traced_compile!(
+            "preprocess",
+            input_file_name,
+            (input_file_name, entry_point_name, source_size),
+            with_include_trace(
+                || with_include_budget(additional_options, || propagate_panic(|| {
+                    let result = unsafe {
+                        scs::shaderc_compile_into_preprocessed_text(
+                            self.raw,
+                            c_source.as_ptr(),
+                            source_size,
+                            // Stage doesn't matter for preprocess
+                            ShaderKind::Vertex as i32,
+                            c_file.as_ptr(),
+                            c_entry_point.as_ptr(),
+                            additional_options.map_or(ptr::null(), |o| o.raw),
+                        )
+                    };
+                    Compiler::handle_compilation_result(result, false, input_file_name)
+                        .and_then(|artifact| diagnostic::apply_policy(artifact, additional_options))
+                }))
+            )
+        )
     }
 
     /// Assembles the given SPIR-V assembly string `source_assembly` into a
@@ -640,20 +1242,147 @@ impl Compiler {
         source_assembly: &str,
         additional_options: Option<&CompileOptions>,
     ) -> Result<CompilationArtifact> {
+        library::check_versions(additional_options)?;
         let source_size = source_assembly.len();
+        if let Some(message) = source_size_error(source_size, additional_options) {
+            return Err(Error::InvalidAssembly(message));
+        }
         let c_source =
             CString::new(source_assembly).expect("cannot convert source_assembly to c string");
-        propagate_panic(|| {
-            let result = unsafe {
-                scs::shaderc_assemble_into_spv(
-                    self.raw,
-                    c_source.as_ptr(),
-                    source_size,
-                    additional_options.map_or(ptr::null(), |o| o.raw),
+        traced_compile!(
+            "assemble",
+            "",
+            (source_size),
+            propagate_panic(|| {
+                let result = unsafe {
+                    scs::shaderc_assemble_into_spv(
+                        self.raw,
+                        c_source.as_ptr(),
+                        source_size,
+                        additional_options.map_or(ptr::null(), |o| o.raw),
+                    )
+                };
+                Compiler::handle_compilation_result(result, true, "")
+            })
+        )
+    }
+
+    /// Assembles `source_assembly` for the target environment `env` at
+    /// `version`, which decides the SPIR-V version and the environment rules
+    /// the assembler applies.
+    ///
+    /// This is `assemble` with options created for the target environment.
+    pub fn assemble_for(
+        &self,
+        env: TargetEnv,
+        version: EnvVersion,
+        source_assembly: &str,
+    ) -> Result<CompilationArtifact> {
+        let mut options = options_or_default(None)?;
+        options.set_target_env(env, version as u32);
+        self.assemble(source_assembly, Some(&options))
+    }
+
+    /// Compiles the source string `source_text` to a SPIR-V binary module for
+    /// each of the given `targets`.
+    ///
+    /// Each target is a target environment, its version and the SPIR-V
+    /// version to generate, which override the ones of `base_options`.
+    /// Returns the result of each target in the order of `targets`.
+    /// libshaderc cannot reuse a parse across target environments, so the
+    /// source is compiled once per target.
+    pub fn compile_matrix(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        targets: &[(TargetEnv, EnvVersion, SpirvVersion)],
+        base_options: Option<&CompileOptions>,
+    ) -> Vec<Result<CompilationArtifact>> {
+        targets
+            .iter()
+            .map(|&(env, version, spirv_version)| {
+                let mut options = options_or_default(base_options)?;
+                options.set_target_env(env, version as u32);
+                options.set_target_spirv(spirv_version);
+                self.compile_into_spirv(
+                    source_text,
+                    shader_kind,
+                    input_file_name,
+                    entry_point_name,
+                    Some(&options),
                 )
-            };
-            Compiler::handle_compilation_result(result, true)
-        })
+            })
+            .collect()
+    }
+
+    /// Compiles the source string `source_text` to a SPIR-V binary module,
+    /// trying each of `spirv_versions` in order until compilation succeeds.
+    ///
+    /// This allows preferring a newer SPIR-V version while falling back to
+    /// older ones when the source, the target environment of `base_options`
+    /// or the native library does not allow it. Only `UnsupportedByLibrary`
+    /// and compilation errors reporting a SPIR-V version, target environment
+    /// or extension requirement cause a retry; other errors, like syntax
+    /// errors, fail the same way for every version. Returns the artifact with
+    /// the version it was compiled for, or the error of the first attempt.
+    pub fn compile_with_spirv_fallback(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        spirv_versions: &[SpirvVersion],
+        base_options: Option<&CompileOptions>,
+    ) -> Result<(CompilationArtifact, SpirvVersion)> {
+        let mut first_error = None;
+        for &spirv_version in spirv_versions {
+            let mut options = options_or_default(base_options)?;
+            options.set_target_spirv(spirv_version);
+            match self.compile_into_spirv(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                Some(&options),
+            ) {
+                Ok(artifact) => return Ok((artifact, spirv_version)),
+                Err(error) if fails_for_spirv_version(&error) => {
+                    first_error.get_or_insert(error);
+                }
+                Err(error @ Error::CompilationError(..)) => {
+                    return Err(first_error.unwrap_or(error))
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Err(first_error.unwrap_or_else(|| {
+            Error::InternalError("no SPIR-V version to compile for".to_string())
+        }))
+    }
+}
+
+/// Parts of the diagnostics of glslang and the SPIR-V validator reporting a
+/// requirement of the targeted SPIR-V version or environment.
+const SPIRV_VERSION_REQUIREMENTS: [&str; 4] = [
+    "targeted SPIR-V version",
+    "requires SPIR-V",
+    "target environment",
+    "requires extension",
+];
+
+/// Returns true if `error` may not happen when targeting another SPIR-V
+/// version.
+fn fails_for_spirv_version(error: &Error) -> bool {
+    match *error {
+        Error::CompilationError(_, ref messages) => messages.lines().any(|line| {
+            SPIRV_VERSION_REQUIREMENTS
+                .iter()
+                .any(|requirement| line.contains(requirement))
+        }),
+        Error::UnsupportedByLibrary(_) => true,
+        _ => false,
     }
 }
 
@@ -663,9 +1392,213 @@ impl Drop for Compiler {
     }
 }
 
+/// The compile entry points of a shader compiler.
+///
+/// `Compiler` implements this trait with libshaderc. Code generic over it, or
+/// taking a `dyn ShaderCompiler`, can also be driven by a `MockCompiler` in
+/// tests, and wrappers adding behavior like caching can be layered on top of
+/// any implementation. The trait is implemented for references, `Box`, `Rc`
+/// and `Arc` of implementations, so a shared compiler can be passed wherever
+/// one is expected. See the methods of `Compiler` for the meaning of the
+/// arguments.
+///
+/// ```
+/// use shaderc::{CompilationArtifact, CompileOptions, Result, ShaderCompiler, ShaderKind};
+///
+/// /// Compiles everything with "main" as the entry point.
+/// struct MainOnly<C>(C);
+///
+/// impl<C: ShaderCompiler> MainOnly<C> {
+///     fn compile(&self, source: &str, kind: ShaderKind, name: &str) -> Result<CompilationArtifact> {
+///         self.0.compile_into_spirv(source, kind, name, "main", None)
+///     }
+/// }
+///
+/// let compiler: Box<dyn ShaderCompiler> = Box::new(shaderc::MockCompiler::new());
+/// let wrapped = MainOnly(compiler);
+/// assert!(wrapped.compile("void main() {}", ShaderKind::Vertex, "a.vert").is_err());
+/// ```
+pub trait ShaderCompiler {
+    /// Compiles the source string `source_text` to a SPIR-V binary module.
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact>;
+
+    /// Compiles the source string `source_text` to SPIR-V assembly text.
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact>;
+
+    /// Preprocesses the source string `source_text`.
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact>;
+
+    /// Assembles the SPIR-V assembly string `source_assembly` into a SPIR-V
+    /// binary module.
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact>;
+}
+
+impl ShaderCompiler for Compiler {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        Compiler::compile_into_spirv(
+            self,
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+    }
+
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        Compiler::compile_into_spirv_assembly(
+            self,
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+    }
+
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        Compiler::preprocess(
+            self,
+            source_text,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        Compiler::assemble(self, source_assembly, additional_options)
+    }
+}
+
+macro_rules! forward_shader_compiler {
+    ($($ptr:ty),*) => {$(
+        impl<'a, C: ShaderCompiler + ?Sized> ShaderCompiler for $ptr {
+            fn compile_into_spirv(
+                &self,
+                source_text: &str,
+                shader_kind: ShaderKind,
+                input_file_name: &str,
+                entry_point_name: &str,
+                additional_options: Option<&CompileOptions>,
+            ) -> Result<CompilationArtifact> {
+                (**self).compile_into_spirv(
+                    source_text,
+                    shader_kind,
+                    input_file_name,
+                    entry_point_name,
+                    additional_options,
+                )
+            }
+
+            fn compile_into_spirv_assembly(
+                &self,
+                source_text: &str,
+                shader_kind: ShaderKind,
+                input_file_name: &str,
+                entry_point_name: &str,
+                additional_options: Option<&CompileOptions>,
+            ) -> Result<CompilationArtifact> {
+                (**self).compile_into_spirv_assembly(
+                    source_text,
+                    shader_kind,
+                    input_file_name,
+                    entry_point_name,
+                    additional_options,
+                )
+            }
+
+            fn preprocess(
+                &self,
+                source_text: &str,
+                input_file_name: &str,
+                entry_point_name: &str,
+                additional_options: Option<&CompileOptions>,
+            ) -> Result<CompilationArtifact> {
+                (**self).preprocess(
+                    source_text,
+                    input_file_name,
+                    entry_point_name,
+                    additional_options,
+                )
+            }
+
+            fn assemble(
+                &self,
+                source_assembly: &str,
+                additional_options: Option<&CompileOptions>,
+            ) -> Result<CompilationArtifact> {
+                (**self).assemble(source_assembly, additional_options)
+            }
+        }
+    )*};
+}
+
+forward_shader_compiler!(&'a C, Box<C>, Rc<C>, Arc<C>);
+
 /// Include callback status.
 pub type IncludeCallbackResult = result::Result<ResolvedInclude, String>;
 
+/// The include result libshaderc takes from a `RawIncludeResolver`.
+pub use scs::shaderc_include_result as RawIncludeResult;
+
+/// An include resolver of libshaderc, called with its user data, the
+/// requested source, the include type (0 for `Relative`, 1 for `Standard`),
+/// the requesting source and the include depth.
+pub type RawIncludeResolver = scs::shaderc_include_resolve_fn;
+
+/// A releaser of the results of a `RawIncludeResolver`, called with the same
+/// user data once libshaderc is done with a result.
+pub type RawIncludeReleaser = scs::shaderc_include_result_release_fn;
+
 type BoxedIncludeCallback<'a> =
     Box<dyn Fn(&str, IncludeType, &str, usize) -> IncludeCallbackResult + 'a>;
 
@@ -673,6 +1606,7 @@ type BoxedIncludeCallback<'a> =
 pub struct CompileOptions<'a> {
     raw: *mut scs::ShadercCompileOptions,
     include_callback_fn: Option<BoxedIncludeCallback<'a>>,
+    settings: Settings,
 }
 
 /// Identifies the type of include directive. `Relative` is for include directives of the form
@@ -699,6 +1633,90 @@ pub struct ResolvedInclude {
 
 thread_local! {
     static PANIC_ERROR: RefCell<Option<Box<dyn Any + Send + 'static>>> = RefCell::new(None);
+    // Bytes of includes left to the compilation running on this thread, if
+    // its options limit them.
+    static INCLUDE_BUDGET: Cell<Option<usize>> = const { Cell::new(None) };
+    // The (included, includer) resolved names of the includes of the
+    // compilation running on this thread.
+    static INCLUDE_TRACE: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns an error if `source_size` exceeds the source size limit of
+/// `options`.
+fn check_source_size(
+    source_size: usize,
+    input_file_name: &str,
+    options: Option<&CompileOptions>,
+) -> Result<()> {
+    match source_size_error(source_size, options) {
+        Some(message) => Err(Error::CompilationError(
+            1,
+            format!("{input_file_name}: error: {message}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn source_size_error(source_size: usize, options: Option<&CompileOptions>) -> Option<String> {
+    match options.and_then(|options| options.settings.max_source_size) {
+        Some(max) if source_size > max => Some(format!(
+            "source is {source_size} bytes, more than the limit of {max} bytes"
+        )),
+        _ => None,
+    }
+}
+
+/// Runs `f` with the include size limit of `options` applied to the include
+/// callback, restoring the budget of any enclosing compilation afterwards.
+fn with_include_budget<T>(options: Option<&CompileOptions>, f: impl FnOnce() -> T) -> T {
+    let budget = options.and_then(|options| options.settings.max_include_bytes);
+    let _outer = IncludeBudgetGuard(INCLUDE_BUDGET.with(|left| left.replace(budget)));
+    f()
+}
+
+/// Puts back the include budget of the enclosing compilation when dropped, so
+/// that it is restored even if the compilation unwinds.
+struct IncludeBudgetGuard(Option<usize>);
+
+impl Drop for IncludeBudgetGuard {
+    fn drop(&mut self) {
+        INCLUDE_BUDGET.with(|left| left.set(self.0));
+    }
+}
+
+/// Runs the compilation `f`, adding the include stack of each diagnostic in
+/// an included file to the messages of its compilation error.
+fn with_include_trace(
+    f: impl FnOnce() -> Result<CompilationArtifact>,
+) -> Result<CompilationArtifact> {
+    let outer = INCLUDE_TRACE.with(|trace| trace.replace(Vec::new()));
+    let result = f();
+    let includes = INCLUDE_TRACE.with(|trace| trace.replace(outer));
+    match result {
+        Err(Error::CompilationError(num_errors, messages)) if !includes.is_empty() => {
+            Err(Error::CompilationError(
+                num_errors,
+                diagnostic::add_include_stacks(&messages, &includes),
+            ))
+        }
+        result => result,
+    }
+}
+
+/// Takes the size of `include` from the include budget, failing the include
+/// if it does not fit.
+fn charge_include(include: ResolvedInclude) -> IncludeCallbackResult {
+    INCLUDE_BUDGET.with(|left| match left.get() {
+        Some(bytes) if include.content.len() > bytes => Err(format!(
+            "including '{}' exceeds the limit on included bytes",
+            include.resolved_name
+        )),
+        Some(bytes) => {
+            left.set(Some(bytes - include.content.len()));
+            Ok(include)
+        }
+        None => Ok(include),
+    })
 }
 
 impl<'a> CompileOptions<'a> {
@@ -718,6 +1736,7 @@ impl<'a> CompileOptions<'a> {
             Some(CompileOptions {
                 raw: p,
                 include_callback_fn: None,
+                settings: Settings::default(),
             })
         }
     }
@@ -727,7 +1746,7 @@ impl<'a> CompileOptions<'a> {
     /// A return of `None` indicates that there was an error copying
     /// the underlying options object.
     #[allow(clippy::should_implement_trait)]
-    pub fn clone(&self) -> Option<CompileOptions> {
+    pub fn clone(&self) -> Option<CompileOptions<'_>> {
         let p = unsafe { scs::shaderc_compile_options_clone(self.raw) };
         if p.is_null() {
             None
@@ -735,6 +1754,7 @@ impl<'a> CompileOptions<'a> {
             Some(CompileOptions {
                 raw: p,
                 include_callback_fn: None,
+                settings: self.settings.clone(),
             })
         }
     }
@@ -748,16 +1768,36 @@ impl<'a> CompileOptions<'a> {
     /// of the target environment.
     /// Note that EnvVersion must be cast to u32 when calling set_target_env.
     /// For example: `options.set_target_env(shaderc::TargetEnv::Vulkan, shaderc::EnvVersion::Vulkan1_1 as u32);`
+    ///
+    /// `EnvVersion::WebGPU` is deprecated, and compiling with it fails with
+    /// `Error::UnsupportedByLibrary`.
     pub fn set_target_env(&mut self, env: TargetEnv, version: u32) {
+        self.settings.target_env = Some((env, version));
         unsafe { scs::shaderc_compile_options_set_target_env(self.raw, env as i32, version) }
     }
 
+    /// Targets SPIR-V for WebGPU implementations, replacing the deprecated
+    /// `EnvVersion::WebGPU`.
+    ///
+    /// This targets Vulkan 1.0 and SPIR-V 1.0, which wgpu and other
+    /// implementations built on naga's SPIR-V front-end consume. Shaders
+    /// written in WGSL can be compiled with `WgslCompiler`, behind the `naga`
+    /// feature, instead.
+    pub fn set_target_webgpu(&mut self) {
+        self.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32);
+        self.set_target_spirv(SpirvVersion::V1_0);
+    }
+
     /// Sets the target SPIR-V version. The generated module will use this version
     /// of SPIR-V. Each target environment determines what versions of SPIR-V
     /// it can consume. Defaults to the highest version of SPIR-V 1.0 which is
     /// required to be supported by the target environment.  E.g. Default to SPIR-V
     /// 1.0 for Vulkan 1.0 and SPIR-V 1.3 for Vulkan 1.1.
+    ///
+    /// See `EnvVersion::default_spirv_version` and
+    /// `TargetEnv::supported_spirv_versions` for this mapping.
     pub fn set_target_spirv(&mut self, version: SpirvVersion) {
+        self.settings.target_spirv = Some(version);
         unsafe { scs::shaderc_compile_options_set_target_spirv(self.raw, version as i32) }
     }
 
@@ -765,6 +1805,7 @@ impl<'a> CompileOptions<'a> {
     ///
     /// The default is GLSL if not set.
     pub fn set_source_language(&mut self, language: SourceLanguage) {
+        self.settings.source_language = Some(language);
         unsafe { scs::shaderc_compile_options_set_source_language(self.raw, language as i32) }
     }
 
@@ -776,6 +1817,7 @@ impl<'a> CompileOptions<'a> {
     /// `GlslProfile::None` for GLSL versions that do not define profiles,
     /// e.g., version below 150.
     pub fn set_forced_version_profile(&mut self, version: u32, profile: GlslProfile) {
+        self.settings.forced_version_profile = Some((version, profile));
         unsafe {
             scs::shaderc_compile_options_set_forced_version_profile(
                 self.raw,
@@ -809,6 +1851,7 @@ impl<'a> CompileOptions<'a> {
         let f = Box::new(f);
         let f_ptr = &*f as *const F;
         self.include_callback_fn = Some(f as BoxedIncludeCallback<'a>);
+        self.settings.include_callback = true;
         unsafe {
             scs::shaderc_compile_options_set_include_callbacks(
                 self.raw,
@@ -822,11 +1865,13 @@ impl<'a> CompileOptions<'a> {
             source_name: CString,
             content: CString,
             wrapped: scs::shaderc_include_result,
+            _guard: debug::IncludeResultGuard,
         }
 
         struct ErrResultWrapper {
             error_message: CString,
             wrapped: scs::shaderc_include_result,
+            _guard: debug::IncludeResultGuard,
         }
 
         extern "C" fn resolver<'a, F>(
@@ -853,7 +1898,29 @@ impl<'a> CompileOptions<'a> {
                 };
                 let requesting_source =
                     unsafe { CStr::from_ptr(requesting_source).to_string_lossy() };
-                match f(&requested_source, type_, &requesting_source, include_depth) {
+                let resolve = || {
+                    events::observe_include(&requested_source, &requesting_source, || {
+                        f(&requested_source, type_, &requesting_source, include_depth)
+                    })
+                };
+                #[cfg(feature = "tracing")]
+                let resolved = trace::in_include_span(
+                    tracing::debug_span!(
+                        "include",
+                        requested_source = &*requested_source,
+                        include_type = ?type_,
+                        requesting_source = &*requesting_source,
+                        include_depth,
+                        resolved_name = tracing::field::Empty,
+                        content_size = tracing::field::Empty,
+                        duration_us = tracing::field::Empty,
+                        error = tracing::field::Empty,
+                    ),
+                    resolve,
+                );
+                #[cfg(not(feature = "tracing"))]
+                let resolved = resolve();
+                match resolved.and_then(charge_include) {
                     Ok(ResolvedInclude {
                         resolved_name,
                         content,
@@ -861,10 +1928,16 @@ impl<'a> CompileOptions<'a> {
                         if resolved_name.is_empty() {
                             panic!("include callback: empty strings for resolved include names not allowed");
                         }
+                        INCLUDE_TRACE.with(|trace| {
+                            trace
+                                .borrow_mut()
+                                .push((resolved_name.clone(), requesting_source.to_string()))
+                        });
                         let mut result = Box::new(OkResultWrapper {
                             source_name: CString::new(resolved_name).expect("include callback: could not convert resolved source name to a c string"),
                             content: CString::new(content).expect("include callback: could not convert content string to a c string"),
                             wrapped: unsafe { mem::zeroed() },
+                            _guard: debug::IncludeResultGuard::new(),
                         });
                         result.wrapped = scs::shaderc_include_result {
                             source_name: result.source_name.as_ptr(),
@@ -883,6 +1956,7 @@ impl<'a> CompileOptions<'a> {
                                 "include callback: could not convert error message to a c string",
                             ),
                             wrapped: unsafe { mem::zeroed() },
+                            _guard: debug::IncludeResultGuard::new(),
                         });
                         result.wrapped = scs::shaderc_include_result {
                             source_name: CStr::from_bytes_with_nul(b"\0").unwrap().as_ptr(),
@@ -906,6 +1980,7 @@ impl<'a> CompileOptions<'a> {
                     let mut result = Box::new(ErrResultWrapper {
                         error_message: CString::new("").unwrap(),
                         wrapped: unsafe { mem::zeroed() },
+                        _guard: debug::IncludeResultGuard::new(),
                     });
                     result.wrapped = scs::shaderc_include_result {
                         source_name: CStr::from_bytes_with_nul(b"\0").unwrap().as_ptr(),
@@ -933,14 +2008,85 @@ impl<'a> CompileOptions<'a> {
         }
     }
 
+    /// Sets the include callbacks of libshaderc directly, for resolvers
+    /// implemented outside of Rust, like the asset system of a C++ engine.
+    ///
+    /// `resolver` is called with `user_data` for each `#include` directive,
+    /// and `releaser` with `user_data` and each result once libshaderc is
+    /// done with it. This replaces any callback set with
+    /// `set_include_callback`. Includes resolved this way are not counted by
+    /// `set_max_include_bytes`, recorded in include stacks or reported to the
+    /// event hook.
+    ///
+    /// # Safety
+    ///
+    /// `resolver` must return a valid result, whose strings stay valid until
+    /// `releaser` is called with it, and neither may unwind. `user_data`
+    /// must stay valid for the callbacks as long as these options, or any
+    /// clone of them, are used to compile.
+    pub unsafe fn set_raw_include_callbacks(
+        &mut self,
+        resolver: RawIncludeResolver,
+        releaser: RawIncludeReleaser,
+        user_data: *mut c_void,
+    ) {
+        self.include_callback_fn = None;
+        self.settings.include_callback = true;
+        scs::shaderc_compile_options_set_include_callbacks(self.raw, resolver, releaser, user_data);
+    }
+
+    /// Sets the largest source, in bytes, accepted when compiling with these
+    /// options. Larger sources fail with a compilation error before reaching
+    /// glslang.
+    ///
+    /// There is no limit by default. Meant for compiling untrusted input,
+    /// along with `set_max_include_bytes`.
+    pub fn set_max_source_size(&mut self, bytes: usize) {
+        self.settings.max_source_size = Some(bytes);
+    }
+
+    /// Sets the total size, in bytes, of the files the include callback may
+    /// return to one compilation, counting every inclusion of a file. An
+    /// include past the limit fails as if the callback had returned an
+    /// error.
+    ///
+    /// There is no limit by default.
+    pub fn set_max_include_bytes(&mut self, bytes: usize) {
+        self.settings.max_include_bytes = Some(bytes);
+    }
+
+    /// Sets the policy escalating or dropping the warnings of specific
+    /// categories, applied to the warnings of each successful compilation.
+    ///
+    /// See `DiagnosticPolicy`.
+    pub fn set_diagnostic_policy(&mut self, policy: DiagnosticPolicy) {
+        self.settings.diagnostic_policy = Some(policy);
+    }
+
+    /// Sets whether diagnostics are reported at the file and line given by
+    /// the `#line` directives of the source, as generators emit to point back
+    /// to their inputs.
+    ///
+    /// When enabled, compiling drops the `#line` directives from the source
+    /// and moves its diagnostics to the lines and `"file"` names of the
+    /// directives preceding them, which `Error::diagnostics` and
+    /// `CompilationArtifact::diagnostics` then report. glslang needs no
+    /// extension enabled for the quoted file names. Preprocessing keeps the
+    /// directives. Disabled by default.
+    pub fn set_rewrite_line_directives(&mut self, rewrite: bool) {
+        self.settings.rewrite_line_directives = rewrite;
+    }
+
     /// Sets the resource `limit` to the given `value`.
     pub fn set_limit(&mut self, limit: Limit, value: i32) {
+        self.settings.set_limit(limit, value);
         unsafe { scs::shaderc_compile_options_set_limit(self.raw, limit as i32, value as c_int) }
     }
 
     /// Sets whether the compiler should automatically assign bindings to uniforms
     /// that aren't already explicitly bound in the shader source.
     pub fn set_auto_bind_uniforms(&mut self, auto_bind: bool) {
+        self.settings.auto_bind_uniforms = auto_bind;
         unsafe {
             scs::shaderc_compile_options_set_auto_bind_uniforms(self.raw, auto_bind);
         }
@@ -949,6 +2095,7 @@ impl<'a> CompileOptions<'a> {
     /// Sets whether the compiler should automatically remove sampler variables
     /// and convert image variables to combined image-sampler variables.
     pub fn set_auto_combined_image_sampler(&mut self, auto_combine: bool) {
+        self.settings.auto_combined_image_sampler = auto_combine;
         unsafe {
             scs::shaderc_compile_options_set_auto_combined_image_sampler(self.raw, auto_combine);
         }
@@ -958,6 +2105,7 @@ impl<'a> CompileOptions<'a> {
     ///
     /// Defaults to false.
     pub fn set_hlsl_io_mapping(&mut self, hlsl_iomap: bool) {
+        self.settings.hlsl_io_mapping = hlsl_iomap;
         unsafe {
             scs::shaderc_compile_options_set_hlsl_io_mapping(self.raw, hlsl_iomap);
         }
@@ -969,6 +2117,7 @@ impl<'a> CompileOptions<'a> {
     /// Defaults to false. Only affects GLSL compilation. HLSL rules are always
     /// used when compiling HLSL.
     pub fn set_hlsl_offsets(&mut self, hlsl_offsets: bool) {
+        self.settings.hlsl_offsets = hlsl_offsets;
         unsafe {
             scs::shaderc_compile_options_set_hlsl_offsets(self.raw, hlsl_offsets);
         }
@@ -981,6 +2130,9 @@ impl<'a> CompileOptions<'a> {
     /// For HLSL compilation, the regsiter number assigned to the resource is added
     /// to this specified base.
     pub fn set_binding_base(&mut self, resource_kind: ResourceKind, base: u32) {
+        self.settings
+            .binding_bases
+            .push((None, resource_kind, base));
         unsafe {
             scs::shaderc_compile_options_set_binding_base(self.raw, resource_kind as i32, base);
         }
@@ -993,6 +2145,9 @@ impl<'a> CompileOptions<'a> {
         resource_kind: ResourceKind,
         base: u32,
     ) {
+        self.settings
+            .binding_bases
+            .push((Some(shader_kind), resource_kind, base));
         unsafe {
             scs::shaderc_compile_options_set_binding_base_for_stage(
                 self.raw,
@@ -1003,8 +2158,44 @@ impl<'a> CompileOptions<'a> {
         }
     }
 
+    /// Calls `set_binding_base` for each resource kind and base of `bases`,
+    /// so a descriptor layout convention can be applied as one table.
+    ///
+    /// ```
+    /// # use shaderc::{CompileOptions, ResourceKind};
+    /// let mut options = CompileOptions::new().unwrap();
+    /// options.set_auto_bind_uniforms(true);
+    /// options.set_binding_bases(&[
+    ///     (ResourceKind::Texture, 0),
+    ///     (ResourceKind::Sampler, 50),
+    ///     (ResourceKind::Buffer, 100),
+    /// ]);
+    /// ```
+    pub fn set_binding_bases(&mut self, bases: &[(ResourceKind, u32)]) {
+        for &(resource_kind, base) in bases {
+            self.set_binding_base(resource_kind, base);
+        }
+    }
+
+    /// Like `set_binding_bases`, but only takes effect when compiling the given shader stage.
+    pub fn set_binding_bases_for_stage(
+        &mut self,
+        shader_kind: ShaderKind,
+        bases: &[(ResourceKind, u32)],
+    ) {
+        for &(resource_kind, base) in bases {
+            self.set_binding_base_for_stage(shader_kind, resource_kind, base);
+        }
+    }
+
     /// Sets a descriptor set and binding for an HLSL register in all shader stages.
     pub fn set_hlsl_register_set_and_binding(&mut self, register: &str, set: &str, binding: &str) {
+        self.settings.hlsl_registers.push((
+            None,
+            register.to_string(),
+            set.to_string(),
+            binding.to_string(),
+        ));
         let c_register = CString::new(register).expect("cannot convert string to c string");
         let c_set = CString::new(set).expect("cannot convert string to c string");
         let c_binding = CString::new(binding).expect("cannot convert string to c string");
@@ -1020,6 +2211,7 @@ impl<'a> CompileOptions<'a> {
 
     /// Automatically assigns locations to shader inputs and outputs.
     pub fn set_auto_map_locations(&mut self, auto_map: bool) {
+        self.settings.auto_map_locations = auto_map;
         unsafe {
             scs::shaderc_compile_options_set_auto_map_locations(self.raw, auto_map);
         }
@@ -1034,6 +2226,12 @@ impl<'a> CompileOptions<'a> {
         set: &str,
         binding: &str,
     ) {
+        self.settings.hlsl_registers.push((
+            Some(kind),
+            register.to_string(),
+            set.to_string(),
+            binding.to_string(),
+        ));
         let c_register = CString::new(register).expect("cannot convert string to c string");
         let c_set = CString::new(set).expect("cannot convert string to c string");
         let c_binding = CString::new(binding).expect("cannot convert string to c string");
@@ -1050,13 +2248,20 @@ impl<'a> CompileOptions<'a> {
 
     /// Sets whether the compiler should enable extension SPV_GOOGLE_hlsl_functionality1.
     pub fn set_hlsl_functionality1(&mut self, enable: bool) {
+        self.settings.hlsl_functionality1 = enable;
         unsafe {
             scs::shaderc_compile_options_set_hlsl_functionality1(self.raw, enable);
         }
     }
 
     /// Sets whether the compiler should invert position.Y output in vertex shader.
+    ///
+    /// Only the stages writing `gl_Position` (or `SV_Position` in HLSL) are
+    /// affected: vertex, tessellation evaluation and geometry shaders. Other
+    /// stages compile the same either way, so the option can be set once for
+    /// a whole pipeline.
     pub fn set_invert_y(&mut self, enable: bool) {
+        self.settings.invert_y = enable;
         unsafe {
             scs::shaderc_compile_options_set_invert_y(self.raw, enable);
         }
@@ -1067,11 +2272,32 @@ impl<'a> CompileOptions<'a> {
     /// builtin will favour the non-NaN operands, as if clamp were implemented
     /// as a composition of max and min.
     pub fn set_nan_clamp(&mut self, enable: bool) {
+        self.settings.nan_clamp = enable;
         unsafe {
             scs::shaderc_compile_options_set_nan_clamp(self.raw, enable);
         }
     }
 
+    /// Configures the options for Vulkan portability implementations running
+    /// on Metal, such as MoltenVK, so shaders behave the same there as on
+    /// other drivers:
+    ///
+    /// * Position.Y is not inverted, as MoltenVK already flips the viewport
+    ///   to the Vulkan convention.
+    /// * NaN operands of min, max and clamp favour the other operand, as
+    ///   Metal's own builtins do.
+    /// * Resources and stage variables without explicit bindings or
+    ///   locations get them assigned, as the translation to Metal requires
+    ///   every resource to be bound.
+    ///
+    /// The settings can be overridden afterwards.
+    pub fn set_portability_defaults(&mut self) {
+        self.set_invert_y(false);
+        self.set_nan_clamp(true);
+        self.set_auto_bind_uniforms(true);
+        self.set_auto_map_locations(true);
+    }
+
     /// Adds a predefined macro to the compilation options.
     ///
     /// This has the same effect as passing `-Dname=value` to the command-line
@@ -1080,6 +2306,7 @@ impl<'a> CompileOptions<'a> {
     /// same name has previously been added, the value is replaced with the
     /// new value.
     pub fn add_macro_definition(&mut self, name: &str, value: Option<&str>) {
+        self.settings.add_macro_definition(name, value);
         let c_name = CString::new(name).expect("cannot convert name to c string");
         if let Some(value) = value {
             let c_value = CString::new(value).expect("cannot convert value to c string");
@@ -1105,16 +2332,87 @@ impl<'a> CompileOptions<'a> {
         }
     }
 
-    /// Sets the optimization level to `level`.
+    /// Returns the macros added with `add_macro_definition`, in the order
+    /// they were first added, with their current values.
     ///
-    /// If mulitple invocations for this method, only the last one takes effect.
-    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
-        unsafe { scs::shaderc_compile_options_set_optimization_level(self.raw, level as i32) }
+    /// Recording these next to a compiled binary is enough to reproduce the
+    /// defines it was compiled with.
+    pub fn macro_definitions(&self) -> &[(String, Option<String>)] {
+        &self.settings.macros
     }
 
-    /// Sets the compiler mode to generate debug information in the output.
-    pub fn set_generate_debug_info(&mut self) {
-        unsafe { scs::shaderc_compile_options_set_generate_debug_info(self.raw) }
+    /// Returns the settings whose values differ between these options and
+    /// `other`, such as the target environment, limits and macros.
+    ///
+    /// Only settings applied through these options are compared, since
+    /// libshaderc cannot report the rest. Printing the differences helps find
+    /// why two builds produce different SPIR-V.
+    ///
+    /// ```
+    /// # use shaderc::{CompileOptions, OptimizationLevel};
+    /// let mut a = CompileOptions::new().unwrap();
+    /// let mut b = CompileOptions::new().unwrap();
+    /// a.set_optimization_level(OptimizationLevel::Performance);
+    /// b.add_macro_definition("DEBUG", None);
+    /// for diff in a.diff(&b) {
+    ///     println!("{}", diff);
+    /// }
+    /// ```
+    pub fn diff(&self, other: &CompileOptions) -> Vec<SettingDiff> {
+        self.settings.diff(&other.settings)
+    }
+
+    /// Returns the glslc command line compiling with these options, to
+    /// reproduce a compilation in a terminal, like when reporting a glslang
+    /// bug.
+    ///
+    /// The stage, output and input file flags are left to add. Settings glslc
+    /// has no flag for, like include callbacks, are listed in comment lines
+    /// after the command.
+    ///
+    /// ```
+    /// # use shaderc::{CompileOptions, OptimizationLevel};
+    /// let mut options = CompileOptions::new().unwrap();
+    /// options.set_optimization_level(OptimizationLevel::Performance);
+    /// options.add_macro_definition("LIGHTS", Some("4"));
+    /// assert_eq!("glslc -DLIGHTS=4 -O", options.explain());
+    /// ```
+    pub fn explain(&self) -> String {
+        self.settings.explain()
+    }
+
+    /// Sets the optimization level to `level`.
+    ///
+    /// If mulitple invocations for this method, only the last one takes effect.
+    /// This also clears the preset set with `set_opt_preset`.
+    pub fn set_optimization_level(&mut self, level: OptimizationLevel) {
+        self.settings.optimization_level = Some(level);
+        #[cfg(feature = "spirv-tools")]
+        {
+            self.settings.opt_preset = None;
+        }
+        unsafe { scs::shaderc_compile_options_set_optimization_level(self.raw, level as i32) }
+    }
+
+    /// Sets a preset of SPIRV-Tools optimizer passes, run on each binary
+    /// module after a compilation without optimization. Available with the
+    /// `spirv-tools` feature.
+    ///
+    /// This sets the optimization level to `OptimizationLevel::Zero`, and
+    /// setting an optimization level afterwards clears the preset. Assembly
+    /// results are disassembled from the optimized binary module. A pass
+    /// SPIRV-Tools does not know fails the compilation with
+    /// `Error::InternalError`. See `OptPreset`.
+    #[cfg(feature = "spirv-tools")]
+    pub fn set_opt_preset(&mut self, preset: OptPreset) {
+        self.set_optimization_level(OptimizationLevel::Zero);
+        self.settings.opt_preset = Some(preset);
+    }
+
+    /// Sets the compiler mode to generate debug information in the output.
+    pub fn set_generate_debug_info(&mut self) {
+        self.settings.generate_debug_info = true;
+        unsafe { scs::shaderc_compile_options_set_generate_debug_info(self.raw) }
     }
 
     /// Sets the compiler mode to suppress warnings.
@@ -1123,6 +2421,7 @@ impl<'a> CompileOptions<'a> {
     /// warnings-as-errors modes are turned on, warning messages will be
     /// inhibited, and will not be emitted as error messages.
     pub fn set_suppress_warnings(&mut self) {
+        self.settings.suppress_warnings = true;
         unsafe { scs::shaderc_compile_options_set_suppress_warnings(self.raw) }
     }
 
@@ -1130,6 +2429,7 @@ impl<'a> CompileOptions<'a> {
     ///
     /// Note that the suppress-warnings mode overrides this.
     pub fn set_warnings_as_errors(&mut self) {
+        self.settings.warnings_as_errors = true;
         unsafe { scs::shaderc_compile_options_set_warnings_as_errors(self.raw) }
     }
 }
@@ -1140,23 +2440,175 @@ impl<'a> Drop for CompileOptions<'a> {
     }
 }
 
+/// The status of a compilation, as returned by
+/// `CompilationArtifact::status`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompilationStatus {
+    /// The raw `shaderc_compilation_status` of libshaderc, 0 for success.
+    pub code: u32,
+    pub num_errors: u32,
+    pub num_warnings: u32,
+    /// The input file name the artifact was compiled from, empty if unknown,
+    /// like for assembled modules.
+    pub input_file_name: String,
+}
+
+impl CompilationStatus {
+    /// Returns whether the compilation succeeded without warnings.
+    pub fn is_clean(&self) -> bool {
+        self.code == 0 && self.num_errors == 0 && self.num_warnings == 0
+    }
+}
+
 /// An opaque object containing the results of compilation.
 pub struct CompilationArtifact {
-    raw: *mut scs::ShadercCompilationResult,
+    data: ArtifactData,
     is_binary: bool,
+    input_file_name: String,
+}
+
+// A native result object is plain data owned by the artifact, which can be
+// read and released from any thread.
+unsafe impl Send for CompilationArtifact {}
+
+/// Storage of the output of a `CompilationArtifact`.
+enum ArtifactData {
+    /// A result object returned by libshaderc.
+    Native(*mut scs::ShadercCompilationResult),
+    /// Output created on the Rust side, e.g. by a `MockCompiler`.
+    Owned(OwnedOutput),
+}
+
+/// Compilation output held on the Rust side.
+#[derive(Clone, Default)]
+struct OwnedOutput {
+    words: Vec<u32>,
+    text: String,
+    num_warnings: u32,
+    warning_messages: String,
 }
 
 impl CompilationArtifact {
-    fn new(result: *mut scs::ShadercCompilationResult, is_binary: bool) -> CompilationArtifact {
+    fn new(
+        result: *mut scs::ShadercCompilationResult,
+        is_binary: bool,
+        input_file_name: &str,
+    ) -> CompilationArtifact {
+        CompilationArtifact {
+            data: ArtifactData::Native(result),
+            is_binary,
+            input_file_name: input_file_name.to_string(),
+        }
+    }
+
+    fn from_owned(output: OwnedOutput, is_binary: bool) -> CompilationArtifact {
         CompilationArtifact {
-            raw: result,
+            data: ArtifactData::Owned(output),
             is_binary,
+            input_file_name: String::new(),
+        }
+    }
+
+    /// Creates an artifact holding the given SPIR-V binary module.
+    ///
+    /// This allows `ShaderCompiler` implementations other than `Compiler`,
+    /// like `MockCompiler`, to return artifacts.
+    pub fn from_binary(words: Vec<u32>) -> CompilationArtifact {
+        CompilationArtifact::from_owned(
+            OwnedOutput {
+                words,
+                ..OwnedOutput::default()
+            },
+            true,
+        )
+    }
+
+    /// Creates an artifact holding the given text, like SPIR-V assembly or
+    /// preprocessed source code.
+    pub fn from_text(text: String) -> CompilationArtifact {
+        CompilationArtifact::from_owned(
+            OwnedOutput {
+                text,
+                ..OwnedOutput::default()
+            },
+            false,
+        )
+    }
+
+    /// Returns this artifact with the given warnings attached.
+    pub fn with_warnings(self, num_warnings: u32, warning_messages: String) -> CompilationArtifact {
+        let mut output = self.to_owned_output();
+        output.num_warnings = num_warnings;
+        output.warning_messages = warning_messages;
+        CompilationArtifact::from_owned(output, self.is_binary)
+            .with_input_file_name(&self.input_file_name)
+    }
+
+    /// Returns this artifact recorded as compiled from the input file
+    /// `input_file_name`, as reported by `status`.
+    pub fn with_input_file_name(mut self, input_file_name: &str) -> CompilationArtifact {
+        self.input_file_name = input_file_name.to_string();
+        self
+    }
+
+    /// Returns the status of the compilation which produced this artifact,
+    /// with its error and warning counts and input file name.
+    ///
+    /// Artifacts are only returned for successful compilations, so the code
+    /// is 0 and there are no errors unless the library reports some
+    /// alongside a module. Artifacts not created by `Compiler` report the
+    /// warnings attached with `with_warnings`.
+    pub fn status(&self) -> CompilationStatus {
+        let (code, num_errors) = match self.data {
+            ArtifactData::Native(raw) => unsafe {
+                (
+                    scs::shaderc_result_get_compilation_status(raw) as u32,
+                    scs::shaderc_result_get_num_errors(raw) as u32,
+                )
+            },
+            ArtifactData::Owned(_) => (0, 0),
+        };
+        CompilationStatus {
+            code,
+            num_errors,
+            num_warnings: self.get_num_warnings(),
+            input_file_name: self.input_file_name.clone(),
+        }
+    }
+
+    /// Returns a copy of the output of this artifact.
+    fn to_owned_output(&self) -> OwnedOutput {
+        match self.data {
+            ArtifactData::Owned(ref output) => output.clone(),
+            ArtifactData::Native(_) => OwnedOutput {
+                words: if self.is_binary {
+                    self.as_binary().to_vec()
+                } else {
+                    Vec::new()
+                },
+                text: if self.is_binary {
+                    String::new()
+                } else {
+                    self.as_text()
+                },
+                num_warnings: self.get_num_warnings(),
+                warning_messages: self.get_warning_messages(),
+            },
         }
     }
 
     /// Returns the number of bytes of the compilation output data.
     pub fn len(&self) -> usize {
-        unsafe { scs::shaderc_result_get_length(self.raw) }
+        match self.data {
+            ArtifactData::Native(raw) => unsafe { scs::shaderc_result_get_length(raw) },
+            ArtifactData::Owned(ref output) => {
+                if self.is_binary {
+                    output.words.len() * 4
+                } else {
+                    output.text.len()
+                }
+            }
+        }
     }
 
     /// Returns true if the compilation output data has a length of 0.
@@ -1178,9 +2630,12 @@ impl CompilationArtifact {
         assert_eq!(0, self.len() % 4);
         let num_words = self.len() / 4;
 
-        unsafe {
-            let p = scs::shaderc_result_get_bytes(self.raw);
-            slice::from_raw_parts(p as *const u32, num_words)
+        match self.data {
+            ArtifactData::Native(raw) => unsafe {
+                let p = scs::shaderc_result_get_bytes(raw);
+                slice::from_raw_parts(p as *const u32, num_words)
+            },
+            ArtifactData::Owned(ref output) => &output.words,
         }
     }
 
@@ -1198,9 +2653,14 @@ impl CompilationArtifact {
 
         assert_eq!(0, self.len() % 4);
 
-        unsafe {
-            let p = scs::shaderc_result_get_bytes(self.raw);
-            slice::from_raw_parts(p as *const u8, self.len())
+        match self.data {
+            ArtifactData::Native(raw) => unsafe {
+                let p = scs::shaderc_result_get_bytes(raw);
+                slice::from_raw_parts(p as *const u8, self.len())
+            },
+            ArtifactData::Owned(ref output) => unsafe {
+                slice::from_raw_parts(output.words.as_ptr() as *const u8, self.len())
+            },
         }
     }
 
@@ -1214,33 +2674,46 @@ impl CompilationArtifact {
         if self.is_binary {
             panic!("not text result")
         }
-        unsafe {
-            let p = scs::shaderc_result_get_bytes(self.raw);
-            let bytes = CStr::from_ptr(p).to_bytes();
-            str::from_utf8(bytes)
-                .expect("invalid utf-8 string")
-                .to_string()
+        match self.data {
+            ArtifactData::Native(raw) => unsafe {
+                let p = scs::shaderc_result_get_bytes(raw);
+                let bytes = CStr::from_ptr(p).to_bytes();
+                str::from_utf8(bytes)
+                    .expect("invalid utf-8 string")
+                    .to_string()
+            },
+            ArtifactData::Owned(ref output) => output.text.clone(),
         }
     }
 
     /// Returns the number of warnings generated during the compilation.
     pub fn get_num_warnings(&self) -> u32 {
-        (unsafe { scs::shaderc_result_get_num_warnings(self.raw) }) as u32
+        match self.data {
+            ArtifactData::Native(raw) => {
+                (unsafe { scs::shaderc_result_get_num_warnings(raw) }) as u32
+            }
+            ArtifactData::Owned(ref output) => output.num_warnings,
+        }
     }
 
     /// Returns the detailed warnings as a string.
     pub fn get_warning_messages(&self) -> String {
-        unsafe {
-            let p = scs::shaderc_result_get_error_message(self.raw);
-            let bytes = CStr::from_ptr(p).to_bytes();
-            safe_str_from_utf8(bytes)
+        match self.data {
+            ArtifactData::Native(raw) => unsafe {
+                let p = scs::shaderc_result_get_error_message(raw);
+                let bytes = CStr::from_ptr(p).to_bytes();
+                safe_str_from_utf8(bytes)
+            },
+            ArtifactData::Owned(ref output) => output.warning_messages.clone(),
         }
     }
 }
 
 impl Drop for CompilationArtifact {
     fn drop(&mut self) {
-        unsafe { scs::shaderc_result_release(self.raw) }
+        if let ArtifactData::Native(raw) = self.data {
+            unsafe { scs::shaderc_result_release(raw) }
+        }
     }
 }
 
@@ -1256,10 +2729,23 @@ pub fn get_spirv_version() -> (u32, u32) {
     (version as u32, revision as u32)
 }
 
+/// Returns the version of the SPIR-V generated by this library and its
+/// revision.
+///
+/// This is `get_spirv_version` with the version unpacked into a
+/// `SpirvVersion`. A version newer than the ones known to this crate gives
+/// `SpirvVersion::latest()`.
+pub fn spirv_tools_version() -> (SpirvVersion, u32) {
+    let (version, revision) = get_spirv_version();
+    let version = SpirvVersion::try_from(version).unwrap_or_else(|_| SpirvVersion::latest());
+    (version, revision)
+}
+
 /// Parses the version and profile from the given `string`.
 ///
 /// The string should contain both version and profile, like: `450core`.
-/// Returns `None` if the string can not be parsed.
+/// Returns `None` if the string can not be parsed; see
+/// `try_parse_version_profile` for the reason.
 pub fn parse_version_profile(string: &str) -> Option<(u32, GlslProfile)> {
     let mut version: i32 = 0;
     let mut profile: i32 = 0;
@@ -1363,6 +2849,30 @@ layout(set=0, binding=0)
 buffer B { float x; vec3 y; } my_ssbo;
 void main() { my_ssbo.x = 1.0; }";
 
+    #[test]
+    fn test_shader_kind_classification() {
+        for &(kind, _, _) in ShaderKind::NAMES {
+            assert_eq!(
+                kind.forced_variant(),
+                kind.default_variant().forced_variant()
+            );
+            let stages = [kind.is_raytracing(), kind.is_graphics(), kind.is_compute()];
+            let named = kind != ShaderKind::InferFromSource && kind != ShaderKind::SpirvAssembly;
+            assert_eq!(
+                named,
+                stages.iter().filter(|&&s| s).count() == 1,
+                "{:?}",
+                kind
+            );
+        }
+        assert_eq!(ShaderKind::DefaultMesh, ShaderKind::Mesh.default_variant());
+        assert!(ShaderKind::DefaultTask.is_mesh_pipeline());
+        assert!(ShaderKind::DefaultTask.is_graphics());
+        assert!(ShaderKind::DefaultMiss.is_default());
+        assert!(!ShaderKind::Miss.is_default());
+        assert!(!ShaderKind::SpirvAssembly.is_default());
+    }
+
     #[test]
     fn test_compile_vertex_shader_into_spirv() {
         let c = Compiler::new().unwrap();
@@ -1375,6 +2885,15 @@ void main() { my_ssbo.x = 1.0; }";
         assert!(result.as_binary().last() == Some(&function_end_word));
     }
 
+    #[test]
+    fn test_assemble_for() {
+        let c = Compiler::new().unwrap();
+        let result = c
+            .assemble_for(TargetEnv::Vulkan, EnvVersion::Vulkan1_1, VOID_MAIN_ASSEMBLY)
+            .unwrap();
+        assert_eq!(Some(&0x0723_0203), result.as_binary().first());
+    }
+
     #[test]
     fn test_compile_vertex_shader_into_spirv_assembly() {
         let c = Compiler::new().unwrap();
@@ -1456,6 +2975,26 @@ void main() { my_ssbo.x = 1.0; }";
         assert_eq!(VOID_MAIN_ASSEMBLY, result.as_text());
     }
 
+    #[test]
+    fn test_compile_options_macro_definitions() {
+        let mut options = CompileOptions::new().unwrap();
+        assert!(options.macro_definitions().is_empty());
+        options.add_macro_definition("E", Some("main"));
+        options.add_macro_definition("F", None);
+        options.add_macro_definition("E", Some("entry"));
+        assert_eq!(
+            &[
+                ("E".to_string(), Some("entry".to_string())),
+                ("F".to_string(), None)
+            ],
+            options.macro_definitions()
+        );
+        assert_eq!(
+            options.macro_definitions(),
+            options.clone().unwrap().macro_definitions()
+        );
+    }
+
     #[test]
     fn test_compile_options_clone() {
         let c = Compiler::new().unwrap();
@@ -1474,6 +3013,80 @@ void main() { my_ssbo.x = 1.0; }";
         assert_eq!(VOID_MAIN_ASSEMBLY, result.as_text());
     }
 
+    #[test]
+    fn test_compile_matrix() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.add_macro_definition("E", None);
+        let results = c.compile_matrix(
+            IFDEF_E,
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            &[
+                (TargetEnv::Vulkan, EnvVersion::Vulkan1_0, SpirvVersion::V1_0),
+                (TargetEnv::Vulkan, EnvVersion::Vulkan1_2, SpirvVersion::V1_5),
+            ],
+            Some(&options),
+        );
+        assert_eq!(2, results.len());
+        let versions: Vec<u32> = results
+            .iter()
+            .map(|result| result.as_ref().unwrap().as_binary()[1])
+            .collect();
+        assert_eq!(
+            vec![SpirvVersion::V1_0 as u32, SpirvVersion::V1_5 as u32],
+            versions
+        );
+    }
+
+    #[test]
+    fn test_compile_with_spirv_fallback() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32);
+        let (artifact, version) = c
+            .compile_with_spirv_fallback(
+                VOID_MAIN,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                &[SpirvVersion::V1_3, SpirvVersion::V1_0],
+                Some(&options),
+            )
+            .unwrap();
+        assert_eq!(version as u32, artifact.as_binary()[1]);
+        assert_matches!(
+            c.compile_with_spirv_fallback(
+                TWO_ERROR,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                &[SpirvVersion::V1_3, SpirvVersion::V1_0],
+                None,
+            )
+            .err(),
+            Some(Error::CompilationError(2, _))
+        );
+    }
+
+    #[test]
+    fn test_fails_for_spirv_version() {
+        assert!(fails_for_spirv_version(&Error::CompilationError(
+            1,
+            "shader.glsl:4: error: 'subgroup op' : not supported for current targeted SPIR-V \
+             version\n"
+                .to_string()
+        )));
+        assert!(!fails_for_spirv_version(&Error::CompilationError(
+            1,
+            "shader.glsl:4: error: 'x' : undeclared identifier\n".to_string()
+        )));
+        assert!(!fails_for_spirv_version(&Error::NullResultObject(
+            String::new()
+        )));
+    }
+
     #[test]
     fn test_compile_options_set_source_language() {
         let c = Compiler::new().unwrap();
@@ -1675,6 +3288,192 @@ void main() { my_ssbo.x = 1.0; }";
         assert_matches!(result.err(), None);
     }
 
+    #[test]
+    fn test_include_results_released() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_include_callback(|name, _, _, _| {
+            if name == "found.glsl" {
+                Ok(ResolvedInclude {
+                    resolved_name: name.to_string(),
+                    content: "void main() {}".to_string(),
+                })
+            } else {
+                Err(format!("Couldn't find header \"{name}\""))
+            }
+        });
+        for source in [
+            "#version 400\n#include \"found.glsl\"\n",
+            "#version 400\n#include \"missing.glsl\"\n",
+        ] {
+            let _ = c.compile_into_spirv_assembly(
+                source,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options),
+            );
+            debug::assert_no_leaked_include_results();
+        }
+    }
+
+    #[test]
+    fn test_raw_include_callbacks() {
+        const NAME: &str = "raw.glsl";
+        const CONTENT: &str = "void main() {}";
+
+        extern "C" fn resolve(
+            _: *mut c_void,
+            _: *const c_char,
+            _: c_int,
+            _: *const c_char,
+            _: size_t,
+        ) -> *mut RawIncludeResult {
+            Box::into_raw(Box::new(RawIncludeResult {
+                source_name: NAME.as_ptr() as *const c_char,
+                source_name_length: NAME.len(),
+                content: CONTENT.as_ptr() as *const c_char,
+                content_length: CONTENT.len(),
+                user_data: ptr::null_mut(),
+            }))
+        }
+
+        extern "C" fn release(user_data: *mut c_void, result: *mut RawIncludeResult) {
+            unsafe { *(user_data as *mut usize) += 1 };
+            drop(unsafe { Box::from_raw(result) });
+        }
+
+        let mut released = 0usize;
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        unsafe {
+            options.set_raw_include_callbacks(
+                resolve,
+                release,
+                &mut released as *mut usize as *mut c_void,
+            );
+        }
+        let result = c.compile_into_spirv(
+            "#version 450\n#include \"raw.glsl\"\n",
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            Some(&options),
+        );
+        assert_matches!(result.err(), None);
+        drop(options);
+        assert_eq!(1, released);
+    }
+
+    #[test]
+    fn test_include_stack() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_include_callback(|name, _, _, _| {
+            let content = match name {
+                "lighting.glsl" => "#include \"common.glsl\"\n",
+                _ => "float f() { return x; }\n",
+            };
+            Ok(ResolvedInclude {
+                resolved_name: name.to_string(),
+                content: content.to_string(),
+            })
+        });
+        let source = "#version 450\n#extension GL_GOOGLE_include_directive : enable\n\
+                      #include \"lighting.glsl\"\nvoid main() {}";
+        let error = c
+            .compile_into_spirv(
+                source,
+                ShaderKind::Vertex,
+                "main.vert",
+                "main",
+                Some(&options),
+            )
+            .err()
+            .unwrap();
+        let diagnostics = error.diagnostics();
+        assert_eq!(Some("common.glsl"), diagnostics[0].file.as_deref());
+        assert_eq!(
+            vec!["lighting.glsl", "main.vert"],
+            diagnostics[0].include_stack
+        );
+    }
+
+    #[test]
+    fn test_compile_options_set_max_source_size() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_max_source_size(VOID_MAIN.len() - 1);
+        let result = c.compile_into_spirv(
+            VOID_MAIN,
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            Some(&options),
+        );
+        assert_matches!(result.err(),
+            Some(Error::CompilationError(1, ref s))
+            if s.starts_with("shader.glsl: error: source is"));
+        options.set_max_source_size(VOID_MAIN.len());
+        assert!(c
+            .compile_into_spirv(
+                VOID_MAIN,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_with_include_budget_restores_on_panic() {
+        let mut options = CompileOptions::new().unwrap();
+        options.set_max_include_bytes(15);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            with_include_budget(Some(&options), || {
+                assert_eq!(INCLUDE_BUDGET.with(|left| left.get()), Some(15));
+                panic!("compilation unwound");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(INCLUDE_BUDGET.with(|left| left.get()), None);
+    }
+
+    #[test]
+    fn test_compile_options_set_max_include_bytes() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_include_callback(|name, _, _, _| {
+            Ok(ResolvedInclude {
+                resolved_name: name.to_string(),
+                content: "// 10 bytes".to_string(),
+            })
+        });
+        options.set_max_include_bytes(15);
+        let source = "#version 450\n#include \"a.glsl\"\n#include \"b.glsl\"\nvoid main() {}";
+        let result = c.compile_into_spirv(
+            source,
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            Some(&options),
+        );
+        assert_matches!(result.err(),
+            Some(Error::CompilationError(_, ref s))
+            if s.contains("including 'b.glsl' exceeds the limit on included bytes"));
+        options.set_max_include_bytes(22);
+        assert!(c
+            .compile_into_spirv(
+                source,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options)
+            )
+            .is_ok());
+    }
+
     #[test]
     fn test_compile_options_set_suppress_warnings() {
         let c = Compiler::new().unwrap();
@@ -1911,6 +3710,49 @@ void main() { my_ssbo.x = 1.0; }";
         assert!(result.contains("OpDecorate %my_ubo Binding 2"));
     }
 
+    #[test]
+    fn test_compile_options_set_binding_bases() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_auto_bind_uniforms(true);
+        options.set_binding_bases(&[(ResourceKind::Image, 44), (ResourceKind::Buffer, 10)]);
+        options.set_binding_bases_for_stage(ShaderKind::Vertex, &[(ResourceKind::Texture, 100)]);
+        let result = c
+            .compile_into_spirv_assembly(
+                UNIFORMS_NO_BINDINGS,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options),
+            )
+            .unwrap()
+            .as_text();
+        assert!(result.contains("OpDecorate %my_tex Binding 100"));
+        assert!(result.contains("OpDecorate %my_img Binding 44"));
+        assert!(result.contains("OpDecorate %my_ubo Binding 10"));
+    }
+
+    #[test]
+    fn test_compile_options_set_portability_defaults() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_invert_y(true);
+        options.set_portability_defaults();
+        assert!(!options.settings.invert_y);
+        assert!(options.settings.nan_clamp);
+        let result = c
+            .compile_into_spirv_assembly(
+                UNIFORMS_NO_BINDINGS,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options),
+            )
+            .unwrap()
+            .as_text();
+        assert!(result.contains("OpDecorate %my_tex Binding"));
+    }
+
     #[test]
     fn test_compile_options_set_binding_base_for_stage_effective() {
         let c = Compiler::new().unwrap();
@@ -1993,12 +3835,95 @@ void main() { my_ssbo.x = 1.0; }";
         assert_eq!(ONE_WARNING_MSG.to_string(), result.get_warning_messages());
     }
 
+    #[test]
+    fn test_compile_options_set_diagnostic_policy() {
+        let c = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.set_diagnostic_policy(
+            DiagnosticPolicy::new().set(DiagnosticCategory::Deprecation, DiagnosticAction::Ignore),
+        );
+        let result = c
+            .compile_into_spirv(
+                ONE_WARNING,
+                ShaderKind::Vertex,
+                "shader.glsl",
+                "main",
+                Some(&options),
+            )
+            .unwrap();
+        assert_eq!(0, result.get_num_warnings());
+        options.set_diagnostic_policy(
+            DiagnosticPolicy::new().set(DiagnosticCategory::Deprecation, DiagnosticAction::Error),
+        );
+        let result = c.compile_into_spirv(
+            ONE_WARNING,
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            Some(&options),
+        );
+        assert_matches!(result.err(), Some(Error::CompilationError(1, _)));
+    }
+
+    #[test]
+    fn test_compilation_status() {
+        let c = Compiler::new().unwrap();
+        let result = c
+            .compile_into_spirv(ONE_WARNING, ShaderKind::Vertex, "shader.glsl", "main", None)
+            .unwrap();
+        let status = result.status();
+        assert_eq!(
+            (0, 0, 1),
+            (status.code, status.num_errors, status.num_warnings)
+        );
+        assert_eq!("shader.glsl", status.input_file_name);
+        assert!(!status.is_clean());
+    }
+
+    #[test]
+    fn test_owned_artifact_status() {
+        let artifact = CompilationArtifact::from_binary(vec![0x0723_0203])
+            .with_input_file_name("a.frag")
+            .with_warnings(2, String::new());
+        assert_eq!(
+            CompilationStatus {
+                code: 0,
+                num_errors: 0,
+                num_warnings: 2,
+                input_file_name: "a.frag".to_string(),
+            },
+            artifact.status()
+        );
+        assert!(CompilationArtifact::from_text(String::new())
+            .status()
+            .is_clean());
+    }
+
+    #[test]
+    fn test_limit_all() {
+        assert_eq!(
+            Limit::MaxDualSourceDrawBuffersExt as usize + 1,
+            Limit::ALL.len()
+        );
+        for (i, &limit) in Limit::ALL.iter().enumerate() {
+            assert_eq!(i, limit as usize);
+        }
+        assert_eq!(-8, Limit::MinProgramTexelOffset.default_value());
+        assert_eq!(65535, Limit::MaxComputeWorkGroupCountX.default_value());
+    }
+
     #[test]
     fn test_get_spirv_version() {
         let (version, _) = get_spirv_version();
         assert_eq!((1 << 16) + (6 << 8), version);
     }
 
+    #[test]
+    fn test_spirv_tools_version() {
+        let (version, revision) = spirv_tools_version();
+        assert_eq!(get_spirv_version(), (version as u32, revision));
+    }
+
     #[test]
     fn test_parse_version_profile() {
         assert_eq!(Some((310, GlslProfile::Es)), parse_version_profile("310es"));
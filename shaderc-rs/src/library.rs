@@ -0,0 +1,221 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The version and capabilities of the loaded native library.
+
+use std::convert::TryFrom;
+use std::sync::OnceLock;
+
+use libc::c_char;
+use shaderc_sys as scs;
+
+use crate::version::{SPIRV_VERSIONS, VULKAN_VERSIONS};
+use crate::{spirv_tools_version, CompileOptions, Compiler, EnvVersion, Error, Result};
+use crate::{ShaderKind, SpirvVersion, TargetEnv};
+
+/// The version and capabilities of the native shaderc library, as returned
+/// by `library_info`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LibraryInfo {
+    /// The newest SPIR-V version the library generates, as reported by
+    /// `get_spirv_version`.
+    pub spirv_version: SpirvVersion,
+    pub spirv_revision: u32,
+    /// The oldest shaderc release supporting the target environments found,
+    /// like `v2022.0`, or `None` if older than any release told apart.
+    ///
+    /// libshaderc reports no version of its own, so this is only a lower
+    /// bound.
+    pub min_release: Option<&'static str>,
+    /// The target environments a trivial shader compiles for.
+    pub target_envs: Vec<EnvVersion>,
+    /// The SPIR-V versions a trivial shader compiles to.
+    pub spirv_versions: Vec<SpirvVersion>,
+}
+
+/// The first shaderc release supporting each target environment, newest
+/// first.
+const RELEASES: [(EnvVersion, &str); 2] = [
+    (EnvVersion::Vulkan1_3, "v2022.0"),
+    (EnvVersion::Vulkan1_2, "v2020.0"),
+];
+
+impl LibraryInfo {
+    /// Returns whether the library compiles for the target environment
+    /// `version`.
+    pub fn supports_target_env(&self, version: EnvVersion) -> bool {
+        self.target_envs.contains(&version)
+    }
+
+    /// Returns whether the library generates SPIR-V `version`.
+    pub fn supports_spirv(&self, version: SpirvVersion) -> bool {
+        self.spirv_versions.contains(&version)
+    }
+}
+
+/// Returns the version and capabilities of the native shaderc library.
+///
+/// Supported target environments and SPIR-V versions are found by compiling
+/// a trivial shader for each of them, the first time this is called, and
+/// checking the SPIR-V version of the module: an old library ignores the
+/// versions it does not know. They are empty if no compiler can be created.
+///
+/// These twelve compilations take a few milliseconds in all, paid once per
+/// process by the first caller, which may be the first compilation setting
+/// a target environment or SPIR-V version. They call the native library
+/// directly, so they fire no `Event` and open no tracing span.
+pub fn library_info() -> LibraryInfo {
+    static INFO: OnceLock<LibraryInfo> = OnceLock::new();
+    INFO.get_or_init(query).clone()
+}
+
+fn query() -> LibraryInfo {
+    let compiler = Compiler::new();
+    // Returns the SPIR-V version of a trivial shader compiled with the
+    // options set by `configure`.
+    let compiled_version = |configure: &dyn Fn(*mut scs::ShadercCompileOptions)| {
+        let compiler = compiler.as_ref()?;
+        let options = CompileOptions::new()?;
+        configure(options.raw);
+        // Compiled through libshaderc directly, so that the probes are not
+        // reported as compilations, nor checked against the info being
+        // computed.
+        let source = "#version 450\nvoid main() {}";
+        let result = unsafe {
+            scs::shaderc_compile_into_spv(
+                compiler.raw,
+                source.as_ptr() as *const c_char,
+                source.len(),
+                ShaderKind::Vertex as i32,
+                b"library_info.vert\0".as_ptr() as *const c_char,
+                b"main\0".as_ptr() as *const c_char,
+                options.raw,
+            )
+        };
+        let artifact =
+            Compiler::handle_compilation_result(result, true, "library_info.vert").ok()?;
+        artifact.as_binary().get(1).copied()
+    };
+    let target_envs: Vec<EnvVersion> = VULKAN_VERSIONS
+        .iter()
+        .map(|&version| (TargetEnv::Vulkan, version))
+        .chain(Some((TargetEnv::OpenGL, EnvVersion::OpenGL4_5)))
+        .filter(|&(env, version)| {
+            compiled_version(&|options| unsafe {
+                scs::shaderc_compile_options_set_target_env(options, env as i32, version as u32)
+            }) == Some(version.default_spirv_version() as u32)
+        })
+        .map(|(_, version)| version)
+        .collect();
+    let spirv_versions = SPIRV_VERSIONS
+        .iter()
+        .copied()
+        .filter(|&version| {
+            compiled_version(&|options| unsafe {
+                scs::shaderc_compile_options_set_target_spirv(options, version as i32)
+            }) == Some(version as u32)
+        })
+        .collect();
+    let (spirv_version, spirv_revision) = spirv_tools_version();
+    LibraryInfo {
+        spirv_version,
+        spirv_revision,
+        min_release: RELEASES
+            .iter()
+            .find(|&&(version, _)| target_envs.contains(&version))
+            .map(|&(_, release)| release),
+        target_envs,
+        spirv_versions,
+    }
+}
+
+/// Checks that the native library supports the target environment and
+/// SPIR-V version requested by `options`, if any.
+pub(crate) fn check_versions(options: Option<&CompileOptions>) -> Result<()> {
+    let Some(settings) = options.map(|options| &options.settings) else {
+        return Ok(());
+    };
+    let env_version = settings
+        .target_env
+        .and_then(|(_, version)| EnvVersion::try_from(version).ok());
+    if env_version.is_none() && settings.target_spirv.is_none() {
+        return Ok(());
+    }
+    if env_version == Some(EnvVersion::WebGPU) {
+        return Err(Error::UnsupportedByLibrary(
+            "the WebGPU target environment is deprecated and not supported by newer shaderc \
+             libraries; use CompileOptions::set_target_webgpu to target the SPIR-V WebGPU \
+             implementations consume, or compile WGSL with WgslCompiler"
+                .to_string(),
+        ));
+    }
+    let info = library_info();
+    let unsupported = |requested: String| {
+        let release = RELEASES
+            .iter()
+            .find(|&&(version, _)| env_version == Some(version))
+            .map_or(String::new(), |&(_, release)| {
+                format!(", which needs shaderc {release} or later")
+            });
+        Err(Error::UnsupportedByLibrary(format!(
+            "the native shaderc library does not support {requested}{release}; link a newer \
+             library, or build it from source with the build-from-source feature"
+        )))
+    };
+    if let Some(version) = env_version.filter(|&version| !info.supports_target_env(version)) {
+        return unsupported(format!("target environment {version}"));
+    }
+    match settings.target_spirv {
+        Some(version) if !info.supports_spirv(version) => {
+            unsupported(format!("SPIR-V version {version}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_info() {
+        let info = library_info();
+        assert!(info.supports_target_env(EnvVersion::Vulkan1_0));
+        assert!(info.supports_target_env(EnvVersion::OpenGL4_5));
+        assert!(info.supports_spirv(SpirvVersion::V1_0));
+        assert!(info.supports_spirv(info.spirv_version));
+        assert_eq!(info, library_info());
+    }
+
+    #[test]
+    fn test_check_versions() {
+        let info = library_info();
+        let mut options = CompileOptions::new().unwrap();
+        assert_eq!(Ok(()), check_versions(Some(&options)));
+        options.set_target_env(TargetEnv::Vulkan, 42);
+        assert_eq!(Ok(()), check_versions(Some(&options)));
+        for &version in &VULKAN_VERSIONS {
+            options.set_target_env(TargetEnv::Vulkan, version as u32);
+            let result = check_versions(Some(&options));
+            assert_eq!(info.supports_target_env(version), result.is_ok());
+        }
+        options.set_target_env(TargetEnv::Vulkan, EnvVersion::WebGPU as u32);
+        assert_matches!(
+            check_versions(Some(&options)),
+            Err(Error::UnsupportedByLibrary(_))
+        );
+        options.set_target_webgpu();
+        assert_eq!(Ok(()), check_versions(Some(&options)));
+    }
+}
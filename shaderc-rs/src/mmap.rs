@@ -0,0 +1,93 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compiling memory-mapped source files, for generated shaders too large to
+//! copy around.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{CompilationArtifact, CompileOptions, Compiler, Error, Result, ShaderKind};
+
+impl Compiler {
+    /// Compiles the source file at `path` to a SPIR-V binary module, handing
+    /// the memory-mapped file to the native library instead of reading it
+    /// into a `String`.
+    ///
+    /// The file is only scanned for NUL bytes, which fail the compilation,
+    /// and is not checked to be UTF-8. Its path is the input file name of the
+    /// compilation. `#line` directives are not rewritten, even if
+    /// `CompileOptions::set_rewrite_line_directives` is set.
+    ///
+    /// The file must not be modified while it is compiled. Files which
+    /// cannot be opened or mapped give `Error::InternalError`.
+    pub fn compile_file_mmap<P: AsRef<Path>>(
+        &self,
+        path: P,
+        shader_kind: ShaderKind,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let path = path.as_ref();
+        let input_file_name = path.to_string_lossy();
+        let map_error = |e| Error::InternalError(format!("cannot map {input_file_name}: {e}"));
+        let file = File::open(path).map_err(map_error)?;
+        // Safety: the file is only read while mapped, and the caller must
+        // not modify it meanwhile.
+        let map = unsafe { Mmap::map(&file) }.map_err(map_error)?;
+        if map.contains(&0) {
+            return Err(Error::CompilationError(
+                1,
+                format!("{input_file_name}: error: source contains a NUL byte"),
+            ));
+        }
+        self.compile_bytes_into_spirv(
+            &map,
+            shader_kind,
+            &input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_compile_file_mmap() {
+        let compiler = Compiler::new().unwrap();
+        let path = env::temp_dir().join("shaderc_test_compile_file_mmap.vert");
+        fs::write(&path, "#version 450\nvoid main() {}\n").unwrap();
+        let artifact = compiler
+            .compile_file_mmap(&path, ShaderKind::Vertex, "main", None)
+            .unwrap();
+        assert_eq!(0x0723_0203, artifact.as_binary()[0]);
+
+        fs::write(&path, "#version 450\nvoid main() {}\0\n").unwrap();
+        assert_matches!(
+            compiler
+                .compile_file_mmap(&path, ShaderKind::Vertex, "main", None)
+                .err(),
+            Some(Error::CompilationError(1, ref s)) if s.ends_with("source contains a NUL byte")
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `ShaderCompiler` with canned results.
+//!
+//! `MockCompiler` stands in for `Compiler` in unit tests of code that drives a
+//! compiler through the `ShaderCompiler` trait, so that such tests neither
+//! need the native library nor depend on its diagnostics.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::{
+    CompilationArtifact, CompileOptions, Error, OwnedOutput, Result, ShaderCompiler, ShaderKind,
+};
+
+/// A canned result: the output and whether it is binary, or an error.
+type CannedResult = Result<(OwnedOutput, bool)>;
+
+/// A `ShaderCompiler` returning canned results, for unit testing code driving
+/// a compiler without the native library.
+///
+/// Results are looked up by input file name, falling back to the default
+/// result if there is one, and to an `InternalError` otherwise. `assemble`
+/// takes no file name and always uses the default result. Every call is
+/// recorded and can be inspected with `calls`.
+///
+/// ```
+/// use shaderc::{CompilationArtifact, Error, MockCompiler, ShaderCompiler, ShaderKind};
+///
+/// let compiler = MockCompiler::new()
+///     .with_result("tri.frag", Ok(CompilationArtifact::from_binary(vec![0x07230203])))
+///     .with_result("broken.frag", Err(Error::CompilationError(1, "broken.frag:1: error".into())));
+///
+/// let artifact = compiler
+///     .compile_into_spirv("void main() {}", ShaderKind::Fragment, "tri.frag", "main", None)
+///     .unwrap();
+/// assert_eq!(&[0x07230203], artifact.as_binary());
+/// assert_eq!("tri.frag", compiler.calls()[0].input_file_name);
+/// ```
+#[derive(Default)]
+pub struct MockCompiler {
+    results: HashMap<String, CannedResult>,
+    default_result: Option<CannedResult>,
+    calls: Mutex<Vec<MockCall>>,
+}
+
+/// A call made to a `MockCompiler`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockCall {
+    /// Name of the `ShaderCompiler` method called.
+    pub method: &'static str,
+    pub source_text: String,
+    /// The shader kind, for `compile_into_spirv` and
+    /// `compile_into_spirv_assembly`.
+    pub shader_kind: Option<ShaderKind>,
+    /// The input file name, empty for `assemble`.
+    pub input_file_name: String,
+    /// The entry point name, empty for `assemble`.
+    pub entry_point_name: String,
+}
+
+impl MockCompiler {
+    /// Creates a mock compiler without canned results.
+    pub fn new() -> MockCompiler {
+        MockCompiler::default()
+    }
+
+    /// Returns this compiler with `result` returned for calls on the input
+    /// file `input_file_name`.
+    pub fn with_result(
+        mut self,
+        input_file_name: &str,
+        result: Result<CompilationArtifact>,
+    ) -> MockCompiler {
+        self.results
+            .insert(input_file_name.to_string(), canned(result));
+        self
+    }
+
+    /// Returns this compiler with `result` returned for calls on input files
+    /// without a canned result.
+    pub fn with_default_result(mut self, result: Result<CompilationArtifact>) -> MockCompiler {
+        self.default_result = Some(canned(result));
+        self
+    }
+
+    /// Returns the calls made so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn respond(&self, call: MockCall) -> Result<CompilationArtifact> {
+        let result = self
+            .results
+            .get(&call.input_file_name)
+            .filter(|_| call.method != "assemble")
+            .or(self.default_result.as_ref());
+        let response = match result {
+            Some(Ok((output, is_binary))) => {
+                Ok(CompilationArtifact::from_owned(output.clone(), *is_binary))
+            }
+            Some(Err(error)) => Err(error.clone()),
+            None => Err(Error::InternalError(format!(
+                "MockCompiler has no result for `{}`",
+                call.input_file_name
+            ))),
+        };
+        self.calls.lock().unwrap().push(call);
+        response
+    }
+}
+
+fn canned(result: Result<CompilationArtifact>) -> CannedResult {
+    result.map(|artifact| (artifact.to_owned_output(), artifact.is_binary))
+}
+
+impl ShaderCompiler for MockCompiler {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        _additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.respond(MockCall {
+            method: "compile_into_spirv",
+            source_text: source_text.to_string(),
+            shader_kind: Some(shader_kind),
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        })
+    }
+
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        _additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.respond(MockCall {
+            method: "compile_into_spirv_assembly",
+            source_text: source_text.to_string(),
+            shader_kind: Some(shader_kind),
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        })
+    }
+
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        _additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.respond(MockCall {
+            method: "preprocess",
+            source_text: source_text.to_string(),
+            shader_kind: None,
+            input_file_name: input_file_name.to_string(),
+            entry_point_name: entry_point_name.to_string(),
+        })
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        _additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.respond(MockCall {
+            method: "assemble",
+            source_text: source_assembly.to_string(),
+            shader_kind: None,
+            input_file_name: String::new(),
+            entry_point_name: String::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn compile_all<C: ShaderCompiler>(compiler: &C, names: &[&str]) -> Vec<Result<usize>> {
+        names
+            .iter()
+            .map(|name| {
+                compiler
+                    .compile_into_spirv("void main() {}", ShaderKind::Vertex, name, "main", None)
+                    .map(|artifact| artifact.len())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mock_canned_results() {
+        let error = Error::CompilationError(1, "b.vert:1: error: oops".to_string());
+        let compiler = MockCompiler::new()
+            .with_result("a.vert", Ok(CompilationArtifact::from_binary(vec![1, 2])))
+            .with_result("b.vert", Err(error.clone()));
+        let results = compile_all(&compiler, &["a.vert", "b.vert", "c.vert"]);
+        assert_eq!(Ok(8), results[0]);
+        assert_eq!(Err(error), results[1]);
+        assert_matches!(results[2], Err(Error::InternalError(_)));
+    }
+
+    #[test]
+    fn test_mock_default_result() {
+        let compiler = MockCompiler::new()
+            .with_default_result(Ok(CompilationArtifact::from_text("; SPIR-V".to_string())
+                .with_warnings(1, "warning: unused".to_string())));
+        let artifact = compiler.assemble("OpCapability Shader", None).unwrap();
+        assert_eq!("; SPIR-V", artifact.as_text());
+        assert_eq!(1, artifact.get_num_warnings());
+        assert_eq!("warning: unused", artifact.get_warning_messages());
+    }
+
+    #[test]
+    fn test_mock_through_pointers() {
+        let compiler = Arc::new(
+            MockCompiler::new().with_default_result(Ok(CompilationArtifact::from_binary(vec![1]))),
+        );
+        let boxed: Box<dyn ShaderCompiler> = Box::new(Arc::clone(&compiler));
+        assert_eq!(vec![Ok(4)], compile_all(&boxed, &["a.vert"]));
+        assert_eq!(vec![Ok(4)], compile_all(&&*compiler, &["b.vert"]));
+        assert_eq!(2, compiler.calls().len());
+    }
+
+    #[test]
+    fn test_mock_records_calls() {
+        let compiler = MockCompiler::new()
+            .with_default_result(Ok(CompilationArtifact::from_text(String::new())));
+        let dyn_compiler: &dyn ShaderCompiler = &compiler;
+        dyn_compiler
+            .preprocess("#define A", "a.glsl", "main", None)
+            .unwrap();
+        assert_eq!(
+            vec![MockCall {
+                method: "preprocess",
+                source_text: "#define A".to_string(),
+                shader_kind: None,
+                input_file_name: "a.glsl".to_string(),
+                entry_point_name: "main".to_string(),
+            }],
+            compiler.calls()
+        );
+    }
+}
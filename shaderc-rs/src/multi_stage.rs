@@ -0,0 +1,144 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation of sources holding several stages, each between an
+//! `#ifdef` of its stage macro.
+
+use std::collections::HashMap;
+
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Compiler, Error, Result, ShaderKind,
+};
+
+impl ShaderKind {
+    /// Returns the macro conventionally defined when compiling this stage of
+    /// a multi-stage source, like `VERTEX` for `Vertex` and `DefaultVertex`,
+    /// or `None` for `InferFromSource` and `SpirvAssembly`.
+    pub fn stage_define(self) -> Option<&'static str> {
+        Some(match self.forced_variant() {
+            ShaderKind::Vertex => "VERTEX",
+            ShaderKind::Fragment => "FRAGMENT",
+            ShaderKind::Compute => "COMPUTE",
+            ShaderKind::Geometry => "GEOMETRY",
+            ShaderKind::TessControl => "TESS_CONTROL",
+            ShaderKind::TessEvaluation => "TESS_EVALUATION",
+            ShaderKind::RayGeneration => "RAY_GENERATION",
+            ShaderKind::AnyHit => "ANY_HIT",
+            ShaderKind::ClosestHit => "CLOSEST_HIT",
+            ShaderKind::Miss => "MISS",
+            ShaderKind::Intersection => "INTERSECTION",
+            ShaderKind::Callable => "CALLABLE",
+            ShaderKind::Task => "TASK",
+            ShaderKind::Mesh => "MESH",
+            _ => return None,
+        })
+    }
+}
+
+impl Compiler {
+    /// Compiles the source string `source_text`, holding several stages, to
+    /// a SPIR-V binary module per stage of `stages`.
+    ///
+    /// Each stage is compiled with its `ShaderKind::stage_define` macro
+    /// defined to 1 on top of `base_options`, so the source keeps the code
+    /// of each stage under `#ifdef VERTEX`, `#ifdef FRAGMENT` and so on.
+    /// Returns the result of each stage by shader kind. Stages without a
+    /// stage macro give `Error::InvalidStage`.
+    ///
+    /// ```no_run
+    /// use shaderc::{Compiler, ShaderKind};
+    ///
+    /// let source = "#version 450
+    /// #ifdef VERTEX
+    /// void main() { gl_Position = vec4(0.0); }
+    /// #endif
+    /// #ifdef FRAGMENT
+    /// layout(location = 0) out vec4 color;
+    /// void main() { color = vec4(1.0); }
+    /// #endif
+    /// ";
+    /// let compiler = Compiler::new().unwrap();
+    /// let stages = [ShaderKind::Vertex, ShaderKind::Fragment];
+    /// let mut artifacts =
+    ///     compiler.compile_multi_stage(source, "material.glsl", "main", &stages, None);
+    /// let fragment = artifacts.remove(&ShaderKind::Fragment).unwrap().unwrap();
+    /// ```
+    pub fn compile_multi_stage(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        stages: &[ShaderKind],
+        base_options: Option<&CompileOptions>,
+    ) -> HashMap<ShaderKind, Result<CompilationArtifact>> {
+        let compile = |kind: ShaderKind| {
+            let define = kind.stage_define().ok_or_else(|| {
+                Error::InvalidStage(format!("{kind:?} has no stage macro to define"))
+            })?;
+            let mut options = options_or_default(base_options)?;
+            options.add_macro_definition(define, Some("1"));
+            self.compile_into_spirv(
+                source_text,
+                kind,
+                input_file_name,
+                entry_point_name,
+                Some(&options),
+            )
+        };
+        stages.iter().map(|&kind| (kind, compile(kind))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_define() {
+        assert_eq!(Some("VERTEX"), ShaderKind::Vertex.stage_define());
+        assert_eq!(Some("VERTEX"), ShaderKind::DefaultVertex.stage_define());
+        assert_eq!(
+            Some("TESS_EVALUATION"),
+            ShaderKind::TessEvaluation.stage_define()
+        );
+        assert_eq!(None, ShaderKind::InferFromSource.stage_define());
+        assert_eq!(None, ShaderKind::SpirvAssembly.stage_define());
+    }
+
+    #[test]
+    fn test_compile_multi_stage() {
+        let source = "#version 450\n\
+                      #ifdef VERTEX\n\
+                      void main() { gl_Position = vec4(0.0); }\n\
+                      #endif\n\
+                      #ifdef FRAGMENT\n\
+                      layout(location = 0) out vec4 color;\n\
+                      void main() { color = vec4(1.0); }\n\
+                      #endif\n";
+        let compiler = Compiler::new().unwrap();
+        let stages = [
+            ShaderKind::Vertex,
+            ShaderKind::Fragment,
+            ShaderKind::InferFromSource,
+        ];
+        let results = compiler.compile_multi_stage(source, "material.glsl", "main", &stages, None);
+        assert_eq!(3, results.len());
+        assert!(results[&ShaderKind::Vertex].is_ok());
+        assert!(results[&ShaderKind::Fragment].is_ok());
+        assert_matches!(
+            results[&ShaderKind::InferFromSource].as_ref().err(),
+            Some(&Error::InvalidStage(_))
+        );
+    }
+}
@@ -0,0 +1,455 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Textual names of the option enums.
+//!
+//! The names follow the spelling used by glslc's command-line flags where one
+//! exists, e.g. `fragment` or `frag` for `ShaderKind::Fragment`, `vulkan1.2`
+//! for `EnvVersion::Vulkan1_2` and `spv1.3` for `SpirvVersion::V1_3`. Limits
+//! use the keys of glslang's resource configuration, as accepted by glslc's
+//! `-flimit`, e.g. `MaxLights` for `Limit::MaxLights`. They are
+//! used for the `Display` and `FromStr` implementations, and for the `serde`
+//! and `clap` implementations when those features are enabled.
+
+use std::str::FromStr;
+use std::{error, fmt};
+
+use crate::{
+    EnvVersion, GlslProfile, Limit, OptimizationLevel, ShaderKind, SourceLanguage, SpirvVersion,
+    TargetEnv,
+};
+
+/// Error returned when parsing an enumerant from an unrecognized name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseEnumError {
+    kind: &'static str,
+    name: String,
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} `{}`", self.kind, self.name)
+    }
+}
+
+impl error::Error for ParseEnumError {}
+
+macro_rules! enum_names {
+    ($ty:ident, $kind:expr, { $($variant:ident => $name:expr $(, $alias:expr)*;)* }) => {
+        impl $ty {
+            /// Every enumerant with its canonical name and accepted aliases.
+            #[cfg_attr(not(feature = "clap"), allow(dead_code))]
+            pub(crate) const NAMES: &'static [($ty, &'static str, &'static [&'static str])] =
+                &[$(($ty::$variant, $name, &[$($alias),*])),*];
+
+            /// Returns the canonical name of this enumerant.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($ty::$variant => $name,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, ParseEnumError> {
+                match s {
+                    $($name $(| $alias)* => Ok($ty::$variant),)*
+                    _ => Err(ParseEnumError {
+                        kind: $kind,
+                        name: s.to_string(),
+                    }),
+                }
+            }
+        }
+
+        #[cfg(feature = "clap")]
+        impl clap::ValueEnum for $ty {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[$($ty::$variant),*]
+            }
+
+            fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+                let &(_, name, aliases) = $ty::NAMES.iter().find(|entry| entry.0 == *self)?;
+                Some(clap::builder::PossibleValue::new(name).aliases(aliases.iter().copied()))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.name())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+                name.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+enum_names!(ShaderKind, "shader kind", {
+    Vertex => "vertex", "vert";
+    Fragment => "fragment", "frag";
+    Compute => "compute", "comp";
+    Geometry => "geometry", "geom";
+    TessControl => "tesscontrol", "tesc";
+    TessEvaluation => "tesseval", "tese";
+    InferFromSource => "infer";
+    DefaultVertex => "default-vertex", "default-vert";
+    DefaultFragment => "default-fragment", "default-frag";
+    DefaultCompute => "default-compute", "default-comp";
+    DefaultGeometry => "default-geometry", "default-geom";
+    DefaultTessControl => "default-tesscontrol", "default-tesc";
+    DefaultTessEvaluation => "default-tesseval", "default-tese";
+    SpirvAssembly => "spirv-assembly", "spvasm";
+    RayGeneration => "rgen";
+    AnyHit => "rahit";
+    ClosestHit => "rchit";
+    Miss => "rmiss";
+    Intersection => "rint";
+    Callable => "rcall";
+    DefaultRayGeneration => "default-rgen";
+    DefaultAnyHit => "default-rahit";
+    DefaultClosestHit => "default-rchit";
+    DefaultMiss => "default-rmiss";
+    DefaultIntersection => "default-rint";
+    DefaultCallable => "default-rcall";
+    Task => "task";
+    Mesh => "mesh";
+    DefaultTask => "default-task";
+    DefaultMesh => "default-mesh";
+});
+
+enum_names!(TargetEnv, "target environment", {
+    Vulkan => "vulkan";
+    OpenGL => "opengl";
+    OpenGLCompat => "opengl_compat";
+});
+
+enum_names!(EnvVersion, "target environment version", {
+    Vulkan1_0 => "vulkan1.0";
+    Vulkan1_1 => "vulkan1.1";
+    Vulkan1_2 => "vulkan1.2";
+    Vulkan1_3 => "vulkan1.3";
+    OpenGL4_5 => "opengl4.5";
+    WebGPU => "webgpu";
+});
+
+enum_names!(SpirvVersion, "SPIR-V version", {
+    V1_0 => "spv1.0", "1.0";
+    V1_1 => "spv1.1", "1.1";
+    V1_2 => "spv1.2", "1.2";
+    V1_3 => "spv1.3", "1.3";
+    V1_4 => "spv1.4", "1.4";
+    V1_5 => "spv1.5", "1.5";
+    V1_6 => "spv1.6", "1.6";
+});
+
+enum_names!(SourceLanguage, "source language", {
+    GLSL => "glsl";
+    HLSL => "hlsl";
+});
+
+enum_names!(OptimizationLevel, "optimization level", {
+    Zero => "zero", "0";
+    Size => "size", "s";
+    Performance => "performance";
+});
+
+enum_names!(GlslProfile, "GLSL profile", {
+    None => "none";
+    Core => "core";
+    Compatibility => "compatibility";
+    Es => "es";
+});
+
+impl TargetEnv {
+    /// Parses a target environment with its version, spelled as by glslc's
+    /// `--target-env`: `vulkan1.3` or `opengl4.5` name both, while `vulkan`
+    /// and `opengl` stand for Vulkan 1.0 and OpenGL 4.5.
+    ///
+    /// `TargetEnv` itself only parses the unversioned names, so this is the
+    /// value parser to give clap for a versioned `--target-env`.
+    pub fn parse_with_version(s: &str) -> Result<(TargetEnv, EnvVersion), ParseEnumError> {
+        match s.parse() {
+            Ok(TargetEnv::Vulkan) => return Ok((TargetEnv::Vulkan, EnvVersion::Vulkan1_0)),
+            Ok(env) => return Ok((env, EnvVersion::OpenGL4_5)),
+            Err(_) => {}
+        }
+        match s.parse() {
+            Ok(EnvVersion::OpenGL4_5) => Ok((TargetEnv::OpenGL, EnvVersion::OpenGL4_5)),
+            Ok(EnvVersion::WebGPU) | Err(_) => Err(ParseEnumError {
+                kind: "target environment",
+                name: s.to_string(),
+            }),
+            Ok(version) => Ok((TargetEnv::Vulkan, version)),
+        }
+    }
+}
+
+enum_names!(Limit, "resource limit", {
+    MaxLights => "MaxLights";
+    MaxClipPlanes => "MaxClipPlanes";
+    MaxTextureUnits => "MaxTextureUnits";
+    MaxTextureCoords => "MaxTextureCoords";
+    MaxVertexAttribs => "MaxVertexAttribs";
+    MaxVertexUniformComponents => "MaxVertexUniformComponents";
+    MaxVaryingFloats => "MaxVaryingFloats";
+    MaxVertexTextureImageUnits => "MaxVertexTextureImageUnits";
+    MaxCombinedTextureImageUnits => "MaxCombinedTextureImageUnits";
+    MaxTextureImageUnits => "MaxTextureImageUnits";
+    MaxFragmentUniformComponents => "MaxFragmentUniformComponents";
+    MaxDrawBuffers => "MaxDrawBuffers";
+    MaxVertexUniformVectors => "MaxVertexUniformVectors";
+    MaxVaryingVectors => "MaxVaryingVectors";
+    MaxFragmentUniformVectors => "MaxFragmentUniformVectors";
+    MaxVertexOutputVectors => "MaxVertexOutputVectors";
+    MaxFragmentInputVectors => "MaxFragmentInputVectors";
+    MinProgramTexelOffset => "MinProgramTexelOffset";
+    MaxProgramTexelOffset => "MaxProgramTexelOffset";
+    MaxClipDistances => "MaxClipDistances";
+    MaxComputeWorkGroupCountX => "MaxComputeWorkGroupCountX";
+    MaxComputeWorkGroupCountY => "MaxComputeWorkGroupCountY";
+    MaxComputeWorkGroupCountZ => "MaxComputeWorkGroupCountZ";
+    MaxComputeWorkGroupSizeX => "MaxComputeWorkGroupSizeX";
+    MaxComputeWorkGroupSizeY => "MaxComputeWorkGroupSizeY";
+    MaxComputeWorkGroupSizeZ => "MaxComputeWorkGroupSizeZ";
+    MaxComputeUniformComponents => "MaxComputeUniformComponents";
+    MaxComputeTextureImageUnits => "MaxComputeTextureImageUnits";
+    MaxComputeImageUniforms => "MaxComputeImageUniforms";
+    MaxComputeAtomicCounters => "MaxComputeAtomicCounters";
+    MaxComputeAtomicCounterBuffers => "MaxComputeAtomicCounterBuffers";
+    MaxVaryingComponents => "MaxVaryingComponents";
+    MaxVertexOutputComponents => "MaxVertexOutputComponents";
+    MaxGeometryInputComponents => "MaxGeometryInputComponents";
+    MaxGeometryOutputComponents => "MaxGeometryOutputComponents";
+    MaxFragmentInputComponents => "MaxFragmentInputComponents";
+    MaxImageUnits => "MaxImageUnits";
+    MaxCombinedImageUnitsAndFragmentOutputs => "MaxCombinedImageUnitsAndFragmentOutputs";
+    MaxCombinedShaderOutputResources => "MaxCombinedShaderOutputResources";
+    MaxImageSamples => "MaxImageSamples";
+    MaxVertexImageUniforms => "MaxVertexImageUniforms";
+    MaxTessControlImageUniforms => "MaxTessControlImageUniforms";
+    MaxTessEvaluationImageUniforms => "MaxTessEvaluationImageUniforms";
+    MaxGeometryImageUniforms => "MaxGeometryImageUniforms";
+    MaxFragmentImageUniforms => "MaxFragmentImageUniforms";
+    MaxCombinedImageUniforms => "MaxCombinedImageUniforms";
+    MaxGeometryTextureImageUnits => "MaxGeometryTextureImageUnits";
+    MaxGeometryOutputVertices => "MaxGeometryOutputVertices";
+    MaxGeometryTotalOutputComponents => "MaxGeometryTotalOutputComponents";
+    MaxGeometryUniformComponents => "MaxGeometryUniformComponents";
+    MaxGeometryVaryingComponents => "MaxGeometryVaryingComponents";
+    MaxTessControlInputComponents => "MaxTessControlInputComponents";
+    MaxTessControlOutputComponents => "MaxTessControlOutputComponents";
+    MaxTessControlTextureImageUnits => "MaxTessControlTextureImageUnits";
+    MaxTessControlUniformComponents => "MaxTessControlUniformComponents";
+    MaxTessControlTotalOutputComponents => "MaxTessControlTotalOutputComponents";
+    MaxTessEvaluationInputComponents => "MaxTessEvaluationInputComponents";
+    MaxTessEvaluationOutputComponents => "MaxTessEvaluationOutputComponents";
+    MaxTessEvaluationTextureImageUnits => "MaxTessEvaluationTextureImageUnits";
+    MaxTessEvaluationUniformComponents => "MaxTessEvaluationUniformComponents";
+    MaxTessPatchComponents => "MaxTessPatchComponents";
+    MaxPatchVertices => "MaxPatchVertices";
+    MaxTessGenLevel => "MaxTessGenLevel";
+    MaxViewports => "MaxViewports";
+    MaxVertexAtomicCounters => "MaxVertexAtomicCounters";
+    MaxTessControlAtomicCounters => "MaxTessControlAtomicCounters";
+    MaxTessEvaluationAtomicCounters => "MaxTessEvaluationAtomicCounters";
+    MaxGeometryAtomicCounters => "MaxGeometryAtomicCounters";
+    MaxFragmentAtomicCounters => "MaxFragmentAtomicCounters";
+    MaxCombinedAtomicCounters => "MaxCombinedAtomicCounters";
+    MaxAtomicCounterBindings => "MaxAtomicCounterBindings";
+    MaxVertexAtomicCounterBuffers => "MaxVertexAtomicCounterBuffers";
+    MaxTessControlAtomicCounterBuffers => "MaxTessControlAtomicCounterBuffers";
+    MaxTessEvaluationAtomicCounterBuffers => "MaxTessEvaluationAtomicCounterBuffers";
+    MaxGeometryAtomicCounterBuffers => "MaxGeometryAtomicCounterBuffers";
+    MaxFragmentAtomicCounterBuffers => "MaxFragmentAtomicCounterBuffers";
+    MaxCombinedAtomicCounterBuffers => "MaxCombinedAtomicCounterBuffers";
+    MaxAtomicCounterBufferSize => "MaxAtomicCounterBufferSize";
+    MaxTransformFeedbackBuffers => "MaxTransformFeedbackBuffers";
+    MaxTransformFeedbackInterleavedComponents => "MaxTransformFeedbackInterleavedComponents";
+    MaxCullDistances => "MaxCullDistances";
+    MaxCombinedClipAndCullDistances => "MaxCombinedClipAndCullDistances";
+    MaxSamples => "MaxSamples";
+    MaxMeshOutputVerticesNv => "MaxMeshOutputVerticesNV";
+    MaxMeshOutputPrimitivesNv => "MaxMeshOutputPrimitivesNV";
+    MaxMeshWorkGroupSizeXNv => "MaxMeshWorkGroupSizeX_NV";
+    MaxMeshWorkGroupSizeYNv => "MaxMeshWorkGroupSizeY_NV";
+    MaxMeshWorkGroupSizeZNv => "MaxMeshWorkGroupSizeZ_NV";
+    MaxTaskWorkGroupSizeXNv => "MaxTaskWorkGroupSizeX_NV";
+    MaxTaskWorkGroupSizeYNv => "MaxTaskWorkGroupSizeY_NV";
+    MaxTaskWorkGroupSizeZNv => "MaxTaskWorkGroupSizeZ_NV";
+    MaxMeshViewCountNv => "MaxMeshViewCountNV";
+    MaxMeshOutputVerticesExt => "MaxMeshOutputVerticesEXT";
+    MaxMeshOutputPrimitivesExt => "MaxMeshOutputPrimitivesEXT";
+    MaxMeshWorkGroupSizeXExt => "MaxMeshWorkGroupSizeX_EXT";
+    MaxMeshWorkGroupSizeYExt => "MaxMeshWorkGroupSizeY_EXT";
+    MaxMeshWorkGroupSizeZExt => "MaxMeshWorkGroupSizeZ_EXT";
+    MaxTaskWorkGroupSizeXExt => "MaxTaskWorkGroupSizeX_EXT";
+    MaxTaskWorkGroupSizeYExt => "MaxTaskWorkGroupSizeY_EXT";
+    MaxTaskWorkGroupSizeZExt => "MaxTaskWorkGroupSizeZ_EXT";
+    MaxMeshViewCountExt => "MaxMeshViewCountEXT";
+    MaxDualSourceDrawBuffersExt => "MaxDualSourceDrawBuffersEXT";
+});
+
+/// Infers the stage of a shader from its file extension, ignoring a trailing
+/// `.glsl` or `.hlsl`.
+pub(crate) fn stage_from_extension(name: &str) -> Option<ShaderKind> {
+    let name = name
+        .strip_suffix(".glsl")
+        .or_else(|| name.strip_suffix(".hlsl"))
+        .unwrap_or(name);
+    match name.rsplit('.').next()?.parse() {
+        Ok(ShaderKind::InferFromSource) | Ok(ShaderKind::SpirvAssembly) | Err(_) => None,
+        Ok(kind) if kind.name().starts_with("default-") => None,
+        Ok(kind) => Some(kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for &(kind, name, aliases) in ShaderKind::NAMES {
+            assert_eq!(name, kind.to_string());
+            assert_eq!(Ok(kind), name.parse());
+            for alias in aliases {
+                assert_eq!(Ok(kind), alias.parse());
+            }
+        }
+        for &(version, name, _) in EnvVersion::NAMES {
+            assert_eq!(Ok(version), name.parse());
+        }
+        for &(version, name, _) in SpirvVersion::NAMES {
+            assert_eq!(Ok(version), name.parse());
+        }
+        for &(limit, name, _) in Limit::NAMES {
+            assert_eq!(Ok(limit), name.parse());
+        }
+    }
+
+    #[test]
+    fn test_names_glslc_spelling() {
+        assert_eq!(Ok(ShaderKind::Fragment), "frag".parse());
+        assert_eq!(Ok(ShaderKind::TessEvaluation), "tesseval".parse());
+        assert_eq!(Ok(TargetEnv::OpenGLCompat), "opengl_compat".parse());
+        assert_eq!("vulkan1.2", EnvVersion::Vulkan1_2.to_string());
+        assert_eq!(Ok(SpirvVersion::V1_3), "spv1.3".parse());
+        assert_eq!(Ok(OptimizationLevel::Size), "s".parse());
+        assert_eq!(Ok(GlslProfile::Es), "es".parse());
+        assert_eq!(Ok(SourceLanguage::HLSL), "hlsl".parse());
+        assert_eq!("MaxMeshViewCountNV", Limit::MaxMeshViewCountNv.name());
+        assert_eq!(
+            Ok(Limit::MaxTaskWorkGroupSizeXExt),
+            "MaxTaskWorkGroupSizeX_EXT".parse()
+        );
+    }
+
+    #[test]
+    fn test_names_unknown() {
+        let err = "pixel".parse::<ShaderKind>().unwrap_err();
+        assert_eq!("unknown shader kind `pixel`", err.to_string());
+        assert!("Vulkan".parse::<TargetEnv>().is_err());
+        assert!("".parse::<EnvVersion>().is_err());
+    }
+
+    #[test]
+    fn test_target_env_with_version() {
+        assert!("vulkan1.3".parse::<TargetEnv>().is_err());
+        assert_eq!(
+            Ok((TargetEnv::Vulkan, EnvVersion::Vulkan1_3)),
+            TargetEnv::parse_with_version("vulkan1.3")
+        );
+        assert_eq!(
+            Ok((TargetEnv::Vulkan, EnvVersion::Vulkan1_0)),
+            TargetEnv::parse_with_version("vulkan")
+        );
+        assert_eq!(
+            Ok((TargetEnv::OpenGL, EnvVersion::OpenGL4_5)),
+            TargetEnv::parse_with_version("opengl4.5")
+        );
+        let err = TargetEnv::parse_with_version("webgpu").unwrap_err();
+        assert_eq!("unknown target environment `webgpu`", err.to_string());
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn test_names_clap_value_enum() {
+        use clap::{Arg, Command};
+
+        let command = Command::new("shaderc")
+            .arg(
+                Arg::new("stage")
+                    .long("stage")
+                    .value_parser(clap::value_parser!(ShaderKind)),
+            )
+            .arg(
+                Arg::new("target-env")
+                    .long("target-env")
+                    .value_parser(clap::value_parser!(EnvVersion)),
+            )
+            .arg(
+                Arg::new("optimize")
+                    .long("optimize")
+                    .value_parser(clap::value_parser!(OptimizationLevel)),
+            );
+        let matches = command
+            .try_get_matches_from([
+                "shaderc",
+                "--stage",
+                "frag",
+                "--target-env",
+                "vulkan1.3",
+                "--optimize",
+                "size",
+            ])
+            .unwrap();
+        assert_eq!(Some(&ShaderKind::Fragment), matches.get_one("stage"));
+        assert_eq!(Some(&EnvVersion::Vulkan1_3), matches.get_one("target-env"));
+        assert_eq!(Some(&OptimizationLevel::Size), matches.get_one("optimize"));
+
+        assert_eq!(
+            Ok(SourceLanguage::HLSL),
+            <SourceLanguage as clap::ValueEnum>::from_str("hlsl", false)
+        );
+        assert!(<TargetEnv as clap::ValueEnum>::from_str("vulkan1.3", false).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_names_deserialize() {
+        use serde::de::value::{Error as ValueError, StrDeserializer};
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize;
+
+        let de: StrDeserializer<ValueError> = "vulkan1.3".into_deserializer();
+        assert_eq!(Ok(EnvVersion::Vulkan1_3), EnvVersion::deserialize(de));
+        let de: StrDeserializer<ValueError> = "pixel".into_deserializer();
+        assert!(ShaderKind::deserialize(de).is_err());
+    }
+}
@@ -0,0 +1,882 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pipelines of shader stages compiled together.
+//!
+//! `Pipeline` compiles the stages of a graphics, mesh or compute pipeline
+//! with any `ShaderCompiler` and checks the interface between consecutive
+//! stages, reporting mismatches as `PipelineError`s.
+
+use std::rc::Rc;
+use std::{error, fmt};
+
+use crate::spirv_module::{op, storage_class, InterfaceVariable, SpirvModule};
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Error, Limit, ShaderCompiler,
+    ShaderKind,
+};
+
+/// The stages of a pipeline, in the order data flows through them.
+const STAGE_ORDER: &[ShaderKind] = &[
+    ShaderKind::Task,
+    ShaderKind::Mesh,
+    ShaderKind::Vertex,
+    ShaderKind::TessControl,
+    ShaderKind::TessEvaluation,
+    ShaderKind::Geometry,
+    ShaderKind::Fragment,
+    ShaderKind::Compute,
+];
+
+/// A set of shader stages compiled and checked together.
+///
+/// Compiling each stage on its own cannot catch mismatches between the
+/// outputs of a stage and the inputs of the next one. `Pipeline` compiles
+/// all its stages, then checks that every input of a stage is written by the
+/// previous stage at the same location with the same type.
+///
+/// ```no_run
+/// # use shaderc::{Compiler, Pipeline};
+/// let compiler = Compiler::new().unwrap();
+/// let pipeline = Pipeline::new()
+///     .vertex("#version 450\nlayout(location = 0) out vec4 c;\nvoid main() {}")
+///     .fragment("#version 450\nlayout(location = 0) in vec3 c;\nvoid main() {}")
+///     .compile(&compiler, None);
+/// assert!(pipeline.is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct Pipeline<'a> {
+    stages: Vec<Stage<'a>>,
+    entry_point_name: Option<&'a str>,
+    stage_options: Vec<(ShaderKind, StageOptions<'a>)>,
+}
+
+/// A change to the options of a single stage.
+type StageOptions<'a> = Rc<dyn Fn(&mut CompileOptions) + 'a>;
+
+impl<'a> fmt::Debug for Pipeline<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stage_options: Vec<ShaderKind> = self.stage_options.iter().map(|o| o.0).collect();
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages)
+            .field("entry_point_name", &self.entry_point_name)
+            .field("stage_options", &stage_options)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Stage<'a> {
+    kind: ShaderKind,
+    input_file_name: String,
+    source_text: &'a str,
+}
+
+/// The compiled stages of a `Pipeline`, in pipeline order.
+pub struct CompiledPipeline {
+    stages: Vec<(ShaderKind, CompilationArtifact)>,
+}
+
+/// A mismatch between the interfaces of two stages of a pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkError {
+    /// The stage writing the outputs.
+    pub producer: ShaderKind,
+    /// The stage reading the inputs.
+    pub consumer: ShaderKind,
+    /// The location of the mismatched input.
+    pub location: u32,
+    pub message: String,
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} location {}: {}",
+            stage_name(self.producer),
+            stage_name(self.consumer),
+            self.location,
+            self.message
+        )
+    }
+}
+
+/// A value declared by a stage exceeding a resource limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LimitViolation {
+    pub stage: ShaderKind,
+    /// The exceeded limit.
+    pub limit: Limit,
+    /// The value of the limit in the compile options.
+    pub maximum: i32,
+    /// The value declared by the stage, like the `max_vertices` of a mesh
+    /// shader.
+    pub value: u32,
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} stage: {} {} exceeds {} {}",
+            stage_name(self.stage),
+            limit_subject(self.limit),
+            self.value,
+            self.limit,
+            self.maximum
+        )
+    }
+}
+
+/// Error returned when a `Pipeline` fails to compile.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PipelineError {
+    /// A stage failed to compile.
+    Compile(ShaderKind, Error),
+    /// A stage compiled to words which are not a valid SPIR-V module.
+    InvalidModule(ShaderKind, String),
+    /// All stages compiled, but their interfaces do not match.
+    Link(Vec<LinkError>),
+    /// All stages compiled, but exceed resource limits, as checked by
+    /// `MeshPipeline`.
+    Limits(Vec<LimitViolation>),
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PipelineError::Compile(kind, ref error) => {
+                write!(f, "{} stage: {}", stage_name(kind), error)
+            }
+            PipelineError::InvalidModule(kind, ref reason) => {
+                write!(f, "{} stage: {}", stage_name(kind), reason)
+            }
+            PipelineError::Link(ref errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n")?;
+                    }
+                    write!(f, "{error}")?;
+                }
+                Ok(())
+            }
+            PipelineError::Limits(ref violations) => {
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n")?;
+                    }
+                    write!(f, "{violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for PipelineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PipelineError::Compile(_, ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Pipeline<'a> {
+    /// Creates a pipeline without stages.
+    pub fn new() -> Pipeline<'a> {
+        Pipeline::default()
+    }
+
+    /// Adds a stage of kind `kind`, compiled from `source_text` under the
+    /// name `input_file_name`, replacing any stage of the same kind.
+    pub fn stage(mut self, kind: ShaderKind, input_file_name: &str, source_text: &'a str) -> Self {
+        self.stages.retain(|stage| stage.kind != kind);
+        self.stages.push(Stage {
+            kind,
+            input_file_name: input_file_name.to_string(),
+            source_text,
+        });
+        self
+    }
+
+    /// Sets the entry point name of all stages, `main` by default.
+    pub fn entry_point(mut self, entry_point_name: &'a str) -> Self {
+        self.entry_point_name = Some(entry_point_name);
+        self
+    }
+
+    /// Changes the options of the stage of kind `kind` with `f`, which is
+    /// called on a copy of the options passed to `compile`.
+    ///
+    /// Changes for the same stage are applied in the order they were given.
+    ///
+    /// ```no_run
+    /// # use shaderc::{Compiler, Pipeline, ShaderKind};
+    /// # let (vertex, fragment) = ("", "");
+    /// let compiler = Compiler::new().unwrap();
+    /// let pipeline = Pipeline::new()
+    ///     .vertex(vertex)
+    ///     .fragment(fragment)
+    ///     .stage_options(ShaderKind::Vertex, |options| options.set_invert_y(true))
+    ///     .stage_options(ShaderKind::Fragment, |options| {
+    ///         options.add_macro_definition("EARLY_Z", None)
+    ///     })
+    ///     .compile(&compiler, None);
+    /// ```
+    pub fn stage_options<F>(mut self, kind: ShaderKind, f: F) -> Self
+    where
+        F: Fn(&mut CompileOptions) + 'a,
+    {
+        self.stage_options.push((kind, Rc::new(f)));
+        self
+    }
+
+    /// Adds a vertex stage, compiled under the name `pipeline.vert`.
+    pub fn vertex(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Vertex, "pipeline.vert", source_text)
+    }
+
+    /// Adds a tessellation control stage, compiled under the name
+    /// `pipeline.tesc`.
+    pub fn tess_control(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::TessControl, "pipeline.tesc", source_text)
+    }
+
+    /// Adds a tessellation evaluation stage, compiled under the name
+    /// `pipeline.tese`.
+    pub fn tess_evaluation(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::TessEvaluation, "pipeline.tese", source_text)
+    }
+
+    /// Adds a geometry stage, compiled under the name `pipeline.geom`.
+    pub fn geometry(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Geometry, "pipeline.geom", source_text)
+    }
+
+    /// Adds a fragment stage, compiled under the name `pipeline.frag`.
+    pub fn fragment(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Fragment, "pipeline.frag", source_text)
+    }
+
+    /// Adds a task stage, compiled under the name `pipeline.task`.
+    pub fn task(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Task, "pipeline.task", source_text)
+    }
+
+    /// Adds a mesh stage, compiled under the name `pipeline.mesh`.
+    pub fn mesh(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Mesh, "pipeline.mesh", source_text)
+    }
+
+    /// Adds a compute stage, compiled under the name `pipeline.comp`.
+    pub fn compute(self, source_text: &'a str) -> Self {
+        self.stage(ShaderKind::Compute, "pipeline.comp", source_text)
+    }
+
+    /// Compiles all stages with `compiler`, then checks that the inputs of
+    /// each stage match the outputs of the previous one.
+    ///
+    /// Stops at the first stage failing to compile. Link errors are only
+    /// reported once all stages compiled, all of them at once.
+    pub fn compile<C: ShaderCompiler + ?Sized>(
+        &self,
+        compiler: &C,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompiledPipeline, PipelineError> {
+        let mut stages: Vec<&Stage> = self.stages.iter().collect();
+        stages.sort_by_key(|stage| stage_index(stage.kind));
+
+        let mut compiled = Vec::new();
+        let mut modules = Vec::new();
+        for stage in stages {
+            let stage_options = self.options_for(stage.kind, additional_options)?;
+            let artifact = compiler
+                .compile_into_spirv(
+                    stage.source_text,
+                    stage.kind,
+                    &stage.input_file_name,
+                    self.entry_point_name.unwrap_or("main"),
+                    stage_options.as_ref().or(additional_options),
+                )
+                .map_err(|error| PipelineError::Compile(stage.kind, error))?;
+            let module = SpirvModule::from_artifact(&artifact)
+                .map_err(|error| PipelineError::InvalidModule(stage.kind, error.to_string()))?;
+            modules.push((stage.kind, module));
+            compiled.push((stage.kind, artifact));
+        }
+
+        let mut errors = Vec::new();
+        for pair in modules.windows(2) {
+            let (producer, ref outputs) = pair[0];
+            let (consumer, ref inputs) = pair[1];
+            if consumer == ShaderKind::Compute
+                || (producer, consumer) == (ShaderKind::Mesh, ShaderKind::Vertex)
+            {
+                continue;
+            }
+            errors.extend(check_interface(producer, outputs, consumer, inputs));
+        }
+        if !errors.is_empty() {
+            return Err(PipelineError::Link(errors));
+        }
+        Ok(CompiledPipeline { stages: compiled })
+    }
+
+    /// Returns a copy of `base` changed for the stage of kind `kind`, or
+    /// `None` if the stage uses `base` unchanged.
+    fn options_for<'b>(
+        &self,
+        kind: ShaderKind,
+        base: Option<&'b CompileOptions>,
+    ) -> Result<Option<CompileOptions<'b>>, PipelineError> {
+        let mut changes = self.stage_options.iter().filter(|o| o.0 == kind).peekable();
+        if changes.peek().is_none() {
+            return Ok(None);
+        }
+        let mut options =
+            options_or_default(base).map_err(|error| PipelineError::Compile(kind, error))?;
+        for (_, change) in changes {
+            change(&mut options);
+        }
+        Ok(Some(options))
+    }
+}
+
+impl CompiledPipeline {
+    /// Returns the artifact of the stage of kind `kind`, if any.
+    pub fn get(&self, kind: ShaderKind) -> Option<&CompilationArtifact> {
+        self.stages
+            .iter()
+            .find(|&&(k, _)| k == kind)
+            .map(|(_, artifact)| artifact)
+    }
+
+    /// Returns the compiled stages, in pipeline order.
+    pub fn stages(&self) -> &[(ShaderKind, CompilationArtifact)] {
+        &self.stages
+    }
+
+    /// Returns the compiled stages, in pipeline order, consuming the
+    /// pipeline.
+    pub fn into_stages(self) -> Vec<(ShaderKind, CompilationArtifact)> {
+        self.stages
+    }
+}
+
+/// A task, mesh and fragment pipeline whose task and mesh stages are checked
+/// against the mesh shading limits of the compile options.
+///
+/// Drivers report a mesh shader declaring more output vertices or primitives
+/// than the device supports, or a larger workgroup, with little more than a
+/// failure to create the pipeline. `MeshPipeline` compiles its stages like
+/// `Pipeline`, then compares the `max_vertices`, `max_primitives` and
+/// `local_size` of the task and mesh stages with the `MaxMeshOutput*`,
+/// `MaxMeshWorkGroupSize*` and `MaxTaskWorkGroupSize*` limits set with
+/// `CompileOptions::set_limit`, or their defaults.
+///
+/// ```no_run
+/// # use shaderc::{CompileOptions, Compiler, Limit, MeshPipeline};
+/// # let (mesh, fragment) = ("", "");
+/// let compiler = Compiler::new().unwrap();
+/// let mut options = CompileOptions::new().unwrap();
+/// options.set_limit(Limit::MaxMeshOutputVerticesExt, 128);
+/// let pipeline = MeshPipeline::new()
+///     .mesh(mesh)
+///     .fragment(fragment)
+///     .compile(&compiler, Some(&options));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MeshPipeline<'a> {
+    pipeline: Pipeline<'a>,
+}
+
+impl<'a> MeshPipeline<'a> {
+    /// Creates a pipeline without stages.
+    pub fn new() -> MeshPipeline<'a> {
+        MeshPipeline::default()
+    }
+
+    /// Adds a task stage, compiled under the name `pipeline.task`.
+    pub fn task(mut self, source_text: &'a str) -> Self {
+        self.pipeline = self.pipeline.task(source_text);
+        self
+    }
+
+    /// Adds a mesh stage, compiled under the name `pipeline.mesh`.
+    pub fn mesh(mut self, source_text: &'a str) -> Self {
+        self.pipeline = self.pipeline.mesh(source_text);
+        self
+    }
+
+    /// Adds a fragment stage, compiled under the name `pipeline.frag`.
+    pub fn fragment(mut self, source_text: &'a str) -> Self {
+        self.pipeline = self.pipeline.fragment(source_text);
+        self
+    }
+
+    /// Sets the entry point name of all stages, `main` by default.
+    pub fn entry_point(mut self, entry_point_name: &'a str) -> Self {
+        self.pipeline = self.pipeline.entry_point(entry_point_name);
+        self
+    }
+
+    /// Changes the options of the stage of kind `kind` with `f`, as
+    /// `Pipeline::stage_options` does. Limits set this way apply to the
+    /// checks of that stage.
+    pub fn stage_options<F>(mut self, kind: ShaderKind, f: F) -> Self
+    where
+        F: Fn(&mut CompileOptions) + 'a,
+    {
+        self.pipeline = self.pipeline.stage_options(kind, f);
+        self
+    }
+
+    /// Compiles and links all stages like `Pipeline::compile`, then checks
+    /// the task and mesh stages against the limits of the options.
+    ///
+    /// All violations are reported at once in `PipelineError::Limits`.
+    pub fn compile<C: ShaderCompiler + ?Sized>(
+        &self,
+        compiler: &C,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompiledPipeline, PipelineError> {
+        let compiled = self.pipeline.compile(compiler, additional_options)?;
+        let mut violations = Vec::new();
+        for &(kind, ref artifact) in compiled.stages() {
+            if kind != ShaderKind::Task && kind != ShaderKind::Mesh {
+                continue;
+            }
+            let module = SpirvModule::from_artifact(artifact)
+                .map_err(|error| PipelineError::InvalidModule(kind, error.to_string()))?;
+            let stage_options = self.pipeline.options_for(kind, additional_options)?;
+            let options = stage_options.as_ref().or(additional_options);
+            violations.extend(check_mesh_limits(kind, &module, options));
+        }
+        if !violations.is_empty() {
+            return Err(PipelineError::Limits(violations));
+        }
+        Ok(compiled)
+    }
+}
+
+/// Returns the value of `limit` in `options`, or its default.
+fn limit_value(options: Option<&CompileOptions>, limit: Limit) -> i32 {
+    options
+        .and_then(|options| {
+            options
+                .settings
+                .limits
+                .iter()
+                .find(|&&(l, _)| l == limit)
+                .map(|&(_, value)| value)
+        })
+        .unwrap_or_else(|| limit.default_value())
+}
+
+/// Returns what a mesh shading limit bounds, for messages.
+fn limit_subject(limit: Limit) -> &'static str {
+    match limit {
+        Limit::MaxMeshOutputVerticesExt | Limit::MaxMeshOutputVerticesNv => "output vertices",
+        Limit::MaxMeshOutputPrimitivesExt | Limit::MaxMeshOutputPrimitivesNv => "output primitives",
+        Limit::MaxMeshWorkGroupSizeXExt
+        | Limit::MaxMeshWorkGroupSizeXNv
+        | Limit::MaxTaskWorkGroupSizeXExt
+        | Limit::MaxTaskWorkGroupSizeXNv => "workgroup size x",
+        Limit::MaxMeshWorkGroupSizeYExt
+        | Limit::MaxMeshWorkGroupSizeYNv
+        | Limit::MaxTaskWorkGroupSizeYExt
+        | Limit::MaxTaskWorkGroupSizeYNv => "workgroup size y",
+        _ => "workgroup size z",
+    }
+}
+
+/// Checks the execution modes of the task or mesh stage `module` against the
+/// limits of `options`.
+///
+/// The limits of `GL_NV_mesh_shader` apply to the entry points of its
+/// execution models, and those of `GL_EXT_mesh_shader` to the others.
+fn check_mesh_limits(
+    kind: ShaderKind,
+    module: &SpirvModule,
+    options: Option<&CompileOptions>,
+) -> Vec<LimitViolation> {
+    // The execution models of GL_NV_mesh_shader.
+    const TASK_NV: u32 = 5267;
+    const MESH_NV: u32 = 5268;
+    // The execution modes bounded by mesh shading limits.
+    const LOCAL_SIZE: u32 = 17;
+    const OUTPUT_VERTICES: u32 = 26;
+    const OUTPUT_PRIMITIVES: u32 = 5270;
+
+    let nv = module.instructions().any(|inst| {
+        inst.opcode() == op::ENTRY_POINT
+            && matches!(inst.operands().first(), Some(&TASK_NV) | Some(&MESH_NV))
+    });
+    let pick = |ext, nv_limit| if nv { nv_limit } else { ext };
+    let local_size = if kind == ShaderKind::Task {
+        [
+            pick(
+                Limit::MaxTaskWorkGroupSizeXExt,
+                Limit::MaxTaskWorkGroupSizeXNv,
+            ),
+            pick(
+                Limit::MaxTaskWorkGroupSizeYExt,
+                Limit::MaxTaskWorkGroupSizeYNv,
+            ),
+            pick(
+                Limit::MaxTaskWorkGroupSizeZExt,
+                Limit::MaxTaskWorkGroupSizeZNv,
+            ),
+        ]
+    } else {
+        [
+            pick(
+                Limit::MaxMeshWorkGroupSizeXExt,
+                Limit::MaxMeshWorkGroupSizeXNv,
+            ),
+            pick(
+                Limit::MaxMeshWorkGroupSizeYExt,
+                Limit::MaxMeshWorkGroupSizeYNv,
+            ),
+            pick(
+                Limit::MaxMeshWorkGroupSizeZExt,
+                Limit::MaxMeshWorkGroupSizeZNv,
+            ),
+        ]
+    };
+
+    let mut checks = Vec::new();
+    for inst in module.instructions() {
+        let operands = inst.operands();
+        if inst.opcode() != op::EXECUTION_MODE || operands.len() < 3 {
+            continue;
+        }
+        match operands[1] {
+            LOCAL_SIZE => {
+                checks.extend(
+                    local_size
+                        .iter()
+                        .copied()
+                        .zip(operands[2..].iter().copied()),
+                );
+            }
+            OUTPUT_VERTICES if kind == ShaderKind::Mesh => checks.push((
+                pick(
+                    Limit::MaxMeshOutputVerticesExt,
+                    Limit::MaxMeshOutputVerticesNv,
+                ),
+                operands[2],
+            )),
+            OUTPUT_PRIMITIVES if kind == ShaderKind::Mesh => checks.push((
+                pick(
+                    Limit::MaxMeshOutputPrimitivesExt,
+                    Limit::MaxMeshOutputPrimitivesNv,
+                ),
+                operands[2],
+            )),
+            _ => {}
+        }
+    }
+    checks
+        .into_iter()
+        .filter_map(|(limit, value)| {
+            let maximum = limit_value(options, limit);
+            if i64::from(value) > i64::from(maximum) {
+                Some(LimitViolation {
+                    stage: kind,
+                    limit,
+                    maximum,
+                    value,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn stage_index(kind: ShaderKind) -> usize {
+    STAGE_ORDER
+        .iter()
+        .position(|&k| k == kind)
+        .unwrap_or(STAGE_ORDER.len())
+}
+
+fn stage_name(kind: ShaderKind) -> &'static str {
+    match kind {
+        ShaderKind::Vertex => "vertex",
+        ShaderKind::TessControl => "tessellation control",
+        ShaderKind::TessEvaluation => "tessellation evaluation",
+        ShaderKind::Geometry => "geometry",
+        ShaderKind::Fragment => "fragment",
+        ShaderKind::Task => "task",
+        ShaderKind::Mesh => "mesh",
+        ShaderKind::Compute => "compute",
+        _ => "unknown",
+    }
+}
+
+/// Returns whether the inputs or outputs of a stage are arrays with one
+/// element per vertex, which are compared by element type.
+fn is_per_vertex(kind: ShaderKind, storage_class: u32) -> bool {
+    match kind {
+        ShaderKind::TessControl => true,
+        ShaderKind::TessEvaluation | ShaderKind::Geometry => storage_class == storage_class::INPUT,
+        ShaderKind::Mesh => storage_class == storage_class::OUTPUT,
+        _ => false,
+    }
+}
+
+/// Checks that each located input of `inputs` is written by an output of
+/// `outputs` of the same type.
+fn check_interface(
+    producer: ShaderKind,
+    outputs: &SpirvModule,
+    consumer: ShaderKind,
+    inputs: &SpirvModule,
+) -> Vec<LinkError> {
+    let describe = |module: &SpirvModule, kind: ShaderKind, storage_class: u32| {
+        let info = module.type_info();
+        module
+            .interface_variables()
+            .into_iter()
+            .filter(|v| v.storage_class == storage_class && !v.builtin && v.location.is_some())
+            .map(|v: InterfaceVariable| {
+                let type_id = if is_per_vertex(kind, storage_class) && !v.patch {
+                    info.element_type(v.type_id).unwrap_or(v.type_id)
+                } else {
+                    v.type_id
+                };
+                let description = info.describe(type_id);
+                (v, description)
+            })
+            .collect::<Vec<_>>()
+    };
+    let outputs = describe(outputs, producer, storage_class::OUTPUT);
+    let inputs = describe(inputs, consumer, storage_class::INPUT);
+
+    let mut errors = Vec::new();
+    for (input, input_type) in &inputs {
+        let location = input.location.unwrap_or(0);
+        let name = match input.name {
+            Some(ref name) => format!("input `{name}`"),
+            None => "input".to_string(),
+        };
+        let output = outputs.iter().find(|(output, _)| {
+            output.location == input.location
+                && output.component == input.component
+                && output.patch == input.patch
+        });
+        let message = match output {
+            None => format!(
+                "{} {} is not written by the {} stage",
+                stage_name(consumer),
+                name,
+                stage_name(producer)
+            ),
+            Some((_, output_type)) if output_type != input_type => format!(
+                "{} {} has type {} but the {} stage writes {}",
+                stage_name(consumer),
+                name,
+                input_type,
+                stage_name(producer),
+                output_type
+            ),
+            Some(_) => continue,
+        };
+        errors.push(LinkError {
+            producer,
+            consumer,
+            location,
+            message,
+        });
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::tests::{inst, module};
+    use crate::spirv_module::{decoration, op};
+    use crate::MockCompiler;
+
+    /// Returns a module with an `Input` or `Output` variable of type `vecN`
+    /// at each of `locations`.
+    fn stage_module(storage_class: u32, locations: &[(u32, u32)]) -> Vec<u32> {
+        let mut instructions = vec![inst(op::TYPE_FLOAT, &[1, 32])];
+        for (i, &(location, size)) in locations.iter().enumerate() {
+            let id = 10 + 3 * i as u32;
+            instructions.push(inst(
+                op::DECORATE,
+                &[id + 2, decoration::LOCATION, location],
+            ));
+            instructions.push(inst(op::TYPE_VECTOR, &[id, 1, size]));
+            instructions.push(inst(op::TYPE_POINTER, &[id + 1, storage_class, id]));
+            instructions.push(inst(op::VARIABLE, &[id + 1, id + 2, storage_class]));
+        }
+        module(&instructions).into_words()
+    }
+
+    #[test]
+    fn test_check_interface() {
+        let outputs = SpirvModule::from_words(stage_module(
+            storage_class::OUTPUT,
+            &[(0, 4), (1, 3), (2, 2)],
+        ))
+        .unwrap();
+        let inputs = SpirvModule::from_words(stage_module(
+            storage_class::INPUT,
+            &[(0, 4), (1, 4), (5, 2)],
+        ))
+        .unwrap();
+        let errors = check_interface(ShaderKind::Vertex, &outputs, ShaderKind::Fragment, &inputs);
+        assert_eq!(2, errors.len());
+        assert_eq!(1, errors[0].location);
+        assert_eq!(
+            "vertex -> fragment location 1: fragment input has type vec4 \
+             but the vertex stage writes vec3",
+            errors[0].to_string()
+        );
+        assert_eq!(5, errors[1].location);
+        assert_eq!(
+            "fragment input is not written by the vertex stage",
+            errors[1].message
+        );
+    }
+
+    #[test]
+    fn test_pipeline_compile() {
+        let vertex = stage_module(storage_class::OUTPUT, &[(0, 4)]);
+        let fragment = stage_module(storage_class::INPUT, &[(0, 4)]);
+        let compiler = MockCompiler::new()
+            .with_result(
+                "pipeline.vert",
+                Ok(CompilationArtifact::from_binary(vertex)),
+            )
+            .with_result(
+                "pipeline.frag",
+                Ok(CompilationArtifact::from_binary(fragment)),
+            );
+        let pipeline = Pipeline::new()
+            .fragment("frag")
+            .vertex("vert")
+            .compile(&compiler, None)
+            .ok()
+            .unwrap();
+        let kinds: Vec<ShaderKind> = pipeline.stages().iter().map(|s| s.0).collect();
+        assert_eq!(vec![ShaderKind::Vertex, ShaderKind::Fragment], kinds);
+        assert!(pipeline.get(ShaderKind::Fragment).is_some());
+        assert_eq!("vert", compiler.calls()[0].source_text);
+
+        let fragment = stage_module(storage_class::INPUT, &[(1, 4)]);
+        let compiler = compiler.with_result(
+            "pipeline.frag",
+            Ok(CompilationArtifact::from_binary(fragment)),
+        );
+        match Pipeline::new()
+            .vertex("")
+            .fragment("")
+            .compile(&compiler, None)
+        {
+            Err(PipelineError::Link(errors)) => assert_eq!(1, errors[0].location),
+            _ => panic!("expected a link error"),
+        }
+
+        let compiler = compiler.with_result(
+            "pipeline.vert",
+            Err(Error::CompilationError(1, String::new())),
+        );
+        match Pipeline::new()
+            .vertex("")
+            .fragment("")
+            .compile(&compiler, None)
+        {
+            Err(PipelineError::Compile(kind, _)) => assert_eq!(ShaderKind::Vertex, kind),
+            _ => panic!("expected a compilation error"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_stage_options() {
+        use std::cell::RefCell;
+
+        let module = stage_module(storage_class::OUTPUT, &[]);
+        let compiler =
+            MockCompiler::new().with_default_result(Ok(CompilationArtifact::from_binary(module)));
+        let macros = RefCell::new(Vec::new());
+        Pipeline::new()
+            .vertex("")
+            .fragment("")
+            .stage_options(ShaderKind::Fragment, |options| {
+                options.add_macro_definition("EARLY_Z", None);
+                macros
+                    .borrow_mut()
+                    .push(options.macro_definitions().to_vec());
+            })
+            .compile(&compiler, None)
+            .ok()
+            .unwrap();
+        assert_eq!(
+            vec![vec![("EARLY_Z".to_string(), None)]],
+            macros.into_inner()
+        );
+    }
+
+    #[test]
+    fn test_mesh_pipeline_limits() {
+        let mut entry_point = vec![5365, 1];
+        entry_point.extend(crate::spirv_module::encode_string("main"));
+        let mesh = module(&[
+            inst(op::ENTRY_POINT, &entry_point),
+            inst(op::EXECUTION_MODE, &[1, 17, 32, 1, 1]),
+            inst(op::EXECUTION_MODE, &[1, 26, 300]),
+            inst(op::EXECUTION_MODE, &[1, 5270, 100]),
+        ]);
+        let fragment = stage_module(storage_class::INPUT, &[]);
+        let compiler = MockCompiler::new()
+            .with_result(
+                "pipeline.mesh",
+                Ok(CompilationArtifact::from_binary(mesh.into_words())),
+            )
+            .with_result(
+                "pipeline.frag",
+                Ok(CompilationArtifact::from_binary(fragment)),
+            );
+        match MeshPipeline::new()
+            .mesh("")
+            .fragment("")
+            .compile(&compiler, None)
+        {
+            Err(PipelineError::Limits(violations)) => {
+                assert_eq!(
+                    vec![LimitViolation {
+                        stage: ShaderKind::Mesh,
+                        limit: Limit::MaxMeshOutputVerticesExt,
+                        maximum: 256,
+                        value: 300,
+                    }],
+                    violations
+                );
+                assert_eq!(
+                    "mesh stage: output vertices 300 exceeds MaxMeshOutputVerticesEXT 256",
+                    violations[0].to_string()
+                );
+            }
+            _ => panic!("expected a limit violation"),
+        }
+    }
+}
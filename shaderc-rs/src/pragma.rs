@@ -0,0 +1,411 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-file settings given by `#pragma shaderc_*` directives of sources, or
+//! by their `//! shaderc:` comment form.
+//!
+//! glslang ignores the pragmas it does not know, so sources carrying them
+//! compile unchanged with or without them applied.
+
+use std::fmt;
+use std::result;
+
+use crate::{CompileOptions, Error, OptimizationLevel, Result};
+
+/// A setting given by a `#pragma shaderc_*` directive of a source, applied
+/// on top of the options of its compilation by
+/// `CompileOptions::apply_source_pragmas`.
+///
+/// ```glsl
+/// #pragma shaderc_define(QUALITY=2)
+/// #pragma shaderc_define(USE_SHADOWS)
+/// #pragma shaderc_optimize(size)
+/// #pragma shaderc_debug_info
+/// ```
+///
+/// Each directive can also be written as a line comment starting with
+/// `//! shaderc:`, for tools that choke on unknown pragmas:
+///
+/// ```glsl
+/// //! shaderc: define(QUALITY=2)
+/// //! shaderc: optimize(size)
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourcePragma {
+    /// `shaderc_define(NAME)` or `shaderc_define(NAME=VALUE)`, like
+    /// `CompileOptions::add_macro_definition`.
+    Define(String, Option<String>),
+    /// `shaderc_optimize(LEVEL)`, with a level named as by
+    /// `OptimizationLevel`'s `FromStr`, like
+    /// `CompileOptions::set_optimization_level`.
+    Optimize(OptimizationLevel),
+    /// `shaderc_debug_info`, like `CompileOptions::set_generate_debug_info`.
+    DebugInfo,
+}
+
+impl fmt::Display for SourcePragma {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SourcePragma::Define(ref name, None) => write!(f, "#pragma shaderc_define({name})"),
+            SourcePragma::Define(ref name, Some(ref value)) => {
+                write!(f, "#pragma shaderc_define({name}={value})")
+            }
+            SourcePragma::Optimize(level) => write!(f, "#pragma shaderc_optimize({level})"),
+            SourcePragma::DebugInfo => f.write_str("#pragma shaderc_debug_info"),
+        }
+    }
+}
+
+/// Returns the name and the arguments of the `#pragma shaderc_*` directive
+/// `line`, if it is one.
+fn split_pragma(line: &str) -> Option<(&str, Option<&str>)> {
+    let rest = line.trim().strip_prefix('#')?.trim_start();
+    let rest = rest.strip_prefix("pragma")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    rest.trim_start().strip_prefix("shaderc_").map(split_call)
+}
+
+/// Returns the name and the arguments of the `//! shaderc:` comment `line`,
+/// if it is one.
+fn split_comment(line: &str) -> Option<(&str, Option<&str>)> {
+    let rest = line.trim().strip_prefix("//!")?.trim_start();
+    rest.strip_prefix("shaderc:")
+        .map(|rest| split_call(rest.trim()))
+}
+
+/// Splits the directive `call` into its name and the text after its opening
+/// parenthesis, if any.
+fn split_call(call: &str) -> (&str, Option<&str>) {
+    match call.split_once('(') {
+        Some((name, args)) => (name.trim_end(), Some(args)),
+        None => (call, None),
+    }
+}
+
+/// Returns `line` with its comments replaced by spaces. `in_comment` tells
+/// whether a block comment is open at the start of the line, and is updated
+/// for the next one.
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut rest = line;
+    loop {
+        if *in_comment {
+            match rest.find("*/") {
+                Some(end) => {
+                    *in_comment = false;
+                    stripped.push(' ');
+                    rest = &rest[end + 2..];
+                }
+                None => return stripped,
+            }
+        }
+        let line_start = rest.find("//");
+        match rest.find("/*") {
+            Some(block_start) if line_start.is_none_or(|start| block_start < start) => {
+                stripped.push_str(&rest[..block_start]);
+                *in_comment = true;
+                rest = &rest[block_start + 2..];
+            }
+            _ => {
+                stripped.push_str(&rest[..line_start.unwrap_or(rest.len())]);
+                return stripped;
+            }
+        }
+    }
+}
+
+/// A `#if` block, with conditions known only when they are integer
+/// literals.
+struct Conditional {
+    /// Whether the current branch is compiled.
+    active: Option<bool>,
+    /// Whether an earlier branch was compiled.
+    taken: Option<bool>,
+}
+
+/// Returns the value of the `#if` or `#elif` condition `condition`, if it is
+/// an integer literal.
+fn literal_condition(condition: &str) -> Option<bool> {
+    condition.trim().parse::<i64>().ok().map(|value| value != 0)
+}
+
+/// Updates `conditionals` for the conditional directive `line`, returning
+/// whether it was one.
+fn track_conditional(line: &str, conditionals: &mut Vec<Conditional>) -> bool {
+    let Some(directive) = line.trim().strip_prefix('#') else {
+        return false;
+    };
+    let directive = directive.trim_start();
+    let (name, condition) = directive
+        .split_once(char::is_whitespace)
+        .unwrap_or((directive, ""));
+    match name {
+        "if" => {
+            let active = literal_condition(condition);
+            conditionals.push(Conditional {
+                active,
+                taken: active,
+            });
+        }
+        "ifdef" | "ifndef" => conditionals.push(Conditional {
+            active: None,
+            taken: None,
+        }),
+        "elif" | "else" => {
+            let Some(conditional) = conditionals.last_mut() else {
+                return true;
+            };
+            let active = match conditional.taken {
+                Some(true) => Some(false),
+                _ if name == "else" => conditional.taken.map(|taken| !taken),
+                _ => literal_condition(condition),
+            };
+            conditional.taken = match (conditional.taken, active) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+            conditional.active = active;
+        }
+        "endif" => {
+            conditionals.pop();
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Returns the pragma `name` with the arguments `args`, the text after its
+/// opening parenthesis.
+fn parse_pragma(name: &str, args: Option<&str>) -> result::Result<SourcePragma, String> {
+    let args = match args {
+        Some(args) => Some(
+            args.strip_suffix(')')
+                .ok_or_else(|| format!("missing ')' after shaderc_{name} arguments"))?
+                .trim(),
+        ),
+        None => None,
+    };
+    match (name, args) {
+        ("define", Some(define)) if !define.is_empty() => Ok(match define.split_once('=') {
+            Some((name, value)) => {
+                SourcePragma::Define(name.trim().to_string(), Some(value.trim().to_string()))
+            }
+            None => SourcePragma::Define(define.to_string(), None),
+        }),
+        ("optimize", Some(level)) => level
+            .parse()
+            .map(SourcePragma::Optimize)
+            .map_err(|e| e.to_string()),
+        ("debug_info", None) | ("debug_info", Some("")) => Ok(SourcePragma::DebugInfo),
+        ("define", _) | ("optimize", _) => Err(format!("shaderc_{name} needs an argument")),
+        _ => Err(format!("unknown pragma shaderc_{name}")),
+    }
+}
+
+impl SourcePragma {
+    /// Returns the `#pragma shaderc_*` directives of `source_text`, and
+    /// those written as `//! shaderc:` comments, in the order they appear.
+    ///
+    /// Directives in other comments and in blocks excluded by `#if 0` are
+    /// skipped. Other conditions are not evaluated, so directives under
+    /// `#ifdef` or `#if` on a macro apply whichever branch they are in.
+    ///
+    /// Returns `Error::CompilationError` with a diagnostic in
+    /// `input_file_name` for each malformed or unknown one.
+    pub fn parse_all(source_text: &str, input_file_name: &str) -> Result<Vec<SourcePragma>> {
+        let mut pragmas = Vec::new();
+        let mut errors = Vec::new();
+        let mut in_comment = false;
+        let mut conditionals = Vec::new();
+        for (index, raw_line) in source_text.lines().enumerate() {
+            let comment = if in_comment {
+                None
+            } else {
+                split_comment(raw_line)
+            };
+            let line = strip_comments(raw_line, &mut in_comment);
+            if track_conditional(&line, &mut conditionals)
+                || conditionals.iter().any(|c| c.active == Some(false))
+            {
+                continue;
+            }
+            let Some((name, args)) = comment.or_else(|| split_pragma(&line)) else {
+                continue;
+            };
+            match parse_pragma(name, args) {
+                Ok(pragma) => pragmas.push(pragma),
+                Err(message) => errors.push(format!(
+                    "{input_file_name}:{}: error: {message}\n",
+                    index + 1
+                )),
+            }
+        }
+        if errors.is_empty() {
+            Ok(pragmas)
+        } else {
+            Err(Error::CompilationError(
+                errors.len() as u32,
+                errors.concat(),
+            ))
+        }
+    }
+}
+
+impl<'a> CompileOptions<'a> {
+    /// Applies the `#pragma shaderc_*` and `//! shaderc:` directives of
+    /// `source_text` on top of these options, letting each source adjust the
+    /// settings it is compiled with. See `SourcePragma` for the directives.
+    ///
+    /// Apply them to a clone of shared options, so that the settings of one
+    /// source do not leak into the compilation of the next. Malformed or
+    /// unknown directives give `Error::CompilationError` and leave the
+    /// options unchanged.
+    pub fn apply_source_pragmas(&mut self, source_text: &str, input_file_name: &str) -> Result<()> {
+        for pragma in SourcePragma::parse_all(source_text, input_file_name)? {
+            match pragma {
+                SourcePragma::Define(name, value) => {
+                    self.add_macro_definition(&name, value.as_deref())
+                }
+                SourcePragma::Optimize(level) => self.set_optimization_level(level),
+                SourcePragma::DebugInfo => self.set_generate_debug_info(),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pragmas() {
+        let source = "#version 450\n\
+                      #pragma shaderc_define(QUALITY = 2)\n\
+                      \x20 # pragma shaderc_define(USE_SHADOWS)\n\
+                      #pragma shaderc_optimize(s)\n\
+                      #pragma shaderc_debug_info\n\
+                      #pragma optimize(off)\n\
+                      void main() {}\n";
+        let pragmas = SourcePragma::parse_all(source, "a.frag").unwrap();
+        assert_eq!(
+            vec![
+                SourcePragma::Define("QUALITY".to_string(), Some("2".to_string())),
+                SourcePragma::Define("USE_SHADOWS".to_string(), None),
+                SourcePragma::Optimize(OptimizationLevel::Size),
+                SourcePragma::DebugInfo,
+            ],
+            pragmas
+        );
+        assert_eq!("#pragma shaderc_define(QUALITY=2)", pragmas[0].to_string());
+
+        let error = SourcePragma::parse_all(
+            "#pragma shaderc_optimize(fast)\n#pragma shaderc_define(A\n#pragma shaderc_inline\n",
+            "a.frag",
+        )
+        .err()
+        .unwrap();
+        let diagnostics = error.diagnostics();
+        assert_eq!(3, diagnostics.len());
+        assert_eq!(Some(2), diagnostics[1].line);
+        assert_eq!(
+            "missing ')' after shaderc_define arguments",
+            diagnostics[1].message
+        );
+        assert_eq!("unknown pragma shaderc_inline", diagnostics[2].message);
+    }
+
+    #[test]
+    fn test_parse_pragmas_skips_comments() {
+        let source = "// #pragma shaderc_define(LINE)\n\
+                      /* #pragma shaderc_define(BLOCK)\n\
+                      #pragma shaderc_debug_info */ #pragma shaderc_define(AFTER)\n\
+                      #pragma shaderc_define(KEPT) // #pragma shaderc_inline\n\
+                      /* one */ #pragma shaderc_optimize(zero) /* two\n\
+                      #pragma shaderc_inline */\n";
+        assert_eq!(
+            vec![
+                SourcePragma::Define("AFTER".to_string(), None),
+                SourcePragma::Define("KEPT".to_string(), None),
+                SourcePragma::Optimize(OptimizationLevel::Zero),
+            ],
+            SourcePragma::parse_all(source, "a.frag").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_comment_directives() {
+        let source = "//! shaderc: define(QUALITY=2)\n\
+                      \x20 //!shaderc:optimize(size)\n\
+                      //! shaderc: debug_info\n\
+                      /* //! shaderc: define(BLOCK) */\n\
+                      // shaderc: define(PLAIN_COMMENT)\n\
+                      #if 0\n\
+                      //! shaderc: define(IF_ZERO)\n\
+                      #endif\n\
+                      #pragma shaderc_define(PRAGMA) //! shaderc: define(TRAILING)\n";
+        assert_eq!(
+            vec![
+                SourcePragma::Define("QUALITY".to_string(), Some("2".to_string())),
+                SourcePragma::Optimize(OptimizationLevel::Size),
+                SourcePragma::DebugInfo,
+                SourcePragma::Define("PRAGMA".to_string(), None),
+            ],
+            SourcePragma::parse_all(source, "a.frag").unwrap()
+        );
+        let error = SourcePragma::parse_all("//! shaderc: inline\n", "a.frag")
+            .err()
+            .unwrap();
+        assert_eq!(
+            "unknown pragma shaderc_inline",
+            error.diagnostics()[0].message
+        );
+    }
+
+    #[test]
+    fn test_parse_pragmas_skips_inactive_blocks() {
+        let source = "#if 0\n\
+                      #pragma shaderc_define(IF_ZERO)\n\
+                      #if 1\n\
+                      #pragma shaderc_inline\n\
+                      #endif\n\
+                      #elif 1\n\
+                      #pragma shaderc_define(ELIF_ONE)\n\
+                      #else\n\
+                      #pragma shaderc_define(ELSE)\n\
+                      #endif\n\
+                      #if 1 // on\n\
+                      #pragma shaderc_define(IF_ONE)\n\
+                      #else\n\
+                      #pragma shaderc_define(ELSE_ONE)\n\
+                      #endif\n\
+                      #ifdef FAST\n\
+                      #pragma shaderc_optimize(performance)\n\
+                      #else\n\
+                      #pragma shaderc_optimize(size)\n\
+                      #endif\n";
+        assert_eq!(
+            vec![
+                SourcePragma::Define("ELIF_ONE".to_string(), None),
+                SourcePragma::Define("IF_ONE".to_string(), None),
+                SourcePragma::Optimize(OptimizationLevel::Performance),
+                SourcePragma::Optimize(OptimizationLevel::Size),
+            ],
+            SourcePragma::parse_all(source, "a.frag").unwrap()
+        );
+    }
+}
@@ -0,0 +1,763 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Manifest-driven compilation of a set of shaders.
+//!
+//! A manifest is a TOML file listing the shaders of a project along with their
+//! compile options. Paths are relative to the directory of the manifest:
+//!
+//! ```toml
+//! output_dir = "target/shaders"
+//!
+//! [defaults]
+//! include_dirs = ["shaders/include"]
+//! target_env = "vulkan1.2"
+//! optimization = "performance"
+//!
+//! [[shader]]
+//! source = "shaders/tri.vert"
+//!
+//! [[shader]]
+//! source = "shaders/lit.frag.hlsl"
+//! entry_point = "PSMain"
+//! defines = { MAX_LIGHTS = "16" }
+//! output = "lit.spv"
+//! ```
+//!
+//! Each shader is written to `output` if given, or to its source path with a
+//! `.spv` extension appended, inside `output_dir`. Shaders accept the same
+//! options as `[defaults]`; `defines` and `include_dirs` add to the defaults,
+//! the other options replace them. The stage is given by `stage` or inferred
+//! from the file extension, like glslc does.
+//!
+//! Builds are incremental: compiled modules are kept in a `ShaderCache` in
+//! `cache_dir` (by default `.cache` inside `output_dir`), keyed by the
+//! preprocessed source and the compile options, so shaders whose source,
+//! includes and options are unchanged are not compiled again.
+//!
+//! Available with the `project` feature.
+
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "watch")]
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::{error, fmt, fs, io};
+
+use crate::cache::{CacheKey, ShaderCache};
+use crate::names::stage_from_extension;
+use crate::{
+    CompileOptions, Compiler, EnvVersion, Error, IncludeType, OptimizationLevel, ResolvedInclude,
+    ShaderKind, SourceLanguage, SpirvVersion, TargetEnv, VariantKey,
+};
+
+/// Builds every shader of the manifest at `path`.
+///
+/// Shorthand for `Manifest::load(path)?.build()`.
+pub fn build<P: AsRef<Path>>(path: P) -> Result<BuildReport, ProjectError> {
+    Manifest::load(path)?.build()
+}
+
+/// Builds the project with the manifest at `path` from a build script,
+/// printing `cargo:rerun-if-changed` for the manifest and for every shader
+/// source and include read, so that editing a shared header rebuilds.
+///
+/// ```no_run
+/// // In build.rs:
+/// shaderc::project::build_script("shaders.toml").unwrap();
+/// ```
+pub fn build_script<P: AsRef<Path>>(path: P) -> Result<BuildReport, ProjectError> {
+    let path = path.as_ref();
+    let mut files = BTreeSet::new();
+    let result = Manifest::load(path).and_then(|manifest| manifest.build_tracked(&mut files));
+    // Inputs read before a failure are printed too, so fixing them reruns.
+    emit_rerun_if_changed(
+        Some(path)
+            .into_iter()
+            .chain(files.iter().map(PathBuf::as_path)),
+    );
+    result
+}
+
+/// Prints a `cargo:rerun-if-changed` line for each of `paths`.
+fn emit_rerun_if_changed<'a, I: IntoIterator<Item = &'a Path>>(paths: I) {
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// Builds the project with the manifest at `path`, then builds it again each
+/// time the manifest, a shader source or one of its includes changes.
+///
+/// The result of each build is passed to `on_build`, which returns
+/// `ControlFlow::Break` to stop watching. Returns an error if the files cannot
+/// be watched.
+///
+/// Available with the `watch` feature.
+#[cfg(feature = "watch")]
+pub fn watch<P, F>(path: P, mut on_build: F) -> notify::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(Result<BuildReport, ProjectError>) -> ControlFlow<()>,
+{
+    let path = path.as_ref();
+    crate::watch::watch(|files| {
+        files.insert(path.to_path_buf());
+        on_build(Manifest::load(path).and_then(|manifest| manifest.build_tracked(files)))
+    })
+}
+
+/// Error of a project build.
+#[derive(Debug)]
+pub enum ProjectError {
+    /// A file could not be read or written.
+    Io(PathBuf, io::Error),
+    /// The manifest is malformed.
+    Manifest(PathBuf, String),
+    /// Some shaders failed to compile; contains their source paths and errors.
+    ///
+    /// The other shaders have been built.
+    Compile(Vec<(PathBuf, Error)>),
+    /// The native compiler could not be set up.
+    Internal(String),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProjectError::Io(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+            ProjectError::Manifest(ref path, ref r) => {
+                write!(f, "invalid manifest {}: {}", path.display(), r)
+            }
+            ProjectError::Compile(ref failures) => {
+                for (i, (path, error)) in failures.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}: {}", path.display(), error)?;
+                }
+                Ok(())
+            }
+            ProjectError::Internal(ref r) => write!(f, "internal error: {r}"),
+        }
+    }
+}
+
+impl error::Error for ProjectError {}
+
+/// Outcome of a successful project build.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// Outputs of the shaders that were compiled.
+    pub compiled: Vec<PathBuf>,
+    /// Outputs of the shaders that were taken from the cache.
+    pub cached: Vec<PathBuf>,
+    /// The shader sources and the files they include, which the outputs
+    /// depend on.
+    pub inputs: Vec<PathBuf>,
+}
+
+impl BuildReport {
+    /// Prints a `cargo:rerun-if-changed` line for each input, so that a build
+    /// script runs again when a shader or one of its includes changes.
+    pub fn emit_rerun_if_changed(&self) {
+        emit_rerun_if_changed(self.inputs.iter().map(PathBuf::as_path));
+    }
+}
+
+/// Compile options of a shader, as written in the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ShaderSettings {
+    /// Directories searched for `#include` directives.
+    pub include_dirs: Vec<PathBuf>,
+    /// Macro definitions.
+    pub defines: BTreeMap<String, String>,
+    /// Entry point name, `main` by default.
+    pub entry_point: Option<String>,
+    /// Source language, inferred from the file extension by default.
+    pub language: Option<SourceLanguage>,
+    /// Target environment and version, like glslc's `--target-env`.
+    pub target_env: Option<EnvVersion>,
+    /// SPIR-V version of the output.
+    pub target_spirv: Option<SpirvVersion>,
+    /// Optimization level.
+    pub optimization: Option<OptimizationLevel>,
+    /// Whether to generate debug information.
+    pub debug_info: Option<bool>,
+}
+
+impl ShaderSettings {
+    /// Returns these settings with `overrides` applied on top.
+    fn merge(&self, overrides: &ShaderSettings) -> ShaderSettings {
+        let mut merged = self.clone();
+        merged
+            .include_dirs
+            .extend(overrides.include_dirs.iter().cloned());
+        merged.defines.extend(overrides.defines.clone());
+        merged.entry_point = overrides.entry_point.clone().or(merged.entry_point);
+        merged.language = overrides.language.or(merged.language);
+        merged.target_env = overrides.target_env.or(merged.target_env);
+        merged.target_spirv = overrides.target_spirv.or(merged.target_spirv);
+        merged.optimization = overrides.optimization.or(merged.optimization);
+        merged.debug_info = overrides.debug_info.or(merged.debug_info);
+        merged
+    }
+}
+
+/// A shader of the manifest.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Shader {
+    /// Path of the source file.
+    pub source: PathBuf,
+    /// Shader stage, inferred from the file extension by default.
+    #[serde(default)]
+    pub stage: Option<ShaderKind>,
+    /// Path of the output, relative to the output directory.
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+    /// Compile options specific to this shader.
+    #[serde(flatten)]
+    pub settings: ShaderSettings,
+}
+
+/// A project manifest.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Directory the outputs are written to.
+    #[serde(default = "default_output_dir")]
+    pub output_dir: PathBuf,
+    /// Directory of the shader cache.
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    /// Compile options shared by all shaders.
+    #[serde(default)]
+    pub defaults: ShaderSettings,
+    /// The shaders to build.
+    #[serde(default, rename = "shader")]
+    pub shaders: Vec<Shader>,
+    /// Directory relative paths are resolved against.
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// Names the outputs of shaders without an `output`; see `set_naming`.
+    #[serde(skip)]
+    naming: Naming,
+}
+
+/// Callback naming the output of a shader from its source path, stage and
+/// macro definitions.
+type NamingFn = dyn Fn(&Path, ShaderKind, &VariantKey) -> PathBuf + Send + Sync;
+
+/// The naming callback of a `Manifest`, if any. Manifests with callbacks
+/// compare equal only if they share the same callback.
+#[derive(Clone, Default)]
+struct Naming(Option<Arc<NamingFn>>);
+
+impl fmt::Debug for Naming {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
+}
+
+impl PartialEq for Naming {
+    fn eq(&self, other: &Naming) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+fn default_output_dir() -> PathBuf {
+    PathBuf::from("shaders-out")
+}
+
+impl Manifest {
+    /// Reads the manifest at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Manifest, ProjectError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ProjectError::Io(path.to_path_buf(), e))?;
+        let root = path.parent().unwrap_or_else(|| Path::new(""));
+        Manifest::parse(&text, root)
+            .map_err(|e| ProjectError::Manifest(path.to_path_buf(), e.to_string()))
+    }
+
+    /// Parses a manifest from its text, resolving paths against `root`.
+    pub fn parse(text: &str, root: &Path) -> Result<Manifest, toml::de::Error> {
+        let mut manifest: Manifest = toml::from_str(text)?;
+        manifest.root = root.to_path_buf();
+        Ok(manifest)
+    }
+
+    /// Sets the callback naming the output of each shader without an
+    /// `output`, relative to the output directory, so that projects can keep
+    /// their existing layout.
+    ///
+    /// The callback is passed the source path as written in the manifest,
+    /// the stage, inferred from the extension if not given, and the macro
+    /// definitions of the shader, defaults included, sorted by name.
+    ///
+    /// ```no_run
+    /// use shaderc::project::Manifest;
+    ///
+    /// let mut manifest = Manifest::load("shaders.toml").unwrap();
+    /// manifest.set_naming(|source, stage, defines| {
+    ///     let stem = source.file_stem().unwrap().to_string_lossy();
+    ///     format!("{}/{}-{}.spv", stage, stem, defines).into()
+    /// });
+    /// manifest.build().unwrap();
+    /// ```
+    pub fn set_naming<F>(&mut self, naming: F)
+    where
+        F: Fn(&Path, ShaderKind, &VariantKey) -> PathBuf + Send + Sync + 'static,
+    {
+        self.naming = Naming(Some(Arc::new(naming)));
+    }
+
+    /// Returns the path of the output of `shader`.
+    pub fn output_path(&self, shader: &Shader) -> PathBuf {
+        let output = match (&shader.output, &self.naming.0) {
+            (Some(output), _) => output.clone(),
+            (None, Some(naming)) => {
+                let name = shader.source.to_string_lossy();
+                let stage = shader
+                    .stage
+                    .or_else(|| stage_from_extension(&name))
+                    .unwrap_or(ShaderKind::InferFromSource);
+                let defines = self.defaults.merge(&shader.settings).defines;
+                naming(
+                    &shader.source,
+                    stage,
+                    &VariantKey::new(defines.into_iter().collect()),
+                )
+            }
+            (None, None) if shader.source.is_absolute() => {
+                let mut name = shader.source.file_name().unwrap_or_default().to_owned();
+                name.push(".spv");
+                PathBuf::from(name)
+            }
+            (None, None) => {
+                let mut name = shader.source.clone().into_os_string();
+                name.push(".spv");
+                PathBuf::from(name)
+            }
+        };
+        self.root.join(&self.output_dir).join(output)
+    }
+
+    /// Returns the directory of the shader cache.
+    pub fn cache_path(&self) -> PathBuf {
+        match self.cache_dir {
+            Some(ref dir) => self.root.join(dir),
+            None => self.root.join(&self.output_dir).join(".cache"),
+        }
+    }
+
+    /// Writes a Rust source file to `path` with a constant embedding the
+    /// output of each shader with `include_bytes!`, for `include!`ing from a
+    /// crate built by a build script.
+    ///
+    /// Constants are named after the output paths relative to the output
+    /// directory, like `SHADERS_TRI_VERT_SPV`, and hold a
+    /// `&util::SpirvBytes<[u8]>`, aligned for `as_words` to borrow the words.
+    /// The shaders must have been built, as outputs are referred to by
+    /// absolute path.
+    pub fn write_index<P: AsRef<Path>>(&self, path: P) -> Result<(), ProjectError> {
+        let path = path.as_ref();
+        let output_dir = self.root.join(&self.output_dir);
+        let mut names: BTreeMap<String, &Path> = BTreeMap::new();
+        let mut index = String::from("// Generated by shaderc::project::Manifest::write_index.\n");
+        for shader in &self.shaders {
+            let output = self.output_path(shader);
+            let relative = output.strip_prefix(&output_dir).unwrap_or(&output);
+            let name = constant_name(&relative.to_string_lossy());
+            if let Some(other) = names.insert(name.clone(), &shader.source) {
+                return Err(ProjectError::Manifest(
+                    path.to_path_buf(),
+                    format!(
+                        "{} and {} both have the index constant {name}",
+                        other.display(),
+                        shader.source.display()
+                    ),
+                ));
+            }
+            let output = fs::canonicalize(&output).map_err(|e| ProjectError::Io(output, e))?;
+            index.push_str(&format!(
+                "pub const {name}: &::shaderc::util::SpirvBytes<[u8]> =\n    \
+                 &::shaderc::util::SpirvBytes::new(*include_bytes!({:?}));\n",
+                output.to_string_lossy()
+            ));
+        }
+        fs::write(path, index).map_err(|e| ProjectError::Io(path.to_path_buf(), e))
+    }
+
+    /// Builds every shader of the manifest.
+    ///
+    /// All shaders are built even if some fail to compile, in which case
+    /// `ProjectError::Compile` lists the failures.
+    pub fn build(&self) -> Result<BuildReport, ProjectError> {
+        self.build_tracked(&mut BTreeSet::new())
+    }
+
+    /// Like `build`, also adding the shader sources and includes read to
+    /// `files`.
+    fn build_tracked(&self, files: &mut BTreeSet<PathBuf>) -> Result<BuildReport, ProjectError> {
+        let compiler = Compiler::new()
+            .ok_or_else(|| ProjectError::Internal("failed to create compiler".to_string()))?;
+        let cache_dir = self.cache_path();
+        let cache = ShaderCache::open(&cache_dir).map_err(|e| ProjectError::Io(cache_dir, e))?;
+
+        let read = Rc::new(RefCell::new(BTreeSet::new()));
+        let mut report = BuildReport::default();
+        let mut failures = Vec::new();
+        let mut inputs = BTreeSet::new();
+        for shader in &self.shaders {
+            let output = self.output_path(shader);
+            let result = self.build_shader(&compiler, &cache, shader, &output, &read);
+            files.extend(read.borrow().iter().cloned());
+            inputs.append(&mut read.borrow_mut());
+            match result {
+                Ok(true) => report.compiled.push(output),
+                Ok(false) => report.cached.push(output),
+                Err(ProjectError::Compile(mut errors)) => failures.append(&mut errors),
+                Err(e) => return Err(e),
+            }
+        }
+        if failures.is_empty() {
+            report.inputs = inputs.into_iter().collect();
+            Ok(report)
+        } else {
+            Err(ProjectError::Compile(failures))
+        }
+    }
+
+    /// Builds `shader` into `output`, returning whether it had to be compiled.
+    ///
+    /// The paths of the source and includes read are added to `read`.
+    fn build_shader(
+        &self,
+        compiler: &Compiler,
+        cache: &ShaderCache,
+        shader: &Shader,
+        output: &Path,
+        read: &Rc<RefCell<BTreeSet<PathBuf>>>,
+    ) -> Result<bool, ProjectError> {
+        let source_path = self.root.join(&shader.source);
+        read.borrow_mut().insert(source_path.clone());
+        let source = fs::read_to_string(&source_path)
+            .map_err(|e| ProjectError::Io(source_path.clone(), e))?;
+        let name = source_path.to_string_lossy();
+        let settings = self.defaults.merge(&shader.settings);
+        let stage = shader
+            .stage
+            .or_else(|| stage_from_extension(&name))
+            .unwrap_or(ShaderKind::InferFromSource);
+        let entry_point = settings.entry_point.as_deref().unwrap_or("main");
+        let failed = |error| ProjectError::Compile(vec![(shader.source.clone(), error)]);
+
+        let options = self
+            .compile_options(&settings, &name, read.clone())
+            .map_err(failed)?;
+        let preprocessed = compiler
+            .preprocess(&source, &name, entry_point, Some(&options))
+            .map_err(failed)?;
+        let key = CacheKey::builder()
+            .add(stage.name())
+            .add(format!("{settings:?}"))
+            .add(preprocessed.as_text())
+            .finish();
+
+        let (spirv, compiled) = match cache.get(&key) {
+            Some(spirv) => (spirv, false),
+            None => {
+                let artifact = compiler
+                    .compile_into_spirv(&source, stage, &name, entry_point, Some(&options))
+                    .map_err(failed)?;
+                let spirv = artifact.as_binary_u8().to_vec();
+                cache
+                    .insert(&key, &spirv)
+                    .map_err(|e| ProjectError::Io(cache.dir().to_path_buf(), e))?;
+                (spirv, true)
+            }
+        };
+        if fs::read(output).ok().as_ref() != Some(&spirv) {
+            if let Some(dir) = output.parent() {
+                fs::create_dir_all(dir).map_err(|e| ProjectError::Io(dir.to_path_buf(), e))?;
+            }
+            fs::write(output, &spirv).map_err(|e| ProjectError::Io(output.to_path_buf(), e))?;
+        }
+        Ok(compiled)
+    }
+
+    /// Translates `settings` into compile options for the source `name`,
+    /// adding the paths of resolved includes to `read`.
+    fn compile_options(
+        &self,
+        settings: &ShaderSettings,
+        name: &str,
+        read: Rc<RefCell<BTreeSet<PathBuf>>>,
+    ) -> Result<CompileOptions<'static>, Error> {
+        let mut options = CompileOptions::new()
+            .ok_or_else(|| Error::NullResultObject("failed to create compile options".into()))?;
+        for (macro_name, value) in &settings.defines {
+            options.add_macro_definition(macro_name, Some(value));
+        }
+        options.set_source_language(settings.language.unwrap_or(if name.ends_with(".hlsl") {
+            SourceLanguage::HLSL
+        } else {
+            SourceLanguage::GLSL
+        }));
+        if let Some(version) = settings.target_env {
+            let env = match version {
+                EnvVersion::OpenGL4_5 => TargetEnv::OpenGL,
+                _ => TargetEnv::Vulkan,
+            };
+            options.set_target_env(env, version as u32);
+        }
+        if let Some(version) = settings.target_spirv {
+            options.set_target_spirv(version);
+        }
+        if let Some(level) = settings.optimization {
+            options.set_optimization_level(level);
+        }
+        if settings.debug_info == Some(true) {
+            options.set_generate_debug_info();
+        }
+        let include_dirs: Vec<PathBuf> = settings
+            .include_dirs
+            .iter()
+            .map(|dir| self.root.join(dir))
+            .collect();
+        options.set_include_callback(move |requested, include_type, requesting, _| {
+            let resolved = resolve_include(&include_dirs, requested, include_type, requesting);
+            if let Ok(ref include) = resolved {
+                read.borrow_mut()
+                    .insert(PathBuf::from(&include.resolved_name));
+            }
+            resolved
+        });
+        Ok(options)
+    }
+}
+
+/// Returns the name of the index constant of the output `path`: its
+/// letters and digits in upper case, with anything else replaced by `_`.
+fn constant_name(path: &str) -> String {
+    let mut name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Resolves `#include "..."` against the directory of the including file, then
+/// against the include directories; `#include <...>` only against the latter.
+fn resolve_include(
+    include_dirs: &[PathBuf],
+    requested: &str,
+    include_type: IncludeType,
+    requesting: &str,
+) -> crate::IncludeCallbackResult {
+    let relative_dir = match include_type {
+        IncludeType::Relative => Path::new(requesting).parent().map(Path::to_path_buf),
+        IncludeType::Standard => None,
+    };
+    for dir in relative_dir.iter().chain(include_dirs) {
+        let path = dir.join(requested);
+        if let Ok(content) = fs::read_to_string(&path) {
+            return Ok(ResolvedInclude {
+                resolved_name: path.to_string_lossy().into_owned(),
+                content,
+            });
+        }
+    }
+    Err(format!("cannot find or open include file '{requested}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        output_dir = "out"
+
+        [defaults]
+        include_dirs = ["include"]
+        defines = { A = "1" }
+        target_env = "vulkan1.2"
+        optimization = "performance"
+
+        [[shader]]
+        source = "shaders/tri.vert"
+
+        [[shader]]
+        source = "shaders/lit.hlsl"
+        stage = "frag"
+        entry_point = "PSMain"
+        include_dirs = ["hlsl"]
+        defines = { A = "2", B = "3" }
+        optimization = "zero"
+        output = "lit.spv"
+    "#;
+
+    #[test]
+    fn test_manifest_parse() {
+        let manifest = Manifest::parse(MANIFEST, Path::new("proj")).unwrap();
+        assert_eq!(2, manifest.shaders.len());
+        assert_eq!(Some(EnvVersion::Vulkan1_2), manifest.defaults.target_env);
+        let lit = &manifest.shaders[1];
+        assert_eq!(Some(ShaderKind::Fragment), lit.stage);
+
+        let settings = manifest.defaults.merge(&lit.settings);
+        assert_eq!(
+            vec![PathBuf::from("include"), PathBuf::from("hlsl")],
+            settings.include_dirs
+        );
+        assert_eq!(Some("2"), settings.defines.get("A").map(String::as_str));
+        assert_eq!(Some("3"), settings.defines.get("B").map(String::as_str));
+        assert_eq!(Some(OptimizationLevel::Zero), settings.optimization);
+        assert_eq!(Some(EnvVersion::Vulkan1_2), settings.target_env);
+        assert_eq!(Some("PSMain".to_string()), settings.entry_point);
+    }
+
+    #[test]
+    fn test_manifest_output_layout() {
+        let manifest = Manifest::parse(MANIFEST, Path::new("proj")).unwrap();
+        assert_eq!(
+            Path::new("proj/out/shaders/tri.vert.spv"),
+            manifest.output_path(&manifest.shaders[0])
+        );
+        assert_eq!(
+            Path::new("proj/out/lit.spv"),
+            manifest.output_path(&manifest.shaders[1])
+        );
+        assert_eq!(Path::new("proj/out/.cache"), manifest.cache_path());
+    }
+
+    #[test]
+    fn test_manifest_naming() {
+        let mut manifest = Manifest::parse(MANIFEST, Path::new("proj")).unwrap();
+        manifest
+            .defaults
+            .defines
+            .insert("B".to_string(), "2".to_string());
+        manifest.shaders[0]
+            .settings
+            .defines
+            .insert("A".to_string(), "1".to_string());
+        let copy = manifest.clone();
+        manifest.set_naming(|source, stage, defines| {
+            let stem = source.file_stem().unwrap().to_string_lossy();
+            format!("{}/{}-{}.spv", stage, stem, defines).into()
+        });
+        assert_ne!(copy, manifest);
+        assert_eq!(manifest, manifest.clone());
+        assert_eq!(
+            Path::new("proj/out/vertex/tri-A=1,B=2.spv"),
+            manifest.output_path(&manifest.shaders[0])
+        );
+        // An explicit output wins over the callback.
+        assert_eq!(
+            Path::new("proj/out/lit.spv"),
+            manifest.output_path(&manifest.shaders[1])
+        );
+    }
+
+    #[test]
+    fn test_manifest_write_index() {
+        assert_eq!(
+            "SHADERS_TRI_VERT_SPV",
+            constant_name("shaders/tri.vert.spv")
+        );
+        assert_eq!("_2D_SPV", constant_name("2d.spv"));
+
+        let dir = std::env::temp_dir().join(format!("shaderc-index-{}", std::process::id()));
+        let manifest = Manifest::parse(MANIFEST, &dir).unwrap();
+        for shader in &manifest.shaders {
+            let output = manifest.output_path(shader);
+            fs::create_dir_all(output.parent().unwrap()).unwrap();
+            fs::write(output, [3, 2, 0x23, 7]).unwrap();
+        }
+        let index_path = dir.join("shaders.rs");
+        manifest.write_index(&index_path).unwrap();
+        let index = fs::read_to_string(&index_path).unwrap();
+        assert!(index.contains(
+            "pub const SHADERS_TRI_VERT_SPV: &::shaderc::util::SpirvBytes<[u8]> =\n    \
+             &::shaderc::util::SpirvBytes::new(*include_bytes!("
+        ));
+        assert!(index.contains("pub const LIT_SPV: &::shaderc::util::SpirvBytes<[u8]>"));
+
+        let mut clash = manifest.clone();
+        clash.shaders[0].output = Some(PathBuf::from("lit.spv"));
+        assert!(matches!(
+            clash.write_index(&index_path),
+            Err(ProjectError::Manifest(..))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_report_inputs() {
+        let dir = std::env::temp_dir().join(format!("shaderc-inputs-{}", std::process::id()));
+        fs::create_dir_all(dir.join("include")).unwrap();
+        fs::write(dir.join("include/common.h"), "#define ONE 1\n").unwrap();
+        fs::write(
+            dir.join("a.vert"),
+            "#version 450\n#include <common.h>\nvoid main() { int x = ONE; }\n",
+        )
+        .unwrap();
+        let manifest =
+            "[defaults]\ninclude_dirs = [\"include\"]\n[[shader]]\nsource = \"a.vert\"\n";
+        let report = Manifest::parse(manifest, &dir).unwrap().build().unwrap();
+        assert_eq!(
+            vec![dir.join("a.vert"), dir.join("include").join("common.h")],
+            report.inputs
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_manifest_errors() {
+        assert!(Manifest::parse("output = \"out\"", Path::new("")).is_err());
+        assert!(Manifest::parse("[[shader]]\nstage = \"vert\"", Path::new("")).is_err());
+        assert!(Manifest::parse(
+            "[[shader]]\nsource = \"a\"\nstage = \"pixel\"",
+            Path::new("")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_stage_from_extension() {
+        assert_eq!(Some(ShaderKind::Vertex), stage_from_extension("a/b.vert"));
+        assert_eq!(
+            Some(ShaderKind::Compute),
+            stage_from_extension("a/b.comp.hlsl")
+        );
+        assert_eq!(None, stage_from_extension("a/b.glsl"));
+    }
+}
@@ -0,0 +1,573 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reflection of the entry points and resources of SPIR-V modules.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::spirv_module::{
+    decoration, literal_string, op, storage_class, ModuleError, SpirvModule,
+};
+use crate::ShaderKind;
+
+/// The entry points and resources of a `SpirvModule`.
+///
+/// ```
+/// # use shaderc::SpirvModule;
+/// # fn reflect(module: &SpirvModule) {
+/// let reflection = module.reflect().unwrap();
+/// for binding in reflection.descriptor_bindings() {
+///     println!("set {} binding {}: {}", binding.set, binding.binding, binding.descriptor_type);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleReflection {
+    entry_points: Vec<EntryPoint>,
+    descriptor_bindings: Vec<DescriptorBinding>,
+    combined_image_samplers: Vec<CombinedImageSampler>,
+    inputs: Vec<StageVariable>,
+    outputs: Vec<StageVariable>,
+}
+
+/// An entry point of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub name: String,
+    /// The SPIR-V execution model of the entry point.
+    pub execution_model: u32,
+    /// The shader kind compiling to the execution model, if any.
+    pub shader_kind: Option<ShaderKind>,
+}
+
+/// A resource bound to a descriptor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    /// The name of the variable, or of its block type for anonymous blocks.
+    pub name: Option<String>,
+    pub set: u32,
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    /// The number of descriptors, more than one for arrays of resources, and
+    /// zero for runtime-sized arrays.
+    pub count: u32,
+}
+
+/// An input or output variable of the interface of a stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StageVariable {
+    pub name: Option<String>,
+    pub location: Option<u32>,
+    pub component: u32,
+    /// Whether the variable is a built-in, like `gl_Position`, or a block of
+    /// built-ins, like `gl_PerVertex`.
+    pub builtin: bool,
+    /// Whether the variable is per patch rather than per vertex, in
+    /// tessellation stages.
+    pub patch: bool,
+    /// The type of the variable in GLSL syntax, like `vec4` or `vec3[3]`.
+    /// Inputs of tessellation and geometry shaders and outputs of
+    /// tessellation control and mesh shaders have an array dimension for
+    /// the vertices.
+    pub type_name: String,
+}
+
+/// A texture sampled with a sampler, as returned by
+/// `ModuleReflection::combined_image_samplers`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CombinedImageSampler {
+    /// The name of the texture.
+    pub image: Option<String>,
+    /// The name of the sampler, or `None` when the compiler fused them into
+    /// a combined image sampler and removed the sampler.
+    pub sampler: Option<String>,
+    /// The set and binding of the combined image sampler, or of the texture
+    /// when the two are bound separately.
+    pub set: u32,
+    pub binding: u32,
+    /// The set and binding of the sampler when it is bound separately.
+    pub sampler_binding: Option<(u32, u32)>,
+}
+
+/// Type of the descriptor a resource is bound to, as in Vulkan's
+/// `VkDescriptorType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DescriptorType {
+    Sampler,
+    CombinedImageSampler,
+    SampledImage,
+    StorageImage,
+    UniformTexelBuffer,
+    StorageTexelBuffer,
+    UniformBuffer,
+    StorageBuffer,
+    InputAttachment,
+    AccelerationStructure,
+}
+
+impl DescriptorType {
+    /// Returns the name of this descriptor type, like `uniform-buffer`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DescriptorType::Sampler => "sampler",
+            DescriptorType::CombinedImageSampler => "combined-image-sampler",
+            DescriptorType::SampledImage => "sampled-image",
+            DescriptorType::StorageImage => "storage-image",
+            DescriptorType::UniformTexelBuffer => "uniform-texel-buffer",
+            DescriptorType::StorageTexelBuffer => "storage-texel-buffer",
+            DescriptorType::UniformBuffer => "uniform-buffer",
+            DescriptorType::StorageBuffer => "storage-buffer",
+            DescriptorType::InputAttachment => "input-attachment",
+            DescriptorType::AccelerationStructure => "acceleration-structure",
+        }
+    }
+}
+
+impl fmt::Display for DescriptorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl ModuleReflection {
+    /// Returns the entry points, in the order they are declared.
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    /// Returns the resources bound to descriptors, sorted by set and
+    /// binding.
+    pub fn descriptor_bindings(&self) -> &[DescriptorBinding] {
+        &self.descriptor_bindings
+    }
+
+    /// Returns the textures and the samplers they are sampled with, sorted
+    /// by set and binding.
+    ///
+    /// With `CompileOptions::set_auto_combined_image_sampler`, the compiler
+    /// fuses textures and samplers into combined image samplers bound at the
+    /// binding of the texture, and the sampler names no longer appear in the
+    /// module. Textures and samplers kept separate are paired where the
+    /// shader combines them, as with `sampler2D(tex, samp)` in GLSL, which
+    /// is what backends emulating separate samplers need to know.
+    pub fn combined_image_samplers(&self) -> &[CombinedImageSampler] {
+        &self.combined_image_samplers
+    }
+
+    /// Returns the input variables of the module, sorted by location, with
+    /// built-ins last.
+    pub fn inputs(&self) -> &[StageVariable] {
+        &self.inputs
+    }
+
+    /// Returns the output variables of the module, sorted like `inputs`.
+    pub fn outputs(&self) -> &[StageVariable] {
+        &self.outputs
+    }
+}
+
+/// Returns the shader kind compiling to the SPIR-V execution model `model`.
+pub(crate) fn shader_kind(model: u32) -> Option<ShaderKind> {
+    match model {
+        0 => Some(ShaderKind::Vertex),
+        1 => Some(ShaderKind::TessControl),
+        2 => Some(ShaderKind::TessEvaluation),
+        3 => Some(ShaderKind::Geometry),
+        4 => Some(ShaderKind::Fragment),
+        5 => Some(ShaderKind::Compute),
+        5267 | 5364 => Some(ShaderKind::Task),
+        5268 | 5365 => Some(ShaderKind::Mesh),
+        5313 => Some(ShaderKind::RayGeneration),
+        5314 => Some(ShaderKind::Intersection),
+        5315 => Some(ShaderKind::AnyHit),
+        5316 => Some(ShaderKind::ClosestHit),
+        5317 => Some(ShaderKind::Miss),
+        5318 => Some(ShaderKind::Callable),
+        _ => None,
+    }
+}
+
+impl SpirvModule {
+    /// Returns the entry points and descriptor bindings of the module.
+    ///
+    /// Fails with `ModuleError::MissingOperands` if an array type of a
+    /// descriptor lacks its element type or length.
+    pub fn reflect(&self) -> Result<ModuleReflection, ModuleError> {
+        let mut entry_points = Vec::new();
+        let mut names = HashMap::new();
+        let mut decorations: HashMap<u32, Vec<(u32, u32)>> = HashMap::new();
+        let mut variables = Vec::new();
+        let mut pointers = HashMap::new();
+        // Loads and access chains, mapped to the pointer they read through,
+        // and the image and sampler of each OpSampledImage.
+        let mut sources = HashMap::new();
+        let mut sampled_images = Vec::new();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            match inst.opcode() {
+                op::ENTRY_POINT if operands.len() >= 3 => entry_points.push(EntryPoint {
+                    name: literal_string(&operands[2..]).0,
+                    execution_model: operands[0],
+                    shader_kind: shader_kind(operands[0]),
+                }),
+                op::NAME if !operands.is_empty() => {
+                    names.insert(operands[0], literal_string(&operands[1..]).0);
+                }
+                op::DECORATE if operands.len() >= 2 => {
+                    let value = operands.get(2).copied().unwrap_or(0);
+                    decorations
+                        .entry(operands[0])
+                        .or_default()
+                        .push((operands[1], value));
+                }
+                op::TYPE_POINTER if operands.len() >= 3 => {
+                    pointers.insert(operands[0], operands[2]);
+                }
+                op::VARIABLE if operands.len() >= 3 => {
+                    variables.push((operands[1], operands[0], operands[2]));
+                }
+                op::LOAD | op::ACCESS_CHAIN | op::IN_BOUNDS_ACCESS_CHAIN | op::COPY_OBJECT
+                    if operands.len() >= 3 =>
+                {
+                    sources.insert(operands[1], operands[2]);
+                }
+                op::SAMPLED_IMAGE if operands.len() >= 4 => {
+                    sampled_images.push((operands[2], operands[3]));
+                }
+                _ => {}
+            }
+        }
+
+        let info = self.type_info();
+        let decorated = |id: u32, decoration: u32| {
+            decorations
+                .get(&id)
+                .and_then(|d| d.iter().find(|d| d.0 == decoration))
+                .map(|d| d.1)
+        };
+        let mut descriptor_bindings = Vec::new();
+        let mut variable_bindings = HashMap::new();
+        for (id, pointer_type, storage) in variables {
+            let binding = match decorated(id, decoration::BINDING) {
+                Some(binding) => binding,
+                None => continue,
+            };
+            let mut type_id = pointers.get(&pointer_type).copied().unwrap_or(0);
+            let mut count = 1;
+            if let Some(array) = info.get(type_id) {
+                let operands = array.operands();
+                let missing = || ModuleError::MissingOperands(array.offset());
+                if array.opcode() == op::TYPE_ARRAY {
+                    count = info
+                        .constant(*operands.get(2).ok_or_else(missing)?)
+                        .unwrap_or(1);
+                    type_id = *operands.get(1).ok_or_else(missing)?;
+                } else if array.opcode() == op::TYPE_RUNTIME_ARRAY {
+                    count = 0;
+                    type_id = *operands.get(1).ok_or_else(missing)?;
+                }
+            }
+            let descriptor_type = match (storage, info.get(type_id)) {
+                (storage_class::STORAGE_BUFFER, _) => DescriptorType::StorageBuffer,
+                (storage_class::UNIFORM, _)
+                    if decorated(type_id, decoration::BUFFER_BLOCK).is_some() =>
+                {
+                    DescriptorType::StorageBuffer
+                }
+                (storage_class::UNIFORM, _) => DescriptorType::UniformBuffer,
+                (storage_class::UNIFORM_CONSTANT, Some(ty)) => match ty.opcode() {
+                    op::TYPE_SAMPLER => DescriptorType::Sampler,
+                    op::TYPE_SAMPLED_IMAGE => DescriptorType::CombinedImageSampler,
+                    op::TYPE_ACCELERATION_STRUCTURE => DescriptorType::AccelerationStructure,
+                    op::TYPE_IMAGE => {
+                        // Operands: result, sampled type, dim, depth, arrayed,
+                        // multisampled, sampled, format.
+                        let operands = ty.operands();
+                        let dim = operands.get(2).copied().unwrap_or(0);
+                        let storage = operands.get(6) == Some(&2);
+                        match (dim, storage) {
+                            (5, false) => DescriptorType::UniformTexelBuffer,
+                            (5, true) => DescriptorType::StorageTexelBuffer,
+                            (6, _) => DescriptorType::InputAttachment,
+                            (_, false) => DescriptorType::SampledImage,
+                            (_, true) => DescriptorType::StorageImage,
+                        }
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let name = [id, type_id]
+                .iter()
+                .filter_map(|id| names.get(id))
+                .find(|name| !name.is_empty())
+                .cloned();
+            variable_bindings.insert(id, descriptor_bindings.len());
+            descriptor_bindings.push(DescriptorBinding {
+                name,
+                set: decorated(id, decoration::DESCRIPTOR_SET).unwrap_or(0),
+                binding,
+                descriptor_type,
+                count,
+            });
+        }
+
+        let mut combined_image_samplers: Vec<CombinedImageSampler> = descriptor_bindings
+            .iter()
+            .filter(|b| b.descriptor_type == DescriptorType::CombinedImageSampler)
+            .map(|b| CombinedImageSampler {
+                image: b.name.clone(),
+                sampler: None,
+                set: b.set,
+                binding: b.binding,
+                sampler_binding: None,
+            })
+            .collect();
+        let resource = |mut id: u32| {
+            // Bounded, as a malformed module may have cycles.
+            for _ in 0..sources.len() + 1 {
+                if let Some(&index) = variable_bindings.get(&id) {
+                    return Some(&descriptor_bindings[index]);
+                }
+                id = *sources.get(&id)?;
+            }
+            None
+        };
+        for (image, sampler) in sampled_images {
+            if let (Some(image), Some(sampler)) = (resource(image), resource(sampler)) {
+                let combined = CombinedImageSampler {
+                    image: image.name.clone(),
+                    sampler: sampler.name.clone(),
+                    set: image.set,
+                    binding: image.binding,
+                    sampler_binding: Some((sampler.set, sampler.binding)),
+                };
+                if !combined_image_samplers.contains(&combined) {
+                    combined_image_samplers.push(combined);
+                }
+            }
+        }
+        combined_image_samplers.sort_by_key(|c| (c.set, c.binding, c.sampler_binding));
+        descriptor_bindings.sort_by_key(|b| (b.set, b.binding));
+
+        let (mut inputs, mut outputs) = (Vec::new(), Vec::new());
+        for variable in self.interface_variables() {
+            let stage_variable = StageVariable {
+                name: variable.name,
+                location: variable.location,
+                component: variable.component,
+                builtin: variable.builtin,
+                patch: variable.patch,
+                type_name: info.describe(variable.type_id),
+            };
+            match variable.storage_class {
+                storage_class::INPUT => inputs.push(stage_variable),
+                _ => outputs.push(stage_variable),
+            }
+        }
+        for variables in [&mut inputs, &mut outputs] {
+            variables.sort_by_key(|v| (v.builtin, v.location.is_none(), v.location, v.component));
+        }
+        Ok(ModuleReflection {
+            entry_points,
+            descriptor_bindings,
+            combined_image_samplers,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::encode_string;
+    use crate::spirv_module::tests::{inst, module};
+
+    #[test]
+    fn test_reflect() {
+        let module = module(&[
+            inst(
+                op::ENTRY_POINT,
+                &[[4, 1].as_ref(), &encode_string("main")].concat(),
+            ),
+            inst(op::NAME, &[[10].as_ref(), &encode_string("tex")].concat()),
+            inst(
+                op::NAME,
+                &[[21].as_ref(), &encode_string("Globals")].concat(),
+            ),
+            inst(op::NAME, &[[20].as_ref(), &encode_string("")].concat()),
+            inst(op::DECORATE, &[10, decoration::DESCRIPTOR_SET, 1]),
+            inst(op::DECORATE, &[10, decoration::BINDING, 2]),
+            inst(op::DECORATE, &[20, decoration::BINDING, 0]),
+            inst(op::DECORATE, &[30, decoration::BINDING, 1]),
+            inst(op::TYPE_FLOAT, &[1, 32]),
+            inst(op::TYPE_INT, &[2, 32, 0]),
+            inst(op::CONSTANT, &[2, 3, 4]),
+            inst(op::TYPE_IMAGE, &[5, 1, 1, 0, 0, 0, 1, 0]),
+            inst(op::TYPE_SAMPLED_IMAGE, &[6, 5]),
+            inst(op::TYPE_ARRAY, &[7, 6, 3]),
+            inst(op::TYPE_POINTER, &[8, storage_class::UNIFORM_CONSTANT, 7]),
+            inst(op::VARIABLE, &[8, 10, storage_class::UNIFORM_CONSTANT]),
+            inst(op::TYPE_STRUCT, &[21, 1]),
+            inst(op::TYPE_POINTER, &[22, storage_class::UNIFORM, 21]),
+            inst(op::VARIABLE, &[22, 20, storage_class::UNIFORM]),
+            inst(op::TYPE_IMAGE, &[23, 1, 1, 0, 0, 0, 2, 1]),
+            inst(op::TYPE_POINTER, &[24, storage_class::UNIFORM_CONSTANT, 23]),
+            inst(op::VARIABLE, &[24, 30, storage_class::UNIFORM_CONSTANT]),
+        ]);
+        let reflection = module.reflect().unwrap();
+        assert_eq!(
+            &[EntryPoint {
+                name: "main".to_string(),
+                execution_model: 4,
+                shader_kind: Some(ShaderKind::Fragment),
+            }],
+            reflection.entry_points()
+        );
+        assert_eq!(
+            &[
+                DescriptorBinding {
+                    name: Some("Globals".to_string()),
+                    set: 0,
+                    binding: 0,
+                    descriptor_type: DescriptorType::UniformBuffer,
+                    count: 1,
+                },
+                DescriptorBinding {
+                    name: None,
+                    set: 0,
+                    binding: 1,
+                    descriptor_type: DescriptorType::StorageImage,
+                    count: 1,
+                },
+                DescriptorBinding {
+                    name: Some("tex".to_string()),
+                    set: 1,
+                    binding: 2,
+                    descriptor_type: DescriptorType::CombinedImageSampler,
+                    count: 4,
+                },
+            ],
+            reflection.descriptor_bindings()
+        );
+        assert_eq!("uniform-buffer", DescriptorType::UniformBuffer.to_string());
+        assert_eq!(
+            &[CombinedImageSampler {
+                image: Some("tex".to_string()),
+                sampler: None,
+                set: 1,
+                binding: 2,
+                sampler_binding: None,
+            }],
+            reflection.combined_image_samplers()
+        );
+    }
+
+    #[test]
+    fn test_reflect_missing_operands() {
+        for array in [
+            inst(op::TYPE_ARRAY, &[7, 1]),
+            inst(op::TYPE_RUNTIME_ARRAY, &[7]),
+        ] {
+            let module = module(&[
+                inst(op::DECORATE, &[10, decoration::BINDING, 0]),
+                inst(op::TYPE_FLOAT, &[1, 32]),
+                array,
+                inst(op::TYPE_POINTER, &[8, storage_class::UNIFORM_CONSTANT, 7]),
+                inst(op::VARIABLE, &[8, 10, storage_class::UNIFORM_CONSTANT]),
+            ]);
+            let offset = module.instructions().nth(2).unwrap().offset();
+            assert_eq!(Err(ModuleError::MissingOperands(offset)), module.reflect());
+        }
+    }
+
+    #[test]
+    fn test_reflect_stage_variables() {
+        let module = module(&[
+            inst(op::NAME, &[[10].as_ref(), &encode_string("color")].concat()),
+            inst(op::DECORATE, &[10, decoration::LOCATION, 1]),
+            inst(op::DECORATE, &[11, decoration::LOCATION, 0]),
+            inst(op::DECORATE, &[11, decoration::COMPONENT, 2]),
+            inst(op::DECORATE, &[12, decoration::BUILT_IN, 0]),
+            inst(op::DECORATE, &[13, decoration::LOCATION, 0]),
+            inst(op::TYPE_FLOAT, &[1, 32]),
+            inst(op::TYPE_VECTOR, &[2, 1, 4]),
+            inst(op::TYPE_POINTER, &[3, storage_class::INPUT, 2]),
+            inst(op::TYPE_POINTER, &[4, storage_class::INPUT, 1]),
+            inst(op::TYPE_POINTER, &[5, storage_class::OUTPUT, 2]),
+            inst(op::VARIABLE, &[3, 10, storage_class::INPUT]),
+            inst(op::VARIABLE, &[4, 11, storage_class::INPUT]),
+            inst(op::VARIABLE, &[3, 12, storage_class::INPUT]),
+            inst(op::VARIABLE, &[5, 13, storage_class::OUTPUT]),
+        ]);
+        let reflection = module.reflect().unwrap();
+        let variable =
+            |name: Option<&str>, location, component, builtin, type_name: &str| StageVariable {
+                name: name.map(str::to_string),
+                location,
+                component,
+                builtin,
+                patch: false,
+                type_name: type_name.to_string(),
+            };
+        assert_eq!(
+            &[
+                variable(None, Some(0), 2, false, "float"),
+                variable(Some("color"), Some(1), 0, false, "vec4"),
+                variable(None, None, 0, true, "vec4"),
+            ],
+            reflection.inputs()
+        );
+        assert_eq!(
+            &[variable(None, Some(0), 0, false, "vec4")],
+            reflection.outputs()
+        );
+    }
+
+    #[test]
+    fn test_reflect_separate_image_samplers() {
+        let module = module(&[
+            inst(op::NAME, &[[10].as_ref(), &encode_string("tex")].concat()),
+            inst(op::NAME, &[[11].as_ref(), &encode_string("samp")].concat()),
+            inst(op::DECORATE, &[10, decoration::BINDING, 0]),
+            inst(op::DECORATE, &[11, decoration::BINDING, 1]),
+            inst(op::TYPE_FLOAT, &[1, 32]),
+            inst(op::TYPE_IMAGE, &[2, 1, 1, 0, 1, 0, 1, 0]),
+            inst(op::TYPE_ARRAY, &[3, 2, 20]),
+            inst(op::TYPE_POINTER, &[4, storage_class::UNIFORM_CONSTANT, 3]),
+            inst(op::VARIABLE, &[4, 10, storage_class::UNIFORM_CONSTANT]),
+            inst(op::TYPE_SAMPLER, &[5]),
+            inst(op::TYPE_POINTER, &[6, storage_class::UNIFORM_CONSTANT, 5]),
+            inst(op::VARIABLE, &[6, 11, storage_class::UNIFORM_CONSTANT]),
+            inst(op::TYPE_SAMPLED_IMAGE, &[7, 2]),
+            inst(op::ACCESS_CHAIN, &[8, 30, 10, 21]),
+            inst(op::LOAD, &[2, 31, 30]),
+            inst(op::LOAD, &[5, 32, 11]),
+            inst(op::SAMPLED_IMAGE, &[7, 33, 31, 32]),
+            inst(op::SAMPLED_IMAGE, &[7, 34, 31, 32]),
+        ]);
+        assert_eq!(
+            &[CombinedImageSampler {
+                image: Some("tex".to_string()),
+                sampler: Some("samp".to_string()),
+                set: 0,
+                binding: 0,
+                sampler_binding: Some((0, 1)),
+            }],
+            module.reflect().unwrap().combined_image_samplers()
+        );
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tables mapping HLSL registers to descriptor sets and bindings.
+
+use std::{error, fmt};
+
+use crate::{CompileOptions, ShaderKind};
+
+/// Class of an HLSL register, given by the letter of its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegisterClass {
+    /// Constant buffers, `b` registers.
+    ConstantBuffer,
+    /// Shader resource views, `t` registers.
+    Texture,
+    /// Samplers, `s` registers.
+    Sampler,
+    /// Unordered access views, `u` registers.
+    UnorderedAccess,
+}
+
+impl RegisterClass {
+    /// Returns the letter naming registers of this class.
+    pub fn letter(self) -> char {
+        match self {
+            RegisterClass::ConstantBuffer => 'b',
+            RegisterClass::Texture => 't',
+            RegisterClass::Sampler => 's',
+            RegisterClass::UnorderedAccess => 'u',
+        }
+    }
+}
+
+/// An entry of an `HlslRegisterMap`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterMapping {
+    /// The stage the entry applies to, or `None` for all stages.
+    pub stage: Option<ShaderKind>,
+    pub class: RegisterClass,
+    pub register: u32,
+    pub set: u32,
+    pub binding: u32,
+}
+
+impl fmt::Display for RegisterMapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{} -> set {} binding {}",
+            self.class.letter(),
+            self.register,
+            self.set,
+            self.binding
+        )?;
+        if let Some(stage) = self.stage {
+            write!(f, " in {stage} shaders")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error for an invalid `HlslRegisterMap`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegisterMapError {
+    /// The register is mapped twice to the same set and binding.
+    Duplicate(RegisterMapping),
+    /// The register is mapped to two different sets or bindings.
+    Conflict(RegisterMapping, RegisterMapping),
+    /// Two registers are mapped to the same set and binding in a stage.
+    SharedBinding(RegisterMapping, RegisterMapping),
+}
+
+impl fmt::Display for RegisterMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegisterMapError::Duplicate(ref mapping) => {
+                write!(f, "duplicate HLSL register mapping {mapping}")
+            }
+            RegisterMapError::Conflict(ref first, ref second) => {
+                write!(f, "conflicting HLSL register mappings {first} and {second}")
+            }
+            RegisterMapError::SharedBinding(ref first, ref second) => {
+                write!(
+                    f,
+                    "HLSL register mappings {first} and {second} share a binding"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for RegisterMapError {}
+
+/// A table of HLSL registers and the descriptor sets and bindings they map
+/// to, applied with `CompileOptions::set_hlsl_register_map`.
+///
+/// Entries for a single stage take precedence over entries for all stages.
+///
+/// ```
+/// use shaderc::{HlslRegisterMap, RegisterClass, ShaderKind};
+///
+/// let map = HlslRegisterMap::new()
+///     .map(RegisterClass::ConstantBuffer, 0, 0, 0)
+///     .map(RegisterClass::Texture, 0, 1, 0)
+///     .map_for_stage(ShaderKind::Fragment, RegisterClass::Sampler, 0, 1, 1);
+/// assert!(map.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HlslRegisterMap {
+    entries: Vec<RegisterMapping>,
+}
+
+impl HlslRegisterMap {
+    /// Creates an empty map.
+    pub fn new() -> HlslRegisterMap {
+        HlslRegisterMap::default()
+    }
+
+    /// Maps register `register` of `class` to `set` and `binding` in all
+    /// stages.
+    pub fn map(self, class: RegisterClass, register: u32, set: u32, binding: u32) -> Self {
+        self.entry(None, class, register, set, binding)
+    }
+
+    /// Like `map`, but only for the given shader stage.
+    pub fn map_for_stage(
+        self,
+        stage: ShaderKind,
+        class: RegisterClass,
+        register: u32,
+        set: u32,
+        binding: u32,
+    ) -> Self {
+        self.entry(Some(stage), class, register, set, binding)
+    }
+
+    fn entry(
+        mut self,
+        stage: Option<ShaderKind>,
+        class: RegisterClass,
+        register: u32,
+        set: u32,
+        binding: u32,
+    ) -> Self {
+        self.entries.push(RegisterMapping {
+            stage,
+            class,
+            register,
+            set,
+            binding,
+        });
+        self
+    }
+
+    /// Returns the entries, in the order they were added.
+    pub fn entries(&self) -> &[RegisterMapping] {
+        &self.entries
+    }
+
+    /// Checks that no register is mapped twice for the same stages, and
+    /// that no two registers share a set and binding in any stage.
+    pub fn validate(&self) -> Result<(), RegisterMapError> {
+        for (i, first) in self.entries.iter().enumerate() {
+            let second = self.entries[i + 1..].iter().find(|second| {
+                (first.stage, first.class, first.register)
+                    == (second.stage, second.class, second.register)
+            });
+            match second {
+                Some(second) if (first.set, first.binding) == (second.set, second.binding) => {
+                    return Err(RegisterMapError::Duplicate(*second))
+                }
+                Some(second) => return Err(RegisterMapError::Conflict(*first, *second)),
+                None => {}
+            }
+        }
+        let mut stages = vec![None];
+        for entry in &self.entries {
+            if !stages.contains(&entry.stage) {
+                stages.push(entry.stage);
+            }
+        }
+        for stage in stages {
+            let mappings = self.stage_mappings(stage);
+            for (i, first) in mappings.iter().enumerate() {
+                let second = mappings[i + 1..]
+                    .iter()
+                    .find(|second| (first.set, first.binding) == (second.set, second.binding));
+                if let Some(second) = second {
+                    return Err(RegisterMapError::SharedBinding(**first, **second));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the entries in effect for `stage`, or for stages without
+    /// entries of their own if `None`: the entries for all stages, except
+    /// for the registers `stage` maps itself, then the entries of `stage`.
+    fn stage_mappings(&self, stage: Option<ShaderKind>) -> Vec<&RegisterMapping> {
+        let own = |entry: &RegisterMapping| stage.is_some() && entry.stage == stage;
+        let global = self.entries.iter().filter(|entry| {
+            entry.stage.is_none()
+                && !self.entries.iter().any(|other| {
+                    own(other) && (other.class, other.register) == (entry.class, entry.register)
+                })
+        });
+        global
+            .chain(self.entries.iter().filter(|entry| own(entry)))
+            .collect()
+    }
+}
+
+impl<'a> CompileOptions<'a> {
+    /// Maps HLSL registers to descriptor sets and bindings as given by `map`.
+    ///
+    /// The map is validated first, and nothing is set if it is invalid. The
+    /// entries for all stages are set before the entries for single stages,
+    /// which thus take precedence whatever order they were added in.
+    pub fn set_hlsl_register_map(&mut self, map: &HlslRegisterMap) -> Result<(), RegisterMapError> {
+        map.validate()?;
+        let (global, staged): (Vec<&RegisterMapping>, Vec<_>) = map
+            .entries()
+            .iter()
+            .partition(|entry| entry.stage.is_none());
+        for entry in global.into_iter().chain(staged) {
+            let register = format!("{}{}", entry.class.letter(), entry.register);
+            let (set, binding) = (entry.set.to_string(), entry.binding.to_string());
+            match entry.stage {
+                Some(stage) => self
+                    .set_hlsl_register_set_and_binding_for_stage(stage, &register, &set, &binding),
+                None => self.set_hlsl_register_set_and_binding(&register, &set, &binding),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hlsl_register_map_validate() {
+        let map = HlslRegisterMap::new()
+            .map(RegisterClass::Texture, 0, 0, 1)
+            .map(RegisterClass::Sampler, 0, 0, 2)
+            .map_for_stage(ShaderKind::Vertex, RegisterClass::Texture, 0, 1, 1);
+        assert_eq!(Ok(()), map.validate());
+
+        let duplicate = map.clone().map(RegisterClass::Sampler, 0, 0, 2);
+        assert_eq!(
+            Err(RegisterMapError::Duplicate(duplicate.entries()[3])),
+            duplicate.validate()
+        );
+        let conflict = map.map(RegisterClass::Texture, 0, 0, 3);
+        let error = conflict.validate().unwrap_err();
+        assert_eq!(
+            RegisterMapError::Conflict(conflict.entries()[0], conflict.entries()[3]),
+            error
+        );
+        assert_eq!(
+            "conflicting HLSL register mappings t0 -> set 0 binding 1 and t0 -> set 0 binding 3",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_hlsl_register_map_shared_binding() {
+        let map = HlslRegisterMap::new()
+            .map(RegisterClass::Texture, 0, 0, 1)
+            .map(RegisterClass::Sampler, 0, 0, 2);
+        let shared = map.clone().map(RegisterClass::Texture, 1, 0, 2);
+        assert_eq!(
+            Err(RegisterMapError::SharedBinding(
+                shared.entries()[1],
+                shared.entries()[2]
+            )),
+            shared.validate()
+        );
+        let staged = map
+            .clone()
+            .map_for_stage(ShaderKind::Vertex, RegisterClass::Texture, 1, 0, 1);
+        assert_eq!(
+            Err(RegisterMapError::SharedBinding(
+                staged.entries()[0],
+                staged.entries()[2]
+            )),
+            staged.validate()
+        );
+        // The vertex stage moves t0 away, freeing its binding for t1.
+        let moved = staged.map_for_stage(ShaderKind::Vertex, RegisterClass::Texture, 0, 1, 1);
+        assert_eq!(Ok(()), moved.validate());
+        assert_eq!(
+            "HLSL register mappings t0 -> set 0 binding 1 and t1 -> set 0 binding 1 in vertex \
+             shaders share a binding",
+            RegisterMapError::SharedBinding(moved.entries()[0], moved.entries()[2]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_set_hlsl_register_map_order() {
+        let map = HlslRegisterMap::new()
+            .map_for_stage(ShaderKind::Fragment, RegisterClass::Texture, 0, 1, 0)
+            .map(RegisterClass::Texture, 0, 0, 0);
+        let mut options = CompileOptions::new().unwrap();
+        options.set_hlsl_register_map(&map).unwrap();
+        assert_eq!(
+            vec![
+                (None, "t0".to_string(), "0".to_string(), "0".to_string()),
+                (
+                    Some(ShaderKind::Fragment),
+                    "t0".to_string(),
+                    "1".to_string(),
+                    "0".to_string()
+                ),
+            ],
+            options.settings.hlsl_registers
+        );
+    }
+}
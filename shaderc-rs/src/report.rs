@@ -0,0 +1,276 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Size and instruction mix reports of compiled modules.
+
+use std::fmt;
+
+use crate::CompilationArtifact;
+
+/// Class of a SPIR-V instruction, following the grouping of the SPIR-V
+/// specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpcodeClass {
+    /// Capabilities, extensions, entry points and execution modes.
+    ModeSetting,
+    /// Names, source and line information.
+    Debug,
+    /// Decorations.
+    Annotation,
+    Type,
+    Constant,
+    Function,
+    /// Variables, loads, stores and access chains.
+    Memory,
+    Composite,
+    Conversion,
+    Arithmetic,
+    Bit,
+    /// Comparisons and logical operations.
+    Relational,
+    Derivative,
+    /// Image sampling, fetches, reads and writes.
+    Image,
+    Atomic,
+    Barrier,
+    /// Group and subgroup operations.
+    Group,
+    /// Branches, labels and merges.
+    ControlFlow,
+    /// Extended instruction set calls, like GLSL.std.450 functions.
+    ExtendedInstruction,
+    Other,
+}
+
+impl OpcodeClass {
+    /// Returns the class of `opcode`.
+    pub fn of(opcode: u16) -> OpcodeClass {
+        match opcode {
+            10 | 11 | 14..=17 | 331 => OpcodeClass::ModeSetting,
+            2..=8 | 317 | 330 => OpcodeClass::Debug,
+            71..=75 | 332 | 5632 | 5633 => OpcodeClass::Annotation,
+            19..=39 | 322 | 327 | 4456 | 4472 | 5341 | 5358 => OpcodeClass::Type,
+            41..=52 => OpcodeClass::Constant,
+            54..=57 => OpcodeClass::Function,
+            59..=70 => OpcodeClass::Memory,
+            77..=84 => OpcodeClass::Composite,
+            109..=126 => OpcodeClass::Conversion,
+            127..=152 => OpcodeClass::Arithmetic,
+            154..=191 => OpcodeClass::Relational,
+            194..=205 => OpcodeClass::Bit,
+            207..=215 => OpcodeClass::Derivative,
+            86..=107 | 305..=316 | 318 => OpcodeClass::Image,
+            227..=242 | 319 => OpcodeClass::Atomic,
+            224 | 225 => OpcodeClass::Barrier,
+            259..=271 | 333..=366 | 4421 | 4428..=4431 => OpcodeClass::Group,
+            245..=257 | 4416 | 5380 => OpcodeClass::ControlFlow,
+            12 => OpcodeClass::ExtendedInstruction,
+            _ => OpcodeClass::Other,
+        }
+    }
+
+    /// Returns the name of this class, like `arithmetic`.
+    pub fn name(self) -> &'static str {
+        match self {
+            OpcodeClass::ModeSetting => "mode-setting",
+            OpcodeClass::Debug => "debug",
+            OpcodeClass::Annotation => "annotation",
+            OpcodeClass::Type => "type",
+            OpcodeClass::Constant => "constant",
+            OpcodeClass::Function => "function",
+            OpcodeClass::Memory => "memory",
+            OpcodeClass::Composite => "composite",
+            OpcodeClass::Conversion => "conversion",
+            OpcodeClass::Arithmetic => "arithmetic",
+            OpcodeClass::Bit => "bit",
+            OpcodeClass::Relational => "relational",
+            OpcodeClass::Derivative => "derivative",
+            OpcodeClass::Image => "image",
+            OpcodeClass::Atomic => "atomic",
+            OpcodeClass::Barrier => "barrier",
+            OpcodeClass::Group => "group",
+            OpcodeClass::ControlFlow => "control-flow",
+            OpcodeClass::ExtendedInstruction => "extended-instruction",
+            OpcodeClass::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for OpcodeClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A first-order measure of the cost of a compiled module, as returned by
+/// `CompilationArtifact::report`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShaderReport {
+    /// The size of the binary in bytes.
+    pub size: usize,
+    pub instructions: usize,
+    pub functions: usize,
+    /// The number of instructions of each class with at least one, in the
+    /// order of `OpcodeClass`.
+    pub instructions_by_class: Vec<(OpcodeClass, usize)>,
+}
+
+impl ShaderReport {
+    /// Builds the report of the module made of `words`, counting the
+    /// instructions up to the first malformed one.
+    fn from_words(words: &[u32]) -> ShaderReport {
+        let mut counts = [0; OpcodeClass::Other as usize + 1];
+        let mut report = ShaderReport {
+            size: words.len() * 4,
+            ..ShaderReport::default()
+        };
+        // Skip the header.
+        let mut offset = 5;
+        while let Some(&first) = words.get(offset) {
+            let count = (first >> 16) as usize;
+            if count == 0 || offset + count > words.len() {
+                break;
+            }
+            let opcode = first as u16;
+            counts[OpcodeClass::of(opcode) as usize] += 1;
+            report.instructions += 1;
+            if opcode == 54 {
+                report.functions += 1;
+            }
+            offset += count;
+        }
+        report.instructions_by_class = ALL_CLASSES
+            .iter()
+            .map(|&class| (class, counts[class as usize]))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        report
+    }
+
+    /// Returns the number of instructions of `class`.
+    pub fn count(&self, class: OpcodeClass) -> usize {
+        self.instructions_by_class
+            .iter()
+            .find(|entry| entry.0 == class)
+            .map_or(0, |entry| entry.1)
+    }
+}
+
+const ALL_CLASSES: [OpcodeClass; OpcodeClass::Other as usize + 1] = [
+    OpcodeClass::ModeSetting,
+    OpcodeClass::Debug,
+    OpcodeClass::Annotation,
+    OpcodeClass::Type,
+    OpcodeClass::Constant,
+    OpcodeClass::Function,
+    OpcodeClass::Memory,
+    OpcodeClass::Composite,
+    OpcodeClass::Conversion,
+    OpcodeClass::Arithmetic,
+    OpcodeClass::Bit,
+    OpcodeClass::Relational,
+    OpcodeClass::Derivative,
+    OpcodeClass::Image,
+    OpcodeClass::Atomic,
+    OpcodeClass::Barrier,
+    OpcodeClass::Group,
+    OpcodeClass::ControlFlow,
+    OpcodeClass::ExtendedInstruction,
+    OpcodeClass::Other,
+];
+
+impl fmt::Display for ShaderReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} bytes, {} instructions, {} functions",
+            self.size, self.instructions, self.functions
+        )?;
+        for &(class, count) in &self.instructions_by_class {
+            writeln!(f, "  {class:<20} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CompilationArtifact {
+    /// Returns the size, instruction counts by class and function count of
+    /// the binary module.
+    ///
+    /// Comparing the reports of compilations at different optimization
+    /// levels shows what optimization saves:
+    ///
+    /// ```no_run
+    /// # use shaderc::{CompileOptions, Compiler, OptimizationLevel, ShaderKind};
+    /// # let source = "#version 450\nvoid main() {}";
+    /// let compiler = Compiler::new().unwrap();
+    /// let mut options = CompileOptions::new().unwrap();
+    /// let mut report = |level| {
+    ///     options.set_optimization_level(level);
+    ///     compiler
+    ///         .compile_into_spirv(source, ShaderKind::Fragment, "a.frag", "main", Some(&options))
+    ///         .unwrap()
+    ///         .report()
+    /// };
+    /// let (before, after) = (report(OptimizationLevel::Zero), report(OptimizationLevel::Performance));
+    /// println!("{} -> {} bytes", before.size, after.size);
+    /// print!("{}", after);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the compilation does not generate a
+    /// binary output.
+    pub fn report(&self) -> ShaderReport {
+        ShaderReport::from_words(self.as_binary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::tests::{inst, module};
+
+    #[test]
+    fn test_report() {
+        let module = module(&[
+            inst(17, &[1]),
+            inst(21, &[1, 32, 0]),
+            inst(54, &[2, 3, 0, 4]),
+            inst(248, &[5]),
+            inst(128, &[1, 6, 7, 7]),
+            inst(128, &[1, 8, 6, 6]),
+            inst(253, &[]),
+            inst(56, &[]),
+        ]);
+        let report = CompilationArtifact::from_binary(module.into_words()).report();
+        assert_eq!(8, report.instructions);
+        assert_eq!(1, report.functions);
+        assert_eq!(2, report.count(OpcodeClass::Arithmetic));
+        assert_eq!(0, report.count(OpcodeClass::Image));
+        assert_eq!(
+            vec![
+                (OpcodeClass::ModeSetting, 1),
+                (OpcodeClass::Type, 1),
+                (OpcodeClass::Function, 2),
+                (OpcodeClass::Arithmetic, 2),
+                (OpcodeClass::ControlFlow, 2),
+            ],
+            report.instructions_by_class
+        );
+        assert!(report
+            .to_string()
+            .starts_with("120 bytes, 8 instructions, 1 functions\n"));
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Self-contained reproducers of compilations, for bug reports.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::subprocess::stage_args;
+use crate::{diagnose_environment, CompileOptions, Compiler, ShaderKind};
+
+impl Compiler {
+    /// Writes a reproducer of compiling `source_text` with these arguments
+    /// into the directory `dir`, to attach to a bug report against shaderc
+    /// or glslang.
+    ///
+    /// The directory gets the source, named after `input_file_name`, its
+    /// preprocessed form `preprocessed.<ext>` with the includes resolved
+    /// inline, `command.sh` compiling the preprocessed source with glslc,
+    /// `result.txt` with the outcome of compiling, and `environment.txt` with
+    /// the report of `diagnose_environment`. Returns an error if the files
+    /// cannot be written; the compilation failing is recorded instead.
+    pub fn write_repro_case<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let file_name = Path::new(input_file_name)
+            .file_name()
+            .map_or("source".into(), |name| name.to_string_lossy());
+        fs::write(dir.join(&*file_name), source_text)?;
+
+        let preprocessed_name = match Path::new(&*file_name).extension() {
+            Some(ext) => format!("preprocessed.{}", ext.to_string_lossy()),
+            None => "preprocessed".to_string(),
+        };
+        match self.preprocess(
+            source_text,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        ) {
+            Ok(artifact) => fs::write(dir.join(&preprocessed_name), artifact.as_text())?,
+            Err(e) => fs::write(dir.join(&preprocessed_name), format!("// {e}\n"))?,
+        }
+
+        let explained = additional_options.map_or("glslc".to_string(), |o| o.explain());
+        let (command, notes) = match explained.split_once('\n') {
+            _ if explained.starts_with('#') => ("glslc", explained.as_str()),
+            Some((command, notes)) => (command, notes),
+            None => (explained.as_str(), ""),
+        };
+        let mut args = vec![command.to_string()];
+        args.extend(stage_args(shader_kind).unwrap_or_default());
+        args.push(format!("-fentry-point={entry_point_name}"));
+        args.extend([
+            "-c".to_string(),
+            preprocessed_name,
+            "-o out.spv".to_string(),
+        ]);
+        let mut script = format!("#!/bin/sh\n{}\n", args.join(" "));
+        if !notes.is_empty() {
+            script.push_str(notes);
+            script.push('\n');
+        }
+        fs::write(dir.join("command.sh"), script)?;
+
+        let result = match self.compile_into_spirv(
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        ) {
+            Ok(artifact) => format!(
+                "compiled to {} words\n{}",
+                artifact.len() / 4,
+                artifact.get_warning_messages()
+            ),
+            Err(e) => format!("{e}\n"),
+        };
+        fs::write(dir.join("result.txt"), result)?;
+        fs::write(
+            dir.join("environment.txt"),
+            diagnose_environment().to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_repro_case() {
+        let dir = std::env::temp_dir().join(format!("shaderc-repro-{}", std::process::id()));
+        let compiler = Compiler::new().unwrap();
+        let mut options = CompileOptions::new().unwrap();
+        options.add_macro_definition("X", Some("1"));
+        compiler
+            .write_repro_case(
+                &dir,
+                "#version 450\nvoid main() { int x = X + y; }",
+                ShaderKind::Vertex,
+                "shaders/a.vert",
+                "main",
+                Some(&options),
+            )
+            .unwrap();
+        let read = |name| fs::read_to_string(dir.join(name)).unwrap();
+        assert!(read("a.vert").contains("X + y"));
+        assert!(read("preprocessed.vert").contains("1 + y"));
+        assert!(read("command.sh")
+            .contains("glslc -DX=1 -fshader-stage=vert -fentry-point=main -c preprocessed.vert"));
+        assert!(read("result.txt").contains("'y' : undeclared identifier"));
+        assert!(read("environment.txt").contains("shaderc crate:"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,599 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust-side record of the settings applied to a `CompileOptions`.
+//!
+//! libshaderc offers no way to read settings back from an options object, so
+//! each setter of `CompileOptions` also records its arguments here. This
+//! shadow state is what lets options be translated to glslc flags, and be
+//! compared with `CompileOptions::diff`.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+#[cfg(feature = "spirv-tools")]
+use crate::OptPreset;
+use crate::{
+    CompileOptions, DiagnosticPolicy, EnvVersion, GlslProfile, Limit, OptimizationLevel,
+    ResourceKind, ShaderKind, SourceLanguage, SpirvVersion, TargetEnv,
+};
+
+/// A setting whose value differs between two `CompileOptions`, as returned by
+/// `CompileOptions::diff`.
+///
+/// A value of `None` means the setting was left at its default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingDiff {
+    /// The setting, like `target_env`, `limit MaxLights` or `macro NAME`.
+    pub setting: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl fmt::Display for SettingDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "(default)".into());
+        write!(
+            f,
+            "{}: {} != {}",
+            self.setting,
+            value(&self.left),
+            value(&self.right)
+        )
+    }
+}
+
+/// The settings applied to a `CompileOptions`, in the order they were set
+/// where that matters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Settings {
+    pub target_env: Option<(TargetEnv, u32)>,
+    pub target_spirv: Option<SpirvVersion>,
+    pub source_language: Option<SourceLanguage>,
+    pub forced_version_profile: Option<(u32, GlslProfile)>,
+    pub include_callback: bool,
+    pub limits: Vec<(Limit, i32)>,
+    pub auto_bind_uniforms: bool,
+    pub auto_combined_image_sampler: bool,
+    pub hlsl_io_mapping: bool,
+    pub hlsl_offsets: bool,
+    pub binding_bases: Vec<(Option<ShaderKind>, ResourceKind, u32)>,
+    pub hlsl_registers: Vec<(Option<ShaderKind>, String, String, String)>,
+    pub auto_map_locations: bool,
+    pub hlsl_functionality1: bool,
+    pub invert_y: bool,
+    pub nan_clamp: bool,
+    pub macros: Vec<(String, Option<String>)>,
+    pub optimization_level: Option<OptimizationLevel>,
+    #[cfg(feature = "spirv-tools")]
+    pub opt_preset: Option<OptPreset>,
+    pub generate_debug_info: bool,
+    pub suppress_warnings: bool,
+    pub warnings_as_errors: bool,
+    pub max_source_size: Option<usize>,
+    pub max_include_bytes: Option<usize>,
+    pub diagnostic_policy: Option<DiagnosticPolicy>,
+    pub rewrite_line_directives: bool,
+}
+
+impl Settings {
+    /// Records a limit, replacing any previous value.
+    pub fn set_limit(&mut self, limit: Limit, value: i32) {
+        self.limits.retain(|&(l, _)| l != limit);
+        self.limits.push((limit, value));
+    }
+
+    /// Records a macro definition, replacing any previous one of that name.
+    pub fn add_macro_definition(&mut self, name: &str, value: Option<&str>) {
+        let value = value.map(str::to_string);
+        match self.macros.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.macros.push((name.to_string(), value)),
+        }
+    }
+
+    /// Applies these settings to `options`, except for the include callback.
+    pub fn apply_to(&self, options: &mut CompileOptions) {
+        if let Some((env, version)) = self.target_env {
+            options.set_target_env(env, version);
+        }
+        if let Some(version) = self.target_spirv {
+            options.set_target_spirv(version);
+        }
+        if let Some(language) = self.source_language {
+            options.set_source_language(language);
+        }
+        if let Some((version, profile)) = self.forced_version_profile {
+            options.set_forced_version_profile(version, profile);
+        }
+        for &(limit, value) in &self.limits {
+            options.set_limit(limit, value);
+        }
+        options.set_auto_bind_uniforms(self.auto_bind_uniforms);
+        options.set_auto_combined_image_sampler(self.auto_combined_image_sampler);
+        options.set_hlsl_io_mapping(self.hlsl_io_mapping);
+        options.set_hlsl_offsets(self.hlsl_offsets);
+        for &(kind, resource, base) in &self.binding_bases {
+            match kind {
+                Some(kind) => options.set_binding_base_for_stage(kind, resource, base),
+                None => options.set_binding_base(resource, base),
+            }
+        }
+        for (kind, register, set, binding) in &self.hlsl_registers {
+            match *kind {
+                Some(kind) => options
+                    .set_hlsl_register_set_and_binding_for_stage(kind, register, set, binding),
+                None => options.set_hlsl_register_set_and_binding(register, set, binding),
+            }
+        }
+        options.set_auto_map_locations(self.auto_map_locations);
+        options.set_hlsl_functionality1(self.hlsl_functionality1);
+        options.set_invert_y(self.invert_y);
+        options.set_nan_clamp(self.nan_clamp);
+        for (name, value) in &self.macros {
+            options.add_macro_definition(name, value.as_deref());
+        }
+        if let Some(level) = self.optimization_level {
+            options.set_optimization_level(level);
+        }
+        #[cfg(feature = "spirv-tools")]
+        if let Some(preset) = self.opt_preset {
+            options.set_opt_preset(preset);
+        }
+        if self.generate_debug_info {
+            options.set_generate_debug_info();
+        }
+        if self.suppress_warnings {
+            options.set_suppress_warnings();
+        }
+        if self.warnings_as_errors {
+            options.set_warnings_as_errors();
+        }
+        if let Some(bytes) = self.max_source_size {
+            options.set_max_source_size(bytes);
+        }
+        if let Some(bytes) = self.max_include_bytes {
+            options.set_max_include_bytes(bytes);
+        }
+        if let Some(ref policy) = self.diagnostic_policy {
+            options.set_diagnostic_policy(policy.clone());
+        }
+        options.set_rewrite_line_directives(self.rewrite_line_directives);
+    }
+
+    /// Returns the settings differing between `self` and `other`, in the
+    /// order they appear in `self` then in `other`.
+    pub fn diff(&self, other: &Settings) -> Vec<SettingDiff> {
+        let (left, right) = (self.entries(), other.entries());
+        let find = |entries: &[(String, String)], setting: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.0 == setting)
+                .map(|entry| entry.1.clone())
+        };
+        let mut diffs = Vec::new();
+        for (setting, value) in &left {
+            let other = find(&right, setting);
+            if other.as_ref() != Some(value) {
+                diffs.push(SettingDiff {
+                    setting: setting.clone(),
+                    left: Some(value.clone()),
+                    right: other,
+                });
+            }
+        }
+        for (setting, value) in &right {
+            if find(&left, setting).is_none() {
+                diffs.push(SettingDiff {
+                    setting: setting.clone(),
+                    left: None,
+                    right: Some(value.clone()),
+                });
+            }
+        }
+        diffs
+    }
+
+    /// Returns the name and value of every setting changed from its default.
+    fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        let mut push = |setting: String, value: String| entries.push((setting, value));
+        if let Some((env, version)) = self.target_env {
+            let version = EnvVersion::try_from(version)
+                .map_or_else(|_| version.to_string(), |v| v.name().to_string());
+            push("target_env".into(), format!("{env} {version}"));
+        }
+        if let Some(version) = self.target_spirv {
+            push("target_spirv".into(), version.to_string());
+        }
+        if let Some(language) = self.source_language {
+            push("source_language".into(), language.to_string());
+        }
+        if let Some((version, profile)) = self.forced_version_profile {
+            push(
+                "forced_version_profile".into(),
+                format!("{version} {profile}"),
+            );
+        }
+        for &(limit, value) in &self.limits {
+            push(format!("limit {limit}"), value.to_string());
+        }
+        let flags = [
+            (self.include_callback, "include_callback"),
+            (self.auto_bind_uniforms, "auto_bind_uniforms"),
+            (
+                self.auto_combined_image_sampler,
+                "auto_combined_image_sampler",
+            ),
+            (self.hlsl_io_mapping, "hlsl_io_mapping"),
+            (self.hlsl_offsets, "hlsl_offsets"),
+            (self.auto_map_locations, "auto_map_locations"),
+            (self.hlsl_functionality1, "hlsl_functionality1"),
+            (self.invert_y, "invert_y"),
+            (self.nan_clamp, "nan_clamp"),
+            (self.generate_debug_info, "generate_debug_info"),
+            (self.suppress_warnings, "suppress_warnings"),
+            (self.warnings_as_errors, "warnings_as_errors"),
+            (self.rewrite_line_directives, "rewrite_line_directives"),
+        ];
+        for &(_, name) in flags.iter().filter(|f| f.0) {
+            push(name.into(), "true".into());
+        }
+        for &(kind, resource, base) in &self.binding_bases {
+            let setting = match kind {
+                Some(kind) => format!("binding_base {resource:?} {kind}"),
+                None => format!("binding_base {resource:?}"),
+            };
+            push(setting, base.to_string());
+        }
+        for (kind, register, set, binding) in &self.hlsl_registers {
+            let setting = match *kind {
+                Some(kind) => format!("hlsl_register {register} {kind}"),
+                None => format!("hlsl_register {register}"),
+            };
+            push(setting, format!("set {set} binding {binding}"));
+        }
+        for (name, value) in &self.macros {
+            push(format!("macro {name}"), value.clone().unwrap_or_default());
+        }
+        if let Some(level) = self.optimization_level {
+            push("optimization_level".into(), level.to_string());
+        }
+        #[cfg(feature = "spirv-tools")]
+        if let Some(preset) = self.opt_preset {
+            push("opt_preset".into(), preset.name.to_string());
+        }
+        if let Some(bytes) = self.max_source_size {
+            push("max_source_size".into(), bytes.to_string());
+        }
+        if let Some(bytes) = self.max_include_bytes {
+            push("max_include_bytes".into(), bytes.to_string());
+        }
+        if let Some(ref policy) = self.diagnostic_policy {
+            push("diagnostic_policy".into(), format!("{policy:?}"));
+        }
+        entries
+    }
+
+    /// Returns the glslc flags applying these settings.
+    ///
+    /// Returns an error naming the first setting glslc has no flag for.
+    pub fn glslc_args(&self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        if self.include_callback {
+            return Err("include callbacks cannot be passed to glslc".to_string());
+        }
+        if self.max_include_bytes.is_some() {
+            return Err("include size limits cannot be passed to glslc".to_string());
+        }
+        #[cfg(feature = "spirv-tools")]
+        if self.opt_preset.is_some() {
+            return Err("optimization presets cannot be passed to glslc".to_string());
+        }
+        if let Some((env, version)) = self.target_env {
+            args.push(format!("--target-env={}", target_env_name(env, version)?));
+        }
+        if let Some(version) = self.target_spirv {
+            args.push(format!("--target-spv={version}"));
+        }
+        if let Some(language) = self.source_language {
+            args.extend(["-x".to_string(), language.to_string()]);
+        }
+        if let Some((version, profile)) = self.forced_version_profile {
+            match profile {
+                GlslProfile::None => args.push(format!("-std={version}")),
+                _ => args.push(format!("-std={version}{profile}")),
+            }
+        }
+        if !self.limits.is_empty() {
+            let limits: Vec<String> = self
+                .limits
+                .iter()
+                .map(|&(limit, value)| format!("{} {}", limit.name(), value))
+                .collect();
+            args.push(format!("-flimit={}", limits.join(" ")));
+        }
+        let flags = [
+            (self.auto_bind_uniforms, "-fauto-bind-uniforms"),
+            (
+                self.auto_combined_image_sampler,
+                "-fauto-combined-image-sampler",
+            ),
+            (self.hlsl_io_mapping, "-fhlsl-iomap"),
+            (self.hlsl_offsets, "-fhlsl-offsets"),
+            (self.auto_map_locations, "-fauto-map-locations"),
+            (self.hlsl_functionality1, "-fhlsl-functionality1"),
+            (self.invert_y, "-finvert-y"),
+            (self.nan_clamp, "-fnan-clamp"),
+            (self.generate_debug_info, "-g"),
+            (self.suppress_warnings, "-w"),
+            (self.warnings_as_errors, "-Werror"),
+        ];
+        args.extend(flags.iter().filter(|f| f.0).map(|f| f.1.to_string()));
+        for &(kind, resource, base) in &self.binding_bases {
+            args.push(binding_base_flag(resource).to_string());
+            if let Some(kind) = kind {
+                args.push(stage_name(kind)?.to_string());
+            }
+            args.push(base.to_string());
+        }
+        for (kind, register, set, binding) in &self.hlsl_registers {
+            args.push("-fresource-set-binding".to_string());
+            if let Some(kind) = *kind {
+                args.push(stage_name(kind)?.to_string());
+            }
+            args.extend([register.clone(), set.clone(), binding.clone()]);
+        }
+        for (name, value) in &self.macros {
+            match value {
+                Some(value) => args.push(format!("-D{name}={value}")),
+                None => args.push(format!("-D{name}")),
+            }
+        }
+        match self.optimization_level {
+            Some(OptimizationLevel::Zero) => args.push("-O0".to_string()),
+            Some(OptimizationLevel::Size) => args.push("-Os".to_string()),
+            Some(OptimizationLevel::Performance) => args.push("-O".to_string()),
+            None => {}
+        }
+        Ok(args)
+    }
+
+    /// Returns the glslc command line applying these settings, with a
+    /// comment line for each setting glslc has no flag for.
+    pub fn explain(&self) -> String {
+        let mut notes = Vec::new();
+        let mut flagged = self.clone();
+        if flagged.include_callback {
+            flagged.include_callback = false;
+            notes.push(
+                "includes are resolved by a callback; pass -I for its directories".to_string(),
+            );
+        }
+        if flagged.max_include_bytes.take().is_some() {
+            notes.push("the include size limit has no glslc flag".to_string());
+        }
+        if flagged.diagnostic_policy.is_some() {
+            notes.push("the diagnostic policy has no glslc flag".to_string());
+        }
+        #[cfg(feature = "spirv-tools")]
+        if let Some(preset) = flagged.opt_preset.take() {
+            notes.push(format!(
+                "then optimize with: spirv-opt {}",
+                preset.passes.join(" ")
+            ));
+        }
+        let mut explained = match flagged.glslc_args() {
+            Ok(args) => {
+                let args: Vec<String> = args.iter().map(|arg| shell_quote(arg)).collect();
+                format!("glslc {}", args.join(" ")).trim_end().to_string()
+            }
+            Err(e) => format!("# cannot be expressed as glslc flags: {e}"),
+        };
+        for note in notes {
+            explained.push_str("\n# ");
+            explained.push_str(&note);
+        }
+        explained
+    }
+}
+
+/// Quotes `arg` for POSIX shells, if it has characters they interpret.
+fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_=.,:/+@%".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Returns the `--target-env` value for `env` at `version`.
+fn target_env_name(env: TargetEnv, version: u32) -> Result<&'static str, String> {
+    let version = EnvVersion::try_from(version).ok();
+    match (env, version) {
+        (TargetEnv::Vulkan, Some(EnvVersion::WebGPU)) => {
+            Err("glslc does not support the WebGPU environment".to_string())
+        }
+        (TargetEnv::Vulkan, Some(EnvVersion::OpenGL4_5)) | (TargetEnv::Vulkan, None) => {
+            Ok("vulkan")
+        }
+        (TargetEnv::Vulkan, Some(version)) => Ok(version.name()),
+        (TargetEnv::OpenGL, Some(EnvVersion::OpenGL4_5)) => Ok("opengl4.5"),
+        (TargetEnv::OpenGL, _) => Ok("opengl"),
+        (TargetEnv::OpenGLCompat, _) => Ok("opengl_compat"),
+    }
+}
+
+/// Returns the glslc flag setting the binding base of `resource`.
+fn binding_base_flag(resource: ResourceKind) -> &'static str {
+    match resource {
+        ResourceKind::Image => "-fimage-binding-base",
+        ResourceKind::Sampler => "-fsampler-binding-base",
+        ResourceKind::Texture => "-ftexture-binding-base",
+        ResourceKind::Buffer => "-fubo-binding-base",
+        ResourceKind::StorageBuffer => "-fssbo-binding-base",
+        ResourceKind::UnorderedAccessView => "-fuav-binding-base",
+    }
+}
+
+/// Returns the stage name glslc accepts in per-stage flags.
+fn stage_name(kind: ShaderKind) -> Result<&'static str, String> {
+    match kind {
+        ShaderKind::Vertex | ShaderKind::DefaultVertex => Ok("vert"),
+        ShaderKind::Fragment | ShaderKind::DefaultFragment => Ok("frag"),
+        ShaderKind::Compute | ShaderKind::DefaultCompute => Ok("comp"),
+        ShaderKind::Geometry | ShaderKind::DefaultGeometry => Ok("geom"),
+        ShaderKind::TessControl | ShaderKind::DefaultTessControl => Ok("tesc"),
+        ShaderKind::TessEvaluation | ShaderKind::DefaultTessEvaluation => Ok("tese"),
+        _ => Err(format!("glslc has no per-stage options for {kind} shaders")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_glslc_args() {
+        let mut settings = Settings {
+            target_env: Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as u32)),
+            target_spirv: Some(SpirvVersion::V1_5),
+            forced_version_profile: Some((450, GlslProfile::Core)),
+            invert_y: true,
+            optimization_level: Some(OptimizationLevel::Size),
+            binding_bases: vec![(Some(ShaderKind::Fragment), ResourceKind::Buffer, 4)],
+            ..Settings::default()
+        };
+        settings.set_limit(Limit::MaxLights, 8);
+        settings.set_limit(Limit::MaxMeshViewCountNv, 4);
+        settings.set_limit(Limit::MaxLights, 16);
+        settings.add_macro_definition("A", Some("1"));
+        settings.add_macro_definition("B", None);
+        settings.add_macro_definition("A", Some("2"));
+        assert_eq!(
+            Ok(vec![
+                "--target-env=vulkan1.2",
+                "--target-spv=spv1.5",
+                "-std=450core",
+                "-flimit=MaxMeshViewCountNV 4 MaxLights 16",
+                "-finvert-y",
+                "-fubo-binding-base",
+                "frag",
+                "4",
+                "-DA=2",
+                "-DB",
+                "-Os",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect()),
+            settings.glslc_args()
+        );
+    }
+
+    #[test]
+    fn test_settings_explain() {
+        let mut settings = Settings {
+            target_env: Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as u32)),
+            include_callback: true,
+            ..Settings::default()
+        };
+        settings.add_macro_definition("NAME", Some("it's"));
+        assert_eq!(
+            "glslc --target-env=vulkan1.2 '-DNAME=it'\\''s'\n\
+             # includes are resolved by a callback; pass -I for its directories",
+            settings.explain()
+        );
+        assert_eq!("glslc", Settings::default().explain());
+        let settings = Settings {
+            binding_bases: vec![(Some(ShaderKind::Mesh), ResourceKind::Image, 0)],
+            ..Settings::default()
+        };
+        assert!(settings.explain().starts_with("# cannot be expressed"));
+    }
+
+    #[cfg(feature = "spirv-tools")]
+    #[test]
+    fn test_settings_explain_preset() {
+        let settings = Settings {
+            optimization_level: Some(OptimizationLevel::Zero),
+            opt_preset: Some(OptPreset {
+                name: "house",
+                passes: &["--merge-return", "--eliminate-dead-functions"],
+            }),
+            ..Settings::default()
+        };
+        assert_eq!(
+            Err("optimization presets cannot be passed to glslc".to_string()),
+            settings.glslc_args()
+        );
+        assert_eq!(
+            "glslc -O0\n\
+             # then optimize with: spirv-opt --merge-return --eliminate-dead-functions",
+            settings.explain()
+        );
+    }
+
+    #[test]
+    fn test_settings_diff() {
+        let mut left = Settings {
+            target_env: Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as u32)),
+            invert_y: true,
+            ..Settings::default()
+        };
+        left.set_limit(Limit::MaxLights, 8);
+        left.add_macro_definition("A", Some("1"));
+        let mut right = left.clone();
+        assert!(left.diff(&right).is_empty());
+
+        right.target_env = Some((TargetEnv::Vulkan, EnvVersion::Vulkan1_3 as u32));
+        right.invert_y = false;
+        right.set_limit(Limit::MaxLights, 16);
+        right.add_macro_definition("B", None);
+        let diffs = left.diff(&right);
+        assert_eq!(
+            vec![
+                "target_env: vulkan vulkan1.2 != vulkan vulkan1.3",
+                "limit MaxLights: 8 != 16",
+                "invert_y: true != (default)",
+                "macro B: (default) != ",
+            ],
+            diffs.iter().map(|d| d.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!(None, diffs[2].right);
+    }
+
+    #[test]
+    fn test_settings_glslc_args_unsupported() {
+        let settings = Settings {
+            include_callback: true,
+            ..Settings::default()
+        };
+        assert!(settings.glslc_args().is_err());
+        let settings = Settings {
+            binding_bases: vec![(Some(ShaderKind::Mesh), ResourceKind::Image, 0)],
+            ..Settings::default()
+        };
+        assert!(settings.glslc_args().is_err());
+        let settings = Settings {
+            target_env: Some((TargetEnv::OpenGL, 0)),
+            ..Settings::default()
+        };
+        assert_eq!(
+            Ok(vec!["--target-env=opengl".to_string()]),
+            settings.glslc_args()
+        );
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compilation from many threads on a pool of compilers.
+//!
+//! A `Compiler` may be shared between threads: libshaderc compilers hold no
+//! state changed by compiling. What libshaderc requires to be synchronized
+//! is the mutation of options, and `CompileOptions` is neither `Send` nor
+//! `Sync` and only mutated through `&mut`, so the borrow checker enforces it.
+//! `ShardedCompiler` additionally gives each compilation a compiler of its
+//! own, which is the recommended way to compile concurrently.
+//!
+//! Pools can hold base options, used by the compilations given no options of
+//! their own. Since `CompileOptions` cannot be shared between threads, the
+//! base is kept as the settings recorded by its setters, and each
+//! compilation applies them to options of its own. Replacing the base
+//! increments its version, which `VersionedResult` reports.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use crate::settings::Settings;
+use crate::{
+    options_or_default, CompilationArtifact, CompileJob, CompileOptions, Compiler, Error, Result,
+    ShaderCompiler, ShaderKind,
+};
+
+thread_local! {
+    // Addresses of the `ShardedCompiler`s whose shard the current thread is
+    // compiling on, when misuse detection is enabled.
+    static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A `ShaderCompiler` spreading compilations over several compilers, each
+/// used by one compilation at a time.
+///
+/// A compilation takes the next idle compiler in turn, or waits for one when
+/// all are busy.
+///
+/// ```no_run
+/// use std::thread;
+/// use shaderc::{ShaderCompiler, ShaderKind, ShardedCompiler};
+///
+/// let compiler = ShardedCompiler::new(4).unwrap();
+/// thread::scope(|scope| {
+///     for name in ["a.frag", "b.frag"] {
+///         let compiler = &compiler;
+///         scope.spawn(move || {
+///             compiler.compile_into_spirv("void main() {}", ShaderKind::Fragment, name, "main", None)
+///         });
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct ShardedCompiler {
+    shards: Vec<Mutex<Compiler>>,
+    next: AtomicUsize,
+    detect_misuse: bool,
+    base: BaseOptions,
+}
+
+/// The result of a compilation with the base options of a pool, along with
+/// the version of these options.
+pub struct VersionedResult {
+    /// The version of the base options the compilation used: the number of
+    /// times they were replaced before it started, so 0 for no base options.
+    pub options_version: u64,
+    pub result: Result<CompilationArtifact>,
+}
+
+/// Base options of a pool, replaced as a whole.
+#[derive(Debug, Default)]
+pub(crate) struct BaseOptions(RwLock<(u64, Option<Arc<Settings>>)>);
+
+impl BaseOptions {
+    /// Replaces the base options by a copy of `options`, returning their new
+    /// version.
+    pub fn set(&self, options: Option<&CompileOptions>) -> Result<u64> {
+        let settings = match options {
+            Some(options) if options.settings.include_callback => {
+                return Err(Error::InternalError(
+                    "include callbacks cannot be used in base options".to_string(),
+                ));
+            }
+            options => options.map(|options| Arc::new(options.settings.clone())),
+        };
+        let mut base = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        base.0 += 1;
+        base.1 = settings;
+        Ok(base.0)
+    }
+
+    /// Returns the version of the base options.
+    pub fn version(&self) -> u64 {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).0
+    }
+
+    /// Returns the version of the base options and new options with their
+    /// settings, `None` if there are no base options.
+    pub fn options(&self) -> (u64, Result<Option<CompileOptions<'static>>>) {
+        let (version, settings) = self
+            .0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let options = settings
+            .map(|settings| {
+                let mut options = options_or_default(None)?;
+                settings.apply_to(&mut options);
+                Ok(options)
+            })
+            .transpose();
+        (version, options)
+    }
+}
+
+impl ShardedCompiler {
+    /// Creates `shards` compilers, at least one.
+    ///
+    /// Returns `None` if a compiler cannot be created.
+    pub fn new(shards: usize) -> Option<ShardedCompiler> {
+        let shards = (0..shards.max(1))
+            .map(|_| Compiler::new().map(Mutex::new))
+            .collect::<Option<_>>()?;
+        Some(ShardedCompiler {
+            shards,
+            next: AtomicUsize::new(0),
+            detect_misuse: false,
+            base: BaseOptions::default(),
+        })
+    }
+
+    /// Returns this compiler with misuse detection enabled or not, disabled
+    /// by default.
+    ///
+    /// With detection enabled, compiling from an include callback of a
+    /// compilation running on this compiler gives an `InternalError` instead
+    /// of waiting for a compiler, which blocks forever when every other
+    /// shard is busy. Detection costs a thread-local lookup per compilation.
+    pub fn detect_misuse(mut self, enabled: bool) -> ShardedCompiler {
+        self.detect_misuse = enabled;
+        self
+    }
+
+    /// Returns the number of compilers.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Replaces the base options, used by compilations given no options, by
+    /// a copy of `options`, or removes them if `None`. Returns the new
+    /// version of the base options.
+    ///
+    /// This can be called while compilations run, for example to reload the
+    /// configuration of an editor: those already started keep the options
+    /// they started with. Options with an include callback cannot be shared
+    /// and give an `Error::InternalError`.
+    pub fn set_base_options(&self, options: Option<&CompileOptions>) -> Result<u64> {
+        self.base.set(options)
+    }
+
+    /// Returns the version of the base options, 0 until they are first set.
+    pub fn base_options_version(&self) -> u64 {
+        self.base.version()
+    }
+
+    /// Compiles `job` into a SPIR-V binary module with the base options,
+    /// returning the result along with the version of the options used.
+    pub fn compile_versioned(&self, job: &CompileJob) -> VersionedResult {
+        let (options_version, options) = self.base.options();
+        let result =
+            options.and_then(|options| self.with_compiler(|c| job.compile(c, options.as_ref())));
+        VersionedResult {
+            options_version,
+            result,
+        }
+    }
+
+    /// Runs `f` with an idle compiler and `additional_options`, or the base
+    /// options if `None`.
+    fn with_options<T>(
+        &self,
+        additional_options: Option<&CompileOptions>,
+        f: impl FnOnce(&Compiler, Option<&CompileOptions>) -> Result<T>,
+    ) -> Result<T> {
+        match additional_options {
+            Some(options) => self.with_compiler(|compiler| f(compiler, Some(options))),
+            None => {
+                let base = self.base.options().1?;
+                self.with_compiler(|compiler| f(compiler, base.as_ref()))
+            }
+        }
+    }
+
+    /// Runs `f` with an idle compiler, waiting for one if none is.
+    fn with_compiler<T>(&self, f: impl FnOnce(&Compiler) -> Result<T>) -> Result<T> {
+        let _held = if self.detect_misuse {
+            Some(Held::enter(self)?)
+        } else {
+            None
+        };
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % self.shards.len();
+        // A compilation panicking in an include callback leaves the compiler
+        // intact, so poisoned locks are taken over.
+        let guard = (0..self.shards.len())
+            .map(|i| &self.shards[(start + i) % self.shards.len()])
+            .find_map(|shard| shard.try_lock().ok())
+            .unwrap_or_else(|| {
+                self.shards[start]
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+            });
+        f(&guard)
+    }
+}
+
+/// Marks the current thread as compiling on a `ShardedCompiler` until
+/// dropped, even by a panic.
+struct Held(usize);
+
+impl Held {
+    fn enter(compiler: &ShardedCompiler) -> Result<Held> {
+        let id = compiler as *const ShardedCompiler as usize;
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if held.contains(&id) {
+                return Err(Error::InternalError(
+                    "ShardedCompiler used again by a thread already compiling on it, like from \
+                     an include callback"
+                        .to_string(),
+                ));
+            }
+            held.push(id);
+            Ok(Held(id))
+        })
+    }
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        HELD.with(|held| held.borrow_mut().retain(|&id| id != self.0));
+    }
+}
+
+impl ShaderCompiler for ShardedCompiler {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.with_options(additional_options, |compiler, options| {
+            compiler.compile_into_spirv(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                options,
+            )
+        })
+    }
+
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.with_options(additional_options, |compiler, options| {
+            compiler.compile_into_spirv_assembly(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                options,
+            )
+        })
+    }
+
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.with_options(additional_options, |compiler, options| {
+            compiler.preprocess(source_text, input_file_name, entry_point_name, options)
+        })
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.with_options(additional_options, |compiler, options| {
+            compiler.assemble(source_assembly, options)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{IncludeType, ResolvedInclude};
+
+    #[test]
+    fn test_sharded_compiler_threads() {
+        let compiler = ShardedCompiler::new(2).unwrap();
+        assert_eq!(2, compiler.shards());
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    compiler
+                        .compile_into_spirv(
+                            "void main() {}",
+                            ShaderKind::Vertex,
+                            "a.vert",
+                            "main",
+                            None,
+                        )
+                        .unwrap()
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_sharded_compiler_base_options() {
+        let compiler = ShardedCompiler::new(2).unwrap();
+        let source = "#version 450\nvoid main() { int x = VALUE; }";
+        let job = CompileJob::new(source, ShaderKind::Vertex, "a.vert");
+        let versioned = compiler.compile_versioned(&job);
+        assert_eq!(0, versioned.options_version);
+        assert!(versioned.result.is_err());
+
+        let mut options = CompileOptions::new().unwrap();
+        options.add_macro_definition("VALUE", Some("1"));
+        assert_eq!(1, compiler.set_base_options(Some(&options)).unwrap());
+        let versioned = compiler.compile_versioned(&job);
+        assert_eq!(1, versioned.options_version);
+        assert!(versioned.result.is_ok());
+        assert!(compiler
+            .compile_into_spirv(source, ShaderKind::Vertex, "a.vert", "main", None)
+            .is_ok());
+
+        options.set_include_callback(|_, _, _, _| Err("no includes".to_string()));
+        assert_matches!(
+            compiler.set_base_options(Some(&options)),
+            Err(Error::InternalError(_))
+        );
+        assert_eq!(1, compiler.base_options_version());
+        assert_eq!(2, compiler.set_base_options(None).unwrap());
+        let versioned = compiler.compile_versioned(&job);
+        assert_eq!(2, versioned.options_version);
+        assert!(versioned.result.is_err());
+    }
+
+    #[test]
+    fn test_sharded_compiler_detect_misuse() {
+        let compiler = ShardedCompiler::new(1).unwrap().detect_misuse(true);
+        let mut options = CompileOptions::new().unwrap();
+        options.set_include_callback(|name, _: IncludeType, _, _| {
+            let error = match compiler.preprocess("", name, "main", None) {
+                Ok(_) => panic!("compiled re-entrantly"),
+                Err(error) => error.to_string(),
+            };
+            assert!(error.contains("already compiling"));
+            Ok(ResolvedInclude {
+                resolved_name: name.to_string(),
+                content: String::new(),
+            })
+        });
+        let source = "#version 450\n#include \"b.glsl\"\nvoid main() {}";
+        compiler
+            .compile_into_spirv(source, ShaderKind::Vertex, "a.vert", "main", Some(&options))
+            .unwrap();
+    }
+}
@@ -0,0 +1,787 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Owned SPIR-V modules and the instructions they are made of.
+
+use std::collections::HashMap;
+use std::{error, fmt};
+
+use crate::util::{byte_swap, detect_endianness, words_from_bytes, Endianness};
+use crate::CompilationArtifact;
+
+/// The magic number starting every SPIR-V module.
+pub(crate) const MAGIC: u32 = 0x0723_0203;
+
+/// Number of words in the header of a SPIR-V module.
+const HEADER_LEN: usize = 5;
+
+pub(crate) mod op {
+    pub const NAME: u16 = 5;
+    pub const EXTENSION: u16 = 10;
+    pub const ENTRY_POINT: u16 = 15;
+    pub const EXECUTION_MODE: u16 = 16;
+    pub const CAPABILITY: u16 = 17;
+    pub const TYPE_BOOL: u16 = 20;
+    pub const TYPE_INT: u16 = 21;
+    pub const TYPE_FLOAT: u16 = 22;
+    pub const TYPE_VECTOR: u16 = 23;
+    pub const TYPE_MATRIX: u16 = 24;
+    pub const TYPE_IMAGE: u16 = 25;
+    pub const TYPE_SAMPLER: u16 = 26;
+    pub const TYPE_SAMPLED_IMAGE: u16 = 27;
+    pub const TYPE_ARRAY: u16 = 28;
+    pub const TYPE_RUNTIME_ARRAY: u16 = 29;
+    pub const TYPE_STRUCT: u16 = 30;
+    pub const TYPE_POINTER: u16 = 32;
+    pub const CONSTANT: u16 = 43;
+    pub const VARIABLE: u16 = 59;
+    pub const LOAD: u16 = 61;
+    pub const ACCESS_CHAIN: u16 = 65;
+    pub const IN_BOUNDS_ACCESS_CHAIN: u16 = 66;
+    pub const DECORATE: u16 = 71;
+    pub const MEMBER_DECORATE: u16 = 72;
+    pub const COPY_OBJECT: u16 = 83;
+    pub const SAMPLED_IMAGE: u16 = 86;
+    pub const TYPE_ACCELERATION_STRUCTURE: u16 = 5341;
+}
+
+pub(crate) mod decoration {
+    pub const BUFFER_BLOCK: u32 = 3;
+    pub const BUILT_IN: u32 = 11;
+    pub const PATCH: u32 = 15;
+    pub const LOCATION: u32 = 30;
+    pub const COMPONENT: u32 = 31;
+    pub const BINDING: u32 = 33;
+    pub const DESCRIPTOR_SET: u32 = 34;
+}
+
+pub(crate) mod storage_class {
+    pub const UNIFORM_CONSTANT: u32 = 0;
+    pub const INPUT: u32 = 1;
+    pub const UNIFORM: u32 = 2;
+    pub const OUTPUT: u32 = 3;
+    pub const STORAGE_BUFFER: u32 = 12;
+}
+
+/// Error returned when words are not a well-formed SPIR-V module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModuleError {
+    /// The words do not start with the SPIR-V magic number.
+    BadMagic,
+    /// The module ends in the middle of the header or of the instruction
+    /// at the given word offset.
+    Truncated(usize),
+    /// The instruction at the given word offset has a word count of zero.
+    ZeroWordCount(usize),
+    /// The instruction at the given word offset has fewer operands than its
+    /// opcode requires.
+    MissingOperands(usize),
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ModuleError::BadMagic => write!(f, "not a SPIR-V module: bad magic number"),
+            ModuleError::Truncated(offset) => {
+                write!(f, "SPIR-V module truncated at word {offset}")
+            }
+            ModuleError::ZeroWordCount(offset) => {
+                write!(
+                    f,
+                    "SPIR-V instruction at word {offset} has a word count of zero"
+                )
+            }
+            ModuleError::MissingOperands(offset) => {
+                write!(f, "SPIR-V instruction at word {offset} is missing operands")
+            }
+        }
+    }
+}
+
+impl error::Error for ModuleError {}
+
+/// An owned SPIR-V binary module.
+///
+/// The module is checked to consist of whole instructions when created, so
+/// its instructions can be iterated over without further error handling.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SpirvModule {
+    words: Vec<u32>,
+}
+
+/// An instruction of a `SpirvModule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction<'a> {
+    offset: usize,
+    words: &'a [u32],
+}
+
+/// Iterator over the instructions of a `SpirvModule`.
+#[derive(Clone, Debug)]
+pub struct Instructions<'a> {
+    offset: usize,
+    words: &'a [u32],
+}
+
+impl SpirvModule {
+    /// Creates a module from its words, checking that they start with a
+    /// SPIR-V header followed by whole instructions.
+    pub fn from_words(words: Vec<u32>) -> Result<SpirvModule, ModuleError> {
+        if words.first() != Some(&MAGIC) {
+            return Err(ModuleError::BadMagic);
+        }
+        if words.len() < HEADER_LEN {
+            return Err(ModuleError::Truncated(words.len()));
+        }
+        let mut offset = HEADER_LEN;
+        while offset < words.len() {
+            let count = (words[offset] >> 16) as usize;
+            if count == 0 {
+                return Err(ModuleError::ZeroWordCount(offset));
+            }
+            if offset + count > words.len() {
+                return Err(ModuleError::Truncated(offset));
+            }
+            offset += count;
+        }
+        Ok(SpirvModule { words })
+    }
+
+    /// Creates a module from its bytes, in either byte order.
+    ///
+    /// SPIR-V files may be written in either byte order, which the magic
+    /// number tells apart; words in the other byte order than expected are
+    /// swapped. Bytes which are not a whole number of words are reported as
+    /// `ModuleError::Truncated`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SpirvModule, ModuleError> {
+        let mut words = words_from_bytes(bytes)
+            .map_err(|_| ModuleError::Truncated(bytes.len() / 4))?
+            .into_owned();
+        if detect_endianness(bytes) == Some(Endianness::Big) {
+            byte_swap(&mut words);
+        }
+        SpirvModule::from_words(words)
+    }
+
+    /// Creates a module from the binary output of a compilation.
+    pub fn from_artifact(artifact: &CompilationArtifact) -> Result<SpirvModule, ModuleError> {
+        SpirvModule::from_words(artifact.as_binary().to_vec())
+    }
+
+    /// Returns the words of the module.
+    pub fn words(&self) -> &[u32] {
+        &self.words
+    }
+
+    /// Returns the words of the module, consuming it.
+    pub fn into_words(self) -> Vec<u32> {
+        self.words
+    }
+
+    /// Returns the ID bound of the module: all IDs are less than it.
+    pub fn bound(&self) -> u32 {
+        self.words[3]
+    }
+
+    /// Returns an iterator over the instructions of the module.
+    pub fn instructions(&self) -> Instructions<'_> {
+        Instructions {
+            offset: HEADER_LEN,
+            words: &self.words,
+        }
+    }
+
+    /// Rewrites the descriptor set and binding decorations of resources.
+    ///
+    /// Each `(old_set, old_binding, new_set, new_binding)` entry of `remaps`
+    /// moves the resources bound at `old_binding` of `old_set` to
+    /// `new_binding` of `new_set`. Remaps are matched against the original
+    /// bindings, so bindings can be swapped. A resource without a
+    /// `DescriptorSet` decoration is in set 0, and gets the decoration added
+    /// when moved to another set.
+    ///
+    /// Returns the number of resources remapped.
+    pub fn remap_bindings(&mut self, remaps: &[(u32, u32, u32, u32)]) -> usize {
+        // Word offsets of the set and binding literals of each resource.
+        let mut resources: Vec<(u32, Option<usize>, Option<usize>, usize)> = Vec::new();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            if inst.opcode() != op::DECORATE || operands.len() < 3 {
+                continue;
+            }
+            let index = match resources.iter().position(|r| r.0 == operands[0]) {
+                Some(index) => index,
+                None => {
+                    resources.push((operands[0], None, None, 0));
+                    resources.len() - 1
+                }
+            };
+            let literal = inst.offset() + 3;
+            match operands[1] {
+                decoration::DESCRIPTOR_SET => resources[index].1 = Some(literal),
+                decoration::BINDING => {
+                    resources[index].2 = Some(literal);
+                    resources[index].3 = inst.offset() + inst.words().len();
+                }
+                _ => {}
+            }
+        }
+
+        let mut insertions = Vec::new();
+        let mut count = 0;
+        for (id, set, binding, binding_end) in resources {
+            let binding = match binding {
+                Some(binding) => binding,
+                None => continue,
+            };
+            let old = (set.map_or(0, |set| self.words[set]), self.words[binding]);
+            let (new_set, new_binding) = match remaps.iter().find(|r| (r.0, r.1) == old) {
+                Some(&(_, _, new_set, new_binding)) => (new_set, new_binding),
+                None => continue,
+            };
+            self.words[binding] = new_binding;
+            match set {
+                Some(set) => self.words[set] = new_set,
+                None if new_set != 0 => insertions.push((binding_end, id, new_set)),
+                None => {}
+            }
+            count += 1;
+        }
+        insertions.sort();
+        for &(offset, id, set) in insertions.iter().rev() {
+            let decorate = [
+                4 << 16 | u32::from(op::DECORATE),
+                id,
+                decoration::DESCRIPTOR_SET,
+                set,
+            ];
+            self.words.splice(offset..offset, decorate.iter().copied());
+        }
+        count
+    }
+
+    /// Renames the entry points named `from` to `to`, along with the debug
+    /// names of their functions.
+    ///
+    /// An entry point is left alone if the module already has an entry point
+    /// named `to` for the same execution model, as that would make the two
+    /// indistinguishable. Returns whether any entry point was renamed.
+    pub fn rename_entry_point(&mut self, from: &str, to: &str) -> bool {
+        let mut entry_points = Vec::new();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            if inst.opcode() == op::ENTRY_POINT && operands.len() >= 3 {
+                let name = literal_string(&operands[2..]).0;
+                entry_points.push((operands[0], operands[1], name));
+            }
+        }
+        let renamed: Vec<(u32, u32)> = entry_points
+            .iter()
+            .filter(|(model, _, name)| {
+                name == from && !entry_points.iter().any(|e| e.0 == *model && e.2 == to)
+            })
+            .map(|&(model, function, _)| (model, function))
+            .collect();
+        if renamed.is_empty() || from == to {
+            return false;
+        }
+
+        let mut words = self.words[..HEADER_LEN].to_vec();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            let (prefix, rest) = match inst.opcode() {
+                op::ENTRY_POINT
+                    if operands.len() >= 3 && renamed.contains(&(operands[0], operands[1])) =>
+                {
+                    let (_, length) = literal_string(&operands[2..]);
+                    (&operands[..2], &operands[2 + length..])
+                }
+                op::NAME
+                    if !operands.is_empty()
+                        && renamed.iter().any(|r| r.1 == operands[0])
+                        && literal_string(&operands[1..]).0 == from =>
+                {
+                    (&operands[..1], &[][..])
+                }
+                _ => {
+                    words.extend_from_slice(inst.words());
+                    continue;
+                }
+            };
+            let name = encode_string(to);
+            let count = 1 + prefix.len() + name.len() + rest.len();
+            words.push((count as u32) << 16 | u32::from(inst.opcode()));
+            words.extend_from_slice(prefix);
+            words.extend_from_slice(&name);
+            words.extend_from_slice(rest);
+        }
+        self.words = words;
+        true
+    }
+}
+
+impl<'a> Instruction<'a> {
+    /// Returns the opcode of the instruction.
+    pub fn opcode(&self) -> u16 {
+        self.words[0] as u16
+    }
+
+    /// Returns the operands of the instruction, after the first word.
+    pub fn operands(&self) -> &'a [u32] {
+        &self.words[1..]
+    }
+
+    /// Returns all words of the instruction.
+    pub fn words(&self) -> &'a [u32] {
+        self.words
+    }
+
+    /// Returns the offset of the instruction in the words of the module.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Instruction<'a>;
+
+    fn next(&mut self) -> Option<Instruction<'a>> {
+        let first = *self.words.get(self.offset)?;
+        let count = (first >> 16) as usize;
+        let instruction = Instruction {
+            offset: self.offset,
+            words: &self.words[self.offset..self.offset + count],
+        };
+        self.offset += count;
+        Some(instruction)
+    }
+}
+
+/// Decodes the literal string starting at `words`, returning it with the
+/// number of words it takes.
+pub(crate) fn literal_string(words: &[u32]) -> (String, usize) {
+    let mut bytes = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        for byte in word.to_le_bytes() {
+            if byte == 0 {
+                return (String::from_utf8_lossy(&bytes).into_owned(), i + 1);
+            }
+            bytes.push(byte);
+        }
+    }
+    (String::from_utf8_lossy(&bytes).into_owned(), words.len())
+}
+
+/// Encodes `string` as a literal string.
+pub(crate) fn encode_string(string: &str) -> Vec<u32> {
+    let mut bytes = string.as_bytes().to_vec();
+    bytes.resize(bytes.len() / 4 * 4 + 4, 0);
+    bytes
+        .chunks(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// An `Input` or `Output` variable of a module, as seen by the stage
+/// interface checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct InterfaceVariable {
+    pub name: Option<String>,
+    pub storage_class: u32,
+    pub location: Option<u32>,
+    pub component: u32,
+    pub builtin: bool,
+    pub patch: bool,
+    /// ID of the type pointed to by the variable.
+    pub type_id: u32,
+}
+
+/// The type and debug information of a module needed to describe its
+/// interface.
+pub(crate) struct TypeInfo<'a> {
+    types: HashMap<u32, Instruction<'a>>,
+    constants: HashMap<u32, u32>,
+}
+
+impl SpirvModule {
+    /// Returns the `Input` and `Output` variables of the module.
+    pub(crate) fn interface_variables(&self) -> Vec<InterfaceVariable> {
+        let mut names = HashMap::new();
+        let mut decorations: HashMap<u32, Vec<&[u32]>> = HashMap::new();
+        let mut builtin_structs = Vec::new();
+        let mut pointers = HashMap::new();
+        let mut variables = Vec::new();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            match inst.opcode() {
+                op::NAME if !operands.is_empty() => {
+                    names.insert(operands[0], literal_string(&operands[1..]).0);
+                }
+                op::DECORATE if operands.len() >= 2 => {
+                    decorations
+                        .entry(operands[0])
+                        .or_default()
+                        .push(&operands[1..]);
+                }
+                op::MEMBER_DECORATE
+                    if operands.len() >= 3 && operands[2] == decoration::BUILT_IN =>
+                {
+                    builtin_structs.push(operands[0]);
+                }
+                op::TYPE_POINTER if operands.len() >= 3 => {
+                    pointers.insert(operands[0], operands[2]);
+                }
+                op::VARIABLE if operands.len() >= 3 => {
+                    let storage_class = operands[2];
+                    if storage_class == storage_class::INPUT
+                        || storage_class == storage_class::OUTPUT
+                    {
+                        variables.push((operands[1], operands[0], storage_class));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let info = self.type_info();
+        variables
+            .into_iter()
+            .map(|(id, pointer_type, storage_class)| {
+                let decorations = decorations.get(&id).map_or(&[][..], |d| &d[..]);
+                let find = |decoration| {
+                    decorations
+                        .iter()
+                        .find(|d| d[0] == decoration)
+                        .map(|d| d.get(1).copied().unwrap_or(0))
+                };
+                let type_id = pointers.get(&pointer_type).copied().unwrap_or(0);
+                InterfaceVariable {
+                    name: names.get(&id).cloned().filter(|name| !name.is_empty()),
+                    storage_class,
+                    location: find(decoration::LOCATION),
+                    component: find(decoration::COMPONENT).unwrap_or(0),
+                    builtin: find(decoration::BUILT_IN).is_some()
+                        || info
+                            .element_type(type_id)
+                            .is_some_and(|element| builtin_structs.contains(&element))
+                        || builtin_structs.contains(&type_id),
+                    patch: find(decoration::PATCH).is_some(),
+                    type_id,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the type declarations and integer constants of the module.
+    pub(crate) fn type_info(&self) -> TypeInfo<'_> {
+        let mut types = HashMap::new();
+        let mut constants = HashMap::new();
+        for inst in self.instructions() {
+            let operands = inst.operands();
+            match inst.opcode() {
+                op::TYPE_BOOL..=op::TYPE_POINTER | op::TYPE_ACCELERATION_STRUCTURE
+                    if !operands.is_empty() =>
+                {
+                    types.insert(operands[0], inst);
+                }
+                op::CONSTANT if operands.len() >= 3 => {
+                    constants.insert(operands[1], operands[2]);
+                }
+                _ => {}
+            }
+        }
+        TypeInfo { types, constants }
+    }
+}
+
+impl<'a> TypeInfo<'a> {
+    /// Returns the instruction declaring the type `id`.
+    pub fn get(&self, id: u32) -> Option<Instruction<'a>> {
+        self.types.get(&id).copied()
+    }
+
+    /// Returns the value of the integer constant `id`.
+    pub fn constant(&self, id: u32) -> Option<u32> {
+        self.constants.get(&id).copied()
+    }
+
+    /// Returns the element type of an array type, or `id` itself for other
+    /// types, or `None` for an array type missing its element type.
+    pub fn element_type(&self, id: u32) -> Option<u32> {
+        match self.types.get(&id) {
+            Some(inst)
+                if inst.opcode() == op::TYPE_ARRAY || inst.opcode() == op::TYPE_RUNTIME_ARRAY =>
+            {
+                inst.operands().get(1).copied()
+            }
+            _ => Some(id),
+        }
+    }
+
+    /// Describes the type `id` in a GLSL-like notation, like `vec4` or
+    /// `float[3]`, so that types of different modules can be compared.
+    pub fn describe(&self, id: u32) -> String {
+        // Bounded, as a malformed module may have cycles.
+        self.describe_within(id, self.types.len())
+    }
+
+    /// Describes the type `id`, giving up on types nested deeper than
+    /// `depth`.
+    fn describe_within(&self, id: u32, depth: usize) -> String {
+        let inst = match self.types.get(&id) {
+            Some(inst) if depth > 0 => inst,
+            _ => return format!("%{id}"),
+        };
+        let describe = |id: u32| self.describe_within(id, depth - 1);
+        let operands = inst.operands();
+        let operand = |i: usize| operands.get(i).copied().unwrap_or(0);
+        match inst.opcode() {
+            op::TYPE_BOOL => "bool".to_string(),
+            op::TYPE_INT => {
+                let prefix = if operand(2) == 0 { "uint" } else { "int" };
+                match operand(1) {
+                    32 => prefix.to_string(),
+                    width => format!("{prefix}{width}_t"),
+                }
+            }
+            op::TYPE_FLOAT => match operand(1) {
+                32 => "float".to_string(),
+                64 => "double".to_string(),
+                width => format!("float{width}_t"),
+            },
+            op::TYPE_VECTOR => {
+                let prefix = match &*describe(operand(1)) {
+                    "float" => "",
+                    "double" => "d",
+                    "int" => "i",
+                    "uint" => "u",
+                    "bool" => "b",
+                    other => return format!("{}[{}]", other, operand(2)),
+                };
+                format!("{}vec{}", prefix, operand(2))
+            }
+            op::TYPE_MATRIX => {
+                let column = describe(operand(1));
+                let rows = column.trim_start_matches(|c: char| !c.is_ascii_digit());
+                let prefix = if column.starts_with('d') { "d" } else { "" };
+                format!("{}mat{}x{}", prefix, operand(2), rows)
+            }
+            op::TYPE_ARRAY => match self.constants.get(&operand(2)) {
+                Some(length) => format!("{}[{}]", describe(operand(1)), length),
+                None => format!("{}[%{}]", describe(operand(1)), operand(2)),
+            },
+            op::TYPE_RUNTIME_ARRAY => format!("{}[]", describe(operand(1))),
+            op::TYPE_STRUCT => {
+                let members: Vec<String> = operands.iter().skip(1).map(|&m| describe(m)).collect();
+                format!("struct {{ {} }}", members.join("; "))
+            }
+            op::TYPE_POINTER => describe(operand(2)),
+            _ => format!("%{id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Encodes an instruction.
+    pub fn inst(opcode: u16, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | u32::from(opcode)];
+        words.extend_from_slice(operands);
+        words
+    }
+
+    /// Builds a module from the given instructions, with a bound of 100.
+    pub fn module(instructions: &[Vec<u32>]) -> SpirvModule {
+        let mut words = vec![MAGIC, 0x0001_0000, 0, 100, 0];
+        for inst in instructions {
+            words.extend_from_slice(inst);
+        }
+        SpirvModule::from_words(words).unwrap()
+    }
+
+    #[test]
+    fn test_module_from_words() {
+        assert_eq!(
+            Err(ModuleError::BadMagic),
+            SpirvModule::from_words(vec![1, 2])
+        );
+        assert_eq!(
+            Err(ModuleError::Truncated(2)),
+            SpirvModule::from_words(vec![MAGIC, 0x0001_0000])
+        );
+        assert_eq!(
+            Err(ModuleError::Truncated(5)),
+            SpirvModule::from_words(vec![MAGIC, 0x0001_0000, 0, 1, 0, 3 << 16 | 17, 1])
+        );
+        assert_eq!(
+            Err(ModuleError::ZeroWordCount(5)),
+            SpirvModule::from_words(vec![MAGIC, 0x0001_0000, 0, 1, 0, 17])
+        );
+        let module = module(&[inst(17, &[1]), inst(14, &[0, 1])]);
+        let opcodes: Vec<u16> = module.instructions().map(|i| i.opcode()).collect();
+        assert_eq!(vec![17, 14], opcodes);
+        assert_eq!(7, module.instructions().nth(1).unwrap().offset());
+        assert_eq!(100, module.bound());
+    }
+
+    #[test]
+    fn test_module_from_bytes() {
+        let module = module(&[inst(17, &[1])]);
+        let little: Vec<u8> = module
+            .words()
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect();
+        let big: Vec<u8> = module
+            .words()
+            .iter()
+            .flat_map(|w| w.to_be_bytes())
+            .collect();
+        assert_eq!(Ok(&module), SpirvModule::from_bytes(&little).as_ref());
+        assert_eq!(Ok(&module), SpirvModule::from_bytes(&big).as_ref());
+        assert_eq!(
+            Err(ModuleError::Truncated(6)),
+            SpirvModule::from_bytes(&little[..26])
+        );
+    }
+
+    #[test]
+    fn test_remap_bindings() {
+        let mut module = module(&[
+            inst(op::DECORATE, &[1, decoration::DESCRIPTOR_SET, 0]),
+            inst(op::DECORATE, &[1, decoration::BINDING, 0]),
+            inst(op::DECORATE, &[2, decoration::DESCRIPTOR_SET, 0]),
+            inst(op::DECORATE, &[2, decoration::BINDING, 1]),
+            inst(op::DECORATE, &[3, decoration::BINDING, 2]),
+            inst(op::DECORATE, &[4, decoration::BINDING, 3]),
+        ]);
+        let remaps = [(0, 0, 0, 1), (0, 1, 0, 0), (0, 2, 3, 5)];
+        assert_eq!(3, module.remap_bindings(&remaps));
+        let expected = self::module(&[
+            inst(op::DECORATE, &[1, decoration::DESCRIPTOR_SET, 0]),
+            inst(op::DECORATE, &[1, decoration::BINDING, 1]),
+            inst(op::DECORATE, &[2, decoration::DESCRIPTOR_SET, 0]),
+            inst(op::DECORATE, &[2, decoration::BINDING, 0]),
+            inst(op::DECORATE, &[3, decoration::BINDING, 5]),
+            inst(op::DECORATE, &[3, decoration::DESCRIPTOR_SET, 3]),
+            inst(op::DECORATE, &[4, decoration::BINDING, 3]),
+        ]);
+        assert_eq!(expected, module);
+        assert_eq!(0, module.remap_bindings(&[(1, 0, 0, 0)]));
+    }
+
+    #[test]
+    fn test_rename_entry_point() {
+        let mut ps_main = vec![4, 1];
+        ps_main.extend(encode_string("PSMain"));
+        ps_main.push(7);
+        let mut module = module(&[
+            inst(op::ENTRY_POINT, &ps_main),
+            inst(op::NAME, &[[1].as_ref(), &encode_string("PSMain")].concat()),
+            inst(
+                op::NAME,
+                &[[2].as_ref(), &encode_string("@PSMain(")].concat(),
+            ),
+        ]);
+        assert!(!module.rename_entry_point("VSMain", "main"));
+        assert!(module.rename_entry_point("PSMain", "main"));
+        let mut main = vec![4, 1];
+        main.extend(encode_string("main"));
+        main.push(7);
+        let expected = self::module(&[
+            inst(op::ENTRY_POINT, &main),
+            inst(op::NAME, &[[1].as_ref(), &encode_string("main")].concat()),
+            inst(
+                op::NAME,
+                &[[2].as_ref(), &encode_string("@PSMain(")].concat(),
+            ),
+        ]);
+        assert_eq!(expected, module);
+        assert!(SpirvModule::from_words(module.clone().into_words()).is_ok());
+
+        let mut other = vec![0, 3];
+        other.extend(encode_string("main"));
+        let mut module = self::module(&[
+            inst(op::ENTRY_POINT, &ps_main),
+            inst(op::ENTRY_POINT, &other),
+        ]);
+        assert!(module.rename_entry_point("PSMain", "main"));
+        let mut module = self::module(&[
+            inst(op::ENTRY_POINT, &main),
+            inst(op::ENTRY_POINT, &ps_main),
+        ]);
+        assert!(!module.rename_entry_point("PSMain", "main"));
+    }
+
+    #[test]
+    fn test_literal_string() {
+        assert_eq!(
+            ("main".to_string(), 2),
+            literal_string(&[0x6e69_616d, 0, 7])
+        );
+        assert_eq!(("ab".to_string(), 1), literal_string(&[0x6261]));
+        assert_eq!(vec![0x6e69_616d, 0], encode_string("main"));
+        assert_eq!(vec![0x6261], encode_string("ab"));
+    }
+
+    #[test]
+    fn test_interface_variables() {
+        let module = module(&[
+            inst(op::NAME, &[10, 0x0074_756f]),
+            inst(op::DECORATE, &[10, decoration::LOCATION, 2]),
+            inst(op::DECORATE, &[11, decoration::BUILT_IN, 0]),
+            inst(op::TYPE_FLOAT, &[1, 32]),
+            inst(op::TYPE_VECTOR, &[2, 1, 4]),
+            inst(op::TYPE_INT, &[5, 32, 0]),
+            inst(op::CONSTANT, &[5, 6, 3]),
+            inst(op::TYPE_ARRAY, &[7, 2, 6]),
+            inst(op::TYPE_POINTER, &[3, storage_class::OUTPUT, 7]),
+            inst(op::TYPE_POINTER, &[4, storage_class::OUTPUT, 2]),
+            inst(op::VARIABLE, &[3, 10, storage_class::OUTPUT]),
+            inst(op::VARIABLE, &[4, 11, storage_class::OUTPUT]),
+        ]);
+        let variables = module.interface_variables();
+        assert_eq!(2, variables.len());
+        assert_eq!(Some("out"), variables[0].name.as_deref());
+        assert_eq!(Some(2), variables[0].location);
+        assert!(!variables[0].builtin);
+        assert!(variables[1].builtin);
+        let info = module.type_info();
+        assert_eq!("vec4[3]", info.describe(variables[0].type_id));
+        assert_eq!(
+            "vec4",
+            info.describe(info.element_type(variables[0].type_id).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_malformed_instructions() {
+        let mut main = vec![4, 1];
+        main.extend(encode_string("main"));
+        let mut module = module(&[
+            inst(op::ENTRY_POINT, &main),
+            inst(op::ENTRY_POINT, &[4]),
+            inst(op::NAME, &[]),
+            inst(op::TYPE_ARRAY, &[7]),
+            inst(op::TYPE_POINTER, &[3, storage_class::OUTPUT, 8]),
+            inst(op::TYPE_RUNTIME_ARRAY, &[8, 3]),
+        ]);
+        assert!(module.rename_entry_point("main", "other"));
+        let info = module.type_info();
+        assert_eq!(None, info.element_type(7));
+        assert_eq!("%3[][]", info.describe(8));
+    }
+}
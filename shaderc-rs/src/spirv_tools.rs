@@ -0,0 +1,535 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Disassembly, reassembly, validation and optimization of SPIR-V modules by
+//! SPIRV-Tools, the library shaderc is built on. Available with the
+//! `spirv-tools` feature.
+
+use libc::c_char;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use shaderc_sys::spirv_tools as spv;
+
+use crate::{
+    CompilationArtifact, CompileOptions, Compiler, EnvVersion, Error, Result, SpirvModule,
+    SpirvVersion, TargetEnv,
+};
+
+/// A named list of SPIRV-Tools optimizer passes, set with
+/// `CompileOptions::set_opt_preset` instead of the built-in sets of
+/// `OptimizationLevel::Size` and `OptimizationLevel::Performance`.
+///
+/// Passes are named by their `spirv-opt` flags and run in order, after a
+/// compilation without optimization:
+///
+/// ```
+/// use shaderc::{CompileOptions, OptPreset};
+///
+/// const HOUSE: OptPreset = OptPreset {
+///     name: "house",
+///     passes: &[
+///         "--inline-entry-points-exhaustive",
+///         "--eliminate-dead-functions",
+///         "--eliminate-dead-code-aggressive",
+///     ],
+/// };
+///
+/// let mut options = CompileOptions::new().unwrap();
+/// options.set_opt_preset(HOUSE);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OptPreset {
+    /// The name of the preset, shown by `CompileOptions::diff` and in
+    /// errors.
+    pub name: &'static str,
+    /// The `spirv-opt` flags of the passes, like `--merge-return`.
+    pub passes: &'static [&'static str],
+}
+
+thread_local! {
+    // Messages of the optimizer running on this thread.
+    static OPTIMIZER_MESSAGES: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Records the warnings and errors of an optimizer in `OPTIMIZER_MESSAGES`.
+extern "C" fn collect_message(
+    level: spv::spv_message_level_t,
+    _source: *const c_char,
+    _position: *const spv::spv_position_t,
+    message: *const c_char,
+) {
+    if level > spv::SPV_MSG_WARNING || message.is_null() {
+        return;
+    }
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    OPTIMIZER_MESSAGES.with(|messages| {
+        let mut messages = messages.borrow_mut();
+        messages.push_str(&message);
+        messages.push('\n');
+    });
+}
+
+/// Returns and clears the messages of the optimizer run on this thread.
+fn take_optimizer_messages() -> String {
+    OPTIMIZER_MESSAGES.with(|messages| messages.borrow_mut().split_off(0))
+}
+
+/// A SPIRV-Tools context, destroyed on drop.
+struct Context(*mut spv::SpvContext);
+
+impl Context {
+    fn new(env: spv::spv_target_env) -> Result<Context> {
+        let raw = unsafe { spv::spvContextCreate(env) };
+        if raw.is_null() {
+            return Err(Error::InternalError(
+                "cannot create a SPIRV-Tools context".to_string(),
+            ));
+        }
+        Ok(Context(raw))
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe { spv::spvContextDestroy(self.0) }
+    }
+}
+
+/// A SPIRV-Tools optimizer with its options, destroyed on drop.
+struct Optimizer(*mut spv::SpvOptimizer, *mut spv::SpvOptimizerOptions);
+
+impl Optimizer {
+    fn new(env: spv::spv_target_env) -> Result<Optimizer> {
+        let raw = unsafe { spv::spvOptimizerCreate(env) };
+        if raw.is_null() {
+            return Err(Error::InternalError(
+                "cannot create a SPIRV-Tools optimizer".to_string(),
+            ));
+        }
+        let options = unsafe { spv::spvOptimizerOptionsCreate() };
+        let optimizer = Optimizer(raw, options);
+        if options.is_null() {
+            return Err(Error::InternalError(
+                "cannot create SPIRV-Tools optimizer options".to_string(),
+            ));
+        }
+        unsafe { spv::spvOptimizerSetMessageConsumer(raw, collect_message) };
+        Ok(optimizer)
+    }
+}
+
+impl Drop for Optimizer {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.1.is_null() {
+                spv::spvOptimizerOptionsDestroy(self.1);
+            }
+            spv::spvOptimizerDestroy(self.0);
+        }
+    }
+}
+
+/// Returns the SPIRV-Tools environment of `target_env` at `env_version`.
+pub(crate) fn spv_target_env(
+    target_env: TargetEnv,
+    env_version: EnvVersion,
+) -> spv::spv_target_env {
+    match (target_env, env_version) {
+        (TargetEnv::Vulkan, EnvVersion::Vulkan1_1) => spv::SPV_ENV_VULKAN_1_1,
+        (TargetEnv::Vulkan, EnvVersion::Vulkan1_2) => spv::SPV_ENV_VULKAN_1_2,
+        (TargetEnv::Vulkan, EnvVersion::Vulkan1_3) => spv::SPV_ENV_VULKAN_1_3,
+        (TargetEnv::Vulkan, _) => spv::SPV_ENV_VULKAN_1_0,
+        (TargetEnv::OpenGL, _) | (TargetEnv::OpenGLCompat, _) => spv::SPV_ENV_OPENGL_4_5,
+    }
+}
+
+/// Returns the SPIRV-Tools environment targeted by compilations with
+/// `options`.
+fn options_target_env(options: &CompileOptions) -> spv::spv_target_env {
+    let settings = &options.settings;
+    let (env, version) = settings
+        .target_env
+        .unwrap_or((TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32));
+    let version = EnvVersion::try_from(version).unwrap_or(EnvVersion::Vulkan1_0);
+    match spv_target_env(env, version) {
+        spv::SPV_ENV_VULKAN_1_1 if settings.target_spirv >= Some(SpirvVersion::V1_4) => {
+            spv::SPV_ENV_VULKAN_1_1_SPIRV_1_4
+        }
+        env => env,
+    }
+}
+
+/// Returns whether `options` optimize with a preset, which only runs on
+/// binary modules.
+pub(crate) fn has_preset(options: Option<&CompileOptions>) -> bool {
+    options.is_some_and(|options| options.settings.opt_preset.is_some())
+}
+
+/// Returns `artifact` with its binary module optimized by the preset of
+/// `options`, if they have one.
+pub(crate) fn apply_preset(
+    artifact: CompilationArtifact,
+    options: Option<&CompileOptions>,
+) -> Result<CompilationArtifact> {
+    let (options, preset) = match options.map(|o| (o, o.settings.opt_preset)) {
+        Some((options, Some(preset))) => (options, preset),
+        _ => return Ok(artifact),
+    };
+    let module = SpirvModule::from_artifact(&artifact)
+        .map_err(|e| Error::InternalError(format!("cannot optimize the module: {e}")))?;
+    let optimized = module.optimize_for(&preset, options_target_env(options))?;
+    Ok(CompilationArtifact::from_binary(optimized.into_words())
+        .with_warnings(artifact.get_num_warnings(), artifact.get_warning_messages())
+        .with_input_file_name(&artifact.input_file_name))
+}
+
+/// Returns the SPIR-V assembly of the binary module of `artifact`, with its
+/// warnings.
+pub(crate) fn to_assembly(artifact: CompilationArtifact) -> Result<CompilationArtifact> {
+    let module = SpirvModule::from_artifact(&artifact)
+        .map_err(|e| Error::InternalError(format!("cannot disassemble the module: {e}")))?;
+    Ok(CompilationArtifact::from_text(module.disassemble()?)
+        .with_warnings(artifact.get_num_warnings(), artifact.get_warning_messages())
+        .with_input_file_name(&artifact.input_file_name))
+}
+
+/// Returns the message of `diagnostic`, which is destroyed, or of `result`
+/// when there is no diagnostic.
+///
+/// Messages about a binary start with the index of the word they refer to.
+pub(crate) fn take_diagnostic(
+    diagnostic: *mut spv::spv_diagnostic_t,
+    result: spv::spv_result_t,
+) -> String {
+    if diagnostic.is_null() {
+        return format!("SPIRV-Tools failed with status {result}");
+    }
+    let message = unsafe {
+        let error = match (*diagnostic).error {
+            error if error.is_null() => String::new(),
+            error => CStr::from_ptr(error).to_string_lossy().into_owned(),
+        };
+        let position = &(*diagnostic).position;
+        if (*diagnostic).is_text_source {
+            format!("{}:{}: {}", position.line + 1, position.column + 1, error)
+        } else {
+            format!("word {}: {}", position.index, error)
+        }
+    };
+    unsafe { spv::spvDiagnosticDestroy(diagnostic) };
+    message
+}
+
+impl SpirvModule {
+    /// Returns the SPIR-V assembly text of this module, as `spirv-dis`
+    /// prints it: indented, with ids named after their debug names.
+    ///
+    /// Modules SPIRV-Tools cannot parse give an `Error::InvalidAssembly`.
+    pub fn disassemble(&self) -> Result<String> {
+        self.to_text(
+            spv::SPV_BINARY_TO_TEXT_OPTION_INDENT | spv::SPV_BINARY_TO_TEXT_OPTION_FRIENDLY_NAMES,
+        )
+    }
+
+    /// Disassembles this module and assembles the text again with `compiler`
+    /// for `target_env` at `env_version`, for tools and tests checking that
+    /// a module survives the round trip through assembly.
+    ///
+    /// The ids are written as numbers in the assembly text, which the
+    /// assembler keeps, so the result has the ids of this module.
+    pub fn reassemble(
+        &self,
+        compiler: &Compiler,
+        target_env: TargetEnv,
+        env_version: EnvVersion,
+    ) -> Result<SpirvModule> {
+        let assembly = self.to_text(spv::SPV_BINARY_TO_TEXT_OPTION_NONE)?;
+        let artifact = compiler.assemble_for(target_env, env_version, &assembly)?;
+        SpirvModule::from_artifact(&artifact)
+            .map_err(|error| Error::InvalidAssembly(error.to_string()))
+    }
+
+    /// Returns the assembly text of this module, written with the
+    /// `SPV_BINARY_TO_TEXT_OPTION_*` flags in `options`.
+    fn to_text(&self, options: u32) -> Result<String> {
+        let context = Context::new(spv::SPV_ENV_UNIVERSAL_1_6)?;
+        let mut text = ptr::null_mut();
+        let mut diagnostic = ptr::null_mut();
+        let result = unsafe {
+            spv::spvBinaryToText(
+                context.0,
+                self.words().as_ptr(),
+                self.words().len(),
+                options,
+                &mut text,
+                &mut diagnostic,
+            )
+        };
+        if result != spv::SPV_SUCCESS || text.is_null() {
+            return Err(Error::InvalidAssembly(take_diagnostic(diagnostic, result)));
+        }
+        let assembly = unsafe {
+            let bytes = std::slice::from_raw_parts((*text).str as *const u8, (*text).length);
+            let assembly = String::from_utf8_lossy(bytes).into_owned();
+            spv::spvTextDestroy(text);
+            assembly
+        };
+        Ok(assembly.trim_end_matches('\0').to_string())
+    }
+
+    /// Validates this module for `target_env` at `env_version`, as
+    /// `spirv-val` does.
+    ///
+    /// An invalid module gives an `Error::CompilationError` with the first
+    /// problem SPIRV-Tools found.
+    pub fn validate(&self, target_env: TargetEnv, env_version: EnvVersion) -> Result<()> {
+        let context = Context::new(spv_target_env(target_env, env_version))?;
+        let mut diagnostic = ptr::null_mut();
+        let result = unsafe {
+            spv::spvValidateBinary(
+                context.0,
+                self.words().as_ptr(),
+                self.words().len(),
+                &mut diagnostic,
+            )
+        };
+        if result != spv::SPV_SUCCESS {
+            return Err(Error::CompilationError(
+                1,
+                take_diagnostic(diagnostic, result),
+            ));
+        }
+        if !diagnostic.is_null() {
+            unsafe { spv::spvDiagnosticDestroy(diagnostic) };
+        }
+        Ok(())
+    }
+
+    /// Returns this module optimized by the passes of `preset`, as
+    /// `spirv-opt` does with their flags, for `target_env` at `env_version`.
+    ///
+    /// The module is validated first. A pass SPIRV-Tools does not know
+    /// gives `Error::InternalError`, and an invalid module or a failing pass
+    /// gives `Error::CompilationError` with the messages of the optimizer.
+    pub fn optimize(
+        &self,
+        preset: &OptPreset,
+        target_env: TargetEnv,
+        env_version: EnvVersion,
+    ) -> Result<SpirvModule> {
+        self.optimize_for(preset, spv_target_env(target_env, env_version))
+    }
+
+    fn optimize_for(&self, preset: &OptPreset, env: spv::spv_target_env) -> Result<SpirvModule> {
+        let optimizer = Optimizer::new(env)?;
+        for &pass in preset.passes {
+            let registered = CString::new(pass).is_ok_and(|flag| unsafe {
+                spv::spvOptimizerRegisterPassFromFlag(optimizer.0, flag.as_ptr())
+            });
+            if !registered {
+                take_optimizer_messages();
+                return Err(Error::InternalError(format!(
+                    "unknown optimizer pass `{pass}` in preset `{}`",
+                    preset.name
+                )));
+            }
+        }
+        let mut binary = ptr::null_mut();
+        let result = unsafe {
+            spv::spvOptimizerRun(
+                optimizer.0,
+                self.words().as_ptr(),
+                self.words().len(),
+                &mut binary,
+                optimizer.1,
+            )
+        };
+        let messages = take_optimizer_messages();
+        if result != spv::SPV_SUCCESS || binary.is_null() {
+            let message = match messages.trim_end() {
+                "" => format!("SPIRV-Tools failed with status {result}"),
+                messages => messages.to_string(),
+            };
+            return Err(Error::CompilationError(1, message));
+        }
+        let words = unsafe {
+            let words = std::slice::from_raw_parts((*binary).code, (*binary).word_count).to_vec();
+            spv::spvBinaryDestroy(binary);
+            words
+        };
+        SpirvModule::from_words(words).map_err(|e| {
+            Error::InternalError(format!("the optimizer returned an invalid module: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv_module::tests::{inst, module};
+    use crate::{CompileOptions, ShaderKind};
+
+    const STRIP: OptPreset = OptPreset {
+        name: "strip",
+        passes: &["--strip-debug", "--eliminate-dead-code-aggressive"],
+    };
+
+    #[test]
+    fn test_spv_target_env() {
+        assert_eq!(
+            spv::SPV_ENV_VULKAN_1_0,
+            spv_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0)
+        );
+        assert_eq!(
+            spv::SPV_ENV_VULKAN_1_2,
+            spv_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_2)
+        );
+        assert_eq!(
+            spv::SPV_ENV_OPENGL_4_5,
+            spv_target_env(TargetEnv::OpenGL, EnvVersion::OpenGL4_5)
+        );
+    }
+
+    #[test]
+    fn test_disassemble_and_validate() {
+        let compiler = Compiler::new().unwrap();
+        let artifact = compiler
+            .compile_into_spirv(
+                "void main() {}",
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                None,
+            )
+            .unwrap();
+        let module = SpirvModule::from_artifact(&artifact).unwrap();
+        let assembly = module.disassemble().unwrap();
+        assert!(assembly.contains("OpEntryPoint Fragment %main \"main\""));
+        assert_eq!(
+            Ok(()),
+            module.validate(TargetEnv::Vulkan, EnvVersion::Vulkan1_0)
+        );
+    }
+
+    #[test]
+    fn test_reassemble() {
+        let compiler = Compiler::new().unwrap();
+        let artifact = compiler
+            .compile_into_spirv(
+                "#version 450\nlayout(location = 0) out vec4 color;\nvoid main() { color = vec4(1.0); }",
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                None,
+            )
+            .unwrap();
+        let module = SpirvModule::from_artifact(&artifact).unwrap();
+        let reassembled = module
+            .reassemble(&compiler, TargetEnv::Vulkan, EnvVersion::Vulkan1_0)
+            .unwrap();
+        // Only the generator word of the header differs.
+        assert_eq!(module.words()[3..], reassembled.words()[3..]);
+        assert_eq!(module.words()[..2], reassembled.words()[..2]);
+    }
+
+    #[test]
+    fn test_validate_invalid_module() {
+        // OpTypeInt 32 0 without the required OpCapability and OpMemoryModel.
+        let module = module(&[inst(21, &[1, 32, 0])]);
+        assert!(module.disassemble().is_ok());
+        match module.validate(TargetEnv::Vulkan, EnvVersion::Vulkan1_0) {
+            Err(Error::CompilationError(1, message)) => assert!(!message.is_empty()),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_compile_with_preset() {
+        let compiler = Compiler::new().unwrap();
+        let source = "#version 450\nlayout(location = 0) out vec4 color;\n\
+                      void main() { color = vec4(1.0); }";
+        let mut options = CompileOptions::new().unwrap();
+        let plain = compiler
+            .compile_into_spirv_assembly(
+                source,
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                Some(&options),
+            )
+            .unwrap()
+            .as_text();
+        assert!(plain.contains("OpName"));
+
+        options.set_opt_preset(STRIP);
+        let artifact = compiler
+            .compile_into_spirv(
+                source,
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                Some(&options),
+            )
+            .unwrap();
+        let module = SpirvModule::from_artifact(&artifact).unwrap();
+        assert!(!module.disassemble().unwrap().contains("OpName"));
+        assert_eq!(
+            Ok(()),
+            module.validate(TargetEnv::Vulkan, EnvVersion::Vulkan1_0)
+        );
+        let assembly = compiler
+            .compile_into_spirv_assembly(
+                source,
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                Some(&options),
+            )
+            .unwrap()
+            .as_text();
+        assert!(assembly.contains("OpEntryPoint Fragment"));
+        assert!(!assembly.contains("OpName"));
+    }
+
+    #[test]
+    fn test_optimize_unknown_pass() {
+        let compiler = Compiler::new().unwrap();
+        let artifact = compiler
+            .compile_into_spirv(
+                "void main() {}",
+                ShaderKind::Fragment,
+                "a.frag",
+                "main",
+                None,
+            )
+            .unwrap();
+        let module = SpirvModule::from_artifact(&artifact).unwrap();
+        let preset = OptPreset {
+            name: "broken",
+            passes: &["--no-such-pass"],
+        };
+        match module.optimize(&preset, TargetEnv::Vulkan, EnvVersion::Vulkan1_0) {
+            Err(Error::InternalError(message)) => {
+                assert_eq!(
+                    "unknown optimizer pass `--no-such-pass` in preset `broken`",
+                    message
+                )
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,504 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `ShaderCompiler` running glslc as a child process.
+//!
+//! `SubprocessCompiler` translates `CompileOptions` into glslc flags and parses
+//! glslc's messages back into the crate's errors and diagnostics, so that
+//! code written against `ShaderCompiler` can run where glslc is installed but
+//! libshaderc cannot be linked.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::diagnostic::{self, summary_count, Diagnostic, LineMap, Severity};
+use crate::{
+    source_size_error, CompilationArtifact, CompileOptions, Error, Result, ShaderCompiler,
+    ShaderKind,
+};
+
+/// Name glslc gives to the source read from stdin in its messages.
+const STDIN_NAME: &str = "<stdin>";
+
+/// A `ShaderCompiler` running an external glslc binary.
+///
+/// This is for environments where glslc is installed, e.g. with the Vulkan
+/// SDK, but linking libshaderc is not an option. Sources are piped to glslc
+/// and its messages are parsed back into `Error`s and warnings, with the
+/// input file name substituted for glslc's `<stdin>`.
+///
+/// The settings of `CompileOptions` are translated into glslc flags. Include
+/// callbacks cannot be, so compiling with them fails with an `InternalError`;
+/// use `include_dir` instead. Note that creating a `CompileOptions` still
+/// requires libshaderc.
+///
+/// ```no_run
+/// use shaderc::{ShaderCompiler, ShaderKind, SubprocessCompiler};
+///
+/// let compiler = SubprocessCompiler::new().include_dir("shaders/include");
+/// let artifact = compiler
+///     .compile_into_spirv("void main() {}", ShaderKind::Vertex, "tri.vert", "main", None)
+///     .unwrap();
+/// assert_eq!(Some(&0x07230203), artifact.as_binary().first());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubprocessCompiler {
+    glslc: PathBuf,
+    include_dirs: Vec<PathBuf>,
+    timeout: Option<Duration>,
+}
+
+impl Default for SubprocessCompiler {
+    fn default() -> SubprocessCompiler {
+        SubprocessCompiler::new()
+    }
+}
+
+impl SubprocessCompiler {
+    /// Returns a compiler running the `glslc` found on `PATH`.
+    pub fn new() -> SubprocessCompiler {
+        SubprocessCompiler::with_glslc("glslc")
+    }
+
+    /// Returns a compiler running the glslc binary at `glslc`.
+    pub fn with_glslc<P: Into<PathBuf>>(glslc: P) -> SubprocessCompiler {
+        SubprocessCompiler {
+            glslc: glslc.into(),
+            include_dirs: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Returns this compiler with `dir` added to the directories searched for
+    /// included files, like glslc's `-I`.
+    pub fn include_dir<P: Into<PathBuf>>(mut self, dir: P) -> SubprocessCompiler {
+        self.include_dirs.push(dir.into());
+        self
+    }
+
+    /// Returns this compiler with glslc killed after running for `timeout`,
+    /// failing the compilation with `Error::TimedOut`. There is no timeout by
+    /// default.
+    pub fn timeout(mut self, timeout: Duration) -> SubprocessCompiler {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the first line of the output of `glslc --version`.
+    ///
+    /// This can be used to check that glslc can be run before compiling.
+    pub fn version(&self) -> Result<String> {
+        let output = Command::new(&self.glslc)
+            .arg("--version")
+            .output()
+            .map_err(|e| Error::InternalError(format!("cannot run glslc: {e}")))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().next().unwrap_or("").to_string())
+    }
+
+    /// Runs glslc on `source`, which is piped to stdin unless `input` is
+    /// given.
+    fn run(&self, mut args: Vec<String>, job: Job) -> Result<CompilationArtifact> {
+        // glslc has no flag limiting the source size.
+        if let Some(message) = source_size_error(job.source.len(), job.options) {
+            return Err(match job.input {
+                Some(_) => Error::InvalidAssembly(message),
+                None => {
+                    Error::CompilationError(1, format!("{}: error: {message}", job.input_file_name))
+                }
+            });
+        }
+        if let Some(options) = job.options {
+            args.extend(
+                options
+                    .settings
+                    .glslc_args()
+                    .map_err(Error::InternalError)?,
+            );
+        }
+        let mut command = Command::new(&self.glslc);
+        command.args(&args);
+        for dir in &self.include_dirs {
+            command.arg("-I").arg(dir);
+        }
+        command.args(["-o", "-"]);
+        match job.input {
+            Some(ref input) => command.arg(input),
+            None => command.arg("-"),
+        };
+        let mut child = command
+            .stdin(if job.input.is_some() {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::InternalError(format!("cannot run glslc: {e}")))?;
+        // Write from another thread, so that glslc filling the stdout pipe
+        // cannot block it while we are still writing.
+        let writer = child.stdin.take().map(|mut stdin| {
+            let source = job.source.to_string();
+            thread::spawn(move || stdin.write_all(source.as_bytes()))
+        });
+        let output = wait_output(child, self.timeout)
+            .map_err(|e| Error::InternalError(format!("cannot run glslc: {e}")))?;
+        if let Some(writer) = writer {
+            // glslc exiting early closes the pipe, which is reported below.
+            let _ = writer.join();
+        }
+        let output = output.ok_or_else(|| {
+            Error::TimedOut(format!(
+                "glslc was killed after running for {:?}",
+                self.timeout.unwrap_or_default()
+            ))
+        })?;
+
+        let input_name = match job.input {
+            Some(ref input) => input.to_string_lossy().into_owned(),
+            None => STDIN_NAME.to_string(),
+        };
+        let mut messages = String::new();
+        let mut counted = None;
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            if let Some(count) = summary_count(line) {
+                counted = Some(count.0);
+                continue;
+            }
+            match line.strip_prefix(&input_name) {
+                Some(rest) => {
+                    messages.push_str(job.input_file_name);
+                    messages.push_str(rest);
+                }
+                None => messages.push_str(line),
+            }
+            messages.push('\n');
+        }
+        let count = |severity| {
+            counted.unwrap_or_else(|| {
+                Diagnostic::parse(&messages)
+                    .iter()
+                    .filter(|d| d.severity == severity)
+                    .count() as u32
+            })
+        };
+
+        if output.status.success() {
+            let artifact = if job.is_binary {
+                if output.stdout.len() % 4 != 0 {
+                    return Err(Error::InternalError(format!(
+                        "glslc output is {} bytes long, not a whole number of words",
+                        output.stdout.len()
+                    )));
+                }
+                let words = output
+                    .stdout
+                    .chunks_exact(4)
+                    .map(|b| u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                CompilationArtifact::from_binary(words)
+            } else {
+                CompilationArtifact::from_text(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            .with_input_file_name(job.input_file_name);
+            let artifact = if messages.is_empty() {
+                artifact
+            } else {
+                artifact.with_warnings(count(Severity::Warning), messages)
+            };
+            diagnostic::apply_policy(artifact, job.options)
+        } else if output.status.code().is_none() {
+            Err(Error::InternalError(format!(
+                "glslc was terminated: {}",
+                output.status
+            )))
+        } else if job.input.is_some() {
+            Err(Error::InvalidAssembly(messages))
+        } else {
+            Err(Error::CompilationError(
+                count(Severity::Error).max(1),
+                messages,
+            ))
+        }
+    }
+}
+
+/// Waits for `child` to exit and collects its output, like
+/// `Child::wait_with_output`, but kills it and returns `None` once it has run
+/// for `timeout`.
+pub(crate) fn wait_output(
+    mut child: Child,
+    timeout: Option<Duration>,
+) -> io::Result<Option<Output>> {
+    let Some(timeout) = timeout else {
+        return child.wait_with_output().map(Some);
+    };
+    fn read_all<R: Read + Send + 'static>(
+        pipe: Option<R>,
+    ) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+        thread::spawn(move || {
+            let mut bytes = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut bytes)?;
+            }
+            Ok(bytes)
+        })
+    }
+    let stdout = read_all(child.stdout.take());
+    let stderr = read_all(child.stderr.take());
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap()?,
+        stderr: stderr.join().unwrap()?,
+    }))
+}
+
+/// The input of a glslc run.
+struct Job<'a> {
+    source: &'a str,
+    /// A file holding the source, which is piped to glslc if this is `None`.
+    input: Option<PathBuf>,
+    input_file_name: &'a str,
+    options: Option<&'a CompileOptions<'a>>,
+    is_binary: bool,
+}
+
+/// Returns the `-fshader-stage` flag for `kind`, if it has one.
+pub(crate) fn stage_args(kind: ShaderKind) -> Result<Vec<String>> {
+    match kind {
+        ShaderKind::InferFromSource => Ok(Vec::new()),
+        ShaderKind::SpirvAssembly => Err(Error::InvalidStage(
+            "SPIR-V assembly cannot be compiled, use assemble".to_string(),
+        )),
+        _ => Ok(vec![format!(
+            "-fshader-stage={}",
+            kind.name().trim_start_matches("default-")
+        )]),
+    }
+}
+
+impl ShaderCompiler for SubprocessCompiler {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let mut args = vec!["-c".to_string()];
+        args.extend(stage_args(shader_kind)?);
+        args.push(format!("-fentry-point={entry_point_name}"));
+        let line_map = LineMap::for_options(source_text, additional_options);
+        let result = self.run(
+            args,
+            Job {
+                source: line_map.as_ref().map_or(source_text, |map| map.source()),
+                input: None,
+                input_file_name,
+                options: additional_options,
+                is_binary: true,
+            },
+        );
+        diagnostic::rewrite_lines(result, line_map.as_ref(), input_file_name)
+    }
+
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let mut args = vec!["-S".to_string()];
+        args.extend(stage_args(shader_kind)?);
+        args.push(format!("-fentry-point={entry_point_name}"));
+        let line_map = LineMap::for_options(source_text, additional_options);
+        let result = self.run(
+            args,
+            Job {
+                source: line_map.as_ref().map_or(source_text, |map| map.source()),
+                input: None,
+                input_file_name,
+                options: additional_options,
+                is_binary: false,
+            },
+        );
+        diagnostic::rewrite_lines(result, line_map.as_ref(), input_file_name)
+    }
+
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        let args = vec![
+            "-E".to_string(),
+            format!("-fentry-point={entry_point_name}"),
+        ];
+        self.run(
+            args,
+            Job {
+                source: source_text,
+                input: None,
+                input_file_name,
+                options: additional_options,
+                is_binary: false,
+            },
+        )
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        // glslc recognizes assembly by the file extension only, so it cannot
+        // be piped.
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let input = env::temp_dir().join(format!(
+            "shaderc-{}-{}.spvasm",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&input, source_assembly)
+            .map_err(|e| Error::InternalError(format!("cannot write {}: {e}", input.display())))?;
+        let result = self.run(
+            vec!["-c".to_string()],
+            Job {
+                source: source_assembly,
+                input: Some(input.clone()),
+                input_file_name: "assembly",
+                options: additional_options,
+                is_binary: true,
+            },
+        );
+        let _ = fs::remove_file(&input);
+        result
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Writes a fake glslc printing `stderr` and exiting with `status`, after
+    /// echoing its arguments and stdin to stdout.
+    fn fake_glslc(name: &str, stderr: &str, status: i32) -> PathBuf {
+        let path = env::temp_dir().join(format!("shaderc-{}-{}", name, process::id()));
+        let script =
+            format!("#!/bin/sh\necho \"$@\"\ncat\nprintf '%s' '{stderr}' >&2\nexit {status}\n");
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_subprocess_warnings() {
+        let glslc = fake_glslc(
+            "glslc-warn",
+            "<stdin>:2: warning: unused\n1 warning generated.\n",
+            0,
+        );
+        let compiler = SubprocessCompiler::with_glslc(&glslc).include_dir("inc");
+        let artifact = compiler
+            .preprocess("#define A", "a.vert", "main", None)
+            .unwrap();
+        assert_eq!(
+            "-E -fentry-point=main -I inc -o - -\n#define A",
+            artifact.as_text()
+        );
+        assert_eq!(1, artifact.get_num_warnings());
+        assert_eq!(
+            "a.vert:2: warning: unused\n",
+            artifact.get_warning_messages()
+        );
+        fs::remove_file(glslc).unwrap();
+    }
+
+    #[test]
+    fn test_subprocess_errors() {
+        let glslc = fake_glslc(
+            "glslc-err",
+            "<stdin>:1: error: a\n<stdin>:2: error: b\n2 errors generated.\n",
+            1,
+        );
+        let compiler = SubprocessCompiler::with_glslc(&glslc);
+        let result = compiler.compile_into_spirv(
+            "void main() {}",
+            ShaderKind::DefaultFragment,
+            "b.frag",
+            "main",
+            None,
+        );
+        assert_eq!(
+            Some(Error::CompilationError(
+                2,
+                "b.frag:1: error: a\nb.frag:2: error: b\n".to_string()
+            )),
+            result.err()
+        );
+        assert_matches!(
+            compiler
+                .compile_into_spirv("", ShaderKind::SpirvAssembly, "c", "main", None)
+                .err(),
+            Some(Error::InvalidStage(_))
+        );
+        fs::remove_file(glslc).unwrap();
+    }
+
+    #[test]
+    fn test_subprocess_timeout() {
+        let glslc = env::temp_dir().join(format!("shaderc-glslc-hang-{}", process::id()));
+        fs::write(&glslc, "#!/bin/sh\nexec sleep 10\n").unwrap();
+        fs::set_permissions(&glslc, fs::Permissions::from_mode(0o755)).unwrap();
+        let compiler = SubprocessCompiler::with_glslc(&glslc).timeout(Duration::from_millis(50));
+        let started = Instant::now();
+        assert_matches!(
+            compiler.preprocess("", "a.vert", "main", None).err(),
+            Some(Error::TimedOut(_))
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+        fs::remove_file(glslc).unwrap();
+    }
+
+    #[test]
+    fn test_subprocess_missing_glslc() {
+        let compiler = SubprocessCompiler::with_glslc("/nonexistent/glslc");
+        assert_matches!(compiler.version(), Err(Error::InternalError(_)));
+    }
+}
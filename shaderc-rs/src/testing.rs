@@ -0,0 +1,368 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for testing shaders.
+//!
+//! `assert_compiles!` compiles a shader and panics with the diagnostics if it
+//! fails. `assert_spirv_matches_snapshot!` compares a SPIR-V module against a
+//! golden file after normalizing it with `normalize`, so that only meaningful
+//! changes to the generated code fail the comparison:
+//!
+//! ```no_run
+//! # #[macro_use] extern crate shaderc;
+//! # fn main() {
+//! let artifact = assert_compiles!("#version 450\nvoid main() {}", shaderc::ShaderKind::Vertex);
+//! assert_spirv_matches_snapshot!(artifact.as_binary(), "tests/snapshots/empty.vert.spv");
+//! # }
+//! ```
+//!
+//! Snapshot paths are relative to the `Cargo.toml` of the crate invoking the
+//! macro. Missing snapshots are created. Set the `SHADERC_UPDATE_SNAPSHOTS`
+//! environment variable to overwrite mismatching snapshots instead of failing.
+//!
+//! Available with the `testing` feature.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::{CompilationArtifact, CompileOptions, Compiler, ShaderKind};
+
+/// Compiles `source` as a shader of the given kind into SPIR-V, panicking with
+/// the diagnostics if it fails.
+///
+/// Returns the `CompilationArtifact`. A third argument passes a
+/// `&CompileOptions`.
+#[macro_export]
+macro_rules! assert_compiles {
+    ($source:expr, $kind:expr) => {
+        $crate::testing::compile_or_panic($source, $kind, None)
+    };
+    ($source:expr, $kind:expr, $options:expr) => {
+        $crate::testing::compile_or_panic($source, $kind, Some($options))
+    };
+}
+
+/// Asserts that a SPIR-V module, given as `&[u32]`, matches the golden file at
+/// the given path once both are normalized.
+///
+/// The path is relative to the `Cargo.toml` of the crate invoking the macro.
+/// See the [module documentation](testing/index.html) for how snapshots are
+/// created and updated.
+#[macro_export]
+macro_rules! assert_spirv_matches_snapshot {
+    ($spirv:expr, $path:expr) => {
+        $crate::testing::assert_matches_snapshot(
+            $spirv,
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+/// Implementation of `assert_compiles!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn compile_or_panic(
+    source: &str,
+    kind: ShaderKind,
+    options: Option<&CompileOptions>,
+) -> CompilationArtifact {
+    let compiler = Compiler::new().expect("failed to create compiler");
+    match compiler.compile_into_spirv(source, kind, "shader.glsl", "main", options) {
+        Ok(artifact) => artifact,
+        Err(error) => panic!("shader failed to compile: {}", error),
+    }
+}
+
+/// Implementation of `assert_spirv_matches_snapshot!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_matches_snapshot(spirv: &[u32], path: &Path) {
+    let actual = normalize(spirv);
+    let update = env::var_os("SHADERC_UPDATE_SNAPSHOTS").is_some();
+    let expected = match fs::read(path) {
+        Ok(bytes) if bytes.len() % 4 == 0 => Some(normalize(
+            &bytes
+                .chunks(4)
+                .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+                .collect::<Vec<u32>>(),
+        )),
+        Ok(_) => panic!("snapshot {} is not a SPIR-V module", path.display()),
+        Err(_) => None,
+    };
+    if expected.as_ref() == Some(&actual) {
+        return;
+    }
+    if expected.is_none() || update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("failed to create snapshot directory");
+        }
+        let bytes: Vec<u8> = actual.iter().flat_map(|word| word.to_le_bytes()).collect();
+        fs::write(path, bytes).expect("failed to write snapshot");
+        return;
+    }
+    let expected = expected.unwrap();
+    let expected_insts = instructions(&expected);
+    let actual_insts = instructions(&actual);
+    let index = expected_insts
+        .iter()
+        .zip(&actual_insts)
+        .position(|(e, a)| e != a)
+        .unwrap_or_else(|| expected_insts.len().min(actual_insts.len()));
+    panic!(
+        "SPIR-V does not match snapshot {} at instruction {}:\n  expected: {:?}\n    actual: {:?}\n\
+         Set SHADERC_UPDATE_SNAPSHOTS=1 to update the snapshot.",
+        path.display(),
+        index,
+        expected_insts.get(index),
+        actual_insts.get(index)
+    );
+}
+
+/// Returns the instructions of `spirv` after the header.
+fn instructions(spirv: &[u32]) -> Vec<&[u32]> {
+    let mut instructions = Vec::new();
+    let mut rest = spirv.get(5..).unwrap_or(&[]);
+    while let Some(&first) = rest.first() {
+        let count = ((first >> 16) as usize).clamp(1, rest.len());
+        instructions.push(&rest[..count]);
+        rest = &rest[count..];
+    }
+    instructions
+}
+
+/// Normalizes the parts of a SPIR-V module that change without affecting its
+/// meaning, so that modules can be compared.
+///
+/// The generator word of the header is zeroed and IDs are renumbered in order
+/// of first appearance, adjusting the ID bound. Words that cannot be parsed
+/// as a module are returned unchanged.
+pub fn normalize(spirv: &[u32]) -> Vec<u32> {
+    let mut words = spirv.to_vec();
+    if words.len() < 5 || words[0] != 0x0723_0203 {
+        return words;
+    }
+    words[2] = 0;
+
+    let mut ids = HashMap::new();
+    let mut offset = 5;
+    while offset < words.len() {
+        let count = (words[offset] >> 16) as usize;
+        let opcode = words[offset] & 0xffff;
+        if count == 0 || offset + count > words.len() {
+            return spirv.to_vec();
+        }
+        for i in id_operands(opcode, &words[offset + 1..offset + count]) {
+            let word = &mut words[offset + 1 + i];
+            let next = ids.len() as u32 + 1;
+            *word = *ids.entry(*word).or_insert(next);
+        }
+        offset += count;
+    }
+    words[3] = ids.len() as u32 + 1;
+    words
+}
+
+/// Returns the indices of the operands of an instruction that are IDs.
+///
+/// Operands are IDs unless the layout of the opcode says otherwise, which
+/// holds for the vast majority of instructions.
+fn id_operands(opcode: u32, operands: &[u32]) -> Vec<usize> {
+    let mut indices = Vec::new();
+    match opcode {
+        // OpLoad, OpStore, OpCopyMemory, OpCopyMemorySized: IDs followed by
+        // memory operands, whose `Aligned` bit adds a literal.
+        61..=64 => {
+            let fixed = match opcode {
+                61 | 64 => 3,
+                _ => 2,
+            };
+            indices.extend(0..fixed.min(operands.len()));
+            let mut i = fixed;
+            while let Some(&mask) = operands.get(i) {
+                i += if mask & 0x2 != 0 { 2 } else { 1 };
+                let ids = (mask & 0x8 != 0) as usize + (mask & 0x10 != 0) as usize;
+                let ids = ids.min(operands.len().saturating_sub(i));
+                indices.extend(i..i + ids);
+                i += ids;
+            }
+        }
+        // OpSwitch: selector, default, then literal and label pairs.
+        251 => {
+            indices.extend(0..2.min(operands.len()));
+            indices.extend((3..operands.len()).step_by(2));
+        }
+        _ => {
+            let layout = operand_layout(opcode).as_bytes();
+            let mut i = 0;
+            let mut k = 0;
+            while i < operands.len() {
+                match layout[k.min(layout.len() - 1)] {
+                    b'i' => {
+                        indices.push(i);
+                        i += 1;
+                    }
+                    b's' => {
+                        while i < operands.len() && operands[i] >> 24 != 0 {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                    _ => i += 1,
+                }
+                k += 1;
+            }
+        }
+    }
+    indices
+}
+
+/// Returns the kinds of the operands of an opcode: `i` for an ID, `l` for a
+/// literal word and `s` for a literal string. The last kind repeats.
+fn operand_layout(opcode: u32) -> &'static str {
+    match opcode {
+        // OpNop, OpNoLine, OpMemoryModel, OpCapability
+        0 | 317 | 14 | 17 => "l",
+        // OpSourceContinued, OpSourceExtension, OpExtension, OpModuleProcessed
+        2 | 4 | 10 | 330 => "s",
+        // OpSource
+        3 => "llis",
+        // OpName, OpString, OpExtInstImport, OpTypeOpaque
+        5 | 7 | 11 | 31 => "is",
+        // OpMemberName
+        6 => "ils",
+        // OpLine
+        8 => "il",
+        // OpExtInst
+        12 => "iiili",
+        // OpEntryPoint
+        15 => "lisi",
+        // OpExecutionMode, OpTypeInt, OpTypeFloat, OpDecorate,
+        // OpMemberDecorate, OpSelectionMerge, OpTypeForwardPointer,
+        // OpLifetimeStart, OpLifetimeStop
+        16 | 21 | 22 | 71 | 72 | 247 | 39 | 256 | 257 => "il",
+        // OpTypeVector, OpTypeMatrix, OpTypeImage, OpConstant,
+        // OpConstantSampler, OpSpecConstant, OpLoopMerge, OpBranchConditional
+        23 | 24 | 25 | 43 | 45 | 50 | 246 => "iil",
+        250 => "iiil",
+        // OpTypePointer
+        32 => "ili",
+        // OpSpecConstantOp, OpFunction, OpVariable
+        52 | 54 | 59 => "iili",
+        // OpVectorShuffle, OpCompositeInsert
+        79 | 82 => "iiiil",
+        // OpCompositeExtract
+        81 => "iiil",
+        // OpImageSampleImplicitLod, OpImageSampleExplicitLod,
+        // OpImageSampleProjImplicitLod, OpImageSampleProjExplicitLod,
+        // OpImageFetch, OpImageRead
+        87 | 88 | 91 | 92 | 95 | 98 => "iiiili",
+        // OpImageSampleDrefImplicitLod, OpImageSampleDrefExplicitLod,
+        // OpImageSampleProjDrefImplicitLod, OpImageSampleProjDrefExplicitLod,
+        // OpImageGather, OpImageDrefGather
+        89 | 90 | 93 | 94 | 96 | 97 => "iiiiili",
+        // OpImageWrite
+        99 => "iiili",
+        // Group operations taking a scope ID and a literal group operation.
+        264..=270 | 342 | 349..=364 => "iiili",
+        // OpExecutionModeId, OpDecorateId
+        331 | 332 => "ili",
+        // OpDecorateString, OpMemberDecorateString
+        5632 => "ils",
+        5633 => "ills",
+        _ => "i",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    /// Encodes an instruction.
+    fn inst(opcode: u32, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | opcode];
+        words.extend_from_slice(operands);
+        words
+    }
+
+    /// Builds a module declaring a vertex shader `main` with IDs offset by
+    /// `base`, as generated by `generator`.
+    fn module(generator: u32, base: u32) -> Vec<u32> {
+        let (void, fn_type, main, label) = (base, base + 3, base + 1, base + 7);
+        let mut words = vec![0x0723_0203, 0x0001_0000, generator, base + 8, 0];
+        words.extend(inst(17, &[1]));
+        words.extend(inst(14, &[0, 1]));
+        // "main" as a literal string.
+        words.extend(inst(15, &[0, main, 0x6e69_616d, 0]));
+        words.extend(inst(5, &[main, 0x6e69_616d, 0]));
+        words.extend(inst(19, &[void]));
+        words.extend(inst(33, &[fn_type, void]));
+        words.extend(inst(54, &[void, main, 0, fn_type]));
+        words.extend(inst(248, &[label]));
+        words.extend(inst(253, &[]));
+        words.extend(inst(56, &[]));
+        words
+    }
+
+    #[test]
+    fn test_normalize() {
+        let normalized = normalize(&module(0x000d_000b, 1));
+        assert_eq!(normalized, normalize(&module(0x0008_0001, 42)));
+        assert_eq!(0, normalized[2]);
+        assert_eq!(5, normalized[3]);
+        // The entry point name is a string, not an ID.
+        assert_eq!(&[0, 1, 0x6e69_616d, 0], &normalized[11..15]);
+    }
+
+    #[test]
+    fn test_normalize_not_spirv() {
+        assert_eq!(vec![1, 2, 3], normalize(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_normalize_member_decorate() {
+        // Two members of a struct with their offsets swapped.
+        let block = |first, second| {
+            let mut words = vec![0x0723_0203, 0x0001_0000, 0, 4, 0];
+            words.extend(inst(72, &[3, first, 35, 0]));
+            words.extend(inst(72, &[3, second, 35, 16]));
+            words
+        };
+        assert_ne!(normalize(&block(0, 1)), normalize(&block(1, 0)));
+        assert_eq!(vec![0], id_operands(72, &[3, 1, 35, 16]));
+    }
+
+    #[test]
+    fn test_id_operands_memory_access() {
+        // OpLoad with Aligned memory access: the alignment is a literal.
+        assert_eq!(vec![0, 1, 2], id_operands(61, &[1, 2, 3, 0x2, 16]));
+        // OpStore with MakePointerAvailable: the scope is an ID.
+        assert_eq!(vec![0, 1, 3], id_operands(62, &[1, 2, 0x8, 4]));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let path = env::temp_dir().join(format!("shaderc-snapshot-{}.spv", process::id()));
+        let _ = fs::remove_file(&path);
+        assert_matches_snapshot(&module(0x000d_000b, 1), &path);
+        assert_matches_snapshot(&module(0x0008_0001, 42), &path);
+        let mut changed = module(0x000d_000b, 1);
+        changed[6] = 2;
+        let result = std::panic::catch_unwind(|| assert_matches_snapshot(&changed, &path));
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Instrumentation with the `tracing` crate.
+//!
+//! Compilation entry points run inside `info`-level spans named after the
+//! method, and each include resolution runs inside a `debug`-level `include`
+//! span nested in the span of the compilation requesting it. Besides the
+//! inputs recorded when the span is created, the following fields are
+//! recorded once the call finishes:
+//!
+//! * `output_size`: the number of bytes of the compilation output, or
+//!   `content_size`: the number of bytes of the resolved include.
+//! * `duration_us`: the time spent in the call, in microseconds.
+//! * `error`: the error message, if the call failed.
+
+use std::time::Instant;
+use tracing::Span;
+
+use crate::{CompilationArtifact, IncludeCallbackResult, Result};
+
+/// Creates the span of a compilation entry point, declaring the fields
+/// recorded by `in_compile_span` in addition to the given ones.
+macro_rules! compile_span {
+    ($name:expr, $($fields:tt)*) => {
+        tracing::info_span!(
+            $name,
+            $($fields)*,
+            output_size = tracing::field::Empty,
+            duration_us = tracing::field::Empty,
+            error = tracing::field::Empty,
+        )
+    };
+}
+
+/// Runs the compilation `f` inside `span`, recording its outcome on the span.
+pub(crate) fn in_compile_span<F>(span: Span, f: F) -> Result<CompilationArtifact>
+where
+    F: FnOnce() -> Result<CompilationArtifact>,
+{
+    let _entered = span.enter();
+    let start = Instant::now();
+    let result = f();
+    span.record("duration_us", start.elapsed().as_micros() as u64);
+    match result {
+        Ok(ref artifact) => span.record("output_size", artifact.len()),
+        Err(ref error) => span.record("error", tracing::field::display(error)),
+    };
+    result
+}
+
+/// Runs the include resolution `f` inside `span`, recording its outcome on the
+/// span.
+pub(crate) fn in_include_span<F>(span: Span, f: F) -> IncludeCallbackResult
+where
+    F: FnOnce() -> IncludeCallbackResult,
+{
+    let _entered = span.enter();
+    let start = Instant::now();
+    let result = f();
+    span.record("duration_us", start.elapsed().as_micros() as u64);
+    match result {
+        Ok(ref include) => {
+            span.record("resolved_name", include.resolved_name.as_str());
+            span.record("content_size", include.content.len())
+        }
+        Err(ref error) => span.record("error", error.as_str()),
+    };
+    result
+}
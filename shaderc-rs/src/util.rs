@@ -0,0 +1,261 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for loading SPIR-V binaries from bytes and checking their header.
+//!
+//! Casting a `&[u8]` to `&[u32]` with `slice::from_raw_parts` is undefined
+//! behavior unless the bytes happen to be 4-byte aligned, which nothing
+//! guarantees for `include_bytes!` or for buffers read from files.
+//! `words_from_bytes` borrows the bytes as words when it can and copies them
+//! otherwise, and `SpirvBytes` embeds bytes with the alignment of words.
+//!
+//! ```
+//! use shaderc::util::SpirvBytes;
+//!
+//! // Usually `SpirvBytes::new(*include_bytes!("shader.spv"))`.
+//! static SHADER: &SpirvBytes<[u8]> = &SpirvBytes::new([0x03, 0x02, 0x23, 0x07]);
+//!
+//! assert_eq!(&[0x0723_0203], &*SHADER.as_words().unwrap());
+//! ```
+//!
+//! Files read from disk may have been written on a big-endian machine.
+//! `detect_endianness` tells from the magic number, and `inspect_header`
+//! reads the header without validating the rest of the module.
+//!
+//! ```no_run
+//! use shaderc::util::{byte_swap, detect_endianness, inspect_header, words_from_bytes, Endianness};
+//!
+//! let bytes = std::fs::read("tri.frag.spv").unwrap();
+//! let mut words = words_from_bytes(&bytes).unwrap().into_owned();
+//! if detect_endianness(&bytes) == Some(Endianness::Big) {
+//!     byte_swap(&mut words);
+//! }
+//! let header = inspect_header(&words).unwrap();
+//! println!("SPIR-V {:?}, id bound {}", header.spirv_version(), header.bound);
+//! ```
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::{error, fmt};
+
+use crate::spirv_module::MAGIC;
+use crate::{ModuleError, SpirvVersion};
+
+/// Error for bytes which are not a whole number of 32-bit words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlignmentError {
+    len: usize,
+}
+
+impl AlignmentError {
+    /// Returns the number of bytes, which is not a multiple of 4.
+    pub fn byte_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for AlignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SPIR-V binary of {} bytes is not a whole number of words",
+            self.len
+        )
+    }
+}
+
+impl error::Error for AlignmentError {}
+
+/// Returns the little-endian words of a SPIR-V binary.
+///
+/// The bytes are borrowed when they are 4-byte aligned on a little-endian
+/// target, and copied otherwise.
+pub fn words_from_bytes(bytes: &[u8]) -> Result<Cow<'_, [u32]>, AlignmentError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(AlignmentError { len: bytes.len() });
+    }
+    if cfg!(target_endian = "little") {
+        // Every bit pattern is a valid u32, so reinterpreting the aligned
+        // middle of the bytes is sound.
+        let (prefix, words, _) = unsafe { bytes.align_to::<u32>() };
+        if prefix.is_empty() {
+            return Ok(Cow::Borrowed(words));
+        }
+    }
+    Ok(Cow::Owned(
+        bytes
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    ))
+}
+
+/// Bytes with the alignment of 32-bit words, for embedding SPIR-V binaries
+/// with `include_bytes!`.
+///
+/// A `&SpirvBytes<[u8; N]>` coerces to `&SpirvBytes<[u8]>`, so binaries of
+/// different sizes can be stored with the same type.
+#[repr(C)]
+pub struct SpirvBytes<B: ?Sized> {
+    _align: [u32; 0],
+    bytes: B,
+}
+
+impl<const N: usize> SpirvBytes<[u8; N]> {
+    /// Wraps `bytes`, usually `*include_bytes!("shader.spv")`.
+    pub const fn new(bytes: [u8; N]) -> SpirvBytes<[u8; N]> {
+        SpirvBytes { _align: [], bytes }
+    }
+}
+
+impl<B: AsRef<[u8]> + ?Sized> SpirvBytes<B> {
+    /// Returns the bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    /// Returns the words of the binary, borrowed on little-endian targets.
+    pub fn as_words(&self) -> Result<Cow<'_, [u32]>, AlignmentError> {
+        words_from_bytes(self.as_bytes())
+    }
+}
+
+impl<B: ?Sized> fmt::Debug for SpirvBytes<B>
+where
+    B: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SpirvBytes")
+            .field("len", &self.as_bytes().len())
+            .finish()
+    }
+}
+
+/// The byte order of a SPIR-V binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Returns the byte order of a SPIR-V binary, as told by its magic number,
+/// or `None` if it does not start with one.
+pub fn detect_endianness(bytes: &[u8]) -> Option<Endianness> {
+    match bytes.get(..4)? {
+        b if b == MAGIC.to_le_bytes() => Some(Endianness::Little),
+        b if b == MAGIC.to_be_bytes() => Some(Endianness::Big),
+        _ => None,
+    }
+}
+
+/// Reverses the bytes of every word, converting words read in the wrong
+/// byte order.
+pub fn byte_swap(words: &mut [u32]) {
+    for word in words {
+        *word = word.swap_bytes();
+    }
+}
+
+/// The header of a SPIR-V module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpirvHeader {
+    /// The version word, like `0x00010300` for SPIR-V 1.3.
+    pub version: u32,
+    /// The generator word: a tool ID in the high 16 bits, from the Khronos
+    /// registry, and the tool's own version in the low 16 bits.
+    pub generator: u32,
+    /// One more than the largest ID used in the module.
+    pub bound: u32,
+    /// Reserved, and zero in valid modules.
+    pub schema: u32,
+}
+
+impl SpirvHeader {
+    /// Returns the SPIR-V version, or `None` if it is not known to this
+    /// crate.
+    pub fn spirv_version(&self) -> Option<SpirvVersion> {
+        SpirvVersion::try_from(self.version).ok()
+    }
+
+    /// Returns the (major, minor) version numbers.
+    pub fn version_numbers(&self) -> (u8, u8) {
+        ((self.version >> 16) as u8, (self.version >> 8) as u8)
+    }
+}
+
+/// Returns the header of a SPIR-V module, without checking the rest of it.
+///
+/// Fails with `ModuleError::BadMagic` if the words do not start with the
+/// magic number, including when they are byte swapped, and with
+/// `ModuleError::Truncated` if there are fewer than the five header words.
+pub fn inspect_header(words: &[u32]) -> Result<SpirvHeader, ModuleError> {
+    if words.first() != Some(&MAGIC) {
+        return Err(ModuleError::BadMagic);
+    }
+    match *words {
+        [_, version, generator, bound, schema, ..] => Ok(SpirvHeader {
+            version,
+            generator,
+            bound,
+            schema,
+        }),
+        _ => Err(ModuleError::Truncated(words.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_from_bytes() {
+        static BYTES: &SpirvBytes<[u8]> = &SpirvBytes::new([3, 2, 0x23, 7, 1, 0, 0, 0]);
+        let words = BYTES.as_words().unwrap();
+        assert_eq!(&[0x0723_0203, 1], &*words);
+        if cfg!(target_endian = "little") {
+            assert!(matches!(words, Cow::Borrowed(_)));
+        }
+
+        // Bytes starting one past a word boundary are copied.
+        static UNALIGNED: &SpirvBytes<[u8]> = &SpirvBytes::new([0, 3, 2, 0x23, 7]);
+        let words = words_from_bytes(&UNALIGNED.as_bytes()[1..]).unwrap();
+        assert_eq!(&[0x0723_0203], &*words);
+        assert!(matches!(words, Cow::Owned(_)));
+
+        assert_eq!(Err(AlignmentError { len: 3 }), words_from_bytes(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_inspect_header() {
+        let mut words = vec![MAGIC, 0x0001_0300, 0x0008_000b, 42, 0, 0x0003_0011];
+        let header = inspect_header(&words).unwrap();
+        assert_eq!(Some(SpirvVersion::V1_3), header.spirv_version());
+        assert_eq!((1, 3), header.version_numbers());
+        assert_eq!(
+            (0x0008_000b, 42, 0),
+            (header.generator, header.bound, header.schema)
+        );
+        assert_eq!(Err(ModuleError::Truncated(4)), inspect_header(&words[..4]));
+
+        byte_swap(&mut words);
+        assert_eq!(Err(ModuleError::BadMagic), inspect_header(&words));
+        let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(Some(Endianness::Big), detect_endianness(&bytes));
+        assert_eq!(
+            Some(Endianness::Little),
+            detect_endianness(&MAGIC.to_le_bytes())
+        );
+        assert_eq!(None, detect_endianness(&[3, 2]));
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ubershader permutations.
+//!
+//! A `VariantSet` lists macro axes and the values each takes, and
+//! `Compiler::compile_variants` compiles a shader once per combination of
+//! values, on several threads when the options allow it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use crate::{
+    options_or_default, CompilationArtifact, CompileOptions, Compiler, IncludeCallbackResult,
+    IncludeType, Result, Settings, ShaderKind,
+};
+
+type SharedIncludeCallback<'a> =
+    Box<dyn Fn(&str, IncludeType, &str, usize) -> IncludeCallbackResult + Send + Sync + 'a>;
+
+/// The macro axes of an ubershader, compiled into one variant per
+/// combination of values with `Compiler::compile_variants`.
+///
+/// ```no_run
+/// use shaderc::{Compiler, ShaderKind, VariantSet};
+///
+/// let variants = VariantSet::new()
+///     .axis("SHADOWS", ["0", "1"])
+///     .axis("MSAA", ["1", "4"]);
+/// assert_eq!(4, variants.len());
+///
+/// let compiler = Compiler::new().unwrap();
+/// let source = "#version 450\nvoid main() {}";
+/// let artifacts =
+///     compiler.compile_variants(source, ShaderKind::Fragment, "lit.frag", "main", &variants, None);
+/// for (key, artifact) in &artifacts {
+///     println!("{}: {} bytes", key, artifact.as_ref().unwrap().len());
+/// }
+/// ```
+#[derive(Default)]
+pub struct VariantSet<'a> {
+    axes: Vec<(String, Vec<String>)>,
+    include_callback: Option<SharedIncludeCallback<'a>>,
+}
+
+/// The macro definitions of one variant of a `VariantSet`, in the order of
+/// the axes.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VariantKey(Vec<(String, String)>);
+
+impl<'a> VariantSet<'a> {
+    /// Returns an empty variant set, which has a single variant without
+    /// definitions.
+    pub fn new() -> VariantSet<'a> {
+        VariantSet::default()
+    }
+
+    /// Returns this set with the macro `name` taking each of `values`,
+    /// replacing the values of any previous axis of that name.
+    pub fn axis<I, S>(mut self, name: &str, values: I) -> VariantSet<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let values = values.into_iter().map(Into::into).collect();
+        match self.axes.iter_mut().find(|axis| axis.0 == name) {
+            Some(axis) => axis.1 = values,
+            None => self.axes.push((name.to_string(), values)),
+        }
+        self
+    }
+
+    /// Returns this set with `f` resolving the `#include` directives of all
+    /// variants, like `CompileOptions::set_include_callback`.
+    ///
+    /// Unlike an include callback of the base options, this one can be
+    /// called from several threads, so that variants are compiled in
+    /// parallel. Its results are shared between the variants of a
+    /// `compile_variants` call, so each include is resolved once.
+    pub fn include_callback<F>(mut self, f: F) -> VariantSet<'a>
+    where
+        F: Fn(&str, IncludeType, &str, usize) -> IncludeCallbackResult + Send + Sync + 'a,
+    {
+        self.include_callback = Some(Box::new(f));
+        self
+    }
+
+    /// Returns the number of variants.
+    pub fn len(&self) -> usize {
+        self.axes.iter().map(|axis| axis.1.len()).product()
+    }
+
+    /// Returns true if an axis has no values, leaving no variants.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the keys of all variants, varying the last axis fastest.
+    pub fn keys(&self) -> Vec<VariantKey> {
+        let mut keys = vec![VariantKey(Vec::new())];
+        for (name, values) in &self.axes {
+            keys = keys
+                .iter()
+                .flat_map(|key| {
+                    values.iter().map(move |value| {
+                        let mut defines = key.0.clone();
+                        defines.push((name.clone(), value.clone()));
+                        VariantKey(defines)
+                    })
+                })
+                .collect();
+        }
+        keys
+    }
+}
+
+impl VariantKey {
+    /// Returns the key of a variant with the macro definitions `defines`.
+    #[cfg(feature = "project")]
+    pub(crate) fn new(defines: Vec<(String, String)>) -> VariantKey {
+        VariantKey(defines)
+    }
+
+    /// Returns the macro definitions of this variant.
+    pub fn defines(&self) -> &[(String, String)] {
+        &self.0
+    }
+
+    /// Returns the value of the macro `name` in this variant.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|define| define.0 == name)
+            .map(|define| &*define.1)
+    }
+}
+
+/// Formats the key as comma-separated `NAME=value` definitions.
+impl fmt::Display for VariantKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+type IncludeKey = (String, IncludeType, String);
+
+/// An include callback caching its results.
+///
+/// Each include has a cell of its own, so that threads needing the same
+/// include wait for the one resolving it instead of calling the callback
+/// again, while other includes are resolved meanwhile.
+struct SharedIncludes<'a, 'b> {
+    callback: &'b SharedIncludeCallback<'a>,
+    results: Mutex<HashMap<IncludeKey, Arc<OnceLock<IncludeCallbackResult>>>>,
+}
+
+impl<'a, 'b> SharedIncludes<'a, 'b> {
+    fn resolve(
+        &self,
+        requested: &str,
+        include_type: IncludeType,
+        requesting: &str,
+        depth: usize,
+    ) -> IncludeCallbackResult {
+        let key = (requested.to_string(), include_type, requesting.to_string());
+        let cell = Arc::clone(self.results.lock().unwrap().entry(key).or_default());
+        cell.get_or_init(|| (self.callback)(requested, include_type, requesting, depth))
+            .clone()
+    }
+}
+
+impl Compiler {
+    /// Compiles the source string `source_text` to a SPIR-V binary module once
+    /// per variant of `variants`, with the macros of the variant defined on
+    /// top of `base_options`.
+    ///
+    /// Variants are compiled in parallel, except when `base_options` has an
+    /// include callback, which can only be called from the current thread;
+    /// use `VariantSet::include_callback` instead. Returns the result of each
+    /// variant by key.
+    pub fn compile_variants(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        variants: &VariantSet,
+        base_options: Option<&CompileOptions>,
+    ) -> BTreeMap<VariantKey, Result<CompilationArtifact>> {
+        let keys = variants.keys();
+        let compile = |key: &VariantKey, options: Result<CompileOptions>| {
+            let mut options = options?;
+            for (name, value) in key.defines() {
+                options.add_macro_definition(name, Some(value));
+            }
+            self.compile_into_spirv(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                Some(&options),
+            )
+        };
+
+        if let Some(base_options) = base_options.filter(|o| o.settings.include_callback) {
+            return keys
+                .into_iter()
+                .map(|key| {
+                    let result = compile(&key, options_or_default(Some(base_options)));
+                    (key, result)
+                })
+                .collect();
+        }
+
+        // Other threads create their options from the settings of the base
+        // options, as options objects cannot be shared between threads.
+        let settings = base_options.map_or_else(Settings::default, |o| o.settings.clone());
+        let includes = variants
+            .include_callback
+            .as_ref()
+            .map(|callback| SharedIncludes {
+                callback,
+                results: Mutex::new(HashMap::new()),
+            });
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(keys.len()));
+        let threads = thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(keys.len());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let key = match keys.get(i) {
+                        Some(key) => key,
+                        None => break,
+                    };
+                    let options = options_or_default(None).map(|mut options| {
+                        settings.apply_to(&mut options);
+                        if let Some(ref includes) = includes {
+                            options.set_include_callback(
+                                move |requested, ty, requesting, depth| {
+                                    includes.resolve(requested, ty, requesting, depth)
+                                },
+                            );
+                        }
+                        options
+                    });
+                    let result = compile(key, options);
+                    results.lock().unwrap().push((key.clone(), result));
+                });
+            }
+        });
+        results.into_inner().unwrap().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_set_keys() {
+        let variants = VariantSet::new()
+            .axis("SHADOWS", ["0", "1"])
+            .axis("MSAA", ["1", "4"])
+            .axis("SHADOWS", ["off", "on"]);
+        assert_eq!(4, variants.len());
+        let keys: Vec<String> = variants.keys().iter().map(|k| k.to_string()).collect();
+        assert_eq!(
+            vec![
+                "SHADOWS=off,MSAA=1",
+                "SHADOWS=off,MSAA=4",
+                "SHADOWS=on,MSAA=1",
+                "SHADOWS=on,MSAA=4",
+            ],
+            keys
+        );
+        assert_eq!(Some("4"), variants.keys()[3].get("MSAA"));
+        assert_eq!(None, variants.keys()[3].get("HDR"));
+    }
+
+    #[test]
+    fn test_variant_set_empty() {
+        assert_eq!(vec![VariantKey(Vec::new())], VariantSet::new().keys());
+        let variants = VariantSet::new().axis("A", Vec::<String>::new());
+        assert!(variants.is_empty());
+        assert!(variants.keys().is_empty());
+    }
+
+    #[test]
+    fn test_shared_includes_resolve_once() {
+        let calls = AtomicUsize::new(0);
+        let callback: SharedIncludeCallback = Box::new(|name, _, _, _| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(20));
+            Ok(crate::ResolvedInclude {
+                resolved_name: name.to_string(),
+                content: String::new(),
+            })
+        });
+        let includes = SharedIncludes {
+            callback: &callback,
+            results: Mutex::new(HashMap::new()),
+        };
+        thread::scope(|scope| {
+            for i in 0..8 {
+                let includes = &includes;
+                scope.spawn(move || {
+                    let name = if i % 2 == 0 { "a.h" } else { "b.h" };
+                    let result = includes.resolve(name, IncludeType::Relative, "main.frag", 1);
+                    assert_eq!(name, result.unwrap().resolved_name);
+                });
+            }
+        });
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_compile_variants() {
+        let compiler = Compiler::new().unwrap();
+        let source = "#version 450\n#include \"defs.h\"\nvoid main() { int x = A + B; }";
+        let variants = VariantSet::new()
+            .axis("A", ["1", "2"])
+            .axis("B", ["3", "4", "5"])
+            .include_callback(|name, _, _, _| {
+                Ok(crate::ResolvedInclude {
+                    resolved_name: name.to_string(),
+                    content: String::new(),
+                })
+            });
+        let results = compiler.compile_variants(
+            source,
+            ShaderKind::Vertex,
+            "shader.glsl",
+            "main",
+            &variants,
+            None,
+        );
+        assert_eq!(6, results.len());
+        assert!(results.values().all(|result| result.is_ok()));
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between version numbers and the version enums.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::{error, fmt};
+
+use crate::{EnvVersion, GlslProfile, SpirvVersion, TargetEnv};
+
+/// Error returned when converting a number which is not a known version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownVersionError {
+    kind: &'static str,
+    value: u32,
+}
+
+impl fmt::Display for UnknownVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} {:#x}", self.kind, self.value)
+    }
+}
+
+impl error::Error for UnknownVersionError {}
+
+pub(crate) const SPIRV_VERSIONS: [SpirvVersion; 7] = [
+    SpirvVersion::V1_0,
+    SpirvVersion::V1_1,
+    SpirvVersion::V1_2,
+    SpirvVersion::V1_3,
+    SpirvVersion::V1_4,
+    SpirvVersion::V1_5,
+    SpirvVersion::V1_6,
+];
+
+pub(crate) const VULKAN_VERSIONS: [EnvVersion; 4] = [
+    EnvVersion::Vulkan1_0,
+    EnvVersion::Vulkan1_1,
+    EnvVersion::Vulkan1_2,
+    EnvVersion::Vulkan1_3,
+];
+
+impl SpirvVersion {
+    /// Returns the newest SPIR-V version known to this crate.
+    pub fn latest() -> SpirvVersion {
+        SpirvVersion::V1_6
+    }
+}
+
+/// Converts the version word of a SPIR-V module header, like `0x00010300`
+/// for SPIR-V 1.3.
+impl TryFrom<u32> for SpirvVersion {
+    type Error = UnknownVersionError;
+
+    fn try_from(value: u32) -> Result<Self, UnknownVersionError> {
+        SPIRV_VERSIONS
+            .iter()
+            .copied()
+            .find(|&version| version as u32 == value)
+            .ok_or(UnknownVersionError {
+                kind: "SPIR-V version",
+                value,
+            })
+    }
+}
+
+impl EnvVersion {
+    /// Returns the SPIR-V version generated by default for this target
+    /// environment version: the newest one it requires to be supported, like
+    /// SPIR-V 1.3 for Vulkan 1.1.
+    pub fn default_spirv_version(self) -> SpirvVersion {
+        match self {
+            EnvVersion::Vulkan1_0 => SpirvVersion::V1_0,
+            EnvVersion::Vulkan1_1 => SpirvVersion::V1_3,
+            EnvVersion::Vulkan1_2 => SpirvVersion::V1_5,
+            EnvVersion::Vulkan1_3 => SpirvVersion::V1_6,
+            EnvVersion::OpenGL4_5 | EnvVersion::WebGPU => SpirvVersion::V1_0,
+        }
+    }
+
+    /// Returns the newest Vulkan version not newer than the Vulkan API
+    /// version `api_version`, as returned by `vkEnumerateInstanceVersion`.
+    ///
+    /// The variant and patch numbers are ignored, so that the API version of
+    /// any Vulkan 1.2 implementation gives `Vulkan1_2`. Vulkan versions newer
+    /// than the ones known to this crate give the newest known one. Returns
+    /// `None` for versions before Vulkan 1.0.
+    pub fn from_vulkan_api_version(api_version: u32) -> Option<EnvVersion> {
+        let major = (api_version >> 22) & 0x7f;
+        let minor = (api_version >> 12) & 0x3ff;
+        match major {
+            0 => None,
+            1 => Some(VULKAN_VERSIONS[(minor as usize).min(VULKAN_VERSIONS.len() - 1)]),
+            _ => VULKAN_VERSIONS.last().copied(),
+        }
+    }
+}
+
+impl TargetEnv {
+    /// Returns the SPIR-V versions the target environment `env` at `version`
+    /// requires to be supported, oldest first.
+    ///
+    /// Newer versions may be consumed through extensions, like SPIR-V 1.4 on
+    /// Vulkan 1.1 with `VK_KHR_spirv_1_4`. Returns an empty slice if `version`
+    /// is not a version of this environment.
+    pub fn supported_spirv_versions(self, version: EnvVersion) -> &'static [SpirvVersion] {
+        static VERSIONS: [SpirvVersion; 7] = SPIRV_VERSIONS;
+        let is_vulkan = VULKAN_VERSIONS.contains(&version);
+        let matches = match self {
+            TargetEnv::Vulkan => is_vulkan,
+            TargetEnv::OpenGL | TargetEnv::OpenGLCompat => version == EnvVersion::OpenGL4_5,
+        };
+        if !matches {
+            return &[];
+        }
+        let newest = version.default_spirv_version();
+        let len = VERSIONS.iter().take_while(|&&v| v <= newest).count();
+        &VERSIONS[..len]
+    }
+}
+
+/// Converts the integer value of a target environment version, as passed to
+/// `CompileOptions::set_target_env`.
+impl TryFrom<u32> for EnvVersion {
+    type Error = UnknownVersionError;
+
+    fn try_from(value: u32) -> Result<Self, UnknownVersionError> {
+        VULKAN_VERSIONS
+            .iter()
+            .chain(&[EnvVersion::OpenGL4_5, EnvVersion::WebGPU])
+            .copied()
+            .find(|&version| version as u32 == value)
+            .ok_or(UnknownVersionError {
+                kind: "target environment version",
+                value,
+            })
+    }
+}
+
+/// Orders versions of the same environment: Vulkan versions compare by age,
+/// while versions of different environments, like `Vulkan1_0` and
+/// `OpenGL4_5`, are not comparable.
+impl PartialOrd for EnvVersion {
+    fn partial_cmp(&self, other: &EnvVersion) -> Option<Ordering> {
+        let is_vulkan = |version: &EnvVersion| VULKAN_VERSIONS.contains(version);
+        if self == other {
+            Some(Ordering::Equal)
+        } else if is_vulkan(self) && is_vulkan(other) {
+            (*self as u32).partial_cmp(&(*other as u32))
+        } else {
+            None
+        }
+    }
+}
+
+/// The `#version` numbers glslang knows: the desktop GLSL versions and the
+/// ESSL versions 100, 300, 310 and 320.
+const GLSL_VERSIONS: [u32; 17] = [
+    100, 110, 120, 130, 140, 150, 300, 310, 320, 330, 400, 410, 420, 430, 440, 450, 460,
+];
+
+/// Error returned by `try_parse_version_profile`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VersionParseError {
+    /// The string does not start with a valid version number.
+    MalformedVersion(String),
+    /// The version number is not a GLSL or ESSL version known to glslang.
+    UnknownVersion(u32),
+    /// The version number is followed by something other than a profile.
+    UnknownProfile(String),
+}
+
+impl fmt::Display for VersionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VersionParseError::MalformedVersion(ref s) => {
+                write!(f, "malformed GLSL version in `{s}`")
+            }
+            VersionParseError::UnknownVersion(version) => {
+                write!(f, "unknown GLSL version {version}")
+            }
+            VersionParseError::UnknownProfile(ref s) => write!(f, "unknown GLSL profile `{s}`"),
+        }
+    }
+}
+
+impl error::Error for VersionParseError {}
+
+/// Parses the version and profile from the given `string`, like
+/// `parse_version_profile`, but reports why parsing failed.
+///
+/// The string is a version number optionally followed by `core`,
+/// `compatibility` or `es`, like `450core` or `140`, and the version must be
+/// one glslang knows, like `450` but not `451`.
+pub fn try_parse_version_profile(string: &str) -> Result<(u32, GlslProfile), VersionParseError> {
+    let digits = string
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(string.len());
+    let version = match string[..digits].parse() {
+        Ok(version) if version > 0 => version,
+        _ => return Err(VersionParseError::MalformedVersion(string.to_string())),
+    };
+    if !GLSL_VERSIONS.contains(&version) {
+        return Err(VersionParseError::UnknownVersion(version));
+    }
+    let profile = match &string[digits..] {
+        "" => GlslProfile::None,
+        "core" => GlslProfile::Core,
+        "compatibility" => GlslProfile::Compatibility,
+        "es" => GlslProfile::Es,
+        other => return Err(VersionParseError::UnknownProfile(other.to_string())),
+    };
+    Ok((version, profile))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spirv_version_try_from() {
+        assert_eq!(Ok(SpirvVersion::V1_3), SpirvVersion::try_from(0x0001_0300));
+        assert_eq!(
+            "unknown SPIR-V version 0x10700",
+            SpirvVersion::try_from(0x0001_0700).unwrap_err().to_string()
+        );
+        assert!(SpirvVersion::V1_0 < SpirvVersion::latest());
+        for &version in &SPIRV_VERSIONS {
+            assert_eq!(Ok(version), SpirvVersion::try_from(version as u32));
+        }
+    }
+
+    #[test]
+    fn test_env_version_try_from() {
+        assert_eq!(Ok(EnvVersion::OpenGL4_5), EnvVersion::try_from(450));
+        assert_eq!(
+            Ok(EnvVersion::Vulkan1_2),
+            EnvVersion::try_from(EnvVersion::Vulkan1_2 as u32)
+        );
+        assert!(EnvVersion::try_from(460).is_err());
+        assert!(EnvVersion::Vulkan1_1 < EnvVersion::Vulkan1_3);
+    }
+
+    #[test]
+    fn test_env_version_order_within_family() {
+        assert!(EnvVersion::Vulkan1_3 > EnvVersion::Vulkan1_0);
+        assert!(EnvVersion::OpenGL4_5 <= EnvVersion::OpenGL4_5);
+        assert_eq!(
+            None,
+            EnvVersion::Vulkan1_0.partial_cmp(&EnvVersion::OpenGL4_5)
+        );
+        assert_eq!(None, EnvVersion::WebGPU.partial_cmp(&EnvVersion::Vulkan1_3));
+    }
+
+    #[test]
+    fn test_env_version_from_vulkan_api_version() {
+        let api_version =
+            |major: u32, minor: u32, patch: u32| (major << 22) | (minor << 12) | patch;
+        assert_eq!(
+            Some(EnvVersion::Vulkan1_2),
+            EnvVersion::from_vulkan_api_version(api_version(1, 2, 189))
+        );
+        assert_eq!(
+            Some(EnvVersion::Vulkan1_3),
+            EnvVersion::from_vulkan_api_version(api_version(1, 4, 0))
+        );
+        assert_eq!(
+            Some(EnvVersion::Vulkan1_0),
+            EnvVersion::from_vulkan_api_version(1 << 29 | api_version(1, 0, 0))
+        );
+        assert_eq!(None, EnvVersion::from_vulkan_api_version(0));
+    }
+
+    #[test]
+    fn test_try_parse_version_profile_unknown_version() {
+        assert_eq!(
+            Err(VersionParseError::UnknownVersion(451)),
+            try_parse_version_profile("451core")
+        );
+        assert_eq!(
+            "unknown GLSL version 470",
+            try_parse_version_profile("470").unwrap_err().to_string()
+        );
+        for &version in &GLSL_VERSIONS {
+            let string = format!("{}", version);
+            assert_eq!(
+                Ok((version, GlslProfile::None)),
+                try_parse_version_profile(&string)
+            );
+        }
+    }
+
+    #[test]
+    fn test_supported_spirv_versions() {
+        assert_eq!(
+            SpirvVersion::V1_3,
+            EnvVersion::Vulkan1_1.default_spirv_version()
+        );
+        assert_eq!(
+            &[SpirvVersion::V1_0],
+            TargetEnv::Vulkan.supported_spirv_versions(EnvVersion::Vulkan1_0)
+        );
+        assert_eq!(
+            Some(&SpirvVersion::V1_5),
+            TargetEnv::Vulkan
+                .supported_spirv_versions(EnvVersion::Vulkan1_2)
+                .last()
+        );
+        assert_eq!(
+            &[SpirvVersion::V1_0],
+            TargetEnv::OpenGL.supported_spirv_versions(EnvVersion::OpenGL4_5)
+        );
+        assert!(TargetEnv::OpenGL
+            .supported_spirv_versions(EnvVersion::Vulkan1_3)
+            .is_empty());
+        assert!(TargetEnv::Vulkan
+            .supported_spirv_versions(EnvVersion::WebGPU)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_try_parse_version_profile() {
+        assert_eq!(
+            Ok((310, GlslProfile::Es)),
+            try_parse_version_profile("310es")
+        );
+        assert_eq!(
+            Ok((140, GlslProfile::None)),
+            try_parse_version_profile("140")
+        );
+        assert_eq!(
+            Err(VersionParseError::UnknownProfile("cor".to_string())),
+            try_parse_version_profile("450cor")
+        );
+        assert_eq!(
+            Err(VersionParseError::MalformedVersion("core".to_string())),
+            try_parse_version_profile("core")
+        );
+        assert_matches!(
+            try_parse_version_profile("99999999999es"),
+            Err(VersionParseError::MalformedVersion(_))
+        );
+        assert_matches!(
+            try_parse_version_profile(""),
+            Err(VersionParseError::MalformedVersion(_))
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rebuilding when source files change.
+//!
+//! Available with the `watch` feature.
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Time to wait for more changes after one is seen, so that a save touching
+/// several files triggers a single rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Runs `build`, then runs it again each time one of the files it read
+/// changes.
+///
+/// `build` adds the paths of the files it read, like shader sources and their
+/// includes, to the set it is given. It returns `ControlFlow::Break` to stop
+/// watching. Returns an error if the files cannot be watched.
+pub fn watch<F>(mut build: F) -> notify::Result<()>
+where
+    F: FnMut(&mut BTreeSet<PathBuf>) -> ControlFlow<()>,
+{
+    loop {
+        let mut files = BTreeSet::new();
+        if build(&mut files).is_break() {
+            return Ok(());
+        }
+
+        // Watch the parent directories rather than the files themselves, so
+        // that editors replacing files on save are noticed.
+        let watched: BTreeSet<(PathBuf, OsString)> =
+            files.iter().filter_map(|f| split(f)).collect();
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        let dirs: BTreeSet<&PathBuf> = watched.iter().map(|(dir, _)| dir).collect();
+        for dir in dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        loop {
+            let event = match rx.recv() {
+                Ok(event) => event?,
+                Err(_) => return Ok(()),
+            };
+            if let EventKind::Access(_) = event.kind {
+                continue;
+            }
+            if event
+                .paths
+                .iter()
+                .filter_map(|path| split(path))
+                .any(|entry| watched.contains(&entry))
+            {
+                break;
+            }
+        }
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+    }
+}
+
+/// Splits `path` into its canonical parent directory and file name.
+fn split(path: &Path) -> Option<(PathBuf, OsString)> {
+    let name = path.file_name()?.to_os_string();
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    Some((fs::canonicalize(dir).ok()?, name))
+}
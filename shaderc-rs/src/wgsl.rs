@@ -0,0 +1,291 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WGSL compilation through the naga front-end.
+//!
+//! libshaderc only reads GLSL and HLSL, so `SourceLanguage` cannot name WGSL:
+//! its values are passed to the library. `WgslCompiler` instead routes the
+//! sources named `*.wgsl` to naga and every other source to a fallback
+//! compiler, behind the single `ShaderCompiler` interface.
+
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::path::Path;
+
+use naga::back::spv;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+use crate::{
+    CompilationArtifact, CompileOptions, Compiler, Diagnostic, EnvVersion, Error, Result, Severity,
+    ShaderCompiler, ShaderKind, SpirvVersion,
+};
+
+/// A `ShaderCompiler` compiling WGSL sources with naga and other sources with
+/// a fallback compiler.
+///
+/// A source is WGSL if its input file name has the `wgsl` extension. Errors
+/// in WGSL sources are reported as `Error::CompilationError` with messages in
+/// the form of glslang's, so `Error::diagnostics` parses them the same way.
+///
+/// ```no_run
+/// use shaderc::{Compiler, ShaderCompiler, ShaderKind, WgslCompiler};
+///
+/// let compiler = WgslCompiler::new(Compiler::new().unwrap());
+/// let source = "@compute @workgroup_size(1) fn main() {}";
+/// let artifact = compiler
+///     .compile_into_spirv(source, ShaderKind::Compute, "shader.wgsl", "main", None)
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct WgslCompiler<C = Compiler> {
+    fallback: C,
+}
+
+impl<C: ShaderCompiler> WgslCompiler<C> {
+    /// Returns a compiler compiling the sources which are not WGSL with
+    /// `fallback`.
+    pub fn new(fallback: C) -> WgslCompiler<C> {
+        WgslCompiler { fallback }
+    }
+
+    /// Returns the compiler compiling the sources which are not WGSL.
+    pub fn fallback(&self) -> &C {
+        &self.fallback
+    }
+}
+
+/// Returns whether the input file `input_file_name` is a WGSL source.
+fn is_wgsl(input_file_name: &str) -> bool {
+    Path::new(input_file_name)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("wgsl"))
+}
+
+/// Returns the compilation error for an error at `location` in
+/// `input_file_name`.
+fn compilation_error(
+    input_file_name: &str,
+    location: Option<naga::SourceLocation>,
+    message: String,
+) -> Error {
+    let diagnostic = Diagnostic {
+        severity: Severity::Error,
+        file: Some(input_file_name.to_string()),
+        line: location.map(|location| location.line_number),
+        message,
+        include_stack: Vec::new(),
+    };
+    Error::CompilationError(1, diagnostic.to_string())
+}
+
+/// Returns the message of `error` followed by the messages of its sources.
+fn error_chain(error: &dyn StdError) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        message.push_str(": ");
+        message.push_str(&error.to_string());
+        source = error.source();
+    }
+    message
+}
+
+/// Returns the SPIR-V version naga generates for `options`: the targeted one,
+/// or the default one of the target environment.
+fn spirv_version(options: Option<&CompileOptions>) -> SpirvVersion {
+    let settings = options.map(|options| &options.settings);
+    if let Some(version) = settings.and_then(|settings| settings.target_spirv) {
+        return version;
+    }
+    settings
+        .and_then(|settings| settings.target_env)
+        .and_then(|(_, version)| EnvVersion::try_from(version).ok())
+        .map_or(SpirvVersion::V1_0, EnvVersion::default_spirv_version)
+}
+
+/// Compiles the WGSL source `source_text` to SPIR-V words.
+///
+/// Only the entry point `entry_point_name` is kept when `shader_kind` names a
+/// stage. `InferFromSource` keeps every entry point of the module.
+fn compile_wgsl(
+    source_text: &str,
+    shader_kind: ShaderKind,
+    input_file_name: &str,
+    entry_point_name: &str,
+    options: Option<&CompileOptions>,
+) -> Result<Vec<u32>> {
+    let pipeline = match shader_kind {
+        ShaderKind::InferFromSource => None,
+        kind => Some(spv::PipelineOptions {
+            shader_stage: naga::ShaderStage::try_from(kind)?,
+            entry_point: entry_point_name.to_string(),
+        }),
+    };
+    let module = naga::front::wgsl::parse_str(source_text).map_err(|error| {
+        compilation_error(
+            input_file_name,
+            error.location(source_text),
+            error.message().to_string(),
+        )
+    })?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|error| {
+            compilation_error(
+                input_file_name,
+                error.location(source_text),
+                error_chain(error.as_inner()),
+            )
+        })?;
+    let version = spirv_version(options) as u32;
+    let spv_options = spv::Options {
+        lang_version: ((version >> 16) as u8, (version >> 8) as u8),
+        ..spv::Options::default()
+    };
+    spv::write_vec(&module, &info, &spv_options, pipeline.as_ref())
+        .map_err(|error| compilation_error(input_file_name, None, error_chain(&error)))
+}
+
+impl<C: ShaderCompiler> ShaderCompiler for WgslCompiler<C> {
+    fn compile_into_spirv(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        if !is_wgsl(input_file_name) {
+            return self.fallback.compile_into_spirv(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                additional_options,
+            );
+        }
+        compile_wgsl(
+            source_text,
+            shader_kind,
+            input_file_name,
+            entry_point_name,
+            additional_options,
+        )
+        .map(|words| CompilationArtifact::from_binary(words).with_input_file_name(input_file_name))
+    }
+
+    /// Compiles the source string `source_text` to SPIR-V assembly text.
+    ///
+    /// naga does not disassemble SPIR-V, so WGSL sources give
+    /// `Error::InternalError`.
+    fn compile_into_spirv_assembly(
+        &self,
+        source_text: &str,
+        shader_kind: ShaderKind,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        if !is_wgsl(input_file_name) {
+            return self.fallback.compile_into_spirv_assembly(
+                source_text,
+                shader_kind,
+                input_file_name,
+                entry_point_name,
+                additional_options,
+            );
+        }
+        Err(Error::InternalError(
+            "SPIR-V assembly output is not supported for WGSL sources".to_string(),
+        ))
+    }
+
+    /// Preprocesses the source string `source_text`.
+    ///
+    /// WGSL has no preprocessor, so WGSL sources are returned unchanged.
+    fn preprocess(
+        &self,
+        source_text: &str,
+        input_file_name: &str,
+        entry_point_name: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        if !is_wgsl(input_file_name) {
+            return self.fallback.preprocess(
+                source_text,
+                input_file_name,
+                entry_point_name,
+                additional_options,
+            );
+        }
+        Ok(CompilationArtifact::from_text(source_text.to_string())
+            .with_input_file_name(input_file_name))
+    }
+
+    fn assemble(
+        &self,
+        source_assembly: &str,
+        additional_options: Option<&CompileOptions>,
+    ) -> Result<CompilationArtifact> {
+        self.fallback.assemble(source_assembly, additional_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockCompiler;
+
+    const COMPUTE: &str = "@compute @workgroup_size(1)\nfn main() {}\n";
+
+    #[test]
+    fn test_wgsl_compile() {
+        let fallback =
+            MockCompiler::new().with_default_result(Ok(CompilationArtifact::from_binary(vec![])));
+        let compiler = WgslCompiler::new(fallback);
+        let artifact = compiler
+            .compile_into_spirv(COMPUTE, ShaderKind::Compute, "a.wgsl", "main", None)
+            .unwrap();
+        let words = artifact.as_binary();
+        assert_eq!(0x0723_0203, words[0]);
+        assert_eq!(SpirvVersion::V1_0 as u32, words[1]);
+        assert!(compiler.fallback().calls().is_empty());
+
+        assert!(compiler
+            .compile_into_spirv(COMPUTE, ShaderKind::Compute, "a.comp", "main", None)
+            .is_ok());
+        assert_eq!(1, compiler.fallback().calls().len());
+    }
+
+    #[test]
+    fn test_wgsl_diagnostics() {
+        let compiler = WgslCompiler::new(MockCompiler::new());
+        let source = "@compute @workgroup_size(1)\nfn main() {\n    let x = y;\n}\n";
+        let error = compiler
+            .compile_into_spirv(source, ShaderKind::Compute, "a.wgsl", "main", None)
+            .err()
+            .unwrap();
+        let diagnostics = error.diagnostics();
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(Some("a.wgsl"), diagnostics[0].file.as_deref());
+        assert_eq!(Some(3), diagnostics[0].line);
+
+        assert_matches!(
+            compiler
+                .compile_into_spirv(COMPUTE, ShaderKind::Geometry, "a.wgsl", "main", None)
+                .err(),
+            Some(Error::InvalidStage(_))
+        );
+    }
+}
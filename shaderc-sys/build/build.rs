@@ -13,17 +13,21 @@
 // limitations under the License.
 
 mod cmd_finder;
+// Shared with the library, which exposes it with the `probe` feature; parts
+// of it are only used there.
+#[allow(dead_code)]
+#[path = "../src/probe.rs"]
+mod probe;
 
 use std::env;
-use std::env::consts;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-static SHADERC_STATIC_LIB: &str = "shaderc_combined";
-static SHADERC_SHARED_LIB: &str = "shaderc_shared";
-static SHADERC_STATIC_LIB_FILE_UNIX: &str = "libshaderc_combined.a";
-static SHADERC_STATIC_LIB_FILE_WIN: &str = "shaderc_combined.lib";
-static MIN_VULKAN_SDK_VERSION: u32 = 182;
+use probe::{LibKind, Origin, ProbeConfig, ProbeError};
+
+static SHADERC_STATIC_LIB: &str = probe::STATIC_LIB;
+static SHADERC_STATIC_LIB_FILE_UNIX: &str = probe::STATIC_LIB_FILE_UNIX;
+static SHADERC_STATIC_LIB_FILE_WIN: &str = probe::STATIC_LIB_FILE_WIN;
 
 fn get_apple_sdk_path() -> Option<PathBuf> {
     let target = std::env::var("TARGET").unwrap();
@@ -57,6 +61,9 @@ fn get_apple_sdk_path() -> Option<PathBuf> {
 }
 
 fn build_shaderc_unix(shaderc_dir: &PathBuf, use_ninja: bool, target_os: String) -> PathBuf {
+    // When cross-compiling, for instance to riscv64 or loongarch64, cmake-rs
+    // sets CMAKE_SYSTEM_NAME and CMAKE_SYSTEM_PROCESSOR and picks the C and
+    // C++ compilers of the target, like riscv64-linux-gnu-gcc.
     let mut config = cmake::Config::new(shaderc_dir);
     config
         .profile("Release")
@@ -81,10 +88,112 @@ fn build_shaderc_unix(shaderc_dir: &PathBuf, use_ninja: bool, target_os: String)
         }
     }
 
+    let sanitizers = sanitizers();
+    if !sanitizers.is_empty() {
+        // Keep debug info and frame pointers for readable sanitizer reports.
+        let flags = format!(
+            "-fsanitize={} -fno-omit-frame-pointer",
+            sanitizers.join(",")
+        );
+        config
+            .profile("RelWithDebInfo")
+            .cflag(&flags)
+            .cxxflag(&flags);
+    }
+
+    if uses_zig() {
+        // zig cc takes its target from the wrapper cargo-zigbuild generates,
+        // and rejects the Rust target triple cc-rs would pass as `--target`.
+        config.no_default_flags(true);
+        if let Some(ar) = target_tool_env("AR") {
+            config.define("CMAKE_AR", ar);
+        }
+        if let Some(ranlib) = target_tool_env("RANLIB") {
+            config.define("CMAKE_RANLIB", ranlib);
+        }
+    }
+
     config.build()
 }
 
+/// Returns the sanitizers to build shaderc from source with, given as a
+/// comma-separated list in `SHADERC_SANITIZE`.
+fn sanitizers() -> Vec<String> {
+    let Ok(list) = env::var("SHADERC_SANITIZE") else {
+        return Vec::new();
+    };
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| match name {
+            "address" | "undefined" => name.to_owned(),
+            _ => panic!(
+                "shaderc: unsupported sanitizer '{}' in SHADERC_SANITIZE; \
+                 expected address or undefined",
+                name
+            ),
+        })
+        .collect()
+}
+
+/// Links the runtimes of the sanitizers shaderc was built with.
+///
+/// GCC's runtimes are libraries of their own. Clang's come with the compiler
+/// and are linked by rustc when building with `-Zsanitizer`.
+fn emit_sanitizer_link() {
+    let sanitizers = sanitizers();
+    if sanitizers.is_empty() {
+        return;
+    }
+    let is_clang = env::var("CARGO_CFG_TARGET_VENDOR").unwrap() == "apple"
+        || target_tool_env("CXX").is_some_and(|cxx| cxx.contains("clang"));
+    if is_clang {
+        println!(
+            "cargo:warning=shaderc: built with Clang sanitizers; build with \
+             RUSTFLAGS=-Zsanitizer={} to link their runtime",
+            sanitizers[0]
+        );
+        return;
+    }
+    for sanitizer in &sanitizers {
+        let runtime = match sanitizer.as_str() {
+            "address" => "asan",
+            _ => "ubsan",
+        };
+        println!("cargo:rustc-link-lib=dylib={runtime}");
+    }
+}
+
+/// Returns the value of the environment variable naming the tool `var` (like
+/// `CXX`) for the target, looked up the way cc-rs does.
+fn target_tool_env(var: &str) -> Option<String> {
+    let target = env::var("TARGET").unwrap();
+    let host = env::var("HOST").unwrap();
+    let kind = if target == host { "HOST" } else { "TARGET" };
+    [
+        format!("{var}_{target}"),
+        format!("{var}_{}", target.replace('-', "_")),
+        format!("{kind}_{var}"),
+        var.to_owned(),
+    ]
+    .iter()
+    .find_map(|name| env::var(name).ok())
+}
+
+/// Returns whether the C++ compiler is zig, as set up by cargo-zigbuild.
+fn uses_zig() -> bool {
+    target_tool_env("CXX").is_some_and(|cxx| {
+        let program = cxx.split_whitespace().next().unwrap_or_default();
+        Path::new(program)
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("zig"))
+    })
+}
+
 fn build_shaderc_msvc(shaderc_dir: &PathBuf) -> PathBuf {
+    if !sanitizers().is_empty() {
+        println!("cargo:warning=shaderc: SHADERC_SANITIZE is ignored with MSVC");
+    }
     let linkage = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
 
     let mut config = cmake::Config::new(shaderc_dir);
@@ -103,6 +212,15 @@ fn build_shaderc_msvc(shaderc_dir: &PathBuf) -> PathBuf {
         .define("SPIRV_WERROR", "OFF")
         .generator("Ninja");
 
+    // Ninja builds for whichever architecture the compiler targets, so only
+    // CMake needs telling when cross-compiling to Windows on ARM.
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    if target_arch == "aarch64" && !host_target().starts_with("aarch64") {
+        config
+            .define("CMAKE_SYSTEM_NAME", "Windows")
+            .define("CMAKE_SYSTEM_PROCESSOR", "ARM64");
+    }
+
     // cmake-rs tries to be clever on Windows by injecting several
     // C/C++ flags, which causes problems. So I have to manually
     // define CMAKE_*_FLAGS_* here to suppress that.
@@ -127,36 +245,6 @@ fn build_shaderc_msvc(shaderc_dir: &PathBuf) -> PathBuf {
     config.build()
 }
 
-fn check_vulkan_sdk_version(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let xml = std::fs::read_to_string(
-        path.join("share")
-            .join("vulkan")
-            .join("registry")
-            .join("vk.xml"),
-    )
-    .map_err(|error| format!("could not read vk.xml in $VULKAN_SDK: {error}"))?;
-    let tree = roxmltree::Document::parse(&xml)
-        .map_err(|error| format!("vk.xml in $VULKAN_SDK is not a valid XML document: {error}"))?;
-    let version = tree
-        .root()
-        .descendants()
-        .find(|node| node.has_tag_name("types"))
-        .ok_or("invalid vk.xml in $VULKAN_SDK is invalid: missing <types> node")?
-        .descendants()
-        .find(|node| node.text() == Some("VK_HEADER_VERSION"))
-        .ok_or("invalid vk.xml in $VULKAN_SDK is invalid: missing VK_HEADER_VERSION node")?
-        .tail()
-        .ok_or("invalid vk.xml in $VULKAN_SDK: no vesion string")?
-        .trim()
-        .parse::<u32>()?;
-    if version < MIN_VULKAN_SDK_VERSION {
-        return Err(Box::from(format!(
-            "requires Vulkan SDK patch version to be at least {MIN_VULKAN_SDK_VERSION}"
-        )));
-    }
-    Ok(())
-}
-
 fn host_target() -> String {
     let output = std::process::Command::new("rustc")
         .arg("-vV")
@@ -187,180 +275,278 @@ fn main() {
 
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
     let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap();
-    let config_build_from_source = env::var("CARGO_FEATURE_BUILD_FROM_SOURCE").is_ok();
-    let config_prefer_static_linking = env::var("CARGO_FEATURE_PREFER_STATIC_LINKING").is_ok();
-    let has_explicit_set_search_dir = env::var("SHADERC_LIB_DIR").is_ok();
-
-    // Initialize explicit shaderc search directory first.
-    let mut search_dir = if let Ok(lib_dir) = env::var("SHADERC_LIB_DIR") {
-        println!("cargo:warning=shaderc: searching native shaderc libraries in '{lib_dir}'");
-        Some(lib_dir)
-    } else {
-        None
-    };
 
-    // Try to find native shaderc library from Vulkan SDK if possible.
-    if search_dir.is_none() {
-        search_dir = if let Ok(sdk_dir) = env::var("VULKAN_SDK") {
-            check_vulkan_sdk_version(Path::new(&sdk_dir)).unwrap();
-            println!("cargo:warning=shaderc: searching native shaderc libraries in Vulkan SDK '{sdk_dir}/lib'");
-            Some(format!("{sdk_dir}/lib/"))
-        } else {
-            None
-        };
+    // A precompiled static library given by the user overrides everything
+    // else.
+    if let Some(lib) = env::var_os("SHADERC_PRECOMPILED_LIB") {
+        link_precompiled(Path::new(&lib), &target_env);
+        return;
     }
 
-    // If no explicit path is set and no explicit request is made to build from
-    // source, check known system locations before falling back to build from source.
-    // This set `search_dir` for later usage.
-    if search_dir.is_none() && !config_build_from_source {
+    let config = ProbeConfig::from_build_env();
+    let probe = probe::probe(&config);
+    match probe.search_dir {
+        Some(ref search_dir) if search_dir.origin == Origin::VulkanSdk => println!(
+            "cargo:warning=shaderc: searching native shaderc libraries in Vulkan SDK '{}'",
+            search_dir.path.display()
+        ),
+        Some(ref search_dir) if search_dir.origin == Origin::LibDir => println!(
+            "cargo:warning=shaderc: searching native shaderc libraries in '{}'",
+            search_dir.path.display()
+        ),
+        Some(_) | None => {}
+    }
+    let searches_system = probe.library == Err(ProbeError::NoSearchDir)
+        || probe.search_dir.as_ref().map(|d| d.origin) == Some(Origin::System);
+    if searches_system {
         println!(
             "cargo:warning=shaderc: searching for native shaderc libraries on system;  \
              use '--features build-from-source' to force building from source code"
         );
+    }
+    for note in &probe.notes {
+        println!("cargo:warning=shaderc: {note}");
+    }
 
-        if target_os == "macos" {
-            // Vulkan SDK is installed in `/usr/local/` by default on macOS
-            let macos_path = "/usr/local/lib/";
-            if Path::new(macos_path).exists() {
-                search_dir = Some(macos_path.to_owned());
+    match probe.library {
+        Ok(library) => {
+            link_system_library(&library, &target_os, &target_env);
+            return;
+        }
+        Err(ProbeError::VulkanSdk(error)) => panic!("{}", error),
+        Err(ProbeError::InvalidSearchDir(path, error)) => {
+            if config.lib_dir.is_some() {
+                println!("cargo:warning=shaderc: {error}");
+                println!(
+                    "cargo:warning=shaderc: failed to canonicalize the given search path '{path:?}'"
+                );
             }
-        } else if target_os == "linux" {
-            // https://wiki.ubuntu.com/MultiarchSpec
-            // https://wiki.debian.org/Multiarch/Implementation
-            let debian_arch = match env::var("CARGO_CFG_TARGET_ARCH").unwrap() {
-                arch if arch == "x86" => "i386".to_owned(),
-                arch => arch,
-            };
-            let debian_triple_path = format!("/usr/lib/{debian_arch}-linux-gnu/");
-
-            search_dir = if Path::new(&debian_triple_path).exists() {
-                // Debian, Ubuntu and their derivatives.
-                Some(debian_triple_path)
-            } else if env::var("CARGO_CFG_TARGET_ARCH").unwrap() == "x86_64"
-                && Path::new("/usr/lib64/").exists()
-            {
-                // Other distributions running on x86_64 usually use this path.
-                Some("/usr/lib64/".to_owned())
+        }
+        Err(error @ ProbeError::TooOld { .. }) => {
+            // Building from source avoids a late link error, unless only the
+            // system library will do.
+            if env::var_os("SHADERC_REQUIRE_SYSTEM").is_some() {
+                panic!("shaderc: {}", error);
+            }
+            println!("cargo:warning=shaderc: {error}; building from source instead");
+        }
+        Err(error @ ProbeError::UnsupportedPlatform(_)) => {
+            println!("cargo:warning=shaderc: {error}");
+        }
+        Err(ProbeError::BuildFromSource)
+        | Err(ProbeError::NoSearchDir)
+        | Err(ProbeError::NotFound(_)) => {}
+    }
+
+    if config.build_from_source {
+        println!("cargo:warning=shaderc: requested to build from source");
+    } else {
+        println!(
+            "cargo:warning=shaderc: cannot find native shaderc library on system; \
+             falling back to build from source"
+        );
+    }
+
+    let lib_path = match env::var_os("SHADERC_BUILD_CACHE_DIR") {
+        Some(cache_dir) => {
+            let static_lib_file = if target_env == "msvc" {
+                SHADERC_STATIC_LIB_FILE_WIN
             } else {
-                // Other distributions, not x86_64.
-                Some("/usr/lib/".to_owned())
+                SHADERC_STATIC_LIB_FILE_UNIX
             };
+            cached_build(Path::new(&cache_dir), static_lib_file, || {
+                build_shaderc(&target_os, &target_env)
+            })
         }
-    }
+        None => build_shaderc(&target_os, &target_env),
+    };
 
-    // Canonicalize the search directory first.
-    let search_dir = if let Some(search_dir) = search_dir {
-        let path = Path::new(&search_dir);
-        let cannonical = fs::canonicalize(path);
-        if path.is_relative() {
-            println!(
-                "cargo:warning=shaderc: the given search path '{path:?}' is relative; \
-                 path must be relative to shaderc-sys crate, \
-                 likely not your current working directory"
-            );
-        } else if !path.is_dir() {
-            println!("cargo:warning=shaderc: the given search path '{path:?}' is not a directory");
+    println!("cargo:rustc-link-search=native={}", lib_path.display());
+    println!("cargo:rustc-link-lib=static={SHADERC_STATIC_LIB}");
+
+    emit_sanitizer_link();
+    emit_std_cpp_link();
+    emit_atomic_link();
+    write_build_info(
+        "source",
+        &lib_path,
+        SHADERC_STATIC_LIB,
+        "static",
+        std_cpp_lib(),
+    );
+}
+
+/// Links the library found on the system.
+fn link_system_library(library: &probe::Library, target_os: &str, target_env: &str) {
+    let search_dir_str = library.dir.to_string_lossy();
+    let (lib_name, lib_kind) = (library.name, library.kind.as_str());
+    println!("cargo:rustc-link-search=native={search_dir_str}");
+    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
+    let cpp_lib = match (target_os, target_env) {
+        ("linux", _) => {
+            println!("cargo:rustc-link-lib=dylib=stdc++");
+            if library.kind == LibKind::Static {
+                emit_atomic_link();
+            }
+            Some("stdc++")
         }
-        if (cannonical.is_err()) && has_explicit_set_search_dir {
-            println!("cargo:warning=shaderc: {:?}", cannonical.err().unwrap());
-            println!(
-                "cargo:warning=shaderc: failed to canonicalize the given search path '{path:?}'"
-            );
+        ("windows", "msvc") => {
+            println!("cargo:warning=shaderc: Windows MSVC static build is experimental");
+            if library.debug {
+                warn_debug_crt(lib_name);
+            }
+            if library.kind == LibKind::Dylib {
+                warn_missing_dll(&library.dir, lib_name);
+            }
             None
-        } else {
-            cannonical.ok()
         }
-    } else {
-        None
+        ("windows", "gnu") => {
+            println!("cargo:warning=shaderc: Windows MinGW static build is experimental");
+            println!("cargo:rustc-link-lib=dylib=stdc++");
+            Some("stdc++")
+        }
+        _ => {
+            println!("cargo:warning=shaderc: macOS static build is experimental");
+            println!("cargo:rustc-link-lib=dylib=c++");
+            Some("c++")
+        }
     };
+    if library.kind == LibKind::Dylib && env::var_os("CARGO_FEATURE_SPIRV_TOOLS").is_some() {
+        // The shared library does not export the SPIRV-Tools it is built on.
+        println!("cargo:rustc-link-lib=SPIRV-Tools-opt");
+        println!("cargo:rustc-link-lib=SPIRV-Tools");
+    }
+    write_build_info("system", &library.dir, lib_name, lib_kind, cpp_lib);
+}
 
-    // Try to build with the dynamic or static library if a path was explicit set
-    // or implicitly chosen.
-    if let Some(search_dir) = search_dir {
-        let search_dir_str = search_dir.to_string_lossy();
+/// Links the static library at `lib`, after copying it to `OUT_DIR` so that
+/// no other library next to it is picked up.
+fn link_precompiled(lib: &Path, target_env: &str) {
+    println!(
+        "cargo:warning=shaderc: linking precompiled native shaderc library '{}'",
+        lib.display()
+    );
+    if let Err(error) = check_archive_arch(lib) {
+        panic!(
+            "shaderc: cannot use SHADERC_PRECOMPILED_LIB '{}': {error}",
+            lib.display()
+        );
+    }
+    let lib_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("precompiled");
+    let file_name = if target_env == "msvc" {
+        SHADERC_STATIC_LIB_FILE_WIN
+    } else {
+        SHADERC_STATIC_LIB_FILE_UNIX
+    };
+    fs::create_dir_all(&lib_dir)
+        .and_then(|_| fs::copy(lib, lib_dir.join(file_name)))
+        .unwrap_or_else(|error| panic!("shaderc: cannot copy '{}': {error}", lib.display()));
+    println!("cargo:rerun-if-changed={}", lib.display());
+    println!("cargo:rerun-if-env-changed=SHADERC_PRECOMPILED_LIB");
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    println!("cargo:rustc-link-lib=static={SHADERC_STATIC_LIB}");
 
-        let static_lib_path = search_dir.join(if target_os == "windows" && target_env == "msvc" {
-            SHADERC_STATIC_LIB_FILE_WIN
-        } else {
-            SHADERC_STATIC_LIB_FILE_UNIX
-        });
+    emit_std_cpp_link();
+    emit_atomic_link();
+    write_build_info(
+        "precompiled",
+        &lib_dir,
+        SHADERC_STATIC_LIB,
+        "static",
+        std_cpp_lib(),
+    );
+}
 
-        let dylib_name = format!(
-            "{}{}{}",
-            consts::DLL_PREFIX,
-            SHADERC_SHARED_LIB,
-            consts::DLL_SUFFIX
-        );
-        let dylib_path = search_dir.join(dylib_name);
-
-        if let Some((lib_name, lib_kind)) = {
-            match (
-                dylib_path.exists(),
-                static_lib_path.exists(),
-                config_prefer_static_linking,
-            ) {
-                // If dylib not exist OR prefer static lib and static lib exist, static.
-                (false, true, _) | (_, true, true) => Some((SHADERC_STATIC_LIB, "static")),
-                // Otherwise, if dylib exist, dynamic.
-                (true, _, _) => Some((SHADERC_SHARED_LIB, "dylib")),
-                // Neither dylib nor static lib exist.
-                _ => None,
-            }
-        } {
-            match (target_os.as_str(), target_env.as_str()) {
-                ("linux", _) => {
-                    println!("cargo:rustc-link-search=native={search_dir_str}");
-                    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
-                    println!("cargo:rustc-link-lib=dylib=stdc++");
-                    return;
-                }
-                ("windows", "msvc") => {
-                    println!("cargo:warning=shaderc: Windows MSVC static build is experimental");
-                    println!("cargo:rustc-link-search=native={search_dir_str}");
-                    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
-                    return;
-                }
-                ("windows", "gnu") => {
-                    println!("cargo:warning=shaderc: Windows MinGW static build is experimental");
-                    println!("cargo:rustc-link-search=native={search_dir_str}");
-                    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
-                    println!("cargo:rustc-link-lib=dylib=stdc++");
-                    return;
-                }
-                ("macos", _) => {
-                    println!("cargo:warning=shaderc: macOS static build is experimental");
-                    println!("cargo:rustc-link-search=native={search_dir_str}");
-                    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
-                    println!("cargo:rustc-link-lib=dylib=c++");
-                    return;
-                }
-                ("ios", _) => {
-                    println!("cargo:warning=shaderc: macOS static build is experimental");
-                    println!("cargo:rustc-link-search=native={search_dir_str}");
-                    println!("cargo:rustc-link-lib={lib_kind}={lib_name}");
-                    println!("cargo:rustc-link-lib=dylib=c++");
-                    return;
-                }
-                (_, _) => {
-                    println!(
-                        "cargo:warning=shaderc: unsupported platform for linking against \
-                         native shaderc libraries installed on system"
-                    );
-                }
+/// Checks that the first object file of the `ar` archive at `path` was built
+/// for the target architecture.
+///
+/// ELF, COFF and Mach-O objects of the common architectures are recognized;
+/// archives of other objects are accepted as they are.
+fn check_archive_arch(path: &Path) -> Result<(), String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(path).map_err(|error| error.to_string())?;
+    let mut magic = [0; 8];
+    file.read_exact(&mut magic)
+        .map_err(|_| "not a static library".to_string())?;
+    if &magic != b"!<arch>\n" {
+        return Err("not a static library".to_string());
+    }
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let mut header = [0; 60];
+    while file.read_exact(&mut header).is_ok() {
+        let name = String::from_utf8_lossy(&header[..16]);
+        let size: u64 = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse()
+            .map_err(|_| "malformed archive member header".to_string())?;
+        let start = file.stream_position().map_err(|error| error.to_string())?;
+        // Skip the symbol and long name tables.
+        let name = name.trim_end();
+        if !name.starts_with('/') && !name.starts_with("__.SYMDEF") {
+            let mut object = [0; 20];
+            let len = file.read(&mut object).map_err(|error| error.to_string())?;
+            if let Some(arch) = object_arch(&object[..len]) {
+                return if arch == target_arch {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "the library is built for {arch}, not for {target_arch}"
+                    ))
+                };
             }
         }
+        file.seek(SeekFrom::Start(start + size + size % 2))
+            .map_err(|error| error.to_string())?;
     }
+    Ok(())
+}
 
-    if config_build_from_source {
-        println!("cargo:warning=shaderc: requested to build from source");
-    } else {
-        println!(
-            "cargo:warning=shaderc: cannot find native shaderc library on system; \
-             falling back to build from source"
-        );
+/// Returns the Rust name of the architecture of the object file starting with
+/// `object`, if recognized.
+fn object_arch(object: &[u8]) -> Option<&'static str> {
+    let u16_at = |offset: usize| {
+        object
+            .get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let u32_at = |offset: usize| {
+        object
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    if object.starts_with(b"\x7fELF") {
+        // e_machine, assuming a little-endian object.
+        return match u16_at(18)? {
+            3 => Some("x86"),
+            40 => Some("arm"),
+            62 => Some("x86_64"),
+            183 => Some("aarch64"),
+            243 if object.get(4) == Some(&1) => Some("riscv32"),
+            243 => Some("riscv64"),
+            258 => Some("loongarch64"),
+            _ => None,
+        };
     }
+    if u32_at(0)? == 0xfeed_facf {
+        // 64-bit Mach-O cputype.
+        return match u32_at(4)? {
+            0x0100_0007 => Some("x86_64"),
+            0x0100_000c => Some("aarch64"),
+            _ => None,
+        };
+    }
+    // COFF machine field.
+    match u16_at(0)? {
+        0x014c => Some("x86"),
+        0x8664 => Some("x86_64"),
+        0xaa64 => Some("aarch64"),
+        _ => None,
+    }
+}
 
+/// Builds shaderc from source, returning the directory of its libraries.
+fn build_shaderc(target_os: &str, target_env: &str) -> PathBuf {
     let mut finder = cmd_finder::CommandFinder::new();
 
     finder.must_have("cmake");
@@ -380,23 +566,284 @@ fn main() {
         build_shaderc_msvc(&shaderc_dir)
     } else {
         let has_ninja = finder.maybe_have("ninja").is_some();
-        build_shaderc_unix(&shaderc_dir, has_ninja, target_os)
+        build_shaderc_unix(&shaderc_dir, has_ninja, target_os.to_owned())
     };
 
     lib_path.push("lib");
-    println!("cargo:rustc-link-search=native={}", lib_path.display());
-    println!("cargo:rustc-link-lib=static={SHADERC_STATIC_LIB}");
+    lib_path
+}
 
-    emit_std_cpp_link();
+/// Returns the directory of the static library `static_lib_file` built by
+/// `build` for the current target and options, reusing the copy kept in
+/// `cache_dir` by an earlier build script run if there is one.
+///
+/// Build scripts of other workspace members and targets may use the cache at
+/// the same time, so every entry is guarded by a file lock.
+fn cached_build(
+    cache_dir: &Path,
+    static_lib_file: &str,
+    build: impl FnOnce() -> PathBuf,
+) -> PathBuf {
+    let target = env::var("TARGET").unwrap();
+    let linkage = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+    let crt = if linkage.contains("crt-static") {
+        "-crt-static"
+    } else {
+        ""
+    };
+    let sanitize: String = sanitizers().iter().map(|s| format!("-{s}")).collect();
+    let key = format!(
+        "{}-{target}{crt}{sanitize}-{:016x}",
+        env::var("CARGO_PKG_VERSION").unwrap(),
+        fnv1a(build_configuration().as_bytes())
+    );
+    let entry = cache_dir.join(key);
+    let lib_dir = entry.join("lib");
+    fs::create_dir_all(&lib_dir)
+        .unwrap_or_else(|error| panic!("cannot create '{}': {error}", lib_dir.display()));
+
+    let lock = fs::File::create(entry.join(".lock"))
+        .unwrap_or_else(|error| panic!("cannot lock '{}': {error}", entry.display()));
+    lock.lock()
+        .unwrap_or_else(|error| panic!("cannot lock '{}': {error}", entry.display()));
+
+    let cached = lib_dir.join(static_lib_file);
+    if cached.exists() {
+        println!(
+            "cargo:warning=shaderc: reusing native shaderc library built in '{}'",
+            entry.display()
+        );
+    } else {
+        let built = build().join(static_lib_file);
+        // Copy under a temporary name first, so an interrupted copy is never
+        // mistaken for a complete library.
+        let partial = lib_dir.join(format!("{static_lib_file}.partial"));
+        fs::copy(&built, &partial)
+            .and_then(|_| fs::rename(&partial, &cached))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "cannot copy '{}' to the build cache: {error}",
+                    built.display()
+                )
+            });
+    }
+    lib_dir
+}
+
+/// Returns what decides the library built from source besides the
+/// shaderc-sys version and the target: the Cargo profile, the enabled
+/// features, and the C and C++ compilers with their flags.
+fn build_configuration() -> String {
+    let mut lines: Vec<String> = ["PROFILE", "OPT_LEVEL", "DEBUG"]
+        .iter()
+        .map(|var| format!("{var}={}", env::var(var).unwrap_or_default()))
+        .collect();
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(name, _)| name.strip_prefix("CARGO_FEATURE_").map(str::to_owned))
+        .collect();
+    features.sort();
+    lines.push(format!("features={}", features.join(",")));
+    for tool in ["CC", "CXX", "CFLAGS", "CXXFLAGS"] {
+        lines.push(format!(
+            "{tool}={}",
+            target_tool_env(tool).unwrap_or_default()
+        ));
+    }
+    lines.push(format!("compiler={}", compiler_version()));
+    lines.join("\n")
+}
+
+/// Returns the first line the C++ compiler prints for `--version`, which
+/// names it and its version, or the compiler command if it cannot be run.
+///
+/// The wrappers of cargo-zigbuild print the version of the clang in zig, so
+/// builds with different zig releases are told apart.
+fn compiler_version() -> String {
+    let compiler = target_tool_env("CXX").unwrap_or_else(|| {
+        let msvc = env::var("CARGO_CFG_TARGET_ENV").is_ok_and(|env| env == "msvc");
+        if msvc { "cl" } else { "c++" }.to_owned()
+    });
+    let mut words = compiler.split_whitespace();
+    let Some(program) = words.next() else {
+        return compiler;
+    };
+    std::process::Command::new(program)
+        .args(words)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| {
+            // cl prints its version on stderr.
+            let text = [output.stdout, output.stderr].concat();
+            String::from_utf8_lossy(&text)
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_owned)
+        })
+        .unwrap_or(compiler)
+}
+
+/// Returns the 64-bit FNV-1a hash of `bytes`, which unlike `DefaultHasher`
+/// stays the same across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Sets `SHADERC_LINK_FINGERPRINT`, read by `LINK_FINGERPRINT`, to the
+/// directory, names, sizes and modification times of the files of library
+/// `lib_name` in `lib_dir`, like `libshaderc_combined.a`, to tell apart two
+/// builds of the library.
+fn emit_link_fingerprint(lib_dir: &Path, lib_name: &str) {
+    let prefixed = format!("lib{lib_name}");
+    let mut files: Vec<String> = fs::read_dir(lib_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stem = name.split('.').next().unwrap_or("");
+            if stem != lib_name && stem != prefixed {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |time| time.as_secs());
+            Some(format!("{name}:{}:{modified}", metadata.len()))
+        })
+        .collect();
+    files.sort();
+    println!(
+        "cargo:rustc-env=SHADERC_LINK_FINGERPRINT={};{}",
+        lib_dir.display(),
+        files.join(";")
+    );
+}
+
+/// Warns when the DLL matching the import library in `lib_dir` is not in the
+/// sibling `Bin` (or `Bin-ARM64` for `Lib-ARM64`) directory, as the program
+/// will not start without it on the `PATH`.
+fn warn_missing_dll(lib_dir: &Path, lib_name: &str) {
+    let dll = format!("{lib_name}.dll");
+    let bin_dir = lib_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.replacen("Lib", "Bin", 1))
+        .and_then(|name| Some(lib_dir.parent()?.join(name)));
+    let found =
+        lib_dir.join(&dll).exists() || bin_dir.is_some_and(|bin_dir| bin_dir.join(&dll).exists());
+    if !found {
+        println!(
+            "cargo:warning=shaderc: linking against the import library in '{}'; \
+             make sure {dll} can be found on the PATH at run time",
+            lib_dir.display()
+        );
+    }
+}
+
+/// Tells which CRT the Debug libraries `lib_name` must have been built with.
+///
+/// Debug builds of shaderc use the debug CRT, which links fine alongside the
+/// release CRT used by Rust only when both are the static or both are the
+/// dynamic flavor.
+fn warn_debug_crt(lib_name: &str) {
+    let linkage = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+    let crt = if linkage.contains("crt-static") {
+        "/MTd"
+    } else {
+        "/MDd"
+    };
+    println!(
+        "cargo:warning=shaderc: linking against the Debug library {lib_name}; \
+         it must be built with {crt} to match the target's C runtime"
+    );
 }
 
 fn emit_std_cpp_link() {
+    if let Some(lib) = std_cpp_lib() {
+        println!("cargo:rustc-link-lib=dylib={lib}");
+    }
+}
+
+/// Returns the C++ standard library to link shaderc against, if it is not
+/// linked by default.
+fn std_cpp_lib() -> Option<&'static str> {
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
     let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap();
 
     match (target_os.as_str(), target_env.as_str()) {
-        ("linux", _) | ("windows", "gnu") => println!("cargo:rustc-link-lib=dylib=stdc++"),
-        ("macos", _) => println!("cargo:rustc-link-lib=dylib=c++"),
-        _ => {}
+        // zig comes with libc++ rather than libstdc++.
+        ("linux", _) | ("windows", "gnu") if uses_zig() => Some("c++"),
+        ("linux", _) | ("windows", "gnu") => Some("stdc++"),
+        ("macos", _) => Some("c++"),
+        _ => None,
+    }
+}
+
+/// Writes how shaderc is linked to the JSON file named by
+/// `SHADERC_EMIT_BUILD_INFO`, for build systems wrapping cargo, and to the
+/// `SHADERC_LINK_*` variables read by the `LINK_*` constants of the crate.
+///
+/// `origin` is `system`, `precompiled` or `source`.
+fn write_build_info(
+    origin: &str,
+    lib_dir: &Path,
+    lib_name: &str,
+    lib_kind: &str,
+    cpp_lib: Option<&str>,
+) {
+    println!("cargo:rustc-env=SHADERC_LINK_ORIGIN={origin}");
+    println!("cargo:rustc-env=SHADERC_LINK_DIR={}", lib_dir.display());
+    println!("cargo:rustc-env=SHADERC_LINK_NAME={lib_name}");
+    println!("cargo:rustc-env=SHADERC_LINK_KIND={lib_kind}");
+    emit_link_fingerprint(lib_dir, lib_name);
+    let Some(path) = env::var_os("SHADERC_EMIT_BUILD_INFO") else {
+        return;
+    };
+    let string = |value: &str| {
+        let mut escaped = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        escaped
+    };
+    let json = format!(
+        "{{\"origin\":{},\"target\":{},\"library_dir\":{},\"library\":{},\
+         \"kind\":{},\"version\":{},\"cpp_runtime\":{}}}\n",
+        string(origin),
+        string(&env::var("TARGET").unwrap()),
+        string(&lib_dir.to_string_lossy()),
+        string(lib_name),
+        string(lib_kind),
+        string(&env::var("CARGO_PKG_VERSION").unwrap()),
+        cpp_lib.map_or("null".to_owned(), string),
+    );
+    fs::write(&path, json).unwrap_or_else(|error| {
+        panic!(
+            "shaderc: cannot write SHADERC_EMIT_BUILD_INFO '{}': {error}",
+            Path::new(&path).display()
+        )
+    });
+}
+
+/// Links libatomic on targets where GCC lowers some atomic operations of the
+/// static library, like 64-bit ones on 32-bit ARM, to `__atomic_*` calls.
+fn emit_atomic_link() {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    // zig provides the `__atomic_*` functions in its compiler-rt.
+    if target_os == "linux" && target_arch == "arm" && !uses_zig() {
+        println!("cargo:rustc-link-lib=dylib=atomic");
     }
 }
@@ -49,8 +49,30 @@
 #![allow(non_camel_case_types)]
 
 extern crate libc;
+#[cfg(feature = "probe")]
+extern crate roxmltree;
 use libc::{c_char, c_int, c_void, size_t};
 
+#[cfg(feature = "probe")]
+pub mod probe;
+#[cfg(feature = "spirv-tools")]
+pub mod spirv_tools;
+
+/// Where the linked library comes from: `system`, `precompiled` or `source`.
+///
+/// The `LINK_*` constants describe the library the build script linked, and
+/// are `None` when it linked none, like on docs.rs.
+pub const LINK_ORIGIN: Option<&str> = option_env!("SHADERC_LINK_ORIGIN");
+/// The directory the linked library was found in or built into.
+pub const LINK_DIR: Option<&str> = option_env!("SHADERC_LINK_DIR");
+/// The name of the linked library, as passed to the linker.
+pub const LINK_NAME: Option<&str> = option_env!("SHADERC_LINK_NAME");
+/// How the library is linked: `static` or `dylib`.
+pub const LINK_KIND: Option<&str> = option_env!("SHADERC_LINK_KIND");
+/// The directory, names, sizes and modification times of the files of the
+/// linked library, as seen by the build script.
+pub const LINK_FINGERPRINT: Option<&str> = option_env!("SHADERC_LINK_FINGERPRINT");
+
 pub enum ShadercCompiler {}
 pub enum ShadercCompileOptions {}
 pub enum ShadercCompilationResult {}
@@ -64,7 +86,7 @@ pub struct shaderc_include_result {
     pub user_data: *mut c_void,
 }
 
-type shaderc_include_resolve_fn = extern "C" fn(
+pub type shaderc_include_resolve_fn = extern "C" fn(
     user_data: *mut c_void,
     requested_source: *const c_char,
     type_: c_int,
@@ -72,7 +94,7 @@ type shaderc_include_resolve_fn = extern "C" fn(
     include_depth: size_t,
 ) -> *mut shaderc_include_result;
 
-type shaderc_include_result_release_fn =
+pub type shaderc_include_result_release_fn =
     extern "C" fn(user_data: *mut c_void, include_result: *mut shaderc_include_result);
 
 extern "C" {
@@ -0,0 +1,491 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Search for a native shaderc library the way the build script does.
+//!
+//! The build script links the library found by `probe`, and builds shaderc
+//! from source when none is found. Tools can call `probe` themselves to tell
+//! whether shaderc will be found on a machine, and why not. Available with
+//! the `probe` feature.
+//!
+//! ```no_run
+//! use shaderc_sys::probe::{probe, ProbeConfig};
+//!
+//! let probe = probe(&ProbeConfig::host());
+//! match probe.library {
+//!     Ok(library) => println!("found {}", library.file.display()),
+//!     Err(error) => println!("shaderc will be built from source: {}", error),
+//! }
+//! ```
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the static library, as passed to the linker.
+pub const STATIC_LIB: &str = "shaderc_combined";
+/// Name of the dynamic library, as passed to the linker.
+pub const SHARED_LIB: &str = "shaderc_shared";
+/// File name of the static library on Unix and with MinGW.
+pub const STATIC_LIB_FILE_UNIX: &str = "libshaderc_combined.a";
+/// File name of the static library with MSVC.
+pub const STATIC_LIB_FILE_WIN: &str = "shaderc_combined.lib";
+const SHARED_LIB_FILE_WIN: &str = "shaderc_shared.lib";
+// MSVC Debug builds of shaderc suffix their libraries with `d`.
+const STATIC_LIB_DEBUG: &str = "shaderc_combinedd";
+const SHARED_LIB_DEBUG: &str = "shaderc_sharedd";
+const MIN_VULKAN_SDK_VERSION: u32 = 182;
+
+/// The target and settings to search a library for.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProbeConfig {
+    /// The target operating system, as in `cfg(target_os)`.
+    pub target_os: String,
+    /// The target environment, as in `cfg(target_env)`.
+    pub target_env: String,
+    /// The target architecture, as in `cfg(target_arch)`.
+    pub target_arch: String,
+    pub is_64_bit: bool,
+    pub is_cross_compiling: bool,
+    /// The directory given by `SHADERC_LIB_DIR`.
+    pub lib_dir: Option<PathBuf>,
+    /// The Vulkan SDK given by `VULKAN_SDK`.
+    pub vulkan_sdk: Option<PathBuf>,
+    /// Whether the `build-from-source` feature is enabled.
+    pub build_from_source: bool,
+    /// Whether the `prefer-static-linking` feature is enabled.
+    pub prefer_static_linking: bool,
+}
+
+impl ProbeConfig {
+    /// Returns the configuration for building on and for the machine the
+    /// calling program runs on, with the environment variables it was given.
+    pub fn host() -> ProbeConfig {
+        let target_env = if cfg!(target_env = "msvc") {
+            "msvc"
+        } else if cfg!(target_env = "gnu") {
+            "gnu"
+        } else if cfg!(target_env = "musl") {
+            "musl"
+        } else {
+            ""
+        };
+        ProbeConfig {
+            target_os: env::consts::OS.to_owned(),
+            target_env: target_env.to_owned(),
+            target_arch: env::consts::ARCH.to_owned(),
+            is_64_bit: cfg!(target_pointer_width = "64"),
+            is_cross_compiling: false,
+            lib_dir: env::var_os("SHADERC_LIB_DIR").map(PathBuf::from),
+            vulkan_sdk: env::var_os("VULKAN_SDK").map(PathBuf::from),
+            build_from_source: false,
+            prefer_static_linking: false,
+        }
+    }
+
+    /// Returns the configuration of the running build script, from the
+    /// variables cargo sets for it.
+    pub fn from_build_env() -> ProbeConfig {
+        let var = |name: &str| env::var(name).unwrap_or_default();
+        ProbeConfig {
+            target_os: var("CARGO_CFG_TARGET_OS"),
+            target_env: var("CARGO_CFG_TARGET_ENV"),
+            target_arch: var("CARGO_CFG_TARGET_ARCH"),
+            is_64_bit: var("CARGO_CFG_TARGET_POINTER_WIDTH") == "64",
+            is_cross_compiling: var("TARGET") != var("HOST"),
+            lib_dir: env::var_os("SHADERC_LIB_DIR").map(PathBuf::from),
+            vulkan_sdk: env::var_os("VULKAN_SDK").map(PathBuf::from),
+            build_from_source: env::var_os("CARGO_FEATURE_BUILD_FROM_SOURCE").is_some(),
+            prefer_static_linking: env::var_os("CARGO_FEATURE_PREFER_STATIC_LINKING").is_some(),
+        }
+    }
+
+    fn is_msvc(&self) -> bool {
+        self.target_os == "windows" && self.target_env == "msvc"
+    }
+}
+
+/// The outcome of `probe`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Probe {
+    /// The directory searched, if any.
+    pub search_dir: Option<SearchDir>,
+    /// Things worth telling about the search, like a relative search path.
+    pub notes: Vec<String>,
+    pub library: Result<Library, ProbeError>,
+}
+
+/// A directory searched for a library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchDir {
+    pub path: PathBuf,
+    pub origin: Origin,
+}
+
+/// Where a search directory comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// The `SHADERC_LIB_DIR` environment variable.
+    LibDir,
+    /// The library directory of the Vulkan SDK given by `VULKAN_SDK`.
+    VulkanSdk,
+    /// A system library directory.
+    System,
+}
+
+/// How a library is linked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibKind {
+    Static,
+    Dylib,
+}
+
+impl LibKind {
+    /// Returns the kind as written in `cargo:rustc-link-lib`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LibKind::Static => "static",
+            LibKind::Dylib => "dylib",
+        }
+    }
+}
+
+/// A native shaderc library found by `probe`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Library {
+    /// The directory to pass to the linker.
+    pub dir: PathBuf,
+    pub file: PathBuf,
+    /// The name to pass to the linker.
+    pub name: &'static str,
+    pub kind: LibKind,
+    /// Whether this is the `d`-suffixed library of an MSVC Debug build.
+    pub debug: bool,
+}
+
+/// Why `probe` found no usable library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProbeError {
+    /// The `build-from-source` feature disables the search.
+    BuildFromSource,
+    /// The Vulkan SDK given by `VULKAN_SDK` is invalid or too old.
+    VulkanSdk(String),
+    /// There is no system library directory to search for the target.
+    NoSearchDir,
+    /// The search directory does not exist or cannot be accessed.
+    InvalidSearchDir(PathBuf, String),
+    /// The search directory has no shaderc library.
+    NotFound(PathBuf),
+    /// The library lacks functions declared by shaderc-sys.
+    TooOld { file: PathBuf, missing: Vec<String> },
+    /// Libraries installed on the system cannot be linked for the target.
+    UnsupportedPlatform(String),
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProbeError::BuildFromSource => write!(f, "building from source was requested"),
+            ProbeError::VulkanSdk(ref error) => write!(f, "{error}"),
+            ProbeError::NoSearchDir => write!(f, "no system library directory to search"),
+            ProbeError::InvalidSearchDir(ref path, ref error) => {
+                write!(f, "cannot search '{}': {error}", path.display())
+            }
+            ProbeError::NotFound(ref path) => {
+                write!(f, "no native shaderc library in '{}'", path.display())
+            }
+            ProbeError::TooOld {
+                ref file,
+                ref missing,
+            } => write!(
+                f,
+                "the native shaderc library '{}' is too old, it lacks {}",
+                file.display(),
+                missing.join(", ")
+            ),
+            ProbeError::UnsupportedPlatform(ref target) => write!(
+                f,
+                "linking against native shaderc libraries installed on system \
+                 is not supported for {target}"
+            ),
+        }
+    }
+}
+
+impl error::Error for ProbeError {}
+
+/// Searches for a native shaderc library for `config`.
+///
+/// Looks in `SHADERC_LIB_DIR` first, then in the Vulkan SDK, then in the
+/// system library directories. The dynamic library is preferred over the
+/// static one unless `prefer_static_linking` is set.
+pub fn probe(config: &ProbeConfig) -> Probe {
+    let mut notes = Vec::new();
+    let search_dir = match search_dir(config) {
+        Ok(search_dir) => search_dir,
+        Err(error) => {
+            return Probe {
+                search_dir: None,
+                notes,
+                library: Err(error),
+            }
+        }
+    };
+
+    let path = &search_dir.path;
+    if path.is_relative() {
+        notes.push(format!(
+            "the given search path '{path:?}' is relative; \
+             path must be relative to shaderc-sys crate, \
+             likely not your current working directory"
+        ));
+    } else if !path.is_dir() {
+        notes.push(format!(
+            "the given search path '{path:?}' is not a directory"
+        ));
+    }
+    let library = match fs::canonicalize(path) {
+        Ok(dir) => find_library(config, &dir),
+        Err(error) => Err(ProbeError::InvalidSearchDir(
+            path.clone(),
+            error.to_string(),
+        )),
+    };
+    Probe {
+        search_dir: Some(search_dir),
+        notes,
+        library,
+    }
+}
+
+fn search_dir(config: &ProbeConfig) -> Result<SearchDir, ProbeError> {
+    if let Some(ref lib_dir) = config.lib_dir {
+        return Ok(SearchDir {
+            path: lib_dir.clone(),
+            origin: Origin::LibDir,
+        });
+    }
+    if let Some(ref sdk_dir) = config.vulkan_sdk {
+        check_vulkan_sdk_version(sdk_dir).map_err(ProbeError::VulkanSdk)?;
+        return Ok(SearchDir {
+            path: vulkan_sdk_lib_dir(config, sdk_dir),
+            origin: Origin::VulkanSdk,
+        });
+    }
+    if config.build_from_source {
+        return Err(ProbeError::BuildFromSource);
+    }
+    system_lib_dir(config)
+        .map(|path| SearchDir {
+            path,
+            origin: Origin::System,
+        })
+        .ok_or(ProbeError::NoSearchDir)
+}
+
+fn check_vulkan_sdk_version(path: &Path) -> Result<(), String> {
+    let xml = fs::read_to_string(
+        path.join("share")
+            .join("vulkan")
+            .join("registry")
+            .join("vk.xml"),
+    )
+    .map_err(|error| format!("could not read vk.xml in $VULKAN_SDK: {error}"))?;
+    let tree = roxmltree::Document::parse(&xml)
+        .map_err(|error| format!("vk.xml in $VULKAN_SDK is not a valid XML document: {error}"))?;
+    let version = tree
+        .root()
+        .descendants()
+        .find(|node| node.has_tag_name("types"))
+        .ok_or("invalid vk.xml in $VULKAN_SDK is invalid: missing <types> node")?
+        .descendants()
+        .find(|node| node.text() == Some("VK_HEADER_VERSION"))
+        .ok_or("invalid vk.xml in $VULKAN_SDK is invalid: missing VK_HEADER_VERSION node")?
+        .tail()
+        .ok_or("invalid vk.xml in $VULKAN_SDK: no vesion string")?
+        .trim()
+        .parse::<u32>()
+        .map_err(|error| error.to_string())?;
+    if version < MIN_VULKAN_SDK_VERSION {
+        return Err(format!(
+            "requires Vulkan SDK patch version to be at least {MIN_VULKAN_SDK_VERSION}"
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the library directory of the Vulkan SDK at `sdk_dir`.
+///
+/// The Windows SDK puts libraries in `Lib` and DLLs in `Bin`, which matters
+/// on case-sensitive file systems, and its ARM64 libraries in `Lib-ARM64`;
+/// other SDKs use `lib`.
+fn vulkan_sdk_lib_dir(config: &ProbeConfig, sdk_dir: &Path) -> PathBuf {
+    let arm64_dir = sdk_dir.join("Lib-ARM64");
+    if config.target_arch == "aarch64" && config.target_os == "windows" && arm64_dir.is_dir() {
+        return arm64_dir;
+    }
+    let windows_dir = sdk_dir.join("Lib");
+    if windows_dir.is_dir() && !sdk_dir.join("lib").is_dir() {
+        windows_dir
+    } else {
+        sdk_dir.join("lib")
+    }
+}
+
+fn system_lib_dir(config: &ProbeConfig) -> Option<PathBuf> {
+    if config.target_os == "macos" {
+        // Vulkan SDK is installed in `/usr/local/` by default on macOS
+        let macos_path = Path::new("/usr/local/lib/");
+        return Some(macos_path.to_owned()).filter(|path| path.exists());
+    }
+    if config.target_os != "linux" {
+        return None;
+    }
+
+    // https://wiki.ubuntu.com/MultiarchSpec
+    // https://wiki.debian.org/Multiarch/Implementation
+    // riscv64 and loongarch64 use their architecture name as is, as in
+    // riscv64-linux-gnu and loongarch64-linux-gnu.
+    let debian_arch = match config.target_arch.as_str() {
+        "x86" => "i386",
+        arch => arch,
+    };
+    let debian_triple_path = PathBuf::from(format!("/usr/lib/{debian_arch}-linux-gnu/"));
+
+    if debian_triple_path.exists() {
+        // Debian, Ubuntu and their derivatives.
+        Some(debian_triple_path)
+    } else if config.is_cross_compiling {
+        // The other system paths hold libraries for the host; build from
+        // source for the target instead.
+        None
+    } else if config.is_64_bit && Path::new("/usr/lib64/").exists() {
+        // Other distributions running on 64-bit architectures, like x86_64,
+        // riscv64 and loongarch64, usually use this path.
+        Some(PathBuf::from("/usr/lib64/"))
+    } else {
+        // Other distributions.
+        Some(PathBuf::from("/usr/lib/"))
+    }
+}
+
+fn find_library(config: &ProbeConfig, search_dir: &Path) -> Result<Library, ProbeError> {
+    let is_msvc = config.is_msvc();
+    let mut static_lib_path = search_dir.join(if is_msvc {
+        STATIC_LIB_FILE_WIN
+    } else {
+        STATIC_LIB_FILE_UNIX
+    });
+
+    // MSVC links against the import library of the DLL, which lives in the
+    // library directory while the DLL itself lives in `bin`.
+    let mut dylib_path = if is_msvc {
+        search_dir.join(SHARED_LIB_FILE_WIN)
+    } else {
+        let (prefix, suffix) = match config.target_os.as_str() {
+            "windows" => ("", ".dll"),
+            "macos" | "ios" => ("lib", ".dylib"),
+            _ => ("lib", ".so"),
+        };
+        search_dir.join(format!("{prefix}{SHARED_LIB}{suffix}"))
+    };
+
+    // Fall back to the libraries of a Debug build when there are no release
+    // libraries.
+    let (mut static_lib, mut shared_lib) = (STATIC_LIB, SHARED_LIB);
+    let debug_static_lib_path = search_dir.join(format!("{STATIC_LIB_DEBUG}.lib"));
+    let debug_dylib_path = search_dir.join(format!("{SHARED_LIB_DEBUG}.lib"));
+    let debug = is_msvc
+        && !static_lib_path.exists()
+        && !dylib_path.exists()
+        && (debug_static_lib_path.exists() || debug_dylib_path.exists());
+    if debug {
+        static_lib_path = debug_static_lib_path;
+        dylib_path = debug_dylib_path;
+        static_lib = STATIC_LIB_DEBUG;
+        shared_lib = SHARED_LIB_DEBUG;
+    }
+
+    let (name, kind, file) = match (
+        dylib_path.exists(),
+        static_lib_path.exists(),
+        config.prefer_static_linking,
+    ) {
+        // If dylib not exist OR prefer static lib and static lib exist, static.
+        (false, true, _) | (_, true, true) => (static_lib, LibKind::Static, static_lib_path),
+        // Otherwise, if dylib exist, dynamic.
+        (true, _, _) => (shared_lib, LibKind::Dylib, dylib_path),
+        // Neither dylib nor static lib exist.
+        _ => return Err(ProbeError::NotFound(search_dir.to_owned())),
+    };
+
+    let missing = missing_functions(&file);
+    if !missing.is_empty() {
+        return Err(ProbeError::TooOld { file, missing });
+    }
+
+    match (config.target_os.as_str(), config.target_env.as_str()) {
+        ("linux", _) | ("windows", "msvc") | ("windows", "gnu") | ("macos", _) | ("ios", _) => {
+            Ok(Library {
+                dir: search_dir.to_owned(),
+                file,
+                name,
+                kind,
+                debug,
+            })
+        }
+        (os, env) => Err(ProbeError::UnsupportedPlatform(format!("{os} {env}"))),
+    }
+}
+
+/// Returns the functions declared by shaderc-sys whose names are missing
+/// from the symbol tables of the library at `path`.
+///
+/// Symbol tables of archives, ELF, Mach-O and COFF files all hold the names
+/// as NUL-terminated strings, so the library is not parsed any further. A
+/// library which cannot be read is assumed to be complete.
+fn missing_functions(path: &Path) -> Vec<String> {
+    let Ok(lib) = fs::read(path) else {
+        return Vec::new();
+    };
+
+    let is_name_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut defined = std::collections::HashSet::new();
+    let prefix = b"shaderc_";
+    let mut start = 0;
+    while let Some(offset) = lib[start..].windows(prefix.len()).position(|w| w == prefix) {
+        let begin = start + offset;
+        let len = lib[begin..]
+            .iter()
+            .take_while(|&&b| is_name_byte(b))
+            .count();
+        if lib.get(begin + len) == Some(&0) {
+            defined.insert(&lib[begin..begin + len]);
+        }
+        start = begin + len.max(1);
+    }
+
+    include_str!("lib.rs")
+        .split("pub fn ")
+        .skip(1)
+        .map(|rest| {
+            let len = rest.bytes().take_while(|&b| is_name_byte(b)).count();
+            &rest[..len]
+        })
+        .filter(|name| name.starts_with("shaderc_") && !defined.contains(name.as_bytes()))
+        .map(str::to_owned)
+        .collect()
+}
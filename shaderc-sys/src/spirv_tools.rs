@@ -0,0 +1,147 @@
+// Copyright 2026 Google Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! FFI to the C interface of SPIRV-Tools, from `spirv-tools/libspirv.h`.
+//!
+//! Shaderc is built on SPIRV-Tools, which `libshaderc_combined` and builds
+//! from source include. The shared `libshaderc_shared` does not export it,
+//! so the build script then also links the SPIRV-Tools libraries next to it.
+//! Available with the `spirv-tools` feature.
+
+use libc::{c_char, size_t};
+
+/// Result of a SPIRV-Tools function; `SPV_SUCCESS` on success.
+pub type spv_result_t = i32;
+pub const SPV_SUCCESS: spv_result_t = 0;
+
+/// Environment a module is validated or optimized for.
+pub type spv_target_env = i32;
+pub const SPV_ENV_UNIVERSAL_1_0: spv_target_env = 0;
+pub const SPV_ENV_VULKAN_1_0: spv_target_env = 1;
+pub const SPV_ENV_UNIVERSAL_1_1: spv_target_env = 2;
+pub const SPV_ENV_OPENGL_4_5: spv_target_env = 9;
+pub const SPV_ENV_UNIVERSAL_1_2: spv_target_env = 10;
+pub const SPV_ENV_UNIVERSAL_1_3: spv_target_env = 17;
+pub const SPV_ENV_VULKAN_1_1: spv_target_env = 18;
+pub const SPV_ENV_UNIVERSAL_1_4: spv_target_env = 20;
+pub const SPV_ENV_VULKAN_1_1_SPIRV_1_4: spv_target_env = 21;
+pub const SPV_ENV_UNIVERSAL_1_5: spv_target_env = 22;
+pub const SPV_ENV_VULKAN_1_2: spv_target_env = 23;
+pub const SPV_ENV_UNIVERSAL_1_6: spv_target_env = 24;
+pub const SPV_ENV_VULKAN_1_3: spv_target_env = 25;
+
+/// Options of `spvBinaryToText`, combined with `|`.
+pub const SPV_BINARY_TO_TEXT_OPTION_NONE: u32 = 1;
+pub const SPV_BINARY_TO_TEXT_OPTION_PRINT: u32 = 2;
+pub const SPV_BINARY_TO_TEXT_OPTION_COLOR: u32 = 4;
+pub const SPV_BINARY_TO_TEXT_OPTION_INDENT: u32 = 8;
+pub const SPV_BINARY_TO_TEXT_OPTION_SHOW_BYTE_OFFSET: u32 = 16;
+pub const SPV_BINARY_TO_TEXT_OPTION_NO_HEADER: u32 = 32;
+pub const SPV_BINARY_TO_TEXT_OPTION_FRIENDLY_NAMES: u32 = 64;
+
+pub enum SpvContext {}
+pub enum SpvOptimizer {}
+pub enum SpvOptimizerOptions {}
+
+/// Severity of a message of the optimizer.
+pub type spv_message_level_t = i32;
+pub const SPV_MSG_FATAL: spv_message_level_t = 0;
+pub const SPV_MSG_INTERNAL_ERROR: spv_message_level_t = 1;
+pub const SPV_MSG_ERROR: spv_message_level_t = 2;
+pub const SPV_MSG_WARNING: spv_message_level_t = 3;
+pub const SPV_MSG_INFO: spv_message_level_t = 4;
+pub const SPV_MSG_DEBUG: spv_message_level_t = 5;
+
+/// Receives the messages of an optimizer, with their source and position.
+pub type spv_message_consumer = extern "C" fn(
+    level: spv_message_level_t,
+    source: *const c_char,
+    position: *const spv_position_t,
+    message: *const c_char,
+);
+
+#[repr(C)]
+pub struct spv_position_t {
+    pub line: size_t,
+    pub column: size_t,
+    pub index: size_t,
+}
+
+#[repr(C)]
+pub struct spv_diagnostic_t {
+    pub position: spv_position_t,
+    pub error: *mut c_char,
+    pub is_text_source: bool,
+}
+
+#[repr(C)]
+pub struct spv_binary_t {
+    pub code: *mut u32,
+    pub word_count: size_t,
+}
+
+#[repr(C)]
+pub struct spv_text_t {
+    pub str: *const c_char,
+    pub length: size_t,
+}
+
+extern "C" {
+    pub fn spvSoftwareVersionDetailsString() -> *const c_char;
+
+    pub fn spvContextCreate(env: spv_target_env) -> *mut SpvContext;
+    pub fn spvContextDestroy(context: *mut SpvContext);
+
+    pub fn spvBinaryToText(
+        context: *const SpvContext,
+        binary: *const u32,
+        word_count: size_t,
+        options: u32,
+        text: *mut *mut spv_text_t,
+        diagnostic: *mut *mut spv_diagnostic_t,
+    ) -> spv_result_t;
+    pub fn spvTextDestroy(text: *mut spv_text_t);
+
+    pub fn spvValidateBinary(
+        context: *const SpvContext,
+        words: *const u32,
+        num_words: size_t,
+        diagnostic: *mut *mut spv_diagnostic_t,
+    ) -> spv_result_t;
+
+    pub fn spvDiagnosticDestroy(diagnostic: *mut spv_diagnostic_t);
+
+    pub fn spvBinaryDestroy(binary: *mut spv_binary_t);
+
+    pub fn spvOptimizerCreate(env: spv_target_env) -> *mut SpvOptimizer;
+    pub fn spvOptimizerDestroy(optimizer: *mut SpvOptimizer);
+    pub fn spvOptimizerSetMessageConsumer(
+        optimizer: *mut SpvOptimizer,
+        consumer: spv_message_consumer,
+    );
+    pub fn spvOptimizerRegisterPassFromFlag(
+        optimizer: *mut SpvOptimizer,
+        flag: *const c_char,
+    ) -> bool;
+    pub fn spvOptimizerRun(
+        optimizer: *mut SpvOptimizer,
+        binary: *const u32,
+        word_count: size_t,
+        optimized_binary: *mut *mut spv_binary_t,
+        options: *const SpvOptimizerOptions,
+    ) -> spv_result_t;
+
+    pub fn spvOptimizerOptionsCreate() -> *mut SpvOptimizerOptions;
+    pub fn spvOptimizerOptionsDestroy(options: *mut SpvOptimizerOptions);
+}